@@ -10,6 +10,9 @@
 //! host real, interactive platform controls.
 //!
 //! Run with: `cargo run -p macos-layers`
+//!
+//! With `--features trace-rich`, each frame's layer changes are traced to
+//! stderr.
 
 #![expect(unsafe_code, reason = "FFI example requires unsafe code")]
 
@@ -39,7 +42,11 @@ use objc2_quartz_core::CALayer;
 use subduction_backend_apple::{LayerPresenter, LayerRoot, Presenter as _};
 use subduction_core::layer::{LayerId, LayerStore};
 use subduction_core::output::Color;
+#[cfg(feature = "trace-rich")]
+use subduction_core::trace::{LayerChange, TraceSink as _};
 use subduction_core::transform::Transform3d;
+#[cfg(feature = "trace-rich")]
+use subduction_debug::pretty::PrettyPrintSink;
 
 use kurbo::Size;
 
@@ -167,6 +174,11 @@ struct AnimState {
     sub_ids: Vec<LayerId>,
     start_ticks: u64,
     timebase: Timebase,
+    /// Per-layer change records, reused across frames.
+    #[cfg(feature = "trace-rich")]
+    layer_changes: Vec<LayerChange>,
+    #[cfg(feature = "trace-rich")]
+    trace_sink: PrettyPrintSink,
 }
 
 thread_local! {
@@ -312,6 +324,10 @@ fn setup_window(mtm: MainThreadMarker) {
             sub_ids,
             start_ticks,
             timebase,
+            #[cfg(feature = "trace-rich")]
+            layer_changes: Vec::new(),
+            #[cfg(feature = "trace-rich")]
+            trace_sink: PrettyPrintSink::stderr(timebase),
         });
     });
 
@@ -367,7 +383,13 @@ fn render_frame(s: &mut AnimState, frame: ActiveFrame) {
     animate_transforms(&mut s.store, &s.sub_ids, t);
 
     let changes = s.store.evaluate();
-    s.presenter.apply(&s.store, &changes);
+    s.presenter.present_frame(&frame.tick(), &s.store, &changes);
+    #[cfg(feature = "trace-rich")]
+    {
+        changes.layer_changes_into(&mut s.layer_changes);
+        s.trace_sink
+            .on_layer_changes(frame.tick().frame_index, &s.layer_changes);
+    }
     let _submit = s.frame_clock.submit_frame_now(frame);
 }
 
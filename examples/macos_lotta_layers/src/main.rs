@@ -7,6 +7,9 @@
 //! Groups orbit the window center; children orbit their group.
 //!
 //! Run with: `cargo run -p macos-lotta-layers`
+//!
+//! With `--features trace-rich`, each frame's layer changes are traced to
+//! stderr.
 
 #![expect(unsafe_code, reason = "FFI example requires unsafe code")]
 
@@ -36,6 +39,10 @@ use objc2_quartz_core::CALayer;
 use subduction_backend_apple::{LayerPresenter, LayerRoot, Presenter as _};
 use subduction_core::layer::{LayerId, LayerStore};
 use subduction_core::output::Color;
+#[cfg(feature = "trace-rich")]
+use subduction_core::trace::{LayerChange, TraceSink as _};
+#[cfg(feature = "trace-rich")]
+use subduction_debug::pretty::PrettyPrintSink;
 
 const WINDOW_W: f64 = 1024.0;
 const WINDOW_H: f64 = 768.0;
@@ -149,6 +156,11 @@ struct AnimState {
     child_ids: Vec<LayerId>,
     start_ticks: u64,
     timebase: Timebase,
+    /// Per-layer change records, reused across frames.
+    #[cfg(feature = "trace-rich")]
+    layer_changes: Vec<LayerChange>,
+    #[cfg(feature = "trace-rich")]
+    trace_sink: PrettyPrintSink,
 }
 
 thread_local! {
@@ -183,7 +195,7 @@ fn setup_window(mtm: MainThreadMarker) {
     let root_layer = content_view.layer().expect("content view has no layer");
 
     // --- Build the subduction layer tree ---
-    let mut store = LayerStore::new();
+    let mut store = LayerStore::with_capacity(1 + NUM_GROUPS * (1 + LAYERS_PER_GROUP));
     let root_id = store.create_layer();
 
     let mut group_ids = Vec::with_capacity(NUM_GROUPS);
@@ -257,6 +269,10 @@ fn setup_window(mtm: MainThreadMarker) {
             child_ids,
             start_ticks,
             timebase,
+            #[cfg(feature = "trace-rich")]
+            layer_changes: Vec::new(),
+            #[cfg(feature = "trace-rich")]
+            trace_sink: PrettyPrintSink::stderr(timebase),
         });
     });
 
@@ -319,7 +335,13 @@ fn render_frame(s: &mut AnimState, frame: ActiveFrame) {
     );
 
     let changes = s.store.evaluate();
-    s.presenter.apply(&s.store, &changes);
+    s.presenter.present_frame(&frame.tick(), &s.store, &changes);
+    #[cfg(feature = "trace-rich")]
+    {
+        changes.layer_changes_into(&mut s.layer_changes);
+        s.trace_sink
+            .on_layer_changes(frame.tick().frame_index, &s.layer_changes);
+    }
     let _submit = s.frame_clock.submit_frame_now(frame);
 }
 
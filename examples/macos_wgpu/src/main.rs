@@ -6,8 +6,13 @@
 //! Five orbiting layers managed by [`LayerPresenter`] (Core Animation compositing).
 //! Layers 0–1 are solid-color `CALayer`s; layers 2–4 have wgpu content rendered
 //! into `CAMetalLayer` sublayers (spinning prism, plasma, Julia set fractal).
+//! [`LayerSurfacePresenter`] keeps those surfaces sized to their layers and
+//! drives their rendering in traversal order.
 //!
 //! Run with: `cargo run -p macos-wgpu`
+//!
+//! With `--features trace-rich`, each frame's layer changes are traced to
+//! stderr.
 
 #![expect(unsafe_code, reason = "FFI example requires unsafe code")]
 
@@ -22,6 +27,7 @@ use frameclock::{
     SchedulerConfig,
 };
 use frameclock_apple::{AppleFeedbackMode, AppleFrameClock, DisplayLink};
+use kurbo::Size;
 use objc2::rc::Retained;
 use objc2::runtime::ProtocolObject;
 use objc2::{MainThreadMarker, MainThreadOnly, define_class, msg_send};
@@ -34,16 +40,19 @@ use objc2_core_graphics::CGColor;
 use objc2_foundation::{NSNotification, NSObject, NSObjectProtocol, NSString};
 use objc2_quartz_core::{CALayer, CAMetalLayer};
 use subduction_backend_apple::{LayerPresenter, LayerRoot, Presenter as _};
+use subduction_backend_wgpu::{LayerFrame, LayerSurface, LayerSurfacePresenter, WgpuLayerSurface};
 use subduction_core::layer::{LayerId, LayerStore};
 use subduction_core::output::Color;
+#[cfg(feature = "trace-rich")]
+use subduction_core::trace::{LayerChange, TraceSink as _};
 use subduction_core::transform::Transform3d;
+#[cfg(feature = "trace-rich")]
+use subduction_debug::pretty::PrettyPrintSink;
 
 const WINDOW_W: f64 = 800.0;
 const WINDOW_H: f64 = 600.0;
 const NUM_LAYERS: usize = 5;
 const LAYER_SIZE: f64 = 200.0;
-/// Pixel size for GPU surfaces.
-const GPU_SIZE: u32 = 200;
 const FALLBACK_REFRESH_INTERVAL_NANOS: u64 = 16_666_667;
 
 /// Colors for the demo layers (RGBA, f64 for `CGColor`).
@@ -323,7 +332,7 @@ struct GpuLayerState {
         reason = "kept alive so the CAMetalLayer is not deallocated"
     )]
     metal_layer: Retained<CAMetalLayer>,
-    surface: wgpu::Surface<'static>,
+    surface: WgpuLayerSurface,
     pipeline: wgpu::RenderPipeline,
     bind_group: wgpu::BindGroup,
     time_buffer: wgpu::Buffer,
@@ -331,6 +340,12 @@ struct GpuLayerState {
     vertex_count: u32,
 }
 
+impl LayerSurface for GpuLayerState {
+    fn configure(&mut self, width: u32, height: u32) {
+        self.surface.configure(width, height);
+    }
+}
+
 define_class! {
     #[unsafe(super(NSObject))]
     #[thread_kind = MainThreadOnly]
@@ -371,11 +386,14 @@ struct AnimState {
     sub_ids: Vec<LayerId>,
     start_ticks: u64,
     timebase: Timebase,
-
-    // wgpu shared state
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-    gpu_layers: Vec<GpuLayerState>,
+    /// Per-layer change records, reused across frames.
+    #[cfg(feature = "trace-rich")]
+    layer_changes: Vec<LayerChange>,
+    #[cfg(feature = "trace-rich")]
+    trace_sink: PrettyPrintSink,
+
+    /// wgpu content for layers 2–4, sized and rendered per layer.
+    gpu_layers: LayerSurfacePresenter<GpuLayerState>,
 }
 
 thread_local! {
@@ -556,6 +574,7 @@ fn setup_window(mtm: MainThreadMarker) {
     for _ in 0..NUM_LAYERS {
         let layer_id = store.create_layer();
         store.add_child(root_id, layer_id);
+        store.set_bounds(layer_id, Size::new(LAYER_SIZE, LAYER_SIZE));
         sub_ids.push(layer_id);
     }
 
@@ -565,14 +584,10 @@ fn setup_window(mtm: MainThreadMarker) {
 
     // --- Set visual properties on the presenter-managed CALayers ---
 
-    // All layers get bounds and corner radius.
+    // All layers get a corner radius.
     for (i, &layer_id) in sub_ids.iter().enumerate() {
         if let Some(ca) = presenter.get_layer(layer_id.index()) {
             ca.setCornerRadius(12.0);
-            ca.setBounds(CGRect::new(
-                CGPoint::new(0.0, 0.0),
-                CGSize::new(LAYER_SIZE, LAYER_SIZE),
-            ));
             // Solid-color layers (0, 1) get a background color.
             if i < 2 {
                 let [r, g, b, a] = COLORS[i];
@@ -630,18 +645,11 @@ fn setup_window(mtm: MainThreadMarker) {
         surfaces.push(surface);
     }
 
-    // Configure all surfaces.
-    for surface in &surfaces {
-        let config = surface
-            .get_default_config(&adapter, GPU_SIZE, GPU_SIZE)
-            .expect("surface not compatible with adapter");
-        surface.configure(&device, &config);
-    }
-
-    let tex_format = surfaces[0]
-        .get_default_config(&adapter, GPU_SIZE, GPU_SIZE)
-        .expect("surface not compatible")
-        .format;
+    // Sized to the layer bounds by the first `apply`.
+    let surface_config = surfaces[0]
+        .get_default_config(&adapter, 1, 1)
+        .expect("surface not compatible with adapter");
+    let tex_format = surface_config.format;
 
     // --- Time-uniform bind group layout (shared by all 3 shaders) ---
     let time_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -691,7 +699,7 @@ fn setup_window(mtm: MainThreadMarker) {
         .expect("prism vertex count must fit in u32 draw-call vertex count");
     let vertex_counts: [u32; 3] = [prism_vertex_count, 6, 6];
 
-    let mut gpu_layers: Vec<GpuLayerState> = Vec::with_capacity(3);
+    let mut gpu_layers = LayerSurfacePresenter::new();
     // We must consume `surfaces` and `metal_layers` together.
     let surface_iter = surfaces.into_iter();
     let ml_iter = metal_layers.into_iter();
@@ -721,16 +729,20 @@ fn setup_window(mtm: MainThreadMarker) {
             vertex_buffers[1].clone()
         };
 
-        gpu_layers.push(GpuLayerState {
-            metal_layer: ml,
-            surface,
-            pipeline,
-            bind_group,
-            time_buffer,
-            vertex_buffer: vb,
-            vertex_count: vertex_counts[i],
-        });
+        gpu_layers.attach(
+            sub_ids[GPU_LAYER_INDICES[i]].index(),
+            GpuLayerState {
+                metal_layer: ml,
+                surface: WgpuLayerSurface::new(&device, &queue, surface, surface_config.clone()),
+                pipeline,
+                bind_group,
+                time_buffer,
+                vertex_buffer: vb,
+                vertex_count: vertex_counts[i],
+            },
+        );
     }
+    gpu_layers.apply(&store, &changes);
 
     window.center();
     window.makeKeyAndOrderFront(None);
@@ -762,8 +774,10 @@ fn setup_window(mtm: MainThreadMarker) {
             sub_ids,
             start_ticks,
             timebase,
-            device,
-            queue,
+            #[cfg(feature = "trace-rich")]
+            layer_changes: Vec::new(),
+            #[cfg(feature = "trace-rich")]
+            trace_sink: PrettyPrintSink::stderr(timebase),
             gpu_layers,
         });
     });
@@ -806,7 +820,14 @@ fn render_frame(s: &mut AnimState, frame: ActiveFrame) {
 
     // Evaluate dirty state and apply to the CALayer tree.
     let changes = s.store.evaluate();
-    s.presenter.apply(&s.store, &changes);
+    s.presenter.present_frame(&frame.tick(), &s.store, &changes);
+    s.gpu_layers.apply(&s.store, &changes);
+    #[cfg(feature = "trace-rich")]
+    {
+        changes.layer_changes_into(&mut s.layer_changes);
+        s.trace_sink
+            .on_layer_changes(frame.tick().frame_index, &s.layer_changes);
+    }
 
     // --- Render wgpu content into GPU layers ---
     #[expect(
@@ -815,61 +836,63 @@ fn render_frame(s: &mut AnimState, frame: ActiveFrame) {
     )]
     let time_f32 = t as f32;
 
-    for gpu in &s.gpu_layers {
-        // Upload time uniform.
-        s.queue
-            .write_buffer(&gpu.time_buffer, 0, bytemuck::bytes_of(&time_f32));
-
-        let frame = match gpu.surface.get_current_texture() {
-            wgpu::CurrentSurfaceTexture::Success(frame)
-            | wgpu::CurrentSurfaceTexture::Suboptimal(frame) => frame,
-            wgpu::CurrentSurfaceTexture::Timeout
-            | wgpu::CurrentSurfaceTexture::Occluded
-            | wgpu::CurrentSurfaceTexture::Outdated => continue,
-            other @ (wgpu::CurrentSurfaceTexture::Lost
-            | wgpu::CurrentSurfaceTexture::Validation) => {
-                eprintln!("failed to acquire GPU frame: {other:?}");
-                continue;
-            }
-        };
-        let view = frame
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        let mut encoder = s
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("gpu layer encoder"),
-            });
+    s.gpu_layers
+        .render(&s.store, |layer| render_gpu_layer(layer, time_f32));
 
-        {
-            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("gpu layer pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-                multiview_mask: None,
-            });
-            pass.set_pipeline(&gpu.pipeline);
-            pass.set_bind_group(0, &gpu.bind_group, &[]);
-            pass.set_vertex_buffer(0, gpu.vertex_buffer.slice(..));
-            pass.draw(0..gpu.vertex_count, 0..1);
+    let _submit = s.frame_clock.submit_frame_now(frame);
+}
+
+fn render_gpu_layer(layer: LayerFrame<'_, GpuLayerState>, time_f32: f32) {
+    let gpu = &*layer.surface;
+    let (device, queue) = (gpu.surface.device(), gpu.surface.queue());
+
+    // Upload time uniform.
+    queue.write_buffer(&gpu.time_buffer, 0, bytemuck::bytes_of(&time_f32));
+
+    let frame = match gpu.surface.surface().get_current_texture() {
+        wgpu::CurrentSurfaceTexture::Success(frame)
+        | wgpu::CurrentSurfaceTexture::Suboptimal(frame) => frame,
+        wgpu::CurrentSurfaceTexture::Timeout
+        | wgpu::CurrentSurfaceTexture::Occluded
+        | wgpu::CurrentSurfaceTexture::Outdated => return,
+        other @ (wgpu::CurrentSurfaceTexture::Lost | wgpu::CurrentSurfaceTexture::Validation) => {
+            eprintln!("failed to acquire GPU frame: {other:?}");
+            return;
         }
+    };
+    let view = frame
+        .texture
+        .create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("gpu layer encoder"),
+    });
 
-        s.queue.submit(Some(encoder.finish()));
-        frame.present();
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("gpu layer pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        pass.set_pipeline(&gpu.pipeline);
+        pass.set_bind_group(0, &gpu.bind_group, &[]);
+        pass.set_vertex_buffer(0, gpu.vertex_buffer.slice(..));
+        pass.draw(0..gpu.vertex_count, 0..1);
     }
 
-    let _submit = s.frame_clock.submit_frame_now(frame);
+    queue.submit(Some(encoder.finish()));
+    frame.present();
 }
 
 fn apply_preferred_frame_interval(s: &AnimState, frame: &ActiveFrame) {
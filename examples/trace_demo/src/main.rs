@@ -5,7 +5,7 @@
 //!
 //! Runs 60 synthetic frames through the scheduler, recording events to both a
 //! [`PrettyPrintSink`] and a
-//! [`RecorderSink`], then exports a
+//! [`RecorderSink`] through a [`TeeSink`], then exports a
 //! Chrome trace JSON file.
 
 use std::fs::File;
@@ -16,17 +16,21 @@ use frameclock::scheduler::Scheduler;
 use frameclock::time::Timebase;
 use frameclock::timing::PresentFeedback;
 use frameclock::{
-    DisplayTiming, Duration, FrameDemand, FrameOpportunity, FrameTick, HostTime, OutputId,
-    PresentHints, SchedulerConfig,
+    DisplayTiming, Duration, FrameDemand, FrameOpportunity, FrameTick, HostTime, PresentHints,
+    SchedulerConfig,
 };
+use kurbo::Size;
+use subduction_core::layer::LayerStore;
 use subduction_core::trace::{
-    FrameSummaryBuilder, PhaseBeginEvent, PhaseEndEvent, PhaseKind, TraceSink, Tracer,
+    FrameSummaryBuilder, PhaseBeginEvent, PhaseEndEvent, PhaseKind, TeeSink, TraceSink, Tracer,
 };
+use subduction_core::transform::Transform3d;
 
 use subduction_debug::pretty::PrettyPrintSink;
 use subduction_debug::recorder::RecorderSink;
 
 const FRAME_COUNT: u64 = 60;
+const LAYER_COUNT: usize = 4;
 /// 16.6ms refresh interval in nanoseconds (≈60 Hz).
 const REFRESH_INTERVAL_NS: u64 = 16_666_667;
 
@@ -35,35 +39,47 @@ fn main() {
     let refresh_interval = REFRESH_INTERVAL_NS;
 
     // -- sinks -------------------------------------------------------------
-    let mut pretty = PrettyPrintSink::new(Box::new(std::io::stdout()), timebase);
-    let mut recorder = RecorderSink::new();
+    // Pretty-print live and record at the same time.
+    let mut sinks = TeeSink::new(
+        PrettyPrintSink::new(Box::new(std::io::stdout()), timebase),
+        RecorderSink::new(),
+    );
 
     // -- scheduler ---------------------------------------------------------
     let config = SchedulerConfig::predictive();
     let mut scheduler = Scheduler::new(config);
 
+    // -- layer tree --------------------------------------------------------
+    let mut store = LayerStore::new();
+    let root = store.create_layer();
+    let layers: Vec<_> = (0..LAYER_COUNT)
+        .map(|_| {
+            let layer = store.create_layer();
+            store.add_child(root, layer);
+            store.set_bounds(layer, Size::new(64.0, 64.0));
+            layer
+        })
+        .collect();
+    let mut movers = layers.iter().copied().cycle();
+    let mut layer_changes = Vec::new();
+    let mut damage = Vec::new();
+
     // -- simulated loop ----------------------------------------------------
     let mut now_ticks: u64 = 1_000_000_000; // start at 1s
 
     for frame_index in 0..FRAME_COUNT {
         // 1. Tick
-        let tick = FrameTick {
-            now: HostTime(now_ticks),
-            predicted_present: Some(HostTime(now_ticks + refresh_interval)),
-            refresh_interval: Some(refresh_interval),
-            frame_index,
-            output: OutputId(0),
-            prev_actual_present: if frame_index > 0 {
-                // Previous frame presented on time.
-                Some(HostTime(now_ticks - refresh_interval))
-            } else {
-                None
-            },
-        };
+        let tick = FrameTick::builder()
+            .now(HostTime(now_ticks))
+            .predicted_present(Some(HostTime(now_ticks + refresh_interval)))
+            .refresh_interval(Some(refresh_interval))
+            .frame_index(frame_index)
+            // Previous frame presented on time.
+            .prev_actual_present((frame_index > 0).then(|| HostTime(now_ticks - refresh_interval)))
+            .build();
 
         let tick_event = FrameTickEvent::from(&tick);
-        pretty.on_frame_tick(&tick_event);
-        recorder.on_frame_tick(&tick_event);
+        sinks.on_frame_tick(&tick_event);
 
         // 2. Hints + plan
         let hints = PresentHints::predictive(
@@ -72,13 +88,7 @@ fn main() {
         );
 
         let plan_start = HostTime(now_ticks + 50_000);
-        emit_phase_begin(
-            &mut pretty,
-            &mut recorder,
-            frame_index,
-            PhaseKind::Plan,
-            plan_start,
-        );
+        emit_phase_begin(&mut sinks, frame_index, PhaseKind::Plan, plan_start);
 
         let opportunity = FrameOpportunity::new(
             tick,
@@ -89,81 +99,58 @@ fn main() {
         let plan_end = HostTime(now_ticks + 100_000);
 
         let plan_event = FramePlanEvent::new(&plan, scheduler.safety_margin_ticks());
-        pretty.on_frame_plan(&plan_event);
-        recorder.on_frame_plan(&plan_event);
+        sinks.on_frame_plan(&plan_event);
 
-        emit_phase_end(
-            &mut pretty,
-            &mut recorder,
-            frame_index,
-            PhaseKind::Plan,
-            plan_end,
-        );
+        emit_phase_end(&mut sinks, frame_index, PhaseKind::Plan, plan_end);
 
-        // 3. Evaluate (simulated)
+        // 3. Evaluate: move one layer per frame and trace the resulting
+        // changes (phase timing is simulated).
         let eval_start = plan_end;
-        emit_phase_begin(
-            &mut pretty,
-            &mut recorder,
-            frame_index,
-            PhaseKind::Evaluate,
-            eval_start,
+        emit_phase_begin(&mut sinks, frame_index, PhaseKind::Evaluate, eval_start);
+        let moved = movers.next().expect("layer cycle is endless");
+        store.set_transform(
+            moved,
+            Transform3d::from_translation(frame_index as f64, 0.0, 0.0),
         );
+        let changes = store.evaluate();
+        changes.layer_changes_into(&mut layer_changes);
+        sinks.on_layer_changes(frame_index, &layer_changes);
+        store.damage_rects_into(&changes, &mut damage);
+        sinks.on_damage_rects(frame_index, &damage);
         let eval_end = HostTime(eval_start.ticks() + 500_000);
-        emit_phase_end(
-            &mut pretty,
-            &mut recorder,
-            frame_index,
-            PhaseKind::Evaluate,
-            eval_end,
-        );
+        emit_phase_end(&mut sinks, frame_index, PhaseKind::Evaluate, eval_end);
 
-        // 4. Render (simulated)
-        let render_start = eval_end;
-        emit_phase_begin(
-            &mut pretty,
-            &mut recorder,
-            frame_index,
-            PhaseKind::Render,
-            render_start,
-        );
+        // 4. Acquire + render (simulated)
+        let acquire_start = eval_end;
+        emit_phase_begin(&mut sinks, frame_index, PhaseKind::Acquire, acquire_start);
+        let acquire_end = HostTime(acquire_start.ticks() + 200_000);
+        emit_phase_end(&mut sinks, frame_index, PhaseKind::Acquire, acquire_end);
+
+        let render_start = acquire_end;
+        emit_phase_begin(&mut sinks, frame_index, PhaseKind::Render, render_start);
         let render_end = HostTime(render_start.ticks() + 2_000_000);
-        emit_phase_end(
-            &mut pretty,
-            &mut recorder,
-            frame_index,
-            PhaseKind::Render,
-            render_end,
-        );
+        emit_phase_end(&mut sinks, frame_index, PhaseKind::Render, render_end);
 
         // 5. Submit
         let submit_start = render_end;
-        emit_phase_begin(
-            &mut pretty,
-            &mut recorder,
-            frame_index,
-            PhaseKind::Submit,
-            submit_start,
-        );
+        emit_phase_begin(&mut sinks, frame_index, PhaseKind::Submit, submit_start);
         let submit_end = HostTime(submit_start.ticks() + 100_000);
-        emit_phase_end(
-            &mut pretty,
-            &mut recorder,
-            frame_index,
-            PhaseKind::Submit,
-            submit_end,
-        );
+        emit_phase_end(&mut sinks, frame_index, PhaseKind::Submit, submit_end);
 
         let submit_event = SubmitEvent {
             frame_index,
             submitted_at: submit_end,
             expected_present: plan.target_present,
         };
-        pretty.on_submit(&submit_event);
-        recorder.on_submit(&submit_event);
+        sinks.on_submit(&submit_event);
+
+        let present_start = submit_end;
+        emit_phase_begin(&mut sinks, frame_index, PhaseKind::Present, present_start);
+        let present_end = HostTime(present_start.ticks() + 150_000);
+        emit_phase_end(&mut sinks, frame_index, PhaseKind::Present, present_end);
 
         // 6. Feedback
-        let missed = submit_end > plan.commit_deadline;
+        let missed = plan.is_past_deadline(submit_end);
         let feedback = PresentFeedback {
             submitted_at: submit_end,
             build_start: plan_start,
@@ -171,6 +158,7 @@ fn main() {
             actual_present: plan.target_present,
             missed_deadline: Some(missed),
             pacing_overrun: None,
+            output: plan.output,
         };
         scheduler.observe(&feedback);
 
@@ -180,8 +168,7 @@ fn main() {
             missed_deadline: Some(missed),
             pacing_overrun: feedback.pacing_overrun,
         };
-        pretty.on_present_feedback(&feedback_event);
-        recorder.on_present_feedback(&feedback_event);
+        sinks.on_present_feedback(&feedback_event);
 
         // 7. Summary
         let mut builder = FrameSummaryBuilder::new(&tick_event, &plan_event);
@@ -189,19 +176,22 @@ fn main() {
         builder.phase_end(PhaseKind::Plan, plan_end);
         builder.phase_begin(PhaseKind::Evaluate, eval_start);
         builder.phase_end(PhaseKind::Evaluate, eval_end);
+        builder.phase_begin(PhaseKind::Acquire, acquire_start);
+        builder.phase_end(PhaseKind::Acquire, acquire_end);
         builder.phase_begin(PhaseKind::Render, render_start);
         builder.phase_end(PhaseKind::Render, render_end);
         builder.phase_begin(PhaseKind::Submit, submit_start);
         builder.phase_end(PhaseKind::Submit, submit_end);
+        builder.phase_begin(PhaseKind::Present, present_start);
+        builder.phase_end(PhaseKind::Present, present_end);
         builder.set_missed_deadline(missed);
         let summary = builder.finish();
 
-        pretty.on_frame_summary(&summary);
-        recorder.on_frame_summary(&summary);
+        sinks.on_frame_summary(&summary);
 
         // Also exercise Tracer wrapper (just to prove it compiles and dispatches).
         if frame_index == 0 {
-            let mut tracer = Tracer::new(&mut pretty);
+            let mut tracer = Tracer::new(&mut sinks.first);
             tracer.frame_tick(&tick_event);
         }
 
@@ -213,6 +203,7 @@ fn main() {
     let path = "trace.json";
     let file = File::create(path).expect("failed to create trace.json");
     let mut writer = BufWriter::new(file);
+    let (_, recorder) = sinks.into_inner();
     subduction_debug::chrome::export(recorder.as_bytes(), timebase, &mut writer)
         .expect("failed to write Chrome trace");
 
@@ -220,8 +211,7 @@ fn main() {
 }
 
 fn emit_phase_begin(
-    pretty: &mut PrettyPrintSink,
-    recorder: &mut RecorderSink,
+    sinks: &mut impl TraceSink,
     frame_index: u64,
     phase: PhaseKind,
     timestamp: HostTime,
@@ -231,13 +221,11 @@ fn emit_phase_begin(
         phase,
         timestamp,
     };
-    pretty.on_phase_begin(&e);
-    recorder.on_phase_begin(&e);
+    sinks.on_phase_begin(&e);
 }
 
 fn emit_phase_end(
-    pretty: &mut PrettyPrintSink,
-    recorder: &mut RecorderSink,
+    sinks: &mut impl TraceSink,
     frame_index: u64,
     phase: PhaseKind,
     timestamp: HostTime,
@@ -247,6 +235,5 @@ fn emit_phase_end(
         phase,
         timestamp,
     };
-    pretty.on_phase_end(&e);
-    recorder.on_phase_end(&e);
+    sinks.on_phase_end(&e);
 }
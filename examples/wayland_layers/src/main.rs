@@ -183,7 +183,7 @@ fn main() {
             );
 
             let changes = store.evaluate();
-            presenter.apply(&store, &changes);
+            presenter.present_frame(&tick, &store, &changes);
 
             let _id = state
                 .wayland
@@ -606,7 +606,7 @@ fn main() {
 
         while let Some(tick) = state.wayland.poll_tick() {
             // Compute animation time from the tick's timestamp.
-            let elapsed_nanos = tick.now.ticks().saturating_sub(start_time.ticks());
+            let elapsed_nanos = tick.now.saturating_sub(start_time).ticks();
             #[expect(
                 clippy::cast_precision_loss,
                 reason = "Nanosecond counter to f64 seconds — precision loss is acceptable for animation"
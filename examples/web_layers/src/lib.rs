@@ -6,7 +6,8 @@
 //! Creates a dark container with six animated elements (a WebGL canvas, a WebGPU
 //! canvas, and four colored divs) that orbit and pulse opacity, demonstrating the
 //! web backend's building blocks: [`RafLoop`] for timing, [`DomPresenter`] for
-//! presentation, and [`WebFrameClock`] for frame planning.
+//! presentation, and [`WebFrameClock`] for frame planning. The WebGPU canvas is
+//! kept sized to its layer by [`LayerSurfacePresenter`].
 //!
 //! Build with: `wasm-pack build --target web examples/web_layers`
 //!
@@ -15,6 +16,7 @@
 //! [`RafLoop`]: frameclock_web::RafLoop
 //! [`DomPresenter`]: subduction_backend_web::DomPresenter
 //! [`WebFrameClock`]: frameclock_web::WebFrameClock
+//! [`LayerSurfacePresenter`]: subduction_backend_wgpu::LayerSurfacePresenter
 
 // This crate only runs in the browser; suppress dead-code warnings when
 // cargo-checking on a native host target.
@@ -51,6 +53,7 @@ use frameclock::{
 use frameclock_web::{DEFAULT_REFRESH_INTERVAL, RafLoop, WebFrameClock};
 use kurbo::Size;
 use subduction_backend_web::{DomPresenter, LayerRoot, Presenter as _};
+use subduction_backend_wgpu::{LayerFrame, LayerSurface, LayerSurfacePresenter, WgpuLayerSurface};
 use subduction_core::layer::{LayerId, LayerStore};
 use subduction_core::output::Color;
 use subduction_core::transform::Transform3d;
@@ -153,15 +156,19 @@ struct WebGlState {
 }
 
 struct WgpuState {
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-    surface: wgpu::Surface<'static>,
+    surface: WgpuLayerSurface,
     pipeline: wgpu::RenderPipeline,
     bind_group: wgpu::BindGroup,
     time_buffer: wgpu::Buffer,
     vertex_buffer: wgpu::Buffer,
 }
 
+impl LayerSurface for WgpuState {
+    fn configure(&mut self, width: u32, height: u32) {
+        self.surface.configure(width, height);
+    }
+}
+
 struct AnimState {
     store: LayerStore,
     frame_clock: WebFrameClock,
@@ -172,7 +179,8 @@ struct AnimState {
     start_us: u64,
     timebase: Timebase,
     webgl: Option<WebGlState>,
-    wgpu: Option<WgpuState>,
+    /// The WebGPU canvas, attached to its layer once wgpu is initialized.
+    wgpu: LayerSurfacePresenter<WgpuState>,
 }
 
 /// Entry point — called automatically by `wasm_bindgen(start)`.
@@ -194,6 +202,9 @@ pub fn main() -> Result<(), JsValue> {
     for &spec in &ELEMENT_SPECS {
         let layer_id = store.create_layer();
         store.add_child(root_id, layer_id);
+        // Rotate each element about its center.
+        store.set_bounds(layer_id, Size::new(spec.0, spec.1));
+        store.set_anchor_point(layer_id, (0.5, 0.5));
         let slot = layer_id.index() as usize;
         if sizes.len() <= slot {
             sizes.resize(slot + 1, (0.0, 0.0));
@@ -211,7 +222,7 @@ pub fn main() -> Result<(), JsValue> {
 
     // Customize the presenter's divs: set sizes, colors, and append canvas children.
     let mut webgl_state: Option<WebGlState> = None;
-    let mut wgpu_canvas: Option<HtmlCanvasElement> = None;
+    let mut wgpu_canvas: Option<(u32, HtmlCanvasElement)> = None;
 
     for (i, &layer_id) in layer_ids.iter().enumerate() {
         let idx = layer_id.index();
@@ -234,7 +245,7 @@ pub fn main() -> Result<(), JsValue> {
                 canvas.set_width(w as u32);
                 canvas.set_height(h as u32);
                 el.append_child(&canvas)?;
-                wgpu_canvas = Some(canvas);
+                wgpu_canvas = Some((idx, canvas));
             }
             _ => {
                 // Colored div.
@@ -256,14 +267,13 @@ pub fn main() -> Result<(), JsValue> {
         start_us,
         timebase,
         webgl: webgl_state,
-        wgpu: None,
+        wgpu: LayerSurfacePresenter::new(),
     }));
 
-    // Spawn async wgpu initialization — populates `state.wgpu` when ready.
-    init_wgpu_async(
-        Rc::clone(&state),
-        wgpu_canvas.expect("WebGPU canvas not created"),
-    );
+    // Spawn async wgpu initialization — attaches the canvas surface to
+    // `state.wgpu` when ready.
+    let (wgpu_slot, wgpu_canvas) = wgpu_canvas.expect("WebGPU canvas not created");
+    init_wgpu_async(Rc::clone(&state), wgpu_slot, wgpu_canvas);
 
     // Start animation immediately: WebGL renders right away, wgpu starts
     // once the async initialization completes.
@@ -281,15 +291,15 @@ pub fn main() -> Result<(), JsValue> {
 ///
 /// On non-wasm targets this is a no-op since the example only runs in a browser.
 #[cfg(target_arch = "wasm32")]
-fn init_wgpu_async(state: Rc<RefCell<AnimState>>, canvas: HtmlCanvasElement) {
+fn init_wgpu_async(state: Rc<RefCell<AnimState>>, slot: u32, canvas: HtmlCanvasElement) {
     wasm_bindgen_futures::spawn_local(async move {
         let wgpu_state = init_wgpu(&canvas).await;
-        state.borrow_mut().wgpu = Some(wgpu_state);
+        state.borrow_mut().wgpu.attach(slot, wgpu_state);
     });
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn init_wgpu_async(_state: Rc<RefCell<AnimState>>, _canvas: HtmlCanvasElement) {}
+fn init_wgpu_async(_state: Rc<RefCell<AnimState>>, _slot: u32, _canvas: HtmlCanvasElement) {}
 
 #[cfg(target_arch = "wasm32")]
 async fn init_wgpu(canvas: &HtmlCanvasElement) -> WgpuState {
@@ -320,8 +330,6 @@ async fn init_wgpu(canvas: &HtmlCanvasElement) -> WgpuState {
     let config = surface
         .get_default_config(&adapter, width, height)
         .expect("surface not compatible with adapter");
-    surface.configure(&device, &config);
-
     let format = config.format;
 
     // Time-uniform bind group layout.
@@ -409,9 +417,7 @@ async fn init_wgpu(canvas: &HtmlCanvasElement) -> WgpuState {
     });
 
     WgpuState {
-        device,
-        queue,
-        surface,
+        surface: WgpuLayerSurface::new(&device, &queue, surface, config),
         pipeline,
         bind_group,
         time_buffer,
@@ -542,11 +548,12 @@ fn render_webgl(gl: &WebGlState, t: f32) {
     ctx.draw_arrays(WebGl2RenderingContext::TRIANGLES, 0, 3);
 }
 
-fn render_wgpu(gpu: &WgpuState, t: f32) {
-    gpu.queue
-        .write_buffer(&gpu.time_buffer, 0, bytemuck::bytes_of(&t));
+fn render_wgpu(layer: LayerFrame<'_, WgpuState>, t: f32) {
+    let gpu = &*layer.surface;
+    let (device, queue) = (gpu.surface.device(), gpu.surface.queue());
+    queue.write_buffer(&gpu.time_buffer, 0, bytemuck::bytes_of(&t));
 
-    let frame = match gpu.surface.get_current_texture() {
+    let frame = match gpu.surface.surface().get_current_texture() {
         wgpu::CurrentSurfaceTexture::Success(frame)
         | wgpu::CurrentSurfaceTexture::Suboptimal(frame) => frame,
         wgpu::CurrentSurfaceTexture::Timeout
@@ -559,11 +566,9 @@ fn render_wgpu(gpu: &WgpuState, t: f32) {
         .texture
         .create_view(&wgpu::TextureViewDescriptor::default());
 
-    let mut encoder = gpu
-        .device
-        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("plasma encoder"),
-        });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("plasma encoder"),
+    });
 
     {
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -588,7 +593,7 @@ fn render_wgpu(gpu: &WgpuState, t: f32) {
         pass.draw(0..6, 0..1);
     }
 
-    gpu.queue.submit(Some(encoder.finish()));
+    queue.submit(Some(encoder.finish()));
     frame.present();
 }
 
@@ -613,27 +618,28 @@ fn on_tick(state: &Rc<RefCell<AnimState>>, tick: FrameTick) {
     let elapsed_nanos = s.timebase.ticks_to_nanos(elapsed_us);
     let t = elapsed_nanos as f64 / 1_000_000_000.0;
 
-    // Destructure to satisfy the borrow checker: mutable store + presenter,
+    // Destructure to satisfy the borrow checker: mutable store + presenters,
     // immutable ids/sizes.
     let AnimState {
         ref mut store,
         ref mut presenter,
         ref layer_ids,
         ref mut sizes,
+        ref webgl,
+        ref mut wgpu,
         ..
     } = *s;
     animate_transforms(store, layer_ids, sizes, t);
     let changes = store.evaluate();
-    presenter.apply(store, &changes);
+    presenter.present_frame(&frame.tick(), store, &changes);
+    wgpu.apply(store, &changes);
 
     // Render GPU content.
     let time_f32 = t as f32;
-    if let Some(ref gl) = s.webgl {
+    if let Some(gl) = webgl {
         render_webgl(gl, time_f32);
     }
-    if let Some(ref gpu) = s.wgpu {
-        render_wgpu(gpu, time_f32);
-    }
+    wgpu.render(store, |layer| render_wgpu(layer, time_f32));
 
     let submitted_at = frameclock_web::now();
     let _ = s
@@ -672,16 +678,11 @@ fn animate_transforms(
             sizes[layer_id.index() as usize] = (w, h);
         }
 
-        // Center of the element for the centering offset (sizes indexed by slot).
+        // Place the element's visual center at (x,y); the center anchor
+        // makes the rotation pivot there (sizes indexed by slot).
         let (w, h) = sizes[layer_id.index() as usize];
-        let half_w = w / 2.0;
-        let half_h = h / 2.0;
-
-        // T(x,y) * Rz(angle) * T(-half_w, -half_h)
-        // Places the element's visual center at (x,y) and rotates around it.
-        let transform = Transform3d::from_translation(x, y, 0.0)
-            * Transform3d::from_rotation_z(rotation)
-            * Transform3d::from_translation(-half_w, -half_h, 0.0);
+        let transform = Transform3d::from_translation(x - w / 2.0, y - h / 2.0, 0.0)
+            * Transform3d::from_rotation_z(rotation);
 
         store.set_transform(layer_id, transform);
 
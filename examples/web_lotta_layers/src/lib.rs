@@ -100,7 +100,9 @@ pub fn main() -> Result<(), JsValue> {
     document.body().expect("no body").append_child(&container)?;
 
     // --- Build layer tree ---
-    let mut store = LayerStore::new();
+    let mut store = LayerStore::with_capacity(1 + num_groups * (1 + layers_per_group));
+    // Every layer only translates and rotates around Z.
+    store.set_2d_only(true);
     let root_id = store.create_layer();
 
     let mut group_ids = Vec::with_capacity(num_groups);
@@ -122,7 +124,8 @@ pub fn main() -> Result<(), JsValue> {
     let initial = store.evaluate();
     let backdrop_color = Color::from_rgba8(0x1a, 0x1a, 0x24, 0xff);
     let root = LayerRoot::new(container.clone()).with_backdrop_color(backdrop_color);
-    let mut presenter = DomPresenter::new(root);
+    // Skip sub-pixel transform and imperceptible opacity updates.
+    let mut presenter = DomPresenter::new(root).with_write_epsilon(1e-4);
     presenter.apply(&store, &initial);
 
     // --- Style child layers ---
@@ -270,7 +273,7 @@ fn on_tick(state: &Rc<RefCell<AnimState>>, tick: FrameTick) {
     );
 
     let changes = store.evaluate();
-    presenter.apply(store, &changes);
+    presenter.present_frame(&frame.tick(), store, &changes);
 
     let submitted_at = frameclock_web::now();
     let _ = s
@@ -41,7 +41,7 @@ use mediaclock::MediaTimeline;
 use subduction_backend_web::{DomPresenter, LayerRoot, Presenter as _};
 use subduction_core::layer::{LayerId, LayerStore};
 use subduction_core::transform::Transform3d;
-use subduction_sync_harness::{PathologyToggles, SyncSample, SyncTracker};
+use subduction_sync_harness::{DualSyncTracker, PathologyToggles, SyncSample};
 use wasm_bindgen::JsCast as _;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::*;
@@ -95,7 +95,7 @@ struct VideoState {
     tooltip_id: LayerId,
     audio: Option<AudioContext>,
     last_beat: u64,
-    sync: SyncTracker<GRAPH_SAMPLES>,
+    sync: DualSyncTracker<GRAPH_SAMPLES>,
     prev_tick_us: Option<u64>,
     dropped_frames: u64,
     duplicated_frames: u64,
@@ -117,6 +117,13 @@ pub fn main() -> Result<(), JsValue> {
         .document()
         .expect("document");
 
+    // Deep-linked pathologies, e.g. "?decode_jitter=1&gpu_stall=1".
+    let initial_pathologies = web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .map_or_else(PathologyToggles::default, |search| {
+            PathologyToggles::from_query(&search)
+        });
+
     let shell = create_shell(&document)?;
     document.body().expect("body").append_child(&shell)?;
 
@@ -193,28 +200,28 @@ pub fn main() -> Result<(), JsValue> {
         &toggles_panel,
         "decode jitter (0-12ms)",
         "Inject random CPU stalls before media sync to simulate decode jitter. Includes periodic spikes.",
-        false,
+        initial_pathologies.decode_jitter,
     )?;
     let gpu_stall = add_checkbox(
         &document,
         &toggles_panel,
         "gpu stall (8ms busy spin)",
         "Inject extra work in the frame critical path to simulate expensive render passes.",
-        false,
+        initial_pathologies.gpu_stall,
     )?;
     let timer_jitter = add_checkbox(
         &document,
         &toggles_panel,
         "timer jitter (+/-4ms)",
         "Perturb semantic time in pacing-only mode to emulate timer noise.",
-        false,
+        initial_pathologies.timer_jitter,
     )?;
     let vary_refresh = add_checkbox(
         &document,
         &toggles_panel,
         "emulate refresh 60<->120",
         "Toggle effective frame budget between 60 Hz and 120 Hz every 5s.",
-        false,
+        initial_pathologies.vary_refresh,
     )?;
 
     let timecode = element(&document, "pre")?;
@@ -288,6 +295,10 @@ pub fn main() -> Result<(), JsValue> {
     store.add_child(root, flash_id);
     store.add_child(root, hand_id);
     store.add_child(root, tooltip_id);
+    store.set_name(sweep_id, "sweep");
+    store.set_name(flash_id, "flash");
+    store.set_name(hand_id, "hand");
+    store.set_name(tooltip_id, "tooltip");
 
     let initial = store.evaluate();
     let root = LayerRoot::new(overlay_host);
@@ -365,7 +376,7 @@ pub fn main() -> Result<(), JsValue> {
         tooltip_id,
         audio: None,
         last_beat: 0,
-        sync: SyncTracker::new(16.67),
+        sync: DualSyncTracker::new(16.67),
         prev_tick_us: None,
         dropped_frames: 0,
         duplicated_frames: 0,
@@ -437,10 +448,11 @@ fn bind_controls(state: &Rc<RefCell<VideoState>>) -> Result<(), JsValue> {
 
     let ended_state = Rc::clone(state);
     let ended_cb = Closure::wrap(Box::new(move |_event: Event| {
-        let mut s = ended_state.borrow_mut();
+        // The loop restart is picked up by the next `observe`, which reanchors
+        // once the jump exceeds the timeline's discontinuity threshold.
+        let s = ended_state.borrow();
         s.video.set_current_time(0.0);
         let _ = s.video.play();
-        s.media_timeline.reanchor(frameclock_web::now(), 0.0);
     }) as Box<dyn FnMut(_)>);
     state
         .borrow()
@@ -476,7 +488,7 @@ fn on_tick(state: &Rc<RefCell<VideoState>>, tick: FrameTick) {
 
     let mut semantic_seconds = ticks_to_secs(
         s.timebase,
-        plan.sample_time.ticks().saturating_sub(s.app_start.ticks()),
+        plan.sample_time.saturating_sub(s.app_start).ticks(),
     );
 
     let pathologies = PathologyToggles {
@@ -497,7 +509,7 @@ fn on_tick(state: &Rc<RefCell<VideoState>>, tick: FrameTick) {
     let target_time = plan.target_present.unwrap_or(plan.sample_time);
     let target_present_seconds = ticks_to_secs(
         s.timebase,
-        target_time.ticks().saturating_sub(s.app_start.ticks()),
+        target_time.saturating_sub(s.app_start).ticks(),
     );
 
     if pathologies.decode_jitter {
@@ -633,7 +645,7 @@ fn on_tick(state: &Rc<RefCell<VideoState>>, tick: FrameTick) {
         ref mut presenter,
         ..
     } = *s;
-    presenter.apply(store, &changes);
+    presenter.present_frame(&frame.tick(), store, &changes);
 
     if pathologies.gpu_stall {
         busy_wait_ms(GPU_STALL_MS);
@@ -648,7 +660,7 @@ fn on_tick(state: &Rc<RefCell<VideoState>>, tick: FrameTick) {
         .summary
         .expect("RAF submission should resolve immediately");
 
-    let build_ms = submitted_at.ticks().saturating_sub(build_start.ticks()) as f64 / 1000.0;
+    let build_ms = submitted_at.saturating_sub(build_start).ticks() as f64 / 1000.0;
     let frame_budget_ms = frame_dur * 1000.0;
     let hard_miss = summary.pacing_overrun.unwrap_or(false);
     let soft_miss = build_ms > frame_budget_ms * 1.20;
@@ -697,33 +709,52 @@ fn on_tick(state: &Rc<RefCell<VideoState>>, tick: FrameTick) {
         "n/a".to_string()
     };
 
-    let report = s.sync.observe(SyncSample {
-        presentation_timing,
-        phase_error_ms,
-        hard_miss,
-        soft_miss,
-        frame_delta_ms,
-    });
-    let grade = report.grade.as_str();
-    let miss_rate = report.miss_rate_per_1000;
-    let color = grade_color(report.grade.as_str());
+    let dual = s.sync.observe(
+        SyncSample {
+            presentation_timing,
+            phase_error_ms,
+            hard_miss,
+            soft_miss,
+            frame_delta_ms,
+        },
+        Some(audio_delta_ms),
+    );
+    let report = dual.video;
+    let grade = dual.grade().as_str();
+    let audio_grade = dual.audio.map_or("n/a", |a| a.grade.as_str());
+    let miss_rate = report.window_miss_rate_per_1000;
+    let color = grade_color(grade);
 
     s.ui.sync_grade.set_text_content(Some(&format!(
-        "Sync Grade {} | phase error {:+.2} ms | miss rate {:.2}/1000",
-        grade, phase_error_ms, miss_rate
+        "Sync Grade {} | video {} ({:.0}) phase error {:+.2} ms | audio {} | miss rate {:.2}/1000 (last {GRAPH_SAMPLES})",
+        grade,
+        report.grade.as_str(),
+        report.score,
+        phase_error_ms,
+        audio_grade,
+        miss_rate
     )));
     let _ = s.ui.sync_grade.style().set_property("color", color);
 
+    let scheduler = s.frame_clock.driver().scheduler();
+    let stats = scheduler.stats();
+    let build_mean_ms = ticks_to_secs(s.timebase, stats.mean_build_cost_ticks) * 1000.0;
+    let build_max_ms = ticks_to_secs(s.timebase, stats.max_build_cost_ticks) * 1000.0;
+    let build_iqr_ms = ticks_to_secs(s.timebase, stats.build_cost_iqr_ticks) * 1000.0;
+    let present_iqr_ms = ticks_to_secs(s.timebase, stats.present_error_iqr_ticks) * 1000.0;
     s.ui.hud.set_text_content(Some(&format!(
-        "PresentationTiming: {presentation_timing_label}\nTs: {ts_ms:.3}ms\nTp: {tp_label}\npipeline depth: {}\nmissed deadlines: {}\ninj(timer/decode/gpu): {:+.2} / {:.2} / {:.2} ms",
-        s.frame_clock.driver().scheduler().pipeline_depth(),
+        "PresentationTiming: {presentation_timing_label}\nTs: {ts_ms:.3}ms\nTp: {tp_label}\npipeline depth: {} ({} changes)\nbuild mean/max: {build_mean_ms:.2} / {build_max_ms:.2} ms\nIQR build/present: {build_iqr_ms:.2} / {present_iqr_ms:.2} ms\nrecent misses: {}/{}\nmissed deadlines: {}\ninj(timer/decode/gpu): {:+.2} / {:.2} / {:.2} ms",
+        scheduler.pipeline_depth(),
+        stats.depth_changes,
+        stats.window_misses,
+        stats.window_len,
         report.missed_frames,
         s.last_timer_jitter_ms,
         s.last_decode_jitter_ms,
         s.last_gpu_stall_ms
     )));
 
-    let graph_str = s.sync.sparkline_ascii(8.0, 25.0);
+    let graph_str = s.sync.video().sparkline_ascii(8.0, 25.0);
     s.ui.graph.set_text_content(Some(&format!(
         "frame dt (last {GRAPH_SAMPLES}): {graph_str}"
     )));
@@ -20,6 +20,8 @@ use std::sync::Arc;
 use subduction_backend_wgpu::{LayerRoot, Presenter as _, WgpuPresenter, WgpuPresenterConfig};
 use subduction_core::layer::{LayerId, LayerStore, SurfaceId, SurfaceIds};
 use subduction_core::output::Color;
+use subduction_core::time::HostTime;
+use subduction_core::timing::FrameTick;
 use subduction_core::transform::Transform3d;
 
 use kurbo::Size;
@@ -32,6 +34,7 @@ const WINDOW_W: u32 = 800;
 const WINDOW_H: u32 = 600;
 const NUM_LAYERS: usize = 5;
 const LAYER_SIZE: u32 = 200;
+const FRAME_NS: u64 = 16_666_667;
 
 /// RGBA colors for the demo layers.
 const COLORS: [[f32; 4]; NUM_LAYERS] = [
@@ -304,9 +307,15 @@ impl ApplicationHandler for App {
                     }
                 }
 
-                // Evaluate and apply.
+                // Evaluate and present. There is no frame clock here, so the
+                // tick is synthesized from the frame count at a nominal 60 Hz.
+                let tick = FrameTick::builder()
+                    .now(HostTime(s.frame_count * FRAME_NS))
+                    .refresh_interval(Some(FRAME_NS))
+                    .frame_index(s.frame_count)
+                    .build();
                 let changes = s.store.evaluate();
-                s.presenter.apply(&s.store, &changes);
+                s.presenter.present_frame(&tick, &s.store, &changes);
 
                 // Render solid colors into each layer's texture.
                 let mut encoder =
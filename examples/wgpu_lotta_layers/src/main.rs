@@ -21,6 +21,8 @@ use lotta_layers_common::LAYER_SIZE;
 use subduction_backend_wgpu::{LayerRoot, Presenter as _, WgpuPresenter, WgpuPresenterConfig};
 use subduction_core::layer::{LayerId, LayerStore, SurfaceId, SurfaceIds};
 use subduction_core::output::Color;
+use subduction_core::time::HostTime;
+use subduction_core::timing::FrameTick;
 use winit::application::ApplicationHandler;
 use winit::event::WindowEvent;
 use winit::event_loop::{ActiveEventLoop, EventLoop};
@@ -31,6 +33,7 @@ const WINDOW_H: u32 = 768;
 const NUM_GROUPS: usize = 10;
 const LAYERS_PER_GROUP: usize = 100;
 const NUM_CHILDREN: usize = NUM_GROUPS * LAYERS_PER_GROUP;
+const FRAME_NS: u64 = 16_666_667;
 
 /// WGSL shader that fills the render target with a solid color from a uniform.
 const FILL_SHADER: &str = r"
@@ -297,8 +300,15 @@ impl ApplicationHandler for App {
                     t,
                 );
 
+                // Evaluate and present. There is no frame clock here, so the
+                // tick is synthesized from the frame count at a nominal 60 Hz.
+                let tick = FrameTick::builder()
+                    .now(HostTime(s.frame_count * FRAME_NS))
+                    .refresh_interval(Some(FRAME_NS))
+                    .frame_index(s.frame_count)
+                    .build();
                 let changes = s.store.evaluate();
-                s.presenter.apply(&s.store, &changes);
+                s.presenter.present_frame(&tick, &s.store, &changes);
 
                 // Fill each child's texture once (content is static).
                 if !s.filled {
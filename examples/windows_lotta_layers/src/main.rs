@@ -362,7 +362,7 @@ fn on_tick() {
         phase: PhaseKind::Render,
         timestamp: render_start,
     });
-    s.presenter.apply(&s.store, &changes);
+    s.presenter.present_frame(&tick, &s.store, &changes);
     let render_end = backend::now();
     summary.phase_end(PhaseKind::Render, render_end);
     s.recorder.on_phase_end(&PhaseEndEvent {
@@ -392,7 +392,7 @@ fn on_tick() {
 
     summary.phase_begin(PhaseKind::Submit, submit_start);
     summary.phase_end(PhaseKind::Submit, submit_end);
-    summary.set_missed_deadline(submit_end > plan.commit_deadline);
+    summary.set_missed_deadline(plan.is_past_deadline(submit_end));
     s.recorder.on_frame_summary(&summary.finish());
 
     // Store pending feedback for next tick.
@@ -364,7 +364,7 @@ impl SyntheticRenderer {
         // The driver recorded frame build start when it returned ActiveFrame;
         // a real backend would provide the queue submit time and attach
         // platform present feedback when available.
-        let budget = plan.commit_deadline.saturating_duration_since(now);
+        let budget = plan.budget_remaining(now);
         let requested_cost = match work_mode {
             WorkMode::LatencySensitive => Duration(SYNTHETIC_BUILD_COST.ticks() / 2),
             WorkMode::Normal => SYNTHETIC_BUILD_COST,
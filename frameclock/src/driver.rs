@@ -401,6 +401,13 @@ impl FrameDriver {
         &self.scheduler
     }
 
+    /// Pins or releases the scheduler pipeline depth.
+    ///
+    /// See [`Scheduler::set_depth_override`].
+    pub fn set_depth_override(&mut self, depth: Option<u8>) {
+        self.scheduler.set_depth_override(depth);
+    }
+
     /// Returns demand that has not yet been planned into a frame.
     #[must_use]
     pub const fn pending_demand(&self) -> FrameDemand {
@@ -745,14 +752,11 @@ mod tests {
     }
 
     fn tick(now: u64, frame_index: u64) -> FrameTick {
-        FrameTick {
-            now: HostTime(now),
-            predicted_present: None,
-            refresh_interval: Some(REFRESH_INTERVAL.ticks()),
-            frame_index,
-            output: OutputId(0),
-            prev_actual_present: None,
-        }
+        FrameTick::builder()
+            .now(HostTime(now))
+            .refresh_interval(Some(REFRESH_INTERVAL.ticks()))
+            .frame_index(frame_index)
+            .build()
     }
 
     fn hints(deadline: u64) -> PresentHints {
@@ -774,12 +778,8 @@ mod tests {
         latest_commit: u64,
     ) -> FrameOpportunity {
         let tick = FrameTick {
-            now: HostTime(now),
             predicted_present: Some(HostTime(desired_present)),
-            refresh_interval: Some(REFRESH_INTERVAL.ticks()),
-            frame_index,
-            output: OutputId(0),
-            prev_actual_present: None,
+            ..tick(now, frame_index)
         };
         let hints = PresentHints::predictive(HostTime(desired_present), HostTime(latest_commit));
         FrameOpportunity::new(tick, hints, DisplayTiming::fixed(REFRESH_INTERVAL))
@@ -1339,6 +1339,7 @@ mod tests {
             actual_present: None,
             missed_deadline: None,
             pacing_overrun: Some(false),
+            output: OutputId(0),
         });
 
         assert!(driver.scheduler().safety_margin_ticks() > 0);
@@ -130,6 +130,8 @@
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+extern crate alloc;
+
 pub mod demand;
 pub mod diagnostics;
 pub mod driver;
@@ -147,4 +149,7 @@ pub use driver::{
 pub use output::OutputId;
 pub use scheduler::SchedulerConfig;
 pub use time::{Duration, HostTime};
-pub use timing::{DisplayTiming, FrameOpportunity, FrameTick, PresentHints};
+pub use timing::{
+    DisplayTiming, FrameOpportunity, FrameTick, FrameTickBuilder, PredictedPresents, PresentHints,
+    PresentHintsBuilder,
+};
@@ -9,9 +9,14 @@
 //! details on pipeline depth and adaptive behavior.
 
 use crate::demand::{FrameDemand, FrameDemandClass};
-use crate::time::{Duration, HostTime};
+use crate::diagnostics::{
+    DiagnosticsSink, FramePlanEvent, FrameTickEvent, PresentFeedbackEvent, SchedulerStateEvent,
+};
+use crate::output::OutputId;
+use crate::time::{Duration, HostTime, Timebase};
 use crate::timing::{
-    DisplayTiming, FrameOpportunity, FramePlan, PresentFeedback, PresentationTiming,
+    DisplayTiming, FrameOpportunity, FramePlan, PendingFeedback, PresentFeedback,
+    PresentationTiming,
 };
 
 /// Controls how the scheduler adapts pipeline depth in response to deadline
@@ -39,6 +44,72 @@ pub enum DegradationPolicy {
     Fixed,
 }
 
+/// Variable-refresh (adaptive-sync) range for [`SchedulerConfig::vrr`].
+///
+/// On adaptive-sync displays the panel refreshes when a frame arrives, as long
+/// as it arrives between [`Self::min_interval`] and [`Self::max_interval`]
+/// after the previous refresh. Past the maximum interval the panel repeats the
+/// previous frame on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VrrRange {
+    min_interval: Duration,
+    max_interval: Duration,
+}
+
+impl VrrRange {
+    /// Creates a range from the shortest and longest refresh intervals.
+    ///
+    /// A `max_interval` shorter than `min_interval` is raised to it.
+    #[must_use]
+    pub const fn new(min_interval: Duration, max_interval: Duration) -> Self {
+        let max_interval = if max_interval.0 < min_interval.0 {
+            min_interval
+        } else {
+            max_interval
+        };
+        Self {
+            min_interval,
+            max_interval,
+        }
+    }
+
+    /// Creates a range from the panel's refresh-rate limits in hertz.
+    ///
+    /// `max_hz` sets the shortest interval and `min_hz` the longest. Rates of
+    /// zero are treated as 1 Hz.
+    #[must_use]
+    pub const fn from_hz(min_hz: u32, max_hz: u32, timebase: Timebase) -> Self {
+        const NANOS_PER_SEC: u64 = 1_000_000_000;
+        let min_hz = if min_hz == 0 { 1 } else { min_hz };
+        let max_hz = if max_hz == 0 { 1 } else { max_hz };
+        Self::new(
+            Duration::from_nanos(NANOS_PER_SEC / max_hz as u64, timebase),
+            Duration::from_nanos(NANOS_PER_SEC / min_hz as u64, timebase),
+        )
+    }
+
+    /// Shortest refresh interval the panel supports.
+    #[must_use]
+    pub const fn min_interval(self) -> Duration {
+        self.min_interval
+    }
+
+    /// Longest refresh interval before the panel repeats a frame.
+    #[must_use]
+    pub const fn max_interval(self) -> Duration {
+        self.max_interval
+    }
+
+    /// Display timing with direct intervals anywhere in the range.
+    const fn display_timing(self) -> DisplayTiming {
+        DisplayTiming::variable(
+            self.min_interval,
+            self.max_interval,
+            Some(self.min_interval),
+        )
+    }
+}
+
 /// Configuration passed to [`Scheduler::new`] or
 /// [`FrameDriver::new`](crate::FrameDriver::new).
 #[derive(Clone, Copy, Debug)]
@@ -70,27 +141,65 @@ pub struct SchedulerConfig {
     pub nominal_latency: Duration,
     /// Policy for adapting pipeline depth.
     pub degradation_policy: DegradationPolicy,
+    /// Minimum number of observed frames between pipeline-depth changes.
+    ///
+    /// Prevents depth from oscillating under borderline load. Hit/miss
+    /// evidence keeps accumulating while a change is held back, so the change
+    /// applies as soon as the window has elapsed. `0` disables hysteresis.
+    pub depth_change_hysteresis: u32,
+    /// EMA smoothing factor (0.0–1.0) for the learned present-time offset.
+    ///
+    /// When non-zero, [`Scheduler::observe`] tracks the smoothed difference
+    /// between [`PresentFeedback::actual_present`] and
+    /// [`PresentFeedback::expected_present`], and [`Scheduler::plan`] shifts
+    /// predictive target-present and sample times by that learned offset.
+    /// This corrects systematic phase error in platform predictions. `0.0`
+    /// disables the correction.
+    pub present_offset_alpha: f64,
+    /// Maximum [`FramePlan::sample_time`] advance per planned frame, as a
+    /// multiple of the source refresh interval.
+    ///
+    /// After a long stall (a GC pause or a decode spike), the next plan would
+    /// normally sample the whole stall at once and animations would visibly
+    /// lurch. With a limit of `n`, sample time advances by at most `n`
+    /// refresh intervals per frame. Wall-clock time advances by one interval
+    /// per frame, so sample time closes the gap by `n - 1` intervals per
+    /// frame until it has caught up. Limits below 2 would never catch up and
+    /// are treated as 2.
+    ///
+    /// A gap longer than [`SAMPLE_CLAMP_RESET_INTERVALS`] is treated as the
+    /// output having been idle rather than stalled, and sample time jumps
+    /// straight to wall-clock time.
+    ///
+    /// This trades strict wall-clock accuracy of sampled state for visual
+    /// smoothness; target-present and deadline times are not affected.
+    /// `None` disables clamping.
+    pub max_sample_advance_intervals: Option<u32>,
+    /// Variable-refresh planning range, or `None` for fixed-grid planning.
+    ///
+    /// With a range set, [`Scheduler::plan`] does not snap presentation to a
+    /// refresh grid. It targets the previous actual present plus a stable
+    /// interval that fits the learned build cost, and caps the commit deadline
+    /// at the previous actual present plus [`VrrRange::max_interval`]. Until
+    /// the first actual present is observed, the tick time stands in for it.
+    pub vrr: Option<VrrRange>,
 }
 
 impl SchedulerConfig {
+    /// Returns a builder for a custom configuration.
+    ///
+    /// The builder starts from the [`Self::predictive`] values.
+    #[must_use]
+    pub const fn builder() -> SchedulerConfigBuilder {
+        SchedulerConfigBuilder::new()
+    }
+
     /// Default configuration for predictive timing.
     ///
     /// Use this when a platform exposes a strong predicted presentation time.
     #[must_use]
     pub const fn predictive() -> Self {
-        Self {
-            initial_depth: 1,
-            min_depth: 1,
-            max_depth: 3,
-            ema_alpha: 0.2,
-            safety_multiplier: 1.5,
-            minimum_frame_start_margin: Duration(1_000_000),
-            nominal_latency: Duration(0),
-            degradation_policy: DegradationPolicy::Adaptive {
-                miss_threshold: 3,
-                recovery_threshold: 10,
-            },
-        }
+        Self::builder().build()
     }
 
     /// Default configuration for estimated timing.
@@ -99,19 +208,7 @@ impl SchedulerConfig {
     /// presentation timing.
     #[must_use]
     pub const fn estimated() -> Self {
-        Self {
-            initial_depth: 1,
-            min_depth: 1,
-            max_depth: 3,
-            ema_alpha: 0.2,
-            safety_multiplier: 2.0,
-            minimum_frame_start_margin: Duration(1_000_000),
-            nominal_latency: Duration(0),
-            degradation_policy: DegradationPolicy::Adaptive {
-                miss_threshold: 3,
-                recovery_threshold: 10,
-            },
-        }
+        Self::builder().safety_multiplier(2.0).build()
     }
 
     /// Default configuration for pacing-only timing.
@@ -120,21 +217,176 @@ impl SchedulerConfig {
     /// presentation time.
     #[must_use]
     pub const fn pacing_only() -> Self {
-        Self {
-            initial_depth: 1,
-            min_depth: 1,
-            max_depth: 3,
-            ema_alpha: 0.15,
-            safety_multiplier: 2.0,
-            minimum_frame_start_margin: Duration(1_000_000),
+        Self::builder()
+            .ema_alpha(0.15)
+            .safety_multiplier(2.0)
             // ~16ms at 1ns tick resolution.
-            nominal_latency: Duration(16_000_000),
-            degradation_policy: DegradationPolicy::Adaptive {
-                miss_threshold: 3,
-                recovery_threshold: 10,
+            .nominal_latency(Duration(16_000_000))
+            .build()
+    }
+}
+
+/// Builder for [`SchedulerConfig`].
+///
+/// Created by [`SchedulerConfig::builder`]. Values are sanitized by
+/// [`Scheduler::new`], so out-of-range depth bounds are clamped rather than
+/// rejected.
+#[derive(Clone, Copy, Debug)]
+pub struct SchedulerConfigBuilder {
+    config: SchedulerConfig,
+}
+
+impl SchedulerConfigBuilder {
+    const DEFAULT_MISS_THRESHOLD: u32 = 3;
+    const DEFAULT_RECOVERY_THRESHOLD: u32 = 10;
+
+    const fn new() -> Self {
+        Self {
+            config: SchedulerConfig {
+                initial_depth: 1,
+                min_depth: 1,
+                max_depth: 3,
+                ema_alpha: 0.2,
+                safety_multiplier: 1.5,
+                minimum_frame_start_margin: Duration(1_000_000),
+                nominal_latency: Duration(0),
+                degradation_policy: DegradationPolicy::Adaptive {
+                    miss_threshold: Self::DEFAULT_MISS_THRESHOLD,
+                    recovery_threshold: Self::DEFAULT_RECOVERY_THRESHOLD,
+                },
+                depth_change_hysteresis: 0,
+                present_offset_alpha: 0.0,
+                max_sample_advance_intervals: None,
+                vrr: None,
             },
         }
     }
+
+    /// Sets [`SchedulerConfig::initial_depth`].
+    #[must_use]
+    pub const fn initial_depth(mut self, depth: u8) -> Self {
+        self.config.initial_depth = depth;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::min_depth`].
+    #[must_use]
+    pub const fn min_depth(mut self, depth: u8) -> Self {
+        self.config.min_depth = depth;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::max_depth`].
+    #[must_use]
+    pub const fn max_depth(mut self, depth: u8) -> Self {
+        self.config.max_depth = depth;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::ema_alpha`].
+    #[must_use]
+    pub const fn ema_alpha(mut self, alpha: f64) -> Self {
+        self.config.ema_alpha = alpha;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::safety_multiplier`].
+    #[must_use]
+    pub const fn safety_multiplier(mut self, multiplier: f64) -> Self {
+        self.config.safety_multiplier = multiplier;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::minimum_frame_start_margin`].
+    #[must_use]
+    pub const fn minimum_frame_start_margin(mut self, margin: Duration) -> Self {
+        self.config.minimum_frame_start_margin = margin;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::nominal_latency`].
+    #[must_use]
+    pub const fn nominal_latency(mut self, latency: Duration) -> Self {
+        self.config.nominal_latency = latency;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::degradation_policy`].
+    #[must_use]
+    pub const fn degradation_policy(mut self, policy: DegradationPolicy) -> Self {
+        self.config.degradation_policy = policy;
+        self
+    }
+
+    /// Sets the number of consecutive misses before depth grows.
+    ///
+    /// Switches a [`DegradationPolicy::Fixed`] policy to
+    /// [`DegradationPolicy::Adaptive`] with the default recovery threshold.
+    #[must_use]
+    pub const fn miss_threshold(mut self, threshold: u32) -> Self {
+        let recovery_threshold = match self.config.degradation_policy {
+            DegradationPolicy::Adaptive {
+                recovery_threshold, ..
+            } => recovery_threshold,
+            DegradationPolicy::Fixed => Self::DEFAULT_RECOVERY_THRESHOLD,
+        };
+        self.config.degradation_policy = DegradationPolicy::Adaptive {
+            miss_threshold: threshold,
+            recovery_threshold,
+        };
+        self
+    }
+
+    /// Sets the number of consecutive hits before depth shrinks.
+    ///
+    /// Switches a [`DegradationPolicy::Fixed`] policy to
+    /// [`DegradationPolicy::Adaptive`] with the default miss threshold.
+    #[must_use]
+    pub const fn recovery_threshold(mut self, threshold: u32) -> Self {
+        let miss_threshold = match self.config.degradation_policy {
+            DegradationPolicy::Adaptive { miss_threshold, .. } => miss_threshold,
+            DegradationPolicy::Fixed => Self::DEFAULT_MISS_THRESHOLD,
+        };
+        self.config.degradation_policy = DegradationPolicy::Adaptive {
+            miss_threshold,
+            recovery_threshold: threshold,
+        };
+        self
+    }
+
+    /// Sets [`SchedulerConfig::depth_change_hysteresis`].
+    #[must_use]
+    pub const fn depth_change_hysteresis(mut self, frames: u32) -> Self {
+        self.config.depth_change_hysteresis = frames;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::present_offset_alpha`].
+    #[must_use]
+    pub const fn present_offset_alpha(mut self, alpha: f64) -> Self {
+        self.config.present_offset_alpha = alpha;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::max_sample_advance_intervals`].
+    #[must_use]
+    pub const fn max_sample_advance_intervals(mut self, intervals: Option<u32>) -> Self {
+        self.config.max_sample_advance_intervals = intervals;
+        self
+    }
+
+    /// Sets [`SchedulerConfig::vrr`].
+    #[must_use]
+    pub const fn vrr(mut self, range: Option<VrrRange>) -> Self {
+        self.config.vrr = range;
+        self
+    }
+
+    /// Returns the configured [`SchedulerConfig`].
+    #[must_use]
+    pub const fn build(self) -> SchedulerConfig {
+        self.config
+    }
 }
 
 /// Snapshot of scheduler adaptation state for diagnostics.
@@ -150,6 +402,122 @@ pub struct SchedulerState {
     pub consecutive_hits: u32,
 }
 
+/// Rolling scheduler statistics for HUDs and tuning.
+///
+/// Returned by [`Scheduler::stats`] and [`Scheduler::stats_for`]. Windowed
+/// values cover the last [`STATS_WINDOW`] observed frames for one output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchedulerStats {
+    /// Number of observed frames currently in the window.
+    pub window_len: u32,
+    /// Mean build cost over the window, in host-time ticks.
+    pub mean_build_cost_ticks: u64,
+    /// Largest build cost in the window, in host-time ticks.
+    pub max_build_cost_ticks: u64,
+    /// Frames in the window reported as deadline misses or pacing overruns.
+    pub window_misses: u32,
+    /// Source refresh interval used by the most recent plan, if any.
+    pub refresh_interval: Option<Duration>,
+    /// Number of times the pipeline depth has changed.
+    pub depth_changes: u32,
+    /// Learned offset of actual presentation from predicted presentation, in
+    /// host-time ticks.
+    ///
+    /// Positive values mean frames present later than predicted. This stays
+    /// zero unless [`SchedulerConfig::present_offset_alpha`] is non-zero.
+    pub present_offset_ticks: i64,
+    /// Interquartile range of build costs over the window, in host-time
+    /// ticks.
+    ///
+    /// A wide spread means the frame pipeline itself is unstable, as opposed
+    /// to the display presenting late.
+    pub build_cost_iqr_ticks: u64,
+    /// Interquartile range of actual minus expected present times over the
+    /// window's frames that reported both, in host-time ticks.
+    ///
+    /// A wide spread means presentation is jittery relative to the
+    /// scheduler's predictions.
+    pub present_error_iqr_ticks: u64,
+}
+
+/// Number of frames covered by windowed [`SchedulerStats`] values.
+pub const STATS_WINDOW: usize = 32;
+
+const _: () = assert!(
+    STATS_WINDOW <= u32::BITS as usize,
+    "misses are tracked as one bit per window slot"
+);
+
+/// Fixed-size ring of recent per-frame observations.
+#[derive(Clone, Copy, Debug)]
+struct FrameHistory {
+    build_costs: [u64; STATS_WINDOW],
+    /// Bit `i` is set when the frame in `build_costs[i]` missed.
+    misses: u32,
+    /// Actual minus expected present time, in signed ticks.
+    present_errors: [i64; STATS_WINDOW],
+    /// Bit `i` is set when `present_errors[i]` holds a sample.
+    present_error_samples: u32,
+    len: usize,
+    next: usize,
+}
+
+impl FrameHistory {
+    const fn new() -> Self {
+        Self {
+            build_costs: [0; STATS_WINDOW],
+            misses: 0,
+            present_errors: [0; STATS_WINDOW],
+            present_error_samples: 0,
+            len: 0,
+            next: 0,
+        }
+    }
+
+    fn push(&mut self, build_cost_ticks: u64, missed: bool, present_error: Option<i64>) {
+        self.build_costs[self.next] = build_cost_ticks;
+        let bit = 1_u32 << self.next;
+        if missed {
+            self.misses |= bit;
+        } else {
+            self.misses &= !bit;
+        }
+        self.present_errors[self.next] = present_error.unwrap_or(0);
+        if present_error.is_some() {
+            self.present_error_samples |= bit;
+        } else {
+            self.present_error_samples &= !bit;
+        }
+        self.next = (self.next + 1) % STATS_WINDOW;
+        self.len = (self.len + 1).min(STATS_WINDOW);
+    }
+
+    fn window(&self) -> &[u64] {
+        &self.build_costs[..self.len]
+    }
+
+    /// Copies the window's present-error samples into `out`, returning how
+    /// many there are.
+    fn present_error_window(&self, out: &mut [i64; STATS_WINDOW]) -> usize {
+        let mut count = 0;
+        for (i, &error) in self.present_errors[..self.len].iter().enumerate() {
+            if self.present_error_samples & (1 << i) != 0 {
+                out[count] = error;
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+/// Sorts `samples` and returns their lower and upper quartiles (nearest
+/// rank), or `None` when empty.
+fn quartiles<T: Copy + Ord>(samples: &mut [T]) -> Option<(T, T)> {
+    samples.sort_unstable();
+    let last = samples.len().checked_sub(1)?;
+    Some((samples[last / 4], samples[last * 3 / 4]))
+}
+
 /// Exponential moving average tracker.
 #[derive(Clone, Copy, Debug)]
 struct Ema {
@@ -192,6 +560,13 @@ fn sanitize_ema_alpha(alpha: f64) -> f64 {
     alpha.clamp(0.0, 1.0)
 }
 
+fn sanitize_present_offset_alpha(alpha: f64) -> f64 {
+    if !alpha.is_finite() {
+        return 0.0;
+    }
+    alpha.clamp(0.0, 1.0)
+}
+
 fn sanitize_safety_multiplier(multiplier: f64) -> f64 {
     if !multiplier.is_finite() || multiplier < 0.0 {
         return 1.0;
@@ -214,229 +589,220 @@ fn f64_ticks_to_u64(ticks: f64) -> u64 {
     ticks as u64
 }
 
-/// Low-level planner that converts [`FrameOpportunity`] values into
-/// [`FramePlan`]s and adapts over time.
-///
-/// Custom integrations call [`Scheduler::plan`] when they are ready to make a
-/// frame plan, then call [`Scheduler::observe`] with [`PresentFeedback`] after
-/// submission. Most host applications should start with
-/// [`FrameDriver`](crate::FrameDriver), which owns queued demand, retained
-/// frame-start waits, and timing-summary construction around this scheduler.
-///
-/// # Pipeline depth
-///
-/// Pipeline depth controls how many selected frame intervals ahead the
-/// scheduler plans smooth background work, bounded by
-/// [`SchedulerConfig::min_depth`] and [`SchedulerConfig::max_depth`]. A depth
-/// of 1 means no extra whole-frame lookahead (lowest latency, highest risk of
-/// missing deadlines). A depth of 3 means animation/background plans target
-/// two selected intervals beyond the next eligible present slot. One-shot
-/// input and continuous input remain latency-first and are not shifted by
-/// pipeline depth.
-///
-/// # Adaptive behavior
-///
-/// With [`DegradationPolicy::Adaptive`] (set via
-/// [`SchedulerConfig::degradation_policy`]), the scheduler increases depth
-/// after consecutive deadline misses (trading latency for safety) and
-/// decreases depth after sustained hits (reclaiming latency). The
-/// EMA-smoothed build cost feeds into a safety margin (build cost ×
-/// multiplier) that backends can query via
-/// [`Scheduler::safety_margin_ticks()`]. The [`FramePlan::frame_start`] field
-/// applies that margin directly so hosts can schedule a redraw wake without
-/// duplicating scheduler policy.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "float-to-int casts saturate and non-finite values are mapped to zero"
+)]
+fn f64_ticks_to_i64(ticks: f64) -> i64 {
+    if !ticks.is_finite() {
+        return 0;
+    }
+    ticks as i64
+}
+
+/// Shifts `time` by a signed tick offset, never moving it before `floor`.
+fn offset_host_time(time: HostTime, offset_ticks: f64, floor: HostTime) -> HostTime {
+    let shifted = if offset_ticks >= 0.0 {
+        time.checked_add(Duration(f64_ticks_to_u64(offset_ticks)))
+            .unwrap_or(HostTime(u64::MAX))
+    } else {
+        time.checked_sub(Duration(f64_ticks_to_u64(-offset_ticks)))
+            .unwrap_or(floor)
+    };
+    shifted.max(floor)
+}
+
+/// Gap, in refresh intervals, past which
+/// [`SchedulerConfig::max_sample_advance_intervals`] stops clamping and sample
+/// time jumps to wall-clock time.
 ///
-/// # Usage
+/// At 60 Hz this is half a second: long enough to cover GC pauses and decode
+/// spikes, but short of an idle period with no frames planned.
+pub const SAMPLE_CLAMP_RESET_INTERVALS: u64 = 30;
+
+/// Maximum number of outputs whose adaptive state a [`Scheduler`] tracks at
+/// once.
 ///
-/// ```rust,ignore
-/// let plan = scheduler.plan(opportunity, demand);
-/// // ... build and submit frame ...
-/// scheduler.observe(&feedback);
-/// ```
-#[derive(Debug)]
-pub struct Scheduler {
-    config: SchedulerConfig,
+/// When a tick or feedback arrives for an untracked output and every slot is
+/// in use, the least recently used output's state is discarded.
+pub const MAX_TRACKED_OUTPUTS: usize = 4;
+
+/// Relative change in refresh interval below which a reported interval is
+/// treated as jitter rather than a refresh-rate switch.
+const REFRESH_CHANGE_TOLERANCE: f64 = 0.05;
+
+/// Adaptive state for one output.
+#[derive(Clone, Copy, Debug)]
+struct OutputState {
+    output: OutputId,
     pipeline_depth: u8,
     build_cost_ema: Ema,
     safety_margin_ticks: u64,
     consecutive_misses: u32,
     consecutive_hits: u32,
+    last_used: u64,
+    history: FrameHistory,
+    refresh_interval: Option<Duration>,
+    depth_changes: u32,
+    frames_since_depth_change: u32,
+    present_offset: Ema,
+    last_sample_time: Option<HostTime>,
+    last_actual_present: Option<HostTime>,
 }
 
-impl Scheduler {
-    /// Creates a low-level scheduler with the given configuration.
-    ///
-    /// Use [`FrameDriver::new`](crate::FrameDriver::new) instead when the host
-    /// wants frameclock to own pending demand and queued frame lifecycle state.
-    #[must_use]
-    pub fn new(mut config: SchedulerConfig) -> Self {
-        config.min_depth = config.min_depth.max(1);
-        config.max_depth = config.max_depth.max(config.min_depth);
-        config.initial_depth = config
-            .initial_depth
-            .clamp(config.min_depth, config.max_depth);
-        config.ema_alpha = sanitize_ema_alpha(config.ema_alpha);
-        config.safety_multiplier = sanitize_safety_multiplier(config.safety_multiplier);
-
+impl OutputState {
+    const fn new(output: OutputId, config: &SchedulerConfig) -> Self {
         Self {
+            output,
             pipeline_depth: config.initial_depth,
             build_cost_ema: Ema::new(config.ema_alpha),
             safety_margin_ticks: 0,
             consecutive_misses: 0,
             consecutive_hits: 0,
-            config,
+            last_used: 0,
+            history: FrameHistory::new(),
+            refresh_interval: None,
+            depth_changes: 0,
+            frames_since_depth_change: u32::MAX,
+            present_offset: Ema::new(config.present_offset_alpha),
+            last_sample_time: None,
+            last_actual_present: None,
         }
     }
 
-    /// Produces a [`FramePlan`] from a frame opportunity and demand.
-    ///
-    /// Hosts should usually call this only with non-empty [`FrameDemand`].
-    /// `FrameDemand::NONE` is accepted for passive pacing diagnostics or
-    /// backend bookkeeping, but it should not be treated as ordinary render
-    /// demand.
-    #[must_use]
-    pub fn plan(&mut self, opportunity: FrameOpportunity, demand: FrameDemand) -> FramePlan {
-        let tick = opportunity.tick;
-        let hints = opportunity.hints;
-        let source_interval = self.source_interval(opportunity);
-        let build_cost = self.build_cost_estimate();
-        let frame_interval = self.frame_interval(
-            demand,
-            opportunity.display_timing,
-            source_interval,
-            build_cost,
-        );
-        let schedule_delta = self.schedule_delta(demand, frame_interval, source_interval);
-        let presentation_timing = hints.presentation_timing();
-        let platform_present = if presentation_timing.has_target_present() {
-            hints
-                .desired_present()
-                .filter(|present| *present >= tick.now)
-        } else {
-            None
+    /// Limits how far `sample_time` may move past the previous plan's sample
+    /// time and records the result.
+    fn clamp_sample_time(
+        &mut self,
+        config: &SchedulerConfig,
+        sample_time: HostTime,
+        source_interval: Duration,
+    ) -> HostTime {
+        let clamped = match (config.max_sample_advance_intervals, self.last_sample_time) {
+            (Some(intervals), Some(last)) if !source_interval.is_zero() => {
+                let reset_gap = source_interval.saturating_mul(SAMPLE_CLAMP_RESET_INTERVALS);
+                if sample_time.saturating_duration_since(last) > reset_gap {
+                    sample_time
+                } else {
+                    let max_advance = source_interval.saturating_mul(u64::from(intervals.max(2)));
+                    let limit = last.checked_add(max_advance).unwrap_or(HostTime(u64::MAX));
+                    sample_time.min(limit)
+                }
+            }
+            _ => sample_time,
         };
-        let base_present = platform_present
-            .unwrap_or_else(|| tick.now.checked_add(source_interval).unwrap_or(tick.now));
-        let scheduled_present = base_present
-            .checked_add(schedule_delta)
-            .unwrap_or(base_present);
-        let base_commit_deadline = hints.latest_commit().max(tick.now);
-        let commit_deadline = base_commit_deadline
-            .checked_add(schedule_delta)
-            .unwrap_or(base_commit_deadline);
+        self.last_sample_time = Some(clamped);
+        clamped
+    }
 
-        let (target_present, sample_time) = match presentation_timing {
-            PresentationTiming::Predictive | PresentationTiming::Estimated => {
-                let target_present = platform_present.map(|_| scheduled_present);
-                let sample_time = target_present.unwrap_or(scheduled_present);
-                (target_present, sample_time)
-            }
-            PresentationTiming::PacingOnly => {
-                // No reliable present time; sample at the scheduler-selected
-                // pacing target but do not report it as presentation truth.
-                (None, scheduled_present)
+    /// Records the output's refresh interval, resetting cadence-dependent
+    /// state when it changes.
+    ///
+    /// A refresh-rate switch (a mode change, or a VRR display dropping to a
+    /// lower rate) does not change how long a frame takes to build, so the
+    /// build-cost estimate and the safety margin derived from it are kept;
+    /// budgets are computed from the new interval on the next plan. Hit/miss
+    /// streaks and the learned present offset describe the old cadence and
+    /// are reset.
+    ///
+    /// Some sources, such as `CVDisplayLink`, report a slightly different
+    /// interval every frame. An interval within [`REFRESH_CHANGE_TOLERANCE`]
+    /// of the recorded one is treated as jitter and leaves the recorded
+    /// interval and the estimates unchanged.
+    fn set_refresh_interval(&mut self, interval: Option<Duration>) {
+        if let (Some(old), Some(new)) = (self.refresh_interval, interval) {
+            let ratio = new.ticks() as f64 / old.ticks() as f64;
+            if (ratio - 1.0).abs() < REFRESH_CHANGE_TOLERANCE {
+                return;
             }
-        };
-
-        FramePlan {
-            demand,
-            frame_interval,
-            frame_start: self.frame_start(tick.now, commit_deadline, demand),
-            sample_time,
-            target_present,
-            presentation_timing,
-            commit_deadline,
-            pipeline_depth: self.pipeline_depth,
-            output: tick.output,
-            frame_index: tick.frame_index,
+            self.consecutive_misses = 0;
+            self.consecutive_hits = 0;
+            self.present_offset = Ema::new(self.present_offset.alpha);
         }
+        self.refresh_interval = interval;
     }
 
-    fn schedule_delta(
-        &self,
-        demand: FrameDemand,
-        frame_interval: Duration,
-        source_interval: Duration,
-    ) -> Duration {
-        let cadence_delta = frame_interval.saturating_sub(source_interval);
-        let depth_delta = self.depth_lookahead_delta(demand, frame_interval);
-        cadence_delta.saturating_add(depth_delta)
-    }
-
-    fn depth_lookahead_delta(&self, demand: FrameDemand, frame_interval: Duration) -> Duration {
-        if matches!(
-            demand.dominant_class(),
-            FrameDemandClass::Input | FrameDemandClass::ContinuousInput
-        ) {
-            return Duration::ZERO;
+    /// Returns the learned present offset in signed host-time ticks.
+    fn present_offset_ticks(&self) -> f64 {
+        if self.present_offset.alpha == 0.0 {
+            return 0.0;
         }
-
-        frame_interval.saturating_mul(u64::from(self.pipeline_depth.saturating_sub(1)))
-    }
-
-    fn source_interval(&self, opportunity: FrameOpportunity) -> Duration {
-        opportunity
-            .tick
-            .refresh_interval
-            .filter(|ticks| *ticks > 0)
-            .map(Duration)
-            .unwrap_or_else(|| {
-                let display_min = opportunity.display_timing.min_interval();
-                if display_min.is_zero() {
-                    self.config.nominal_latency
-                } else {
-                    display_min
-                }
-            })
+        self.present_offset.get()
     }
 
-    fn frame_interval(
-        &self,
-        demand: FrameDemand,
-        display: DisplayTiming,
-        source_interval: Duration,
-        build_cost: Duration,
-    ) -> Duration {
-        let source_interval = if source_interval.is_zero() {
-            display.min_interval()
-        } else {
-            source_interval
-        };
-        let policy = demand.dominant_class();
-        let needed = match policy {
-            FrameDemandClass::None | FrameDemandClass::Input => return source_interval,
-            FrameDemandClass::ContinuousInput => build_cost,
-            FrameDemandClass::Animation => build_cost.saturating_add(source_interval.div_u64(4)),
-            FrameDemandClass::Background => build_cost
-                .saturating_add(source_interval.div_u64(4))
-                .max(source_interval.saturating_mul(2)),
+    /// Folds one actual-vs-expected present sample into the learned offset.
+    ///
+    /// `expected_present` comes from a plan that already carried the learned
+    /// offset, so the raw platform bias is the residual plus that offset.
+    /// Residuals larger than half a refresh interval are whole-frame misses
+    /// rather than prediction bias, so they are ignored.
+    fn observe_present_offset(&mut self, feedback: &PresentFeedback) {
+        if self.present_offset.alpha == 0.0 {
+            return;
+        }
+        let (Some(actual), Some(expected)) = (feedback.actual_present, feedback.expected_present)
+        else {
+            return;
         };
-
-        display.choose_interval(needed).max(source_interval)
+        let residual = actual.ticks() as f64 - expected.ticks() as f64;
+        if let Some(interval) = self.refresh_interval
+            && residual.abs() * 2.0 > interval.ticks() as f64
+        {
+            return;
+        }
+        self.present_offset
+            .update(self.present_offset.get() + residual);
     }
 
-    fn frame_start(
-        &self,
-        now: HostTime,
-        commit_deadline: HostTime,
-        demand: FrameDemand,
-    ) -> HostTime {
-        if demand.dominant_class() == FrameDemandClass::Input {
-            return now;
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "window length is bounded by STATS_WINDOW"
+    )]
+    fn stats(&self) -> SchedulerStats {
+        let window = self.history.window();
+        let sum = window
+            .iter()
+            .fold(0_u64, |sum, cost| sum.saturating_add(*cost));
+        let mut costs = [0; STATS_WINDOW];
+        costs[..window.len()].copy_from_slice(window);
+        let mut errors = [0; STATS_WINDOW];
+        let error_count = self.history.present_error_window(&mut errors);
+        SchedulerStats {
+            window_len: window.len() as u32,
+            mean_build_cost_ticks: sum.checked_div(window.len() as u64).unwrap_or(0),
+            max_build_cost_ticks: window.iter().copied().max().unwrap_or(0),
+            window_misses: self.history.misses.count_ones(),
+            refresh_interval: self.refresh_interval,
+            depth_changes: self.depth_changes,
+            present_offset_ticks: f64_ticks_to_i64(self.present_offset_ticks()),
+            build_cost_iqr_ticks: quartiles(&mut costs[..window.len()])
+                .map_or(0, |(q1, q3)| q3 - q1),
+            present_error_iqr_ticks: quartiles(&mut errors[..error_count])
+                .map_or(0, |(q1, q3)| q3.abs_diff(q1)),
         }
+    }
 
-        commit_deadline
-            .checked_sub(self.frame_start_margin())
-            .unwrap_or(now)
-            .max(now)
+    /// Changes the pipeline depth unless the hysteresis window is still open.
+    ///
+    /// Returns whether the depth changed.
+    fn try_set_pipeline_depth(&mut self, config: &SchedulerConfig, depth: u8) -> bool {
+        if depth == self.pipeline_depth
+            || self.frames_since_depth_change < config.depth_change_hysteresis
+        {
+            return false;
+        }
+        self.pipeline_depth = depth;
+        self.depth_changes = self.depth_changes.saturating_add(1);
+        self.frames_since_depth_change = 0;
+        true
     }
 
-    fn frame_start_margin(&self) -> Duration {
-        let learned = Duration(self.safety_margin_ticks);
-        if learned > self.config.minimum_frame_start_margin {
-            learned
-        } else {
-            self.config.minimum_frame_start_margin
+    const fn snapshot(&self) -> SchedulerState {
+        SchedulerState {
+            pipeline_depth: self.pipeline_depth,
+            safety_margin_ticks: self.safety_margin_ticks,
+            consecutive_misses: self.consecutive_misses,
+            consecutive_hits: self.consecutive_hits,
         }
     }
 
@@ -448,22 +814,35 @@ impl Scheduler {
         Duration(f64_ticks_to_u64(self.build_cost_ema.get()))
     }
 
-    /// Feeds presentation feedback to adapt scheduling parameters.
-    ///
-    /// Call this after submitting a frame planned by [`Self::plan`]. Hosts using
-    /// [`FrameDriver::submit_frame`](crate::FrameDriver::submit_frame) do not
-    /// call this directly because the driver observes feedback internally.
-    pub fn observe(&mut self, feedback: &PresentFeedback) {
+    fn observe(&mut self, config: &SchedulerConfig, feedback: &PresentFeedback, adapt: bool) {
         // Update build cost EMA.
         let build_ticks = feedback
             .submitted_at
             .saturating_duration_since(feedback.build_start)
             .ticks();
         self.build_cost_ema.update(build_ticks as f64);
+        let missed = feedback.missed_deadline.unwrap_or(false)
+            || (feedback.missed_deadline.is_none() && feedback.pacing_overrun == Some(true));
+        let present_error = match (feedback.actual_present, feedback.expected_present) {
+            (Some(actual), Some(expected)) => {
+                Some(actual.ticks().wrapping_sub(expected.ticks()).cast_signed())
+            }
+            _ => None,
+        };
+        self.history.push(build_ticks, missed, present_error);
+        self.frames_since_depth_change = self.frames_since_depth_change.saturating_add(1);
+        self.observe_present_offset(feedback);
+        if feedback.actual_present.is_some() {
+            self.last_actual_present = feedback.actual_present;
+        }
 
         // Update safety margin.
         self.safety_margin_ticks =
-            f64_ticks_to_u64(self.build_cost_ema.get() * self.config.safety_multiplier);
+            f64_ticks_to_u64(self.build_cost_ema.get() * config.safety_multiplier);
+
+        if !adapt {
+            return;
+        }
 
         // Adapt pipeline depth according to degradation policy.
         //
@@ -473,7 +852,7 @@ impl Scheduler {
         // boundary. We still use it, but more conservatively, so pacing-only
         // backends can apply pressure without pretending they know actual
         // presentation truth.
-        match self.config.degradation_policy {
+        match config.degradation_policy {
             DegradationPolicy::Adaptive {
                 miss_threshold,
                 recovery_threshold,
@@ -483,9 +862,9 @@ impl Scheduler {
                     self.consecutive_misses += 1;
                     self.consecutive_hits = 0;
                     if self.consecutive_misses >= miss_threshold
-                        && self.pipeline_depth < self.config.max_depth
+                        && self.pipeline_depth < config.max_depth
+                        && self.try_set_pipeline_depth(config, self.pipeline_depth + 1)
                     {
-                        self.pipeline_depth += 1;
                         self.consecutive_misses = 0;
                     }
                 }
@@ -494,9 +873,9 @@ impl Scheduler {
                     self.consecutive_hits += 1;
                     self.consecutive_misses = 0;
                     if self.consecutive_hits >= recovery_threshold
-                        && self.pipeline_depth > self.config.min_depth
+                        && self.pipeline_depth > config.min_depth
+                        && self.try_set_pipeline_depth(config, self.pipeline_depth - 1)
                     {
-                        self.pipeline_depth -= 1;
                         self.consecutive_hits = 0;
                     }
                 }
@@ -508,9 +887,9 @@ impl Scheduler {
                         self.consecutive_hits = 0;
                         let pacing_threshold = miss_threshold.saturating_mul(2).max(1);
                         if self.consecutive_misses >= pacing_threshold
-                            && self.pipeline_depth < self.config.max_depth
+                            && self.pipeline_depth < config.max_depth
+                            && self.try_set_pipeline_depth(config, self.pipeline_depth + 1)
                         {
-                            self.pipeline_depth += 1;
                             self.consecutive_misses = 0;
                         }
                     }
@@ -526,769 +905,2235 @@ impl Scheduler {
             DegradationPolicy::Fixed => {}
         }
     }
+}
 
-    /// Returns the current pipeline depth.
-    #[must_use]
-    pub fn pipeline_depth(&self) -> u8 {
-        self.pipeline_depth
-    }
+/// Low-level planner that converts [`FrameOpportunity`] values into
+/// [`FramePlan`]s and adapts over time.
+///
+/// Custom integrations call [`Scheduler::plan`] when they are ready to make a
+/// frame plan, then call [`Scheduler::observe`] with [`PresentFeedback`] after
+/// submission. Most host applications should start with
+/// [`FrameDriver`](crate::FrameDriver), which owns queued demand, retained
+/// frame-start waits, and timing-summary construction around this scheduler.
+///
+/// # Pipeline depth
+///
+/// Pipeline depth controls how many selected frame intervals ahead the
+/// scheduler plans smooth background work, bounded by
+/// [`SchedulerConfig::min_depth`] and [`SchedulerConfig::max_depth`]. A depth
+/// of 1 means no extra whole-frame lookahead (lowest latency, highest risk of
+/// missing deadlines). A depth of 3 means animation/background plans target
+/// two selected intervals beyond the next eligible present slot. One-shot
+/// input and continuous input remain latency-first and are not shifted by
+/// pipeline depth.
+///
+/// # Adaptive behavior
+///
+/// With [`DegradationPolicy::Adaptive`] (set via
+/// [`SchedulerConfig::degradation_policy`]), the scheduler increases depth
+/// after consecutive deadline misses (trading latency for safety) and
+/// decreases depth after sustained hits (reclaiming latency). The
+/// EMA-smoothed build cost feeds into a safety margin (build cost ×
+/// multiplier) that backends can query via
+/// [`Scheduler::safety_margin_ticks()`]. The [`FramePlan::frame_start`] field
+/// applies that margin directly so hosts can schedule a redraw wake without
+/// duplicating scheduler policy.
+///
+/// When a tick arrives with a different
+/// [`FrameTick::refresh_interval`](crate::FrameTick::refresh_interval) than
+/// the previous one for the same output, the build-cost estimate and safety
+/// margin are rescaled to the new interval and hit/miss counters restart, so
+/// a refresh-rate switch is not mistaken for jitter.
+///
+/// # Multiple outputs
+///
+/// Adaptive state (pipeline depth, build-cost estimate, safety margin, and
+/// hit/miss counters) is kept separately for each [`OutputId`], up to
+/// [`MAX_TRACKED_OUTPUTS`]. [`Scheduler::plan`] selects state from
+/// [`FrameTick::output`](crate::FrameTick::output) and [`Scheduler::observe`]
+/// from [`PresentFeedback::output`], so ticks for displays with different
+/// refresh rates do not corrupt each other's estimates. The un-suffixed
+/// accessors such as [`Scheduler::pipeline_depth`] report the output that was
+/// most recently planned or observed; use the `_for` variants to query a
+/// specific output.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let plan = scheduler.plan(opportunity, demand);
+/// // ... build and submit frame ...
+/// scheduler.observe(&feedback);
+/// ```
+#[derive(Debug)]
+pub struct Scheduler {
+    config: SchedulerConfig,
+    outputs: [Option<OutputState>; MAX_TRACKED_OUTPUTS],
+    current_output: OutputId,
+    use_counter: u64,
+    depth_override: Option<u8>,
+    pending_feedback: Option<PendingFeedback>,
+}
 
-    /// Returns the current estimated safety margin in ticks.
+impl Scheduler {
+    /// Creates a low-level scheduler with the given configuration.
+    ///
+    /// Use [`FrameDriver::new`](crate::FrameDriver::new) instead when the host
+    /// wants frameclock to own pending demand and queued frame lifecycle state.
     #[must_use]
-    pub fn safety_margin_ticks(&self) -> u64 {
-        self.safety_margin_ticks
-    }
+    pub fn new(mut config: SchedulerConfig) -> Self {
+        config.min_depth = config.min_depth.max(1);
+        config.max_depth = config.max_depth.max(config.min_depth);
+        config.initial_depth = config
+            .initial_depth
+            .clamp(config.min_depth, config.max_depth);
+        config.ema_alpha = sanitize_ema_alpha(config.ema_alpha);
+        config.safety_multiplier = sanitize_safety_multiplier(config.safety_multiplier);
+        config.present_offset_alpha = sanitize_present_offset_alpha(config.present_offset_alpha);
 
-    /// Returns a snapshot of the current scheduler adaptation state.
-    #[must_use]
-    pub const fn state(&self) -> SchedulerState {
-        SchedulerState {
-            pipeline_depth: self.pipeline_depth,
-            safety_margin_ticks: self.safety_margin_ticks,
-            consecutive_misses: self.consecutive_misses,
-            consecutive_hits: self.consecutive_hits,
+        Self {
+            outputs: [None; MAX_TRACKED_OUTPUTS],
+            current_output: OutputId::default(),
+            use_counter: 0,
+            depth_override: None,
+            pending_feedback: None,
+            config,
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::output::OutputId;
-    use crate::time::HostTime;
-    use crate::timing::{FrameTick, PresentHints};
 
-    use super::*;
-
-    const REFRESH_INTERVAL: Duration = Duration(16_666_667);
-
-    fn make_tick(now: u64, predicted: Option<u64>) -> FrameTick {
-        FrameTick {
-            now: HostTime(now),
-            predicted_present: predicted.map(HostTime),
-            refresh_interval: Some(REFRESH_INTERVAL.ticks()),
-            frame_index: 0,
-            output: OutputId(0),
-            prev_actual_present: None,
-        }
+    /// Returns the tracked state for `output`, if any.
+    fn output_state(&self, output: OutputId) -> Option<&OutputState> {
+        self.outputs
+            .iter()
+            .flatten()
+            .find(|state| state.output == output)
     }
 
-    fn make_hints(
-        presentation_timing: PresentationTiming,
-        predicted: Option<u64>,
-        deadline: u64,
-    ) -> PresentHints {
-        if let Some(predicted) = predicted
-            && presentation_timing.has_target_present()
-        {
-            return PresentHints::new(
-                presentation_timing,
-                Some(HostTime(predicted)),
-                HostTime(deadline),
-            );
-        }
-
-        PresentHints::new(presentation_timing, None, HostTime(deadline))
+    /// Returns the state for `output`, creating it if needed, and marks it as
+    /// the current output.
+    fn output_state_mut(&mut self, output: OutputId) -> &mut OutputState {
+        self.current_output = output;
+        self.use_counter += 1;
+        let use_counter = self.use_counter;
+
+        let idx = self
+            .outputs
+            .iter()
+            .position(|slot| slot.is_some_and(|state| state.output == output))
+            .or_else(|| self.outputs.iter().position(Option::is_none))
+            .unwrap_or_else(|| {
+                // Evict the least recently used output.
+                self.outputs
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, slot)| slot.map_or(0, |state| state.last_used))
+                    .map_or(0, |(idx, _)| idx)
+            });
+        let config = self.config;
+        let state = self.outputs[idx]
+            .take()
+            .filter(|state| state.output == output)
+            .unwrap_or_else(|| OutputState::new(output, &config));
+        let state = self.outputs[idx].insert(state);
+        state.last_used = use_counter;
+        state
     }
 
-    fn make_opportunity(
-        presentation_timing: PresentationTiming,
-        now: u64,
-        predicted: Option<u64>,
-        deadline: u64,
-    ) -> FrameOpportunity {
-        let tick = make_tick(now, predicted);
-        let hints = make_hints(presentation_timing, predicted, deadline);
-        FrameOpportunity {
-            tick,
-            hints,
-            display_timing: DisplayTiming::fixed(REFRESH_INTERVAL),
+    /// Returns a snapshot for `output`, using initial state if it is untracked.
+    fn snapshot_for(&self, output: OutputId) -> SchedulerState {
+        let mut snapshot = self
+            .output_state(output)
+            .copied()
+            .unwrap_or_else(|| OutputState::new(output, &self.config))
+            .snapshot();
+        if let Some(depth) = self.depth_override {
+            snapshot.pipeline_depth = depth;
         }
+        snapshot
     }
 
-    #[test]
-    fn predictive_plan_uses_predicted_present() {
-        let config = SchedulerConfig::predictive();
-        let mut sched = Scheduler::new(config);
-
-        let plan = sched.plan(
-            make_opportunity(PresentationTiming::Predictive, 1000, Some(2000), 1800),
-            FrameDemand::ANIMATION,
-        );
-
-        assert_eq!(plan.demand, FrameDemand::ANIMATION);
-        assert_eq!(plan.frame_interval, REFRESH_INTERVAL);
-        assert_eq!(plan.target_present, Some(HostTime(2000)));
-        assert_eq!(plan.sample_time, HostTime(2000));
-        assert_eq!(plan.commit_deadline, HostTime(1800));
-        assert_eq!(plan.frame_start, HostTime(1000));
+    /// Pins the pipeline depth for every output, or resumes adaptation.
+    ///
+    /// While an override is set, [`Self::plan`] uses `depth` for lookahead and
+    /// reports it in [`FramePlan::pipeline_depth`], and [`Self::observe`] keeps
+    /// training build cost, safety margin, and statistics but never changes
+    /// the adaptive depth. Passing `None` resumes adaptation from the depth
+    /// each output had before the override. A depth of 0 is treated as 1.
+    ///
+    /// This is intended for deterministic latency, such as a kiosk that must
+    /// stay at depth 1 or a benchmark pinned to depth 3.
+    pub fn set_depth_override(&mut self, depth: Option<u8>) {
+        self.depth_override = depth.map(|depth| depth.max(1));
     }
 
-    #[test]
-    fn pacing_only_plan_has_no_target_present() {
-        let config = SchedulerConfig::pacing_only();
-        let mut sched = Scheduler::new(config);
+    /// Returns the pinned pipeline depth, if one is set.
+    #[must_use]
+    pub const fn depth_override(&self) -> Option<u8> {
+        self.depth_override
+    }
 
-        let plan = sched.plan(
-            make_opportunity(PresentationTiming::PacingOnly, 1_000_000, None, 17_000_000),
+    /// Produces a [`FramePlan`] from a frame opportunity and demand.
+    ///
+    /// Hosts should usually call this only with non-empty [`FrameDemand`].
+    /// `FrameDemand::NONE` is accepted for passive pacing diagnostics or
+    /// backend bookkeeping, but it should not be treated as ordinary render
+    /// demand.
+    #[must_use]
+    pub fn plan(&mut self, opportunity: FrameOpportunity, demand: FrameDemand) -> FramePlan {
+        if let Some(range) = self.config.vrr {
+            return self.plan_vrr(opportunity, demand, range);
+        }
+        let tick = opportunity.tick;
+        let hints = opportunity.hints;
+        let source_interval = self.source_interval(opportunity);
+        let state = self.output_state_mut(tick.output);
+        state.set_refresh_interval(Some(source_interval).filter(|interval| !interval.is_zero()));
+        let mut state = *state;
+        if let Some(depth) = self.depth_override {
+            state.pipeline_depth = depth;
+        }
+        let build_cost = state.build_cost_estimate();
+        let frame_interval = self.frame_interval(
+            demand,
+            opportunity.display_timing,
+            source_interval,
+            build_cost,
+        );
+        let schedule_delta = Self::schedule_delta(&state, demand, frame_interval, source_interval);
+        let presentation_timing = hints.presentation_timing();
+        let platform_present = if presentation_timing.has_target_present() {
+            hints
+                .desired_present()
+                .filter(|present| *present >= tick.now)
+        } else {
+            None
+        };
+        let base_present = platform_present
+            .unwrap_or_else(|| tick.now.checked_add(source_interval).unwrap_or(tick.now));
+        let scheduled_present = base_present
+            .checked_add(schedule_delta)
+            .unwrap_or(base_present);
+        let base_commit_deadline = hints.latest_commit().max(tick.now);
+        let commit_deadline = base_commit_deadline
+            .checked_add(schedule_delta)
+            .unwrap_or(base_commit_deadline);
+
+        let (target_present, sample_time) = match presentation_timing {
+            PresentationTiming::Predictive | PresentationTiming::Estimated => {
+                let target_present = platform_present.map(|_| {
+                    offset_host_time(scheduled_present, state.present_offset_ticks(), tick.now)
+                });
+                let sample_time = target_present.unwrap_or(scheduled_present);
+                (target_present, sample_time)
+            }
+            PresentationTiming::PacingOnly => {
+                // No reliable present time; sample at the scheduler-selected
+                // pacing target but do not report it as presentation truth.
+                (None, scheduled_present)
+            }
+        };
+
+        let config = self.config;
+        let sample_time = self.output_state_mut(tick.output).clamp_sample_time(
+            &config,
+            sample_time,
+            source_interval,
+        );
+
+        FramePlan {
+            demand,
+            frame_interval,
+            frame_start: self.frame_start(&state, tick.now, commit_deadline, demand),
+            sample_time,
+            target_present,
+            presentation_timing,
+            commit_deadline,
+            pipeline_depth: state.pipeline_depth,
+            output: tick.output,
+            frame_index: tick.frame_index,
+        }
+    }
+
+    /// Plans a frame for an adaptive-sync display.
+    ///
+    /// The interval is chosen like a fixed-rate plan over a continuous range,
+    /// but never shorter than the frame-start margin so the learned build cost
+    /// fits between the previous present and the target.
+    ///
+    /// The commit deadline is the backend's
+    /// [`latest_commit`](PresentHints::latest_commit) when that is still in
+    /// the future. Otherwise it is the scheduled present (capped at the
+    /// slowest refresh the panel allows) less the frame-start margin, leaving
+    /// the compositor time to scan out.
+    fn plan_vrr(
+        &mut self,
+        opportunity: FrameOpportunity,
+        demand: FrameDemand,
+        range: VrrRange,
+    ) -> FramePlan {
+        let tick = opportunity.tick;
+        let hints = opportunity.hints;
+        let state = self.output_state_mut(tick.output);
+        state.set_refresh_interval(Some(range.min_interval).filter(|i| !i.is_zero()));
+        let mut state = *state;
+        if let Some(depth) = self.depth_override {
+            state.pipeline_depth = depth;
+        }
+        let build_cost = state.build_cost_estimate();
+        let frame_interval = self
+            .frame_interval(
+                demand,
+                range.display_timing(),
+                range.min_interval,
+                build_cost,
+            )
+            .max(self.frame_start_margin(&state));
+        let depth_delta = Self::depth_lookahead_delta(&state, demand, frame_interval);
+
+        let last_present = state
+            .last_actual_present
+            .filter(|present| *present <= tick.now)
+            .unwrap_or(tick.now);
+        let earliest = tick.now.checked_add(build_cost).unwrap_or(tick.now);
+        let scheduled_present = last_present
+            .checked_add(frame_interval.saturating_add(depth_delta))
+            .unwrap_or(HostTime(u64::MAX))
+            .max(earliest);
+        let refresh_limit = last_present
+            .checked_add(range.max_interval.saturating_add(depth_delta))
+            .unwrap_or(HostTime(u64::MAX));
+        let commit_deadline = if hints.latest_commit() > tick.now {
+            hints.latest_commit()
+        } else {
+            scheduled_present
+                .min(refresh_limit)
+                .checked_sub(self.frame_start_margin(&state))
+                .unwrap_or(tick.now)
+                .max(tick.now)
+        };
+
+        let presentation_timing = hints.presentation_timing();
+        let target_present = presentation_timing
+            .has_target_present()
+            .then_some(scheduled_present);
+
+        let config = self.config;
+        let sample_time = self.output_state_mut(tick.output).clamp_sample_time(
+            &config,
+            scheduled_present,
+            range.min_interval,
+        );
+
+        FramePlan {
+            demand,
+            frame_interval,
+            frame_start: self.frame_start(&state, tick.now, commit_deadline, demand),
+            sample_time,
+            target_present,
+            presentation_timing,
+            commit_deadline,
+            pipeline_depth: state.pipeline_depth,
+            output: tick.output,
+            frame_index: tick.frame_index,
+        }
+    }
+
+    fn schedule_delta(
+        state: &OutputState,
+        demand: FrameDemand,
+        frame_interval: Duration,
+        source_interval: Duration,
+    ) -> Duration {
+        let cadence_delta = frame_interval.saturating_sub(source_interval);
+        let depth_delta = Self::depth_lookahead_delta(state, demand, frame_interval);
+        cadence_delta.saturating_add(depth_delta)
+    }
+
+    fn depth_lookahead_delta(
+        state: &OutputState,
+        demand: FrameDemand,
+        frame_interval: Duration,
+    ) -> Duration {
+        if matches!(
+            demand.dominant_class(),
+            FrameDemandClass::Input | FrameDemandClass::ContinuousInput
+        ) {
+            return Duration::ZERO;
+        }
+
+        frame_interval.saturating_mul(u64::from(state.pipeline_depth.saturating_sub(1)))
+    }
+
+    fn source_interval(&self, opportunity: FrameOpportunity) -> Duration {
+        opportunity
+            .tick
+            .refresh_interval
+            .filter(|ticks| *ticks > 0)
+            .map(Duration)
+            .unwrap_or_else(|| {
+                let display_min = opportunity.display_timing.min_interval();
+                if display_min.is_zero() {
+                    self.config.nominal_latency
+                } else {
+                    display_min
+                }
+            })
+    }
+
+    fn frame_interval(
+        &self,
+        demand: FrameDemand,
+        display: DisplayTiming,
+        source_interval: Duration,
+        build_cost: Duration,
+    ) -> Duration {
+        let source_interval = if source_interval.is_zero() {
+            display.min_interval()
+        } else {
+            source_interval
+        };
+        let policy = demand.dominant_class();
+        let needed = match policy {
+            FrameDemandClass::None | FrameDemandClass::Input => return source_interval,
+            FrameDemandClass::ContinuousInput => build_cost,
+            FrameDemandClass::Animation => build_cost.saturating_add(source_interval.div_u64(4)),
+            FrameDemandClass::Background => build_cost
+                .saturating_add(source_interval.div_u64(4))
+                .max(source_interval.saturating_mul(2)),
+        };
+
+        display.choose_interval(needed).max(source_interval)
+    }
+
+    fn frame_start(
+        &self,
+        state: &OutputState,
+        now: HostTime,
+        commit_deadline: HostTime,
+        demand: FrameDemand,
+    ) -> HostTime {
+        if demand.dominant_class() == FrameDemandClass::Input {
+            return now;
+        }
+
+        commit_deadline
+            .checked_sub(self.frame_start_margin(state))
+            .unwrap_or(now)
+            .max(now)
+    }
+
+    fn frame_start_margin(&self, state: &OutputState) -> Duration {
+        let learned = Duration(state.safety_margin_ticks);
+        if learned > self.config.minimum_frame_start_margin {
+            learned
+        } else {
+            self.config.minimum_frame_start_margin
+        }
+    }
+
+    /// Feeds presentation feedback to adapt scheduling parameters.
+    ///
+    /// Call this after submitting a frame planned by [`Self::plan`]. Hosts using
+    /// [`FrameDriver::submit_frame`](crate::FrameDriver::submit_frame) do not
+    /// call this directly because the driver observes feedback internally.
+    ///
+    /// Only the adaptive state for [`PresentFeedback::output`] is updated.
+    pub fn observe(&mut self, feedback: &PresentFeedback) {
+        let config = self.config;
+        let adapt = self.depth_override.is_none();
+        self.output_state_mut(feedback.output)
+            .observe(&config, feedback, adapt);
+    }
+
+    /// Holds submission facts for `plan` until its actual present time is
+    /// known.
+    ///
+    /// This is the deferred-feedback path for platforms that report a frame's
+    /// actual present time on the next tick, such as
+    /// [`FrameTick::prev_actual_present`](crate::FrameTick::prev_actual_present).
+    /// Call [`Self::observe_actual_present`] when that time arrives; the
+    /// scheduler keeps the [`PendingFeedback`] in between. If an earlier
+    /// submission is still pending, it is observed first without an actual
+    /// present time, as commit-only feedback.
+    pub fn submit(&mut self, plan: &FramePlan, build_start: HostTime, submitted_at: HostTime) {
+        if self.pending_feedback.is_some() {
+            let _ = self.observe_actual_present(None);
+        }
+        self.pending_feedback = Some(PendingFeedback::new(*plan, build_start, submitted_at));
+    }
+
+    /// Resolves the submission held by [`Self::submit`] and observes it.
+    ///
+    /// Pass the actual present time the platform reported for that frame, or
+    /// `None` when it cannot report one. Returns the observed feedback, or
+    /// `None` if nothing was pending.
+    pub fn observe_actual_present(
+        &mut self,
+        actual_present: Option<HostTime>,
+    ) -> Option<PresentFeedback> {
+        let feedback = self.pending_feedback.take()?.resolve(actual_present);
+        self.observe(&feedback);
+        Some(feedback)
+    }
+
+    /// Returns whether a submission is waiting for
+    /// [`Self::observe_actual_present`].
+    #[must_use]
+    pub const fn has_pending_feedback(&self) -> bool {
+        self.pending_feedback.is_some()
+    }
+
+    /// Like [`plan`](Self::plan), but also reports the frame to `sink`.
+    ///
+    /// Emits a [`FrameTickEvent`] for the opportunity's tick, then a
+    /// [`FramePlanEvent`] for the returned plan with the safety margin in
+    /// effect after planning. Hosts that trace scheduler calls this way only
+    /// need to instrument their own frame-loop phases.
+    #[must_use]
+    pub fn plan_traced(
+        &mut self,
+        opportunity: FrameOpportunity,
+        demand: FrameDemand,
+        sink: &mut dyn DiagnosticsSink,
+    ) -> FramePlan {
+        sink.on_frame_tick(&FrameTickEvent::from(&opportunity.tick));
+        let plan = self.plan(opportunity, demand);
+        let margin = self.safety_margin_ticks_for(plan.output);
+        sink.on_frame_plan(&FramePlanEvent::new(&plan, margin));
+        plan
+    }
+
+    /// Like [`observe`](Self::observe), but also reports the feedback to
+    /// `sink`.
+    ///
+    /// Emits a [`PresentFeedbackEvent`] for `frame_index`, then a
+    /// [`SchedulerStateEvent`] with the feedback output's state after
+    /// adaptation.
+    pub fn observe_traced(
+        &mut self,
+        frame_index: u64,
+        feedback: &PresentFeedback,
+        sink: &mut dyn DiagnosticsSink,
+    ) {
+        self.observe(feedback);
+        sink.on_present_feedback(&PresentFeedbackEvent::new(frame_index, feedback));
+        sink.on_scheduler_state(&SchedulerStateEvent {
+            state: self.state_for(feedback.output),
+        });
+    }
+
+    /// Returns the current pipeline depth for the most recently planned or
+    /// observed output.
+    #[must_use]
+    pub fn pipeline_depth(&self) -> u8 {
+        self.pipeline_depth_for(self.current_output)
+    }
+
+    /// Returns the current pipeline depth for `output`.
+    ///
+    /// Outputs that have not been planned or observed yet report
+    /// [`SchedulerConfig::initial_depth`].
+    #[must_use]
+    pub fn pipeline_depth_for(&self, output: OutputId) -> u8 {
+        self.snapshot_for(output).pipeline_depth
+    }
+
+    /// Returns the current estimated safety margin in ticks for the most
+    /// recently planned or observed output.
+    #[must_use]
+    pub fn safety_margin_ticks(&self) -> u64 {
+        self.safety_margin_ticks_for(self.current_output)
+    }
+
+    /// Returns the current estimated safety margin in ticks for `output`.
+    #[must_use]
+    pub fn safety_margin_ticks_for(&self, output: OutputId) -> u64 {
+        self.snapshot_for(output).safety_margin_ticks
+    }
+
+    /// Returns a snapshot of the adaptation state for the most recently
+    /// planned or observed output.
+    #[must_use]
+    pub fn state(&self) -> SchedulerState {
+        self.state_for(self.current_output)
+    }
+
+    /// Returns a snapshot of the adaptation state for `output`.
+    #[must_use]
+    pub fn state_for(&self, output: OutputId) -> SchedulerState {
+        self.snapshot_for(output)
+    }
+
+    /// Returns rolling statistics for the most recently planned or observed
+    /// output.
+    #[must_use]
+    pub fn stats(&self) -> SchedulerStats {
+        self.stats_for(self.current_output)
+    }
+
+    /// Returns rolling statistics for `output`.
+    #[must_use]
+    pub fn stats_for(&self, output: OutputId) -> SchedulerStats {
+        self.output_state(output)
+            .copied()
+            .unwrap_or_else(|| OutputState::new(output, &self.config))
+            .stats()
+    }
+
+    /// Returns the output most recently passed to [`Self::plan`] or
+    /// [`Self::observe`].
+    #[must_use]
+    pub const fn current_output(&self) -> OutputId {
+        self.current_output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::OutputId;
+    use crate::time::HostTime;
+    use crate::timing::{FrameTick, PresentHints};
+
+    use super::*;
+
+    const REFRESH_INTERVAL: Duration = Duration(16_666_667);
+
+    fn make_tick(now: u64, predicted: Option<u64>) -> FrameTick {
+        FrameTick::builder()
+            .now(HostTime(now))
+            .predicted_present(predicted.map(HostTime))
+            .refresh_interval(Some(REFRESH_INTERVAL.ticks()))
+            .build()
+    }
+
+    fn make_hints(
+        presentation_timing: PresentationTiming,
+        predicted: Option<u64>,
+        deadline: u64,
+    ) -> PresentHints {
+        if let Some(predicted) = predicted
+            && presentation_timing.has_target_present()
+        {
+            return PresentHints::new(
+                presentation_timing,
+                Some(HostTime(predicted)),
+                HostTime(deadline),
+            );
+        }
+
+        PresentHints::new(presentation_timing, None, HostTime(deadline))
+    }
+
+    fn make_opportunity(
+        presentation_timing: PresentationTiming,
+        now: u64,
+        predicted: Option<u64>,
+        deadline: u64,
+    ) -> FrameOpportunity {
+        let tick = make_tick(now, predicted);
+        let hints = make_hints(presentation_timing, predicted, deadline);
+        FrameOpportunity {
+            tick,
+            hints,
+            display_timing: DisplayTiming::fixed(REFRESH_INTERVAL),
+        }
+    }
+
+    /// Records the events the traced scheduler methods emit.
+    #[derive(Default)]
+    struct Recorder {
+        ticks: u32,
+        plans: u32,
+        last_plan: Option<FramePlanEvent>,
+        feedback: Option<PresentFeedbackEvent>,
+        state: Option<SchedulerStateEvent>,
+    }
+
+    impl DiagnosticsSink for Recorder {
+        fn on_frame_tick(&mut self, _event: &FrameTickEvent) {
+            self.ticks += 1;
+        }
+
+        fn on_frame_plan(&mut self, event: &FramePlanEvent) {
+            self.plans += 1;
+            self.last_plan = Some(*event);
+        }
+
+        fn on_present_feedback(&mut self, event: &PresentFeedbackEvent) {
+            self.feedback = Some(*event);
+        }
+
+        fn on_scheduler_state(&mut self, event: &SchedulerStateEvent) {
+            self.state = Some(*event);
+        }
+    }
+
+    #[test]
+    fn plan_traced_emits_matching_plan_event() {
+        let mut sched = Scheduler::new(SchedulerConfig::predictive());
+        let mut recorder = Recorder::default();
+
+        let plan = sched.plan_traced(
+            make_opportunity(PresentationTiming::Predictive, 1000, Some(2000), 1800),
+            FrameDemand::ANIMATION,
+            &mut recorder,
+        );
+
+        assert_eq!(recorder.ticks, 1);
+        assert_eq!(recorder.plans, 1);
+        let event = recorder.last_plan.unwrap();
+        assert_eq!(event.frame_index, plan.frame_index);
+        assert_eq!(event.output, plan.output);
+        assert_eq!(event.demand, plan.demand);
+        assert_eq!(event.frame_interval, plan.frame_interval);
+        assert_eq!(event.frame_start, plan.frame_start);
+        assert_eq!(event.sample_time, plan.sample_time);
+        assert_eq!(event.target_present, plan.target_present);
+        assert_eq!(event.presentation_timing, plan.presentation_timing);
+        assert_eq!(event.commit_deadline, plan.commit_deadline);
+        assert_eq!(event.pipeline_depth, plan.pipeline_depth);
+        assert_eq!(event.safety_margin_ticks, sched.safety_margin_ticks());
+    }
+
+    #[test]
+    fn observe_traced_reports_feedback_and_state() {
+        let mut sched = Scheduler::new(SchedulerConfig::predictive());
+        let mut recorder = Recorder::default();
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1000, Some(2000), 1800),
+            FrameDemand::ANIMATION,
+        );
+        let feedback = PresentFeedback::scripted(&plan, Duration(100), 0);
+
+        sched.observe_traced(7, &feedback, &mut recorder);
+
+        let event = recorder.feedback.unwrap();
+        assert_eq!(event.frame_index, 7);
+        assert_eq!(event.actual_present, feedback.actual_present);
+        assert_eq!(event.missed_deadline, feedback.missed_deadline);
+        assert_eq!(recorder.state.unwrap().state, sched.state());
+    }
+
+    #[test]
+    fn predictive_plan_uses_predicted_present() {
+        let config = SchedulerConfig::predictive();
+        let mut sched = Scheduler::new(config);
+
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1000, Some(2000), 1800),
+            FrameDemand::ANIMATION,
+        );
+
+        assert_eq!(plan.demand, FrameDemand::ANIMATION);
+        assert_eq!(plan.frame_interval, REFRESH_INTERVAL);
+        assert_eq!(plan.target_present, Some(HostTime(2000)));
+        assert_eq!(plan.sample_time, HostTime(2000));
+        assert_eq!(plan.commit_deadline, HostTime(1800));
+        assert_eq!(plan.frame_start, HostTime(1000));
+    }
+
+    #[test]
+    fn pacing_only_plan_has_no_target_present() {
+        let config = SchedulerConfig::pacing_only();
+        let mut sched = Scheduler::new(config);
+
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::PacingOnly, 1_000_000, None, 17_000_000),
+            FrameDemand::ANIMATION,
+        );
+
+        assert_eq!(plan.target_present, None);
+        // sample_time = now + the selected pacing interval.
+        assert_eq!(plan.sample_time, HostTime(17_666_667));
+        assert_eq!(plan.frame_start, HostTime(16_000_000));
+    }
+
+    #[test]
+    fn frame_start_uses_configured_start_margin() {
+        let mut config = SchedulerConfig::predictive();
+        config.minimum_frame_start_margin = Duration(250);
+        let mut sched = Scheduler::new(config);
+
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1000, Some(2000), 1800),
+            FrameDemand::ANIMATION,
+        );
+
+        assert_eq!(plan.frame_start, HostTime(1550));
+    }
+
+    #[test]
+    fn frame_start_uses_learned_safety_margin_when_larger() {
+        let mut config = SchedulerConfig::predictive();
+        config.minimum_frame_start_margin = Duration(100);
+        config.safety_multiplier = 2.0;
+        let mut sched = Scheduler::new(config);
+        let feedback = PresentFeedback {
+            submitted_at: HostTime(1_200),
+            build_start: HostTime(1_000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(false),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+        sched.observe(&feedback);
+
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 2_000),
+            FrameDemand::ANIMATION,
+        );
+
+        assert_eq!(sched.safety_margin_ticks(), 400);
+        assert_eq!(plan.frame_start, HostTime(1_600));
+    }
+
+    #[test]
+    fn scheduler_sanitizes_non_finite_float_config() {
+        let mut config = SchedulerConfig::predictive();
+        config.ema_alpha = f64::NAN;
+        config.safety_multiplier = f64::INFINITY;
+        let mut sched = Scheduler::new(config);
+        let feedback = PresentFeedback {
+            submitted_at: HostTime(1_200),
+            build_start: HostTime(1_000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(false),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+
+        sched.observe(&feedback);
+
+        assert_eq!(sched.safety_margin_ticks(), 200);
+    }
+
+    #[test]
+    fn frame_start_clamps_to_tick_now_when_start_is_due() {
+        let mut config = SchedulerConfig::predictive();
+        config.minimum_frame_start_margin = Duration(1_000);
+        let mut sched = Scheduler::new(config);
+
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1_500, Some(2_000), 1_800),
+            FrameDemand::ANIMATION,
+        );
+
+        assert_eq!(plan.frame_start, HostTime(1_500));
+    }
+
+    #[test]
+    fn stale_predictive_present_is_ignored() {
+        let mut config = SchedulerConfig::predictive();
+        config.minimum_frame_start_margin = Duration(250);
+        let mut sched = Scheduler::new(config);
+        let tick = make_tick(2_000, Some(1_500));
+        let opportunity = FrameOpportunity {
+            tick,
+            hints: PresentHints::predictive(HostTime(1_500), HostTime(1_250)),
+            display_timing: DisplayTiming::fixed(REFRESH_INTERVAL),
+        };
+
+        let plan = sched.plan(opportunity, FrameDemand::ANIMATION);
+
+        assert_eq!(plan.target_present, None);
+        assert_eq!(plan.sample_time, HostTime(2_000) + REFRESH_INTERVAL);
+        assert_eq!(plan.commit_deadline, HostTime(2_000));
+        assert_eq!(plan.frame_start, HostTime(2_000));
+    }
+
+    #[test]
+    fn input_demand_starts_immediately() {
+        let mut config = SchedulerConfig::predictive();
+        config.minimum_frame_start_margin = Duration(250);
+        let mut sched = Scheduler::new(config);
+
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
+            FrameDemand::INPUT,
+        );
+
+        assert_eq!(plan.demand, FrameDemand::INPUT);
+        assert_eq!(plan.frame_interval, REFRESH_INTERVAL);
+        assert_eq!(plan.frame_start, HostTime(1_000));
+    }
+
+    #[test]
+    fn animation_uses_stable_fixed_rate_divisor_when_work_is_slow() {
+        let mut config = SchedulerConfig::predictive();
+        config.ema_alpha = 1.0;
+        config.safety_multiplier = 1.0;
+        let mut sched = Scheduler::new(config);
+        let feedback = PresentFeedback {
+            submitted_at: HostTime(21_000_000),
+            build_start: HostTime(1_000_000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(false),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+        sched.observe(&feedback);
+
+        let plan = sched.plan(
+            make_opportunity(
+                PresentationTiming::Predictive,
+                1_000_000,
+                Some(17_666_667),
+                16_666_667,
+            ),
             FrameDemand::ANIMATION,
         );
 
-        assert_eq!(plan.target_present, None);
-        // sample_time = now + the selected pacing interval.
-        assert_eq!(plan.sample_time, HostTime(17_666_667));
-        assert_eq!(plan.frame_start, HostTime(16_000_000));
+        assert_eq!(plan.frame_interval, REFRESH_INTERVAL.saturating_mul(2));
+        assert_eq!(plan.target_present, Some(HostTime(34_333_334)));
+        assert_eq!(plan.commit_deadline, HostTime(33_333_334));
+        assert_eq!(plan.frame_start, HostTime(13_333_334));
     }
 
     #[test]
-    fn frame_start_uses_configured_start_margin() {
+    fn safety_multiplier_does_not_force_cadence_drop() {
+        let mut config = SchedulerConfig::predictive();
+        config.ema_alpha = 1.0;
+        config.safety_multiplier = 4.0;
+        config.minimum_frame_start_margin = Duration::ZERO;
+        let mut sched = Scheduler::new(config);
+        let feedback = PresentFeedback {
+            submitted_at: HostTime(11_000_000),
+            build_start: HostTime(1_000_000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(false),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+        sched.observe(&feedback);
+
+        let plan = sched.plan(
+            make_opportunity(
+                PresentationTiming::Predictive,
+                1_000_000,
+                Some(50_000_000),
+                50_000_000,
+            ),
+            FrameDemand::ANIMATION,
+        );
+
+        assert_eq!(sched.safety_margin_ticks(), 40_000_000);
+        assert_eq!(plan.frame_interval, REFRESH_INTERVAL);
+        assert_eq!(plan.commit_deadline, HostTime(50_000_000));
+        assert_eq!(plan.frame_start, HostTime(10_000_000));
+    }
+
+    #[test]
+    fn variable_refresh_without_granularity_uses_stable_divisor() {
+        let mut config = SchedulerConfig::predictive();
+        config.ema_alpha = 1.0;
+        config.safety_multiplier = 1.0;
+        let mut sched = Scheduler::new(config);
+        let feedback = PresentFeedback {
+            submitted_at: HostTime(13_000_000),
+            build_start: HostTime(1_000_000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(false),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+        sched.observe(&feedback);
+        let tick = FrameTick {
+            now: HostTime(1_000_000),
+            predicted_present: Some(HostTime(9_333_333)),
+            refresh_interval: Some(8_333_333),
+            frame_index: 0,
+            output: OutputId(0),
+            prev_actual_present: None,
+        };
+        let opportunity = FrameOpportunity {
+            tick,
+            hints: PresentHints::predictive(HostTime(9_333_333), HostTime(8_333_333)),
+            display_timing: DisplayTiming::variable(
+                Duration(8_333_333),
+                Duration(33_333_333),
+                None,
+            ),
+        };
+
+        let plan = sched.plan(opportunity, FrameDemand::ANIMATION);
+
+        assert_eq!(plan.frame_interval, Duration(16_666_666));
+        assert_eq!(plan.target_present, Some(HostTime(17_666_666)));
+        assert_eq!(plan.commit_deadline, HostTime(16_666_666));
+        assert_eq!(plan.frame_start, HostTime(4_666_666));
+    }
+
+    #[test]
+    fn variable_refresh_with_granularity_uses_direct_step() {
+        let mut config = SchedulerConfig::predictive();
+        config.ema_alpha = 1.0;
+        config.safety_multiplier = 1.0;
+        let mut sched = Scheduler::new(config);
+        let feedback = PresentFeedback {
+            submitted_at: HostTime(13_000_000),
+            build_start: HostTime(1_000_000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(false),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+        sched.observe(&feedback);
+        let tick = FrameTick {
+            now: HostTime(1_000_000),
+            predicted_present: Some(HostTime(9_333_333)),
+            refresh_interval: Some(8_333_333),
+            frame_index: 0,
+            output: OutputId(0),
+            prev_actual_present: None,
+        };
+        let opportunity = FrameOpportunity {
+            tick,
+            hints: PresentHints::predictive(HostTime(9_333_333), HostTime(8_333_333)),
+            display_timing: DisplayTiming::variable(
+                Duration(8_333_333),
+                Duration(33_333_333),
+                Some(Duration(1_000_000)),
+            ),
+        };
+
+        let plan = sched.plan(opportunity, FrameDemand::ANIMATION);
+
+        assert_eq!(plan.frame_interval, Duration(15_000_000));
+        assert_eq!(plan.target_present, Some(HostTime(16_000_000)));
+        assert_eq!(plan.commit_deadline, HostTime(15_000_000));
+        assert_eq!(plan.frame_start, HostTime(3_000_000));
+    }
+
+    #[test]
+    fn pipeline_depth_increases_after_misses() {
+        let config = SchedulerConfig::predictive();
+        let mut sched = Scheduler::new(config);
+        assert_eq!(sched.pipeline_depth(), 1);
+
+        let feedback = PresentFeedback {
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(true),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+
+        sched.observe(&feedback);
+        assert_eq!(sched.pipeline_depth(), 1); // 1 miss
+        sched.observe(&feedback);
+        assert_eq!(sched.pipeline_depth(), 1); // 2 misses
+        sched.observe(&feedback);
+        assert_eq!(sched.pipeline_depth(), 2); // 3 misses → increase
+    }
+
+    #[test]
+    fn scripted_late_then_on_time_frames_move_depth() {
+        let mut sched = Scheduler::new(SchedulerConfig::predictive());
+        let mut now = 1_000_000;
+        let mut frame = |sched: &mut Scheduler, present_offset: i64| {
+            let predicted = now + REFRESH_INTERVAL.ticks();
+            let plan = sched.plan(
+                make_opportunity(
+                    PresentationTiming::Predictive,
+                    now,
+                    Some(predicted),
+                    predicted - 2_000_000,
+                ),
+                FrameDemand::ANIMATION,
+            );
+            sched.observe(&PresentFeedback::scripted(
+                &plan,
+                Duration(2_000_000),
+                present_offset,
+            ));
+            now += REFRESH_INTERVAL.ticks();
+            sched.pipeline_depth()
+        };
+
+        // Three late frames raise depth; ten on-time frames lower it again.
+        let late: [u8; 3] = core::array::from_fn(|_| frame(&mut sched, 4_000_000));
+        assert_eq!(late, [1, 1, 2]);
+        let on_time: [u8; 10] = core::array::from_fn(|_| frame(&mut sched, 0));
+        assert_eq!(on_time, [2, 2, 2, 2, 2, 2, 2, 2, 2, 1]);
+    }
+
+    #[test]
+    fn scripted_missed_deadline_override_drives_depth() {
+        let mut sched = Scheduler::new(SchedulerConfig::predictive());
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::PacingOnly, 1_000, None, 2_000),
+            FrameDemand::ANIMATION,
+        );
+        let miss = PresentFeedback::scripted(&plan, Duration(500), 0).with_missed_deadline(true);
+
+        for _ in 0..3 {
+            sched.observe(&miss);
+        }
+        assert_eq!(sched.pipeline_depth(), 2);
+    }
+
+    #[test]
+    fn pipeline_depth_shifts_non_input_plan_by_whole_intervals() {
         let mut config = SchedulerConfig::predictive();
+        config.initial_depth = 3;
         config.minimum_frame_start_margin = Duration(250);
         let mut sched = Scheduler::new(config);
 
         let plan = sched.plan(
-            make_opportunity(PresentationTiming::Predictive, 1000, Some(2000), 1800),
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
             FrameDemand::ANIMATION,
         );
 
-        assert_eq!(plan.frame_start, HostTime(1550));
+        let lookahead = REFRESH_INTERVAL.saturating_mul(2);
+        assert_eq!(plan.pipeline_depth, 3);
+        assert_eq!(plan.frame_interval, REFRESH_INTERVAL);
+        assert_eq!(plan.target_present, Some(HostTime(2_000) + lookahead));
+        assert_eq!(plan.sample_time, HostTime(2_000) + lookahead);
+        assert_eq!(plan.commit_deadline, HostTime(1_800) + lookahead);
+        assert_eq!(
+            plan.frame_start,
+            HostTime(1_800) + lookahead - Duration(250)
+        );
+    }
+
+    #[test]
+    fn pipeline_depth_does_not_shift_one_shot_input() {
+        let mut config = SchedulerConfig::predictive();
+        config.initial_depth = 3;
+        config.minimum_frame_start_margin = Duration(250);
+        let mut sched = Scheduler::new(config);
+
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
+            FrameDemand::INPUT,
+        );
+
+        assert_eq!(plan.pipeline_depth, 3);
+        assert_eq!(plan.target_present, Some(HostTime(2_000)));
+        assert_eq!(plan.sample_time, HostTime(2_000));
+        assert_eq!(plan.commit_deadline, HostTime(1_800));
+        assert_eq!(plan.frame_start, HostTime(1_000));
+    }
+
+    #[test]
+    fn pipeline_depth_does_not_shift_continuous_input() {
+        let mut config = SchedulerConfig::predictive();
+        config.initial_depth = 3;
+        config.minimum_frame_start_margin = Duration(250);
+        let mut sched = Scheduler::new(config);
+
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
+            FrameDemand::CONTINUOUS_INPUT,
+        );
+
+        assert_eq!(plan.pipeline_depth, 3);
+        assert_eq!(plan.target_present, Some(HostTime(2_000)));
+        assert_eq!(plan.sample_time, HostTime(2_000));
+        assert_eq!(plan.commit_deadline, HostTime(1_800));
+        assert_eq!(plan.frame_start, HostTime(1_550));
+    }
+
+    #[test]
+    fn consecutive_miss_counter_resets_on_success() {
+        let config = SchedulerConfig::predictive();
+        let mut sched = Scheduler::new(config);
+
+        let miss = PresentFeedback {
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(true),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+        let hit = PresentFeedback {
+            missed_deadline: Some(false),
+            ..miss
+        };
+
+        sched.observe(&miss);
+        sched.observe(&miss);
+        sched.observe(&hit); // resets counter
+        sched.observe(&miss);
+        sched.observe(&miss);
+        // Only 2 consecutive misses, should not increase.
+        assert_eq!(sched.pipeline_depth(), 1);
     }
 
     #[test]
-    fn frame_start_uses_learned_safety_margin_when_larger() {
+    fn pipeline_depth_decreases_after_sustained_hits() {
         let mut config = SchedulerConfig::predictive();
-        config.minimum_frame_start_margin = Duration(100);
-        config.safety_multiplier = 2.0;
+        config.initial_depth = 3;
         let mut sched = Scheduler::new(config);
-        let feedback = PresentFeedback {
-            submitted_at: HostTime(1_200),
-            build_start: HostTime(1_000),
+        assert_eq!(sched.pipeline_depth(), 3);
+
+        let hit = PresentFeedback {
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
             expected_present: None,
             actual_present: None,
             missed_deadline: Some(false),
             pacing_overrun: None,
+            output: OutputId(0),
         };
-        sched.observe(&feedback);
-
-        let plan = sched.plan(
-            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 2_000),
-            FrameDemand::ANIMATION,
-        );
 
-        assert_eq!(sched.safety_margin_ticks(), 400);
-        assert_eq!(plan.frame_start, HostTime(1_600));
+        for _ in 0..9 {
+            sched.observe(&hit);
+        }
+        assert_eq!(sched.pipeline_depth(), 3); // 9 hits, not enough
+        sched.observe(&hit); // 10th hit → decrease
+        assert_eq!(sched.pipeline_depth(), 2);
     }
 
     #[test]
-    fn scheduler_sanitizes_non_finite_float_config() {
+    fn fixed_policy_never_changes_depth() {
         let mut config = SchedulerConfig::predictive();
-        config.ema_alpha = f64::NAN;
-        config.safety_multiplier = f64::INFINITY;
+        config.degradation_policy = DegradationPolicy::Fixed;
+        config.initial_depth = 2;
         let mut sched = Scheduler::new(config);
-        let feedback = PresentFeedback {
-            submitted_at: HostTime(1_200),
-            build_start: HostTime(1_000),
+        assert_eq!(sched.pipeline_depth(), 2);
+
+        let miss = PresentFeedback {
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
             expected_present: None,
             actual_present: None,
-            missed_deadline: Some(false),
+            missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
         };
 
-        sched.observe(&feedback);
+        for _ in 0..10 {
+            sched.observe(&miss);
+        }
+        assert_eq!(sched.pipeline_depth(), 2); // unchanged
 
-        assert_eq!(sched.safety_margin_ticks(), 200);
+        // Safety margin should still be tracked.
+        assert!(sched.safety_margin_ticks() > 0);
     }
 
     #[test]
-    fn frame_start_clamps_to_tick_now_when_start_is_due() {
+    fn custom_miss_threshold() {
         let mut config = SchedulerConfig::predictive();
-        config.minimum_frame_start_margin = Duration(1_000);
+        config.degradation_policy = DegradationPolicy::Adaptive {
+            miss_threshold: 5,
+            recovery_threshold: 10,
+        };
         let mut sched = Scheduler::new(config);
 
-        let plan = sched.plan(
-            make_opportunity(PresentationTiming::Predictive, 1_500, Some(2_000), 1_800),
-            FrameDemand::ANIMATION,
-        );
+        let miss = PresentFeedback {
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(true),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
 
-        assert_eq!(plan.frame_start, HostTime(1_500));
+        for _ in 0..4 {
+            sched.observe(&miss);
+        }
+        assert_eq!(sched.pipeline_depth(), 1); // 4 misses, threshold is 5
+        sched.observe(&miss); // 5th miss → increase
+        assert_eq!(sched.pipeline_depth(), 2);
     }
 
     #[test]
-    fn stale_predictive_present_is_ignored() {
+    fn recovery_respects_min_depth() {
         let mut config = SchedulerConfig::predictive();
-        config.minimum_frame_start_margin = Duration(250);
+        config.initial_depth = 2;
+        config.degradation_policy = DegradationPolicy::Adaptive {
+            miss_threshold: 3,
+            recovery_threshold: 2,
+        };
         let mut sched = Scheduler::new(config);
-        let tick = make_tick(2_000, Some(1_500));
-        let opportunity = FrameOpportunity {
-            tick,
-            hints: PresentHints::predictive(HostTime(1_500), HostTime(1_250)),
-            display_timing: DisplayTiming::fixed(REFRESH_INTERVAL),
+        assert_eq!(sched.pipeline_depth(), 2);
+
+        let hit = PresentFeedback {
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(false),
+            pacing_overrun: None,
+            output: OutputId(0),
         };
 
-        let plan = sched.plan(opportunity, FrameDemand::ANIMATION);
+        // 2 hits → decrease to 1 (min_depth).
+        sched.observe(&hit);
+        sched.observe(&hit);
+        assert_eq!(sched.pipeline_depth(), 1);
 
-        assert_eq!(plan.target_present, None);
-        assert_eq!(plan.sample_time, HostTime(2_000) + REFRESH_INTERVAL);
-        assert_eq!(plan.commit_deadline, HostTime(2_000));
-        assert_eq!(plan.frame_start, HostTime(2_000));
+        // 2 more hits → should stay at 1, can't go below min_depth.
+        sched.observe(&hit);
+        sched.observe(&hit);
+        assert_eq!(sched.pipeline_depth(), 1);
     }
 
     #[test]
-    fn input_demand_starts_immediately() {
-        let mut config = SchedulerConfig::predictive();
-        config.minimum_frame_start_margin = Duration(250);
+    fn estimated_presentation_timing_uses_desired_present() {
+        let config = SchedulerConfig::predictive();
         let mut sched = Scheduler::new(config);
 
         let plan = sched.plan(
-            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
-            FrameDemand::INPUT,
+            make_opportunity(PresentationTiming::Estimated, 1000, Some(2000), 1800),
+            FrameDemand::ANIMATION,
         );
 
-        assert_eq!(plan.demand, FrameDemand::INPUT);
-        assert_eq!(plan.frame_interval, REFRESH_INTERVAL);
-        assert_eq!(plan.frame_start, HostTime(1_000));
+        // Estimated behaves like Predictive for target selection; hosts choose
+        // a more conservative SchedulerConfig separately.
+        assert_eq!(plan.target_present, Some(HostTime(2000)));
+        assert_eq!(plan.sample_time, HostTime(2000));
     }
 
     #[test]
-    fn animation_uses_stable_fixed_rate_divisor_when_work_is_slow() {
-        let mut config = SchedulerConfig::predictive();
-        config.ema_alpha = 1.0;
-        config.safety_multiplier = 1.0;
+    fn observe_with_unknown_deadline_resets_counters() {
+        let config = SchedulerConfig::predictive();
         let mut sched = Scheduler::new(config);
-        let feedback = PresentFeedback {
-            submitted_at: HostTime(21_000_000),
-            build_start: HostTime(1_000_000),
+
+        let miss = PresentFeedback {
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
             expected_present: None,
             actual_present: None,
-            missed_deadline: Some(false),
+            missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
+        };
+        let unknown = PresentFeedback {
+            missed_deadline: None,
+            pacing_overrun: None,
+            ..miss
         };
-        sched.observe(&feedback);
 
-        let plan = sched.plan(
-            make_opportunity(
-                PresentationTiming::Predictive,
-                1_000_000,
-                Some(17_666_667),
-                16_666_667,
-            ),
-            FrameDemand::ANIMATION,
-        );
+        // Accumulate 2 misses, then an unknown.
+        sched.observe(&miss);
+        sched.observe(&miss);
+        sched.observe(&unknown); // resets both counters
 
-        assert_eq!(plan.frame_interval, REFRESH_INTERVAL.saturating_mul(2));
-        assert_eq!(plan.target_present, Some(HostTime(34_333_334)));
-        assert_eq!(plan.commit_deadline, HostTime(33_333_334));
-        assert_eq!(plan.frame_start, HostTime(13_333_334));
+        // Two more misses — total consecutive is only 2, not 4.
+        sched.observe(&miss);
+        sched.observe(&miss);
+        assert_eq!(sched.pipeline_depth(), 1, "counter should have been reset");
+
+        // One more miss pushes to 3 consecutive → depth increases.
+        sched.observe(&miss);
+        assert_eq!(sched.pipeline_depth(), 2);
     }
 
     #[test]
-    fn safety_multiplier_does_not_force_cadence_drop() {
-        let mut config = SchedulerConfig::predictive();
-        config.ema_alpha = 1.0;
-        config.safety_multiplier = 4.0;
-        config.minimum_frame_start_margin = Duration::ZERO;
+    fn pacing_only_unknown_feedback_does_not_raise_depth() {
+        let config = SchedulerConfig::pacing_only();
         let mut sched = Scheduler::new(config);
-        let feedback = PresentFeedback {
-            submitted_at: HostTime(11_000_000),
-            build_start: HostTime(1_000_000),
+
+        let unknown = PresentFeedback {
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
             expected_present: None,
             actual_present: None,
-            missed_deadline: Some(false),
+            missed_deadline: None,
             pacing_overrun: None,
+            output: OutputId(0),
         };
-        sched.observe(&feedback);
 
-        let plan = sched.plan(
-            make_opportunity(
-                PresentationTiming::Predictive,
-                1_000_000,
-                Some(50_000_000),
-                50_000_000,
-            ),
-            FrameDemand::ANIMATION,
-        );
+        for _ in 0..8 {
+            sched.observe(&unknown);
+        }
 
-        assert_eq!(sched.safety_margin_ticks(), 40_000_000);
-        assert_eq!(plan.frame_interval, REFRESH_INTERVAL);
-        assert_eq!(plan.commit_deadline, HostTime(50_000_000));
-        assert_eq!(plan.frame_start, HostTime(10_000_000));
+        assert_eq!(sched.pipeline_depth(), 1);
+        assert!(
+            sched.safety_margin_ticks() > 0,
+            "unknown deadline feedback should still train build-cost EMA"
+        );
     }
 
     #[test]
-    fn variable_refresh_without_granularity_uses_stable_divisor() {
+    fn depth_clamped_at_max() {
         let mut config = SchedulerConfig::predictive();
-        config.ema_alpha = 1.0;
-        config.safety_multiplier = 1.0;
+        config.max_depth = 3;
+        config.degradation_policy = DegradationPolicy::Adaptive {
+            miss_threshold: 1,
+            recovery_threshold: 10,
+        };
+        let mut sched = Scheduler::new(config);
+        assert_eq!(sched.pipeline_depth(), 1);
+
+        let miss = PresentFeedback {
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: Some(true),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
+
+        // Many consecutive misses should not exceed max_depth.
+        for _ in 0..20 {
+            sched.observe(&miss);
+        }
+        assert_eq!(sched.pipeline_depth(), 3, "depth should be clamped at max");
+    }
+
+    #[test]
+    fn build_cost_ema_updates() {
+        let config = SchedulerConfig::predictive();
         let mut sched = Scheduler::new(config);
+        assert_eq!(sched.safety_margin_ticks(), 0);
+
         let feedback = PresentFeedback {
-            submitted_at: HostTime(13_000_000),
-            build_start: HostTime(1_000_000),
+            submitted_at: HostTime(2000),
+            build_start: HostTime(1000),
             expected_present: None,
             actual_present: None,
             missed_deadline: Some(false),
             pacing_overrun: None,
-        };
-        sched.observe(&feedback);
-        let tick = FrameTick {
-            now: HostTime(1_000_000),
-            predicted_present: Some(HostTime(9_333_333)),
-            refresh_interval: Some(8_333_333),
-            frame_index: 0,
             output: OutputId(0),
-            prev_actual_present: None,
-        };
-        let opportunity = FrameOpportunity {
-            tick,
-            hints: PresentHints::predictive(HostTime(9_333_333), HostTime(8_333_333)),
-            display_timing: DisplayTiming::variable(
-                Duration(8_333_333),
-                Duration(33_333_333),
-                None,
-            ),
         };
 
-        let plan = sched.plan(opportunity, FrameDemand::ANIMATION);
-
-        assert_eq!(plan.frame_interval, Duration(16_666_666));
-        assert_eq!(plan.target_present, Some(HostTime(17_666_666)));
-        assert_eq!(plan.commit_deadline, HostTime(16_666_666));
-        assert_eq!(plan.frame_start, HostTime(4_666_666));
+        sched.observe(&feedback);
+        assert!(
+            sched.safety_margin_ticks() > 0,
+            "safety margin should increase after observing build cost"
+        );
     }
 
     #[test]
-    fn variable_refresh_with_granularity_uses_direct_step() {
-        let mut config = SchedulerConfig::predictive();
-        config.ema_alpha = 1.0;
-        config.safety_multiplier = 1.0;
+    fn refresh_rate_change_keeps_build_cost_and_resets_streaks() {
+        let config = SchedulerConfig::predictive();
         let mut sched = Scheduler::new(config);
+        let opportunity = |now: u64, interval: Duration| {
+            let mut opportunity =
+                make_opportunity(PresentationTiming::Predictive, now, None, now + 1_000);
+            opportunity.tick.refresh_interval = Some(interval.ticks());
+            opportunity.display_timing = DisplayTiming::fixed(interval);
+            opportunity
+        };
+        // Build work takes 4 ms regardless of the refresh rate.
         let feedback = PresentFeedback {
-            submitted_at: HostTime(13_000_000),
-            build_start: HostTime(1_000_000),
+            submitted_at: HostTime(4_000_000),
+            build_start: HostTime(0),
             expected_present: None,
             actual_present: None,
             missed_deadline: Some(false),
             pacing_overrun: None,
-        };
-        sched.observe(&feedback);
-        let tick = FrameTick {
-            now: HostTime(1_000_000),
-            predicted_present: Some(HostTime(9_333_333)),
-            refresh_interval: Some(8_333_333),
-            frame_index: 0,
             output: OutputId(0),
-            prev_actual_present: None,
-        };
-        let opportunity = FrameOpportunity {
-            tick,
-            hints: PresentHints::predictive(HostTime(9_333_333), HostTime(8_333_333)),
-            display_timing: DisplayTiming::variable(
-                Duration(8_333_333),
-                Duration(33_333_333),
-                Some(Duration(1_000_000)),
-            ),
         };
+        let expected = f64_ticks_to_u64(4_000_000.0 * config.safety_multiplier);
+
+        let interval_60 = REFRESH_INTERVAL;
+        let interval_120 = Duration(8_333_333);
+        for frame in 0..20 {
+            let _ = sched.plan(
+                opportunity(frame * 20_000_000, interval_60),
+                FrameDemand::ANIMATION,
+            );
+            sched.observe(&feedback);
+        }
+        assert!(sched.safety_margin_ticks().abs_diff(expected) <= 1);
+        assert!(sched.state().consecutive_hits > 0);
 
-        let plan = sched.plan(opportunity, FrameDemand::ANIMATION);
+        // The switch resets the hit streak but not the learned build cost.
+        let _ = sched.plan(
+            opportunity(400_000_000, interval_120),
+            FrameDemand::ANIMATION,
+        );
+        assert_eq!(sched.stats().refresh_interval, Some(interval_120));
+        assert_eq!(sched.state().consecutive_hits, 0);
+        assert!(
+            sched.safety_margin_ticks().abs_diff(expected) <= 1,
+            "build-cost estimate should survive the switch"
+        );
 
-        assert_eq!(plan.frame_interval, Duration(15_000_000));
-        assert_eq!(plan.target_present, Some(HostTime(16_000_000)));
-        assert_eq!(plan.commit_deadline, HostTime(15_000_000));
-        assert_eq!(plan.frame_start, HostTime(3_000_000));
+        for frame in 1..4 {
+            sched.observe(&feedback);
+            let _ = sched.plan(
+                opportunity(400_000_000 + frame * 10_000_000, interval_120),
+                FrameDemand::ANIMATION,
+            );
+            assert!(sched.safety_margin_ticks().abs_diff(expected) <= 1);
+        }
     }
 
     #[test]
-    fn pipeline_depth_increases_after_misses() {
-        let config = SchedulerConfig::predictive();
-        let mut sched = Scheduler::new(config);
-        assert_eq!(sched.pipeline_depth(), 1);
-
+    fn jittered_refresh_interval_keeps_estimates() {
+        let mut sched = Scheduler::new(SchedulerConfig::predictive());
         let feedback = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+            submitted_at: HostTime(8_000_000),
+            build_start: HostTime(0),
             expected_present: None,
             actual_present: None,
-            missed_deadline: Some(true),
+            missed_deadline: Some(false),
             pacing_overrun: None,
+            output: OutputId(0),
         };
+        // Intervals wander by up to 1% around 60 Hz, as display links report.
+        let jitter: [i64; 6] = [0, 120_000, -90_000, 160_000, -150_000, 40_000];
+        let mut now = 0;
+        let mut margin = None;
+        for delta in jitter {
+            let interval = Duration(REFRESH_INTERVAL.ticks().saturating_add_signed(delta));
+            let mut opportunity =
+                make_opportunity(PresentationTiming::Predictive, now, None, now + 1_000);
+            opportunity.tick.refresh_interval = Some(interval.ticks());
+            opportunity.display_timing = DisplayTiming::fixed(interval);
+            let _ = sched.plan(opportunity, FrameDemand::ANIMATION);
+            // Jitter does not rescale the margin learned from earlier frames.
+            if let Some(margin) = margin {
+                assert_eq!(sched.safety_margin_ticks(), margin);
+            }
+            sched.observe(&feedback);
+            margin = Some(sched.safety_margin_ticks());
+            now += interval.ticks();
+        }
+        assert_eq!(sched.state().consecutive_hits, 6);
+        assert_eq!(sched.stats().refresh_interval, Some(REFRESH_INTERVAL));
+    }
 
-        sched.observe(&feedback);
-        assert_eq!(sched.pipeline_depth(), 1); // 1 miss
-        sched.observe(&feedback);
-        assert_eq!(sched.pipeline_depth(), 1); // 2 misses
-        sched.observe(&feedback);
-        assert_eq!(sched.pipeline_depth(), 2); // 3 misses → increase
+    /// Build costs, in nanoseconds, for the VRR comparison tests.
+    const VARYING_COSTS: [u64; 12] = [
+        12_000_000, 12_000_000, 12_000_000, 12_000_000, 17_500_000, 12_000_000, 12_000_000,
+        12_000_000, 17_500_000, 12_000_000, 12_000_000, 12_000_000,
+    ];
+
+    #[test]
+    fn vrr_range_from_hz_inverts_rates() {
+        let range = VrrRange::from_hz(48, 144, Timebase::NANOS);
+        assert_eq!(range.min_interval(), Duration(6_944_444));
+        assert_eq!(range.max_interval(), Duration(20_833_333));
+
+        let swapped = VrrRange::new(Duration(10), Duration(5));
+        assert_eq!(swapped.max_interval(), Duration(10));
     }
 
     #[test]
-    fn pipeline_depth_shifts_non_input_plan_by_whole_intervals() {
-        let mut config = SchedulerConfig::predictive();
-        config.initial_depth = 3;
-        config.minimum_frame_start_margin = Duration(250);
-        let mut sched = Scheduler::new(config);
+    fn vrr_plans_adapt_to_build_cost_without_misses() {
+        let range = VrrRange::from_hz(30, 144, Timebase::NANOS);
+        let mut vrr = Scheduler::new(SchedulerConfig::builder().vrr(Some(range)).build());
+        let mut now = 0;
+        let mut vrr_misses = 0;
+        let mut intervals = [0_u64; VARYING_COSTS.len()];
+        for (frame, cost) in VARYING_COSTS.into_iter().enumerate() {
+            let opportunity = FrameOpportunity {
+                tick: FrameTick::builder().now(HostTime(now)).build(),
+                hints: PresentHints::new(PresentationTiming::Predictive, None, HostTime(now)),
+                display_timing: range.display_timing(),
+            };
+            let plan = vrr.plan(opportunity, FrameDemand::ANIMATION);
+            let target = plan.target_present.unwrap();
+            assert!(plan.commit_deadline <= HostTime(now + range.max_interval().ticks()));
+            // The panel refreshes when the frame arrives, but not before the
+            // requested present time.
+            let submitted = HostTime(plan.frame_start.ticks() + cost);
+            let actual = submitted.max(target);
+            let feedback = PresentFeedback::new(&plan, plan.frame_start, submitted, Some(actual));
+            // The first frame has no build-cost estimate yet.
+            if frame > 0 && feedback.missed_deadline == Some(true) {
+                vrr_misses += 1;
+            }
+            vrr.observe(&feedback);
+            intervals[frame] = actual.ticks() - now;
+            now = actual.ticks();
+        }
 
-        let plan = sched.plan(
-            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
-            FrameDemand::ANIMATION,
+        // The same costs on a fixed 60 Hz grid, where a late frame waits for
+        // the next refresh.
+        let mut fixed = Scheduler::new(SchedulerConfig::predictive());
+        let grid = REFRESH_INTERVAL.ticks();
+        let mut now = 0;
+        let mut fixed_misses = 0;
+        for (frame, cost) in VARYING_COSTS.into_iter().enumerate() {
+            let opportunity = make_opportunity(
+                PresentationTiming::Predictive,
+                now,
+                Some(now + grid),
+                now + grid,
+            );
+            let plan = fixed.plan(opportunity, FrameDemand::ANIMATION);
+            let submitted = HostTime(plan.frame_start.ticks() + cost);
+            let actual = HostTime(submitted.ticks().div_ceil(grid) * grid);
+            let feedback = PresentFeedback::new(&plan, plan.frame_start, submitted, Some(actual));
+            if frame > 0 && feedback.missed_deadline == Some(true) {
+                fixed_misses += 1;
+            }
+            fixed.observe(&feedback);
+            now = actual.ticks();
+        }
+
+        assert_eq!(vrr_misses, 0);
+        assert!(fixed_misses > 0, "fixed grid should miss on cost spikes");
+        // The spike on frame 4 raises the learned build cost, so the next
+        // present interval stretches instead of snapping to a whole refresh.
+        let steady = intervals[3];
+        assert!(
+            intervals[5] > steady,
+            "present interval should stretch after an expensive frame"
         );
+        for interval in &intervals[1..] {
+            assert!(
+                (range.min_interval().ticks()..=range.max_interval().ticks()).contains(interval),
+                "interval {interval} outside VRR range"
+            );
+            assert_ne!(interval % grid, 0, "interval should not snap to a grid");
+        }
+    }
 
-        let lookahead = REFRESH_INTERVAL.saturating_mul(2);
-        assert_eq!(plan.pipeline_depth, 3);
-        assert_eq!(plan.frame_interval, REFRESH_INTERVAL);
-        assert_eq!(plan.target_present, Some(HostTime(2_000) + lookahead));
-        assert_eq!(plan.sample_time, HostTime(2_000) + lookahead);
-        assert_eq!(plan.commit_deadline, HostTime(1_800) + lookahead);
+    #[test]
+    fn vrr_commit_deadline_precedes_target_present() {
+        let range = VrrRange::from_hz(30, 144, Timebase::NANOS);
+        let config = SchedulerConfig::builder().vrr(Some(range)).build();
+        let mut sched = Scheduler::new(config);
+        let now = 1_000_000_000;
+        let opportunity = |latest_commit| FrameOpportunity {
+            tick: FrameTick::builder().now(HostTime(now)).build(),
+            hints: PresentHints::new(PresentationTiming::Predictive, None, latest_commit),
+            display_timing: range.display_timing(),
+        };
+
+        // Without a backend deadline, the margin is reserved before present.
+        let plan = sched.plan(opportunity(HostTime(now)), FrameDemand::ANIMATION);
+        let target = plan.target_present.unwrap();
+        assert!(plan.commit_deadline < target);
         assert_eq!(
-            plan.frame_start,
-            HostTime(1_800) + lookahead - Duration(250)
+            target.ticks() - plan.commit_deadline.ticks(),
+            config.minimum_frame_start_margin.ticks()
         );
+
+        // A backend deadline is taken as-is.
+        let latest_commit = HostTime(now + 3_000_000);
+        let plan = sched.plan(opportunity(latest_commit), FrameDemand::ANIMATION);
+        assert_eq!(plan.commit_deadline, latest_commit);
+        assert!(plan.commit_deadline < plan.target_present.unwrap());
     }
 
     #[test]
-    fn pipeline_depth_does_not_shift_one_shot_input() {
-        let mut config = SchedulerConfig::predictive();
-        config.initial_depth = 3;
-        config.minimum_frame_start_margin = Duration(250);
+    fn deferred_feedback_detects_late_present() {
+        let mut sched = Scheduler::new(SchedulerConfig::predictive());
+        let desired = 20_000_000;
+        let opportunity = make_opportunity(
+            PresentationTiming::Predictive,
+            1_000_000,
+            Some(desired),
+            15_833_334,
+        );
+        assert_eq!(sched.observe_actual_present(None), None);
+
+        // Frame N presents one refresh late; the next tick reports it.
+        let plan = sched.plan(opportunity, FrameDemand::ANIMATION);
+        sched.submit(&plan, HostTime(1_000_000), HostTime(5_000_000));
+        assert!(sched.has_pending_feedback());
+        let late = HostTime(desired + REFRESH_INTERVAL.ticks());
+        let feedback = sched.observe_actual_present(Some(late)).unwrap();
+        assert_eq!(feedback.expected_present, Some(HostTime(desired)));
+        assert_eq!(feedback.actual_present, Some(late));
+        assert_eq!(feedback.missed_deadline, Some(true));
+        assert_eq!(sched.state().consecutive_misses, 1);
+        assert!(!sched.has_pending_feedback());
+
+        // An on-time present counts as a hit.
+        let plan = sched.plan(opportunity, FrameDemand::ANIMATION);
+        sched.submit(&plan, HostTime(1_000_000), HostTime(5_000_000));
+        let feedback = sched.observe_actual_present(Some(HostTime(desired)));
+        assert_eq!(feedback.unwrap().missed_deadline, Some(false));
+        assert_eq!(sched.state().consecutive_hits, 1);
+
+        // A second submission resolves the first as commit-only feedback.
+        sched.submit(&plan, HostTime(1_000_000), HostTime(5_000_000));
+        sched.submit(&plan, HostTime(1_000_000), HostTime(5_000_000));
+        assert_eq!(sched.stats().window_len, 3);
+        assert!(sched.has_pending_feedback());
+    }
+
+    #[test]
+    fn pacing_overrun_raises_depth_more_conservatively() {
+        let config = SchedulerConfig::pacing_only();
         let mut sched = Scheduler::new(config);
 
-        let plan = sched.plan(
-            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
-            FrameDemand::INPUT,
-        );
+        let overrun = PresentFeedback {
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: None,
+            pacing_overrun: Some(true),
+            output: OutputId(0),
+        };
 
-        assert_eq!(plan.pipeline_depth, 3);
-        assert_eq!(plan.target_present, Some(HostTime(2_000)));
-        assert_eq!(plan.sample_time, HostTime(2_000));
-        assert_eq!(plan.commit_deadline, HostTime(1_800));
-        assert_eq!(plan.frame_start, HostTime(1_000));
+        for _ in 0..5 {
+            sched.observe(&overrun);
+        }
+        assert_eq!(sched.pipeline_depth(), 1);
+
+        sched.observe(&overrun);
+        assert_eq!(sched.pipeline_depth(), 2);
     }
 
     #[test]
-    fn pipeline_depth_does_not_shift_continuous_input() {
-        let mut config = SchedulerConfig::predictive();
-        config.initial_depth = 3;
-        config.minimum_frame_start_margin = Duration(250);
+    fn pacing_overrun_counters_reset_on_clear_tick() {
+        let config = SchedulerConfig::pacing_only();
         let mut sched = Scheduler::new(config);
 
-        let plan = sched.plan(
-            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
-            FrameDemand::CONTINUOUS_INPUT,
-        );
+        let overrun = PresentFeedback {
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
+            expected_present: None,
+            actual_present: None,
+            missed_deadline: None,
+            pacing_overrun: Some(true),
+            output: OutputId(0),
+        };
+        let clear = PresentFeedback {
+            pacing_overrun: Some(false),
+            ..overrun
+        };
 
-        assert_eq!(plan.pipeline_depth, 3);
-        assert_eq!(plan.target_present, Some(HostTime(2_000)));
-        assert_eq!(plan.sample_time, HostTime(2_000));
-        assert_eq!(plan.commit_deadline, HostTime(1_800));
-        assert_eq!(plan.frame_start, HostTime(1_550));
+        for _ in 0..3 {
+            sched.observe(&overrun);
+        }
+        sched.observe(&clear);
+        for _ in 0..5 {
+            sched.observe(&overrun);
+        }
+
+        assert_eq!(sched.pipeline_depth(), 1);
+        sched.observe(&overrun);
+        assert_eq!(sched.pipeline_depth(), 2);
     }
 
     #[test]
-    fn consecutive_miss_counter_resets_on_success() {
+    fn outputs_adapt_depth_independently() {
         let config = SchedulerConfig::predictive();
         let mut sched = Scheduler::new(config);
-
         let miss = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
             expected_present: None,
             actual_present: None,
             missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
         };
-        let hit = PresentFeedback {
+        let external_hit = PresentFeedback {
             missed_deadline: Some(false),
+            output: OutputId(1),
             ..miss
         };
 
-        sched.observe(&miss);
-        sched.observe(&miss);
-        sched.observe(&hit); // resets counter
-        sched.observe(&miss);
-        sched.observe(&miss);
-        // Only 2 consecutive misses, should not increase.
+        // Interleave the two outputs: the internal panel keeps missing while
+        // the external monitor keeps hitting.
+        for _ in 0..6 {
+            sched.observe(&miss);
+            sched.observe(&external_hit);
+        }
+
+        assert_eq!(sched.pipeline_depth_for(OutputId(0)), 3);
+        assert_eq!(sched.pipeline_depth_for(OutputId(1)), 1);
+        assert_eq!(sched.state_for(OutputId(1)).consecutive_hits, 6);
+        assert_eq!(sched.current_output(), OutputId(1));
         assert_eq!(sched.pipeline_depth(), 1);
     }
 
     #[test]
-    fn pipeline_depth_decreases_after_sustained_hits() {
+    fn plan_uses_state_for_tick_output() {
         let mut config = SchedulerConfig::predictive();
-        config.initial_depth = 3;
+        config.minimum_frame_start_margin = Duration(250);
+        config.degradation_policy = DegradationPolicy::Adaptive {
+            miss_threshold: 1,
+            recovery_threshold: 10,
+        };
         let mut sched = Scheduler::new(config);
-        assert_eq!(sched.pipeline_depth(), 3);
-
-        let hit = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+        sched.observe(&PresentFeedback {
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
             expected_present: None,
             actual_present: None,
-            missed_deadline: Some(false),
+            missed_deadline: Some(true),
             pacing_overrun: None,
-        };
-
-        for _ in 0..9 {
-            sched.observe(&hit);
-        }
-        assert_eq!(sched.pipeline_depth(), 3); // 9 hits, not enough
-        sched.observe(&hit); // 10th hit → decrease
-        assert_eq!(sched.pipeline_depth(), 2);
+            output: OutputId(1),
+        });
+
+        let mut opportunity =
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800);
+        let plan_primary = sched.plan(opportunity, FrameDemand::ANIMATION);
+        opportunity.tick.output = OutputId(1);
+        let plan_external = sched.plan(opportunity, FrameDemand::ANIMATION);
+
+        assert_eq!(plan_primary.pipeline_depth, 1);
+        assert_eq!(plan_primary.target_present, Some(HostTime(2_000)));
+        assert_eq!(plan_external.pipeline_depth, 2);
+        assert_eq!(plan_external.output, OutputId(1));
+        assert_eq!(
+            plan_external.target_present,
+            Some(HostTime(2_000) + REFRESH_INTERVAL)
+        );
     }
 
     #[test]
-    fn fixed_policy_never_changes_depth() {
+    fn least_recently_used_output_is_evicted() {
         let mut config = SchedulerConfig::predictive();
-        config.degradation_policy = DegradationPolicy::Fixed;
-        config.initial_depth = 2;
+        config.degradation_policy = DegradationPolicy::Adaptive {
+            miss_threshold: 1,
+            recovery_threshold: 10,
+        };
         let mut sched = Scheduler::new(config);
-        assert_eq!(sched.pipeline_depth(), 2);
-
         let miss = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
             expected_present: None,
             actual_present: None,
             missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
         };
 
-        for _ in 0..10 {
-            sched.observe(&miss);
+        for idx in 0..MAX_TRACKED_OUTPUTS {
+            sched.observe(&PresentFeedback {
+                output: OutputId(u32::try_from(idx).expect("small output index")),
+                ..miss
+            });
         }
-        assert_eq!(sched.pipeline_depth(), 2); // unchanged
+        // Touch output 0 so output 1 becomes the least recently used.
+        sched.observe(&miss);
+        sched.observe(&PresentFeedback {
+            output: OutputId(99),
+            ..miss
+        });
 
-        // Safety margin should still be tracked.
-        assert!(sched.safety_margin_ticks() > 0);
+        assert_eq!(sched.pipeline_depth_for(OutputId(0)), 3);
+        assert_eq!(sched.pipeline_depth_for(OutputId(1)), 1, "evicted");
+        assert_eq!(sched.pipeline_depth_for(OutputId(2)), 2);
+        assert_eq!(sched.pipeline_depth_for(OutputId(99)), 2);
     }
 
     #[test]
-    fn custom_miss_threshold() {
-        let mut config = SchedulerConfig::predictive();
-        config.degradation_policy = DegradationPolicy::Adaptive {
-            miss_threshold: 5,
-            recovery_threshold: 10,
-        };
+    fn stats_report_windowed_build_cost_and_misses() {
+        let config = SchedulerConfig::predictive();
         let mut sched = Scheduler::new(config);
-
-        let miss = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+        let hit = PresentFeedback {
+            submitted_at: HostTime(1_000),
+            build_start: HostTime(0),
             expected_present: None,
             actual_present: None,
-            missed_deadline: Some(true),
+            missed_deadline: Some(false),
             pacing_overrun: None,
+            output: OutputId(0),
         };
 
-        for _ in 0..4 {
-            sched.observe(&miss);
+        for (cost, missed) in [(1_000, false), (3_000, true), (2_000, false), (6_000, true)] {
+            sched.observe(&PresentFeedback {
+                submitted_at: HostTime(cost),
+                missed_deadline: Some(missed),
+                ..hit
+            });
         }
-        assert_eq!(sched.pipeline_depth(), 1); // 4 misses, threshold is 5
-        sched.observe(&miss); // 5th miss → increase
-        assert_eq!(sched.pipeline_depth(), 2);
+        let _ = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
+            FrameDemand::ANIMATION,
+        );
+
+        let stats = sched.stats();
+        assert_eq!(stats.window_len, 4);
+        assert_eq!(stats.mean_build_cost_ticks, 3_000);
+        assert_eq!(stats.max_build_cost_ticks, 6_000);
+        assert_eq!(stats.window_misses, 2);
+        assert_eq!(stats.refresh_interval, Some(REFRESH_INTERVAL));
+        assert_eq!(stats.depth_changes, 0);
     }
 
     #[test]
-    fn recovery_respects_min_depth() {
+    fn stats_window_drops_old_frames_and_counts_depth_changes() {
         let mut config = SchedulerConfig::predictive();
-        config.initial_depth = 2;
         config.degradation_policy = DegradationPolicy::Adaptive {
-            miss_threshold: 3,
-            recovery_threshold: 2,
+            miss_threshold: 1,
+            recovery_threshold: 1,
         };
         let mut sched = Scheduler::new(config);
-        assert_eq!(sched.pipeline_depth(), 2);
-
-        let hit = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+        let miss = PresentFeedback {
+            submitted_at: HostTime(10_000),
+            build_start: HostTime(0),
             expected_present: None,
             actual_present: None,
-            missed_deadline: Some(false),
+            missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
+        };
+        let hit = PresentFeedback {
+            submitted_at: HostTime(500),
+            missed_deadline: Some(false),
+            ..miss
         };
 
-        // 2 hits → decrease to 1 (min_depth).
-        sched.observe(&hit);
-        sched.observe(&hit);
-        assert_eq!(sched.pipeline_depth(), 1);
+        sched.observe(&miss);
+        for _ in 0..STATS_WINDOW {
+            sched.observe(&hit);
+        }
 
-        // 2 more hits → should stay at 1, can't go below min_depth.
-        sched.observe(&hit);
-        sched.observe(&hit);
-        assert_eq!(sched.pipeline_depth(), 1);
+        let stats = sched.stats();
+        assert_eq!(stats.window_len, 32);
+        assert_eq!(stats.mean_build_cost_ticks, 500);
+        assert_eq!(stats.max_build_cost_ticks, 500);
+        assert_eq!(stats.window_misses, 0);
+        // Raised once by the miss, lowered once by the first hit.
+        assert_eq!(stats.depth_changes, 2);
     }
 
     #[test]
-    fn estimated_presentation_timing_uses_desired_present() {
-        let config = SchedulerConfig::predictive();
-        let mut sched = Scheduler::new(config);
+    fn stats_report_build_cost_and_present_error_spread() {
+        let mut sched = Scheduler::new(SchedulerConfig::predictive());
+        let frame = |cost: u64, present_error: u64| PresentFeedback {
+            submitted_at: HostTime(cost),
+            build_start: HostTime(0),
+            expected_present: Some(HostTime(100_000)),
+            actual_present: Some(HostTime(100_000 + present_error)),
+            missed_deadline: Some(false),
+            pacing_overrun: None,
+            output: OutputId(0),
+        };
 
-        let plan = sched.plan(
-            make_opportunity(PresentationTiming::Estimated, 1000, Some(2000), 1800),
-            FrameDemand::ANIMATION,
+        for _ in 0..STATS_WINDOW {
+            sched.observe(&frame(4_000, 100));
+        }
+        let steady = sched.stats();
+        assert_eq!(steady.build_cost_iqr_ticks, 0);
+        assert_eq!(steady.present_error_iqr_ticks, 0);
+
+        // Alternate cheap and expensive builds; presentation stays steady.
+        for i in 0..STATS_WINDOW as u64 {
+            sched.observe(&frame(if i % 2 == 0 { 1_000 } else { 9_000 }, 100));
+        }
+        let unstable = sched.stats();
+        assert_eq!(unstable.build_cost_iqr_ticks, 8_000);
+        assert_eq!(unstable.present_error_iqr_ticks, 0);
+        assert_eq!(
+            unstable.mean_build_cost_ticks,
+            steady.mean_build_cost_ticks + 1_000
         );
 
-        // Estimated behaves like Predictive for target selection; hosts choose
-        // a more conservative SchedulerConfig separately.
-        assert_eq!(plan.target_present, Some(HostTime(2000)));
-        assert_eq!(plan.sample_time, HostTime(2000));
+        // Jittery presentation with steady builds.
+        for i in 0..STATS_WINDOW as u64 {
+            sched.observe(&frame(4_000, if i % 2 == 0 { 0 } else { 3_000 }));
+        }
+        let jittery = sched.stats();
+        assert_eq!(jittery.build_cost_iqr_ticks, 0);
+        assert_eq!(jittery.present_error_iqr_ticks, 3_000);
     }
 
     #[test]
-    fn observe_with_unknown_deadline_resets_counters() {
-        let config = SchedulerConfig::predictive();
-        let mut sched = Scheduler::new(config);
+    fn builder_presets_match_documented_values() {
+        let pacing = SchedulerConfig::pacing_only();
+        assert_eq!(pacing.nominal_latency, Duration(16_000_000));
+        assert!((pacing.ema_alpha - 0.15).abs() < f64::EPSILON);
+        assert!((SchedulerConfig::estimated().safety_multiplier - 2.0).abs() < f64::EPSILON);
+        assert_eq!(SchedulerConfig::predictive().depth_change_hysteresis, 0);
+
+        let config = SchedulerConfig::builder()
+            .degradation_policy(DegradationPolicy::Fixed)
+            .recovery_threshold(4)
+            .build();
+        assert_eq!(
+            config.degradation_policy,
+            DegradationPolicy::Adaptive {
+                miss_threshold: 3,
+                recovery_threshold: 4,
+            }
+        );
+    }
 
+    #[test]
+    fn builder_max_depth_caps_growth_under_sustained_misses() {
+        let config = SchedulerConfig::builder()
+            .max_depth(2)
+            .miss_threshold(1)
+            .build();
+        let mut sched = Scheduler::new(config);
         let miss = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
             expected_present: None,
             actual_present: None,
             missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
         };
-        let unknown = PresentFeedback {
-            missed_deadline: None,
-            pacing_overrun: None,
-            ..miss
-        };
-
-        // Accumulate 2 misses, then an unknown.
-        sched.observe(&miss);
-        sched.observe(&miss);
-        sched.observe(&unknown); // resets both counters
 
-        // Two more misses — total consecutive is only 2, not 4.
-        sched.observe(&miss);
-        sched.observe(&miss);
-        assert_eq!(sched.pipeline_depth(), 1, "counter should have been reset");
+        for _ in 0..50 {
+            sched.observe(&miss);
+        }
 
-        // One more miss pushes to 3 consecutive → depth increases.
-        sched.observe(&miss);
         assert_eq!(sched.pipeline_depth(), 2);
+        assert_eq!(sched.stats().depth_changes, 1);
     }
 
     #[test]
-    fn pacing_only_unknown_feedback_does_not_raise_depth() {
-        let config = SchedulerConfig::pacing_only();
-        let mut sched = Scheduler::new(config);
-
-        let unknown = PresentFeedback {
+    fn hysteresis_suppresses_depth_flapping() {
+        let miss = PresentFeedback {
             submitted_at: HostTime(2_000),
             build_start: HostTime(1_000),
             expected_present: None,
             actual_present: None,
-            missed_deadline: None,
+            missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
         };
-
-        for _ in 0..8 {
-            sched.observe(&unknown);
+        let hit = PresentFeedback {
+            missed_deadline: Some(false),
+            ..miss
+        };
+        let builder = SchedulerConfig::builder()
+            .miss_threshold(1)
+            .recovery_threshold(1);
+
+        let mut flapping = Scheduler::new(builder.build());
+        let mut damped = Scheduler::new(builder.depth_change_hysteresis(8).build());
+        for _ in 0..16 {
+            for sched in [&mut flapping, &mut damped] {
+                sched.observe(&miss);
+                sched.observe(&hit);
+            }
         }
 
-        assert_eq!(sched.pipeline_depth(), 1);
-        assert!(
-            sched.safety_margin_ticks() > 0,
-            "unknown deadline feedback should still train build-cost EMA"
-        );
+        assert_eq!(flapping.stats().depth_changes, 32);
+        // The first miss changes depth at once; after that each change waits
+        // out 8 frames, so 32 observed frames allow exactly 4.
+        assert_eq!(damped.stats().depth_changes, 4);
     }
 
     #[test]
-    fn depth_clamped_at_max() {
+    fn depth_override_survives_miss_storm() {
         let mut config = SchedulerConfig::predictive();
-        config.max_depth = 3;
-        config.degradation_policy = DegradationPolicy::Adaptive {
-            miss_threshold: 1,
-            recovery_threshold: 10,
-        };
+        config.minimum_frame_start_margin = Duration(250);
         let mut sched = Scheduler::new(config);
-        assert_eq!(sched.pipeline_depth(), 1);
-
+        sched.set_depth_override(Some(1));
         let miss = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
             expected_present: None,
             actual_present: None,
             missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
         };
 
-        // Many consecutive misses should not exceed max_depth.
-        for _ in 0..20 {
+        for _ in 0..30 {
             sched.observe(&miss);
         }
-        assert_eq!(sched.pipeline_depth(), 3, "depth should be clamped at max");
+
+        assert_eq!(sched.pipeline_depth(), 1);
+        assert_eq!(sched.stats().window_misses, 30);
+        assert_eq!(sched.safety_margin_ticks(), 1_500, "stats still train");
+
+        // A pinned depth above the adaptive one still shifts plans correctly.
+        sched.set_depth_override(Some(3));
+        let plan = sched.plan(
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800),
+            FrameDemand::ANIMATION,
+        );
+        let lookahead = REFRESH_INTERVAL.saturating_mul(2);
+        assert_eq!(plan.pipeline_depth, 3);
+        assert_eq!(plan.sample_time, HostTime(2_000) + lookahead);
+        assert_eq!(plan.commit_deadline, HostTime(1_800) + lookahead);
     }
 
     #[test]
-    fn build_cost_ema_updates() {
-        let config = SchedulerConfig::predictive();
-        let mut sched = Scheduler::new(config);
-        assert_eq!(sched.safety_margin_ticks(), 0);
-
-        let feedback = PresentFeedback {
-            submitted_at: HostTime(2000),
-            build_start: HostTime(1000),
+    fn clearing_depth_override_resumes_adaptation() {
+        let mut sched = Scheduler::new(SchedulerConfig::predictive());
+        sched.set_depth_override(Some(2));
+        let miss = PresentFeedback {
+            submitted_at: HostTime(2_000),
+            build_start: HostTime(1_000),
             expected_present: None,
             actual_present: None,
-            missed_deadline: Some(false),
+            missed_deadline: Some(true),
             pacing_overrun: None,
+            output: OutputId(0),
         };
+        for _ in 0..10 {
+            sched.observe(&miss);
+        }
+        assert_eq!(sched.pipeline_depth(), 2);
 
-        sched.observe(&feedback);
-        assert!(
-            sched.safety_margin_ticks() > 0,
-            "safety margin should increase after observing build cost"
-        );
+        sched.set_depth_override(None);
+        assert_eq!(sched.depth_override(), None);
+        assert_eq!(sched.pipeline_depth(), 1, "adaptive depth was untouched");
+        for _ in 0..3 {
+            sched.observe(&miss);
+        }
+        assert_eq!(sched.pipeline_depth(), 2);
     }
 
     #[test]
-    fn pacing_overrun_raises_depth_more_conservatively() {
-        let config = SchedulerConfig::pacing_only();
+    fn learned_present_offset_shifts_planned_present() {
+        let config = SchedulerConfig::builder()
+            .present_offset_alpha(0.5)
+            .minimum_frame_start_margin(Duration(250))
+            .build();
         let mut sched = Scheduler::new(config);
+        let bias = Duration(2_000_000);
+        let mut now = 1_000_000;
+        let mut previous_target = 0;
+
+        for frame in 0..8 {
+            let predicted = now + REFRESH_INTERVAL.ticks();
+            let plan = sched.plan(
+                make_opportunity(
+                    PresentationTiming::Predictive,
+                    now,
+                    Some(predicted),
+                    predicted - 1_000_000,
+                ),
+                FrameDemand::ANIMATION,
+            );
+            let target = plan.target_present.expect("predictive plan").ticks();
+            let shift = target - predicted;
+            assert!(shift <= bias.ticks(), "frame {frame}: overshot the bias");
+            assert!(shift >= previous_target, "frame {frame}: shift regressed");
+            assert_eq!(plan.sample_time.ticks(), target);
+            previous_target = shift;
+
+            // The platform always presents 2ms after its own prediction.
+            let feedback = PresentFeedback::new(
+                &plan,
+                HostTime(now),
+                HostTime(now + 500_000),
+                Some(HostTime(predicted) + bias),
+            );
+            sched.observe(&feedback);
+            now += REFRESH_INTERVAL.ticks();
+        }
 
-        let overrun = PresentFeedback {
-            submitted_at: HostTime(2_000),
+        assert!(
+            previous_target > bias.ticks() * 9 / 10,
+            "planned present should converge toward the +2ms bias, got {previous_target}"
+        );
+        let learned = sched.stats().present_offset_ticks;
+        assert!(
+            learned > 1_900_000 && learned <= 2_000_000,
+            "learned offset {learned}"
+        );
+    }
+
+    #[test]
+    fn present_offset_ignores_whole_frame_misses_and_is_off_by_default() {
+        let mut enabled =
+            Scheduler::new(SchedulerConfig::builder().present_offset_alpha(1.0).build());
+        let mut disabled = Scheduler::new(SchedulerConfig::predictive());
+        let opportunity =
+            make_opportunity(PresentationTiming::Predictive, 1_000, Some(2_000), 1_800);
+        let feedback = PresentFeedback {
+            submitted_at: HostTime(1_500),
             build_start: HostTime(1_000),
-            expected_present: None,
-            actual_present: None,
-            missed_deadline: None,
-            pacing_overrun: Some(true),
+            expected_present: Some(HostTime(2_000)),
+            actual_present: Some(HostTime(2_000) + REFRESH_INTERVAL),
+            missed_deadline: Some(true),
+            pacing_overrun: None,
+            output: OutputId(0),
         };
 
-        for _ in 0..5 {
-            sched.observe(&overrun);
+        for sched in [&mut enabled, &mut disabled] {
+            let _ = sched.plan(opportunity, FrameDemand::ANIMATION);
+            sched.observe(&feedback);
+            sched.observe(&PresentFeedback {
+                actual_present: Some(HostTime(2_300)),
+                ..feedback
+            });
         }
-        assert_eq!(sched.pipeline_depth(), 1);
 
-        sched.observe(&overrun);
-        assert_eq!(sched.pipeline_depth(), 2);
+        assert_eq!(enabled.stats().present_offset_ticks, 300);
+        assert_eq!(disabled.stats().present_offset_ticks, 0);
+        let plan = disabled.plan(opportunity, FrameDemand::ANIMATION);
+        assert_eq!(plan.target_present, Some(HostTime(2_000)));
     }
 
     #[test]
-    fn pacing_overrun_counters_reset_on_clear_tick() {
-        let config = SchedulerConfig::pacing_only();
+    fn sample_time_catches_up_after_stall_when_clamped() {
+        let config = SchedulerConfig::builder()
+            .max_sample_advance_intervals(Some(2))
+            .build();
         let mut sched = Scheduler::new(config);
-
-        let overrun = PresentFeedback {
-            submitted_at: HostTime(2_000),
-            build_start: HostTime(1_000),
-            expected_present: None,
-            actual_present: None,
-            missed_deadline: None,
-            pacing_overrun: Some(true),
-        };
-        let clear = PresentFeedback {
-            pacing_overrun: Some(false),
-            ..overrun
+        let max_advance = REFRESH_INTERVAL.saturating_mul(2);
+        let plan_at = |sched: &mut Scheduler, now: u64| {
+            let predicted = now + REFRESH_INTERVAL.ticks();
+            sched.plan(
+                make_opportunity(
+                    PresentationTiming::Predictive,
+                    now,
+                    Some(predicted),
+                    predicted - 1_000_000,
+                ),
+                FrameDemand::ANIMATION,
+            )
         };
 
-        for _ in 0..3 {
-            sched.observe(&overrun);
+        let first = plan_at(&mut sched, 1_000_000);
+        // A 200ms stall before the next tick.
+        let mut now = 1_000_000 + 200_000_000;
+        let mut previous = first.sample_time;
+        let mut caught_up = false;
+        for _ in 0..12 {
+            let plan = plan_at(&mut sched, now);
+            let advance = plan.sample_time.saturating_duration_since(previous);
+            assert!(advance <= max_advance, "advanced {advance:?} in one frame");
+            assert!(plan.sample_time <= plan.target_present.expect("predictive"));
+            caught_up = plan.sample_time == plan.target_present.expect("predictive");
+            previous = plan.sample_time;
+            now += REFRESH_INTERVAL.ticks();
         }
-        sched.observe(&clear);
-        for _ in 0..5 {
-            sched.observe(&overrun);
+        assert!(caught_up, "sample time should rejoin wall-clock time");
+
+        let mut unclamped = Scheduler::new(SchedulerConfig::predictive());
+        let _ = plan_at(&mut unclamped, 1_000_000);
+        let plan = plan_at(&mut unclamped, 201_000_000);
+        assert_eq!(plan.sample_time, HostTime(201_000_000) + REFRESH_INTERVAL);
+    }
+
+    #[test]
+    fn sample_clamp_resets_after_idle_gap() {
+        let plan_at = |sched: &mut Scheduler, now: u64| {
+            let predicted = now + REFRESH_INTERVAL.ticks();
+            sched.plan(
+                make_opportunity(
+                    PresentationTiming::Predictive,
+                    now,
+                    Some(predicted),
+                    predicted - 1_000_000,
+                ),
+                FrameDemand::ANIMATION,
+            )
+        };
+
+        // Limits below 2 behave as 2 rather than freezing or never catching up.
+        for intervals in [0, 1, 2] {
+            let config = SchedulerConfig::builder()
+                .max_sample_advance_intervals(Some(intervals))
+                .build();
+            let mut sched = Scheduler::new(config);
+            let first = plan_at(&mut sched, 1_000_000);
+            let plan = plan_at(&mut sched, 201_000_000);
+            assert_eq!(
+                plan.sample_time,
+                first.sample_time + REFRESH_INTERVAL.saturating_mul(2),
+                "limit {intervals}"
+            );
         }
 
-        assert_eq!(sched.pipeline_depth(), 1);
-        sched.observe(&overrun);
-        assert_eq!(sched.pipeline_depth(), 2);
+        // A gap longer than the reset threshold samples wall-clock time.
+        let config = SchedulerConfig::builder()
+            .max_sample_advance_intervals(Some(2))
+            .build();
+        let mut sched = Scheduler::new(config);
+        let _ = plan_at(&mut sched, 1_000_000);
+        let idle = REFRESH_INTERVAL.ticks() * (SAMPLE_CLAMP_RESET_INTERVALS + 2);
+        let plan = plan_at(&mut sched, 1_000_000 + idle);
+        assert_eq!(plan.sample_time, plan.target_present.expect("predictive"));
     }
 }
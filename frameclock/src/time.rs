@@ -76,7 +76,33 @@ impl HostTime {
         Duration(self.0.saturating_sub(earlier.0))
     }
 
+    /// Returns the duration from `other` to `self`, or zero if `other` is
+    /// after `self`.
+    ///
+    /// This is the same as `self - other`, spelled out for call sites that
+    /// would otherwise subtract raw ticks.
+    #[inline]
+    #[must_use]
+    pub const fn saturating_sub(self, other: Self) -> Duration {
+        self.saturating_duration_since(other)
+    }
+
+    /// Returns an adapter that displays this time in milliseconds, converted
+    /// with `timebase`.
+    ///
+    /// The output has microsecond precision, e.g. `1234.567ms`.
+    #[inline]
+    #[must_use]
+    pub const fn fmt_with(self, timebase: Timebase) -> HostTimeMillis {
+        HostTimeMillis {
+            time: self,
+            timebase,
+        }
+    }
+
     /// Checked addition of a duration.
+    ///
+    /// Returns `None` instead of saturating when the result would overflow.
     #[inline]
     #[must_use]
     pub const fn checked_add(self, duration: Duration) -> Option<Self> {
@@ -130,6 +156,31 @@ impl fmt::Debug for HostTime {
     }
 }
 
+impl fmt::Display for HostTime {
+    /// Renders the raw tick value, e.g. `1500 ticks`.
+    ///
+    /// Use [`fmt_with`](Self::fmt_with) for real units.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ticks", self.0)
+    }
+}
+
+/// Displays a [`HostTime`] in milliseconds; returned by
+/// [`HostTime::fmt_with`].
+#[derive(Clone, Copy, Debug)]
+pub struct HostTimeMillis {
+    time: HostTime,
+    timebase: Timebase,
+}
+
+impl fmt::Display for HostTimeMillis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.time.to_nanos(self.timebase);
+        let micros = nanos / 1_000;
+        write!(f, "{}.{:03}ms", micros / 1_000, micros % 1_000)
+    }
+}
+
 /// Rational conversion factor from ticks to nanoseconds.
 ///
 /// `nanoseconds = ticks * numer / denom`
@@ -138,9 +189,11 @@ impl fmt::Debug for HostTime {
 /// instance for a given platform is provided by the platform adapter's
 /// `timebase()` function.
 ///
-/// Use [`Timebase::NANOS`] when a backend chooses nanoseconds as its host tick
-/// unit. Do not mix values converted with different timebases inside one
-/// `FrameDriver` or `Scheduler`.
+/// Use [`Timebase::NANOS`] or [`Timebase::MICROS`] when a backend chooses
+/// nanoseconds or microseconds as its host tick unit, and
+/// [`Timebase::from_hz`] for counters described by a tick frequency. Do not
+/// mix values converted with different timebases inside one `FrameDriver` or
+/// `Scheduler`.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Timebase {
     /// Numerator of the ticks-to-nanoseconds ratio.
@@ -153,6 +206,12 @@ impl Timebase {
     /// A timebase where ticks are already nanoseconds (1:1).
     pub const NANOS: Self = Self { numer: 1, denom: 1 };
 
+    /// A timebase where ticks are microseconds (1000:1).
+    pub const MICROS: Self = Self {
+        numer: 1000,
+        denom: 1,
+    };
+
     /// Creates a new timebase with the given numerator and denominator.
     ///
     /// # Panics
@@ -165,7 +224,20 @@ impl Timebase {
         Self { numer, denom }
     }
 
+    /// Creates a timebase for a counter that advances `hz` ticks per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero.
+    #[inline]
+    #[must_use]
+    pub const fn from_hz(hz: u32) -> Self {
+        Self::new(1_000_000_000, hz)
+    }
+
     /// Converts a tick count to nanoseconds.
+    ///
+    /// The result is rounded toward zero.
     #[inline]
     #[must_use]
     #[expect(
@@ -178,6 +250,12 @@ impl Timebase {
     }
 
     /// Converts nanoseconds to a tick count.
+    ///
+    /// This is the inverse of [`ticks_to_nanos`](Self::ticks_to_nanos), rounded
+    /// toward zero. When one tick is a whole number of nanoseconds (as with
+    /// [`NANOS`](Self::NANOS) and [`MICROS`](Self::MICROS)),
+    /// `nanos_to_ticks(ticks_to_nanos(x)) == x` for every `x` whose
+    /// nanosecond value fits in `u64`.
     #[inline]
     #[must_use]
     #[expect(
@@ -203,6 +281,11 @@ impl fmt::Debug for Timebase {
 /// deadlines; app code can convert to or from nanoseconds with the backend's
 /// [`Timebase`] when it needs human-readable diagnostics or platform APIs that
 /// use nanoseconds.
+///
+/// The tuple constructor is the raw-tick path. Prefer
+/// [`from_millis`](Self::from_millis), [`from_micros`](Self::from_micros),
+/// [`from_nanos`](Self::from_nanos), or [`from_secs_f64`](Self::from_secs_f64)
+/// when starting from real units, so the timebase is explicit.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct Duration(pub u64);
 
@@ -238,6 +321,42 @@ impl Duration {
         Self(timebase.nanos_to_ticks(nanos))
     }
 
+    /// Creates a duration from a microsecond value and timebase.
+    #[inline]
+    #[must_use]
+    pub const fn from_micros(micros: u64, timebase: Timebase) -> Self {
+        Self::from_nanos(micros.saturating_mul(1_000), timebase)
+    }
+
+    /// Creates a duration from a millisecond value and timebase.
+    #[inline]
+    #[must_use]
+    pub const fn from_millis(millis: u64, timebase: Timebase) -> Self {
+        Self::from_nanos(millis.saturating_mul(1_000_000), timebase)
+    }
+
+    /// Creates a duration from seconds and a timebase.
+    ///
+    /// Negative and NaN values become zero; values too large for `u64`
+    /// nanoseconds saturate.
+    #[inline]
+    #[must_use]
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "float-to-int casts saturate, which is the documented behavior"
+    )]
+    pub fn from_secs_f64(secs: f64, timebase: Timebase) -> Self {
+        Self::from_nanos((secs * 1e9) as u64, timebase)
+    }
+
+    /// Converts this duration to seconds using the given timebase.
+    #[inline]
+    #[must_use]
+    pub fn as_secs_f64(self, timebase: Timebase) -> f64 {
+        self.to_nanos(timebase) as f64 / 1e9
+    }
+
     /// Saturating addition.
     #[inline]
     #[must_use]
@@ -318,6 +437,50 @@ mod tests {
         assert_eq!(back.ticks(), ticks);
     }
 
+    #[test]
+    fn nanos_round_trip_micros() {
+        let tb = Timebase::MICROS;
+        for ticks in [0, 1, 999, 16_667, 1_000_000, u64::MAX / 1000] {
+            let nanos = tb.ticks_to_nanos(ticks);
+            assert_eq!(nanos, ticks * 1000, "microsecond ticks are 1000 ns");
+            assert_eq!(
+                tb.nanos_to_ticks(nanos),
+                ticks,
+                "round trip must be exact for {ticks} ticks"
+            );
+        }
+        // Sub-tick nanoseconds round toward zero.
+        assert_eq!(tb.nanos_to_ticks(1_999), 1, "partial ticks truncate");
+    }
+
+    #[test]
+    fn from_hz_matches_tick_frequency() {
+        assert_eq!(
+            Timebase::from_hz(1_000_000_000).ticks_to_nanos(12_345),
+            12_345,
+            "1 GHz ticks are nanoseconds"
+        );
+        let micros = Timebase::from_hz(1_000_000);
+        assert_eq!(micros.ticks_to_nanos(1), 1000, "1 MHz ticks are 1 us");
+        assert_eq!(
+            micros.nanos_to_ticks(micros.ticks_to_nanos(16_667)),
+            16_667,
+            "1 MHz round trip must be exact"
+        );
+
+        let qpc = Timebase::from_hz(10_000_000);
+        assert_eq!(
+            qpc.ticks_to_nanos(10_000_000),
+            1_000_000_000,
+            "10 MHz → 1 s"
+        );
+        assert_eq!(
+            qpc.nanos_to_ticks(1_000_000_000),
+            10_000_000,
+            "1 s → 10 MHz"
+        );
+    }
+
     #[test]
     fn overflow_safe_conversion() {
         // Large tick value that would overflow u64 if multiplied naively
@@ -338,6 +501,40 @@ mod tests {
         assert_eq!((Duration(0) - Duration(1)).ticks(), 0);
     }
 
+    #[test]
+    fn duration_unit_constructors_nanos() {
+        let tb = Timebase::NANOS;
+        assert_eq!(Duration::from_millis(16, tb), Duration(16_000_000));
+        assert_eq!(Duration::from_micros(16_667, tb), Duration(16_667_000));
+        assert_eq!(Duration::from_secs_f64(0.5, tb), Duration(500_000_000));
+        assert_eq!(Duration(250_000_000).as_secs_f64(tb), 0.25);
+    }
+
+    #[test]
+    fn duration_unit_constructors_micros() {
+        let tb = Timebase::MICROS;
+        assert_eq!(Duration::from_millis(16, tb), Duration(16_000));
+        assert_eq!(Duration::from_micros(16_667, tb), Duration(16_667));
+        assert_eq!(Duration::from_secs_f64(0.5, tb), Duration(500_000));
+        assert_eq!(Duration(250_000).as_secs_f64(tb), 0.25);
+        assert_eq!(
+            Duration::from_millis(5, tb) + Duration::from_micros(500, tb),
+            Duration(5_500),
+            "unit constructors compose with tick arithmetic"
+        );
+    }
+
+    #[test]
+    fn duration_from_secs_f64_clamps_invalid_input() {
+        let tb = Timebase::NANOS;
+        assert_eq!(Duration::from_secs_f64(-1.0, tb), Duration::ZERO);
+        assert_eq!(Duration::from_secs_f64(f64::NAN, tb), Duration::ZERO);
+        assert_eq!(
+            Duration::from_secs_f64(f64::INFINITY, tb),
+            Duration(u64::MAX)
+        );
+    }
+
     #[test]
     fn host_time_duration_ops() {
         let t = HostTime(1000);
@@ -350,4 +547,61 @@ mod tests {
         assert_eq!(t.saturating_duration_since(HostTime(1500)), Duration::ZERO);
         assert_eq!(t.saturating_duration_since(HostTime(400)), Duration(600));
     }
+
+    #[test]
+    fn host_time_saturating_sub_stops_at_zero() {
+        assert_eq!(HostTime(1000).saturating_sub(HostTime(400)), Duration(600));
+        assert_eq!(HostTime(400).saturating_sub(HostTime(1000)), Duration::ZERO);
+        assert_eq!(
+            HostTime(0).saturating_sub(HostTime(u64::MAX)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn host_time_checked_add_detects_overflow() {
+        assert_eq!(HostTime(10).checked_add(Duration(5)), Some(HostTime(15)));
+        assert_eq!(
+            HostTime(u64::MAX - 1).checked_add(Duration(1)),
+            Some(HostTime(u64::MAX))
+        );
+        assert_eq!(HostTime(u64::MAX).checked_add(Duration(1)), None);
+    }
+
+    /// Formats `value` into a fixed buffer, since this crate has no `alloc`.
+    fn render(value: impl fmt::Display) -> ([u8; 64], usize) {
+        struct Buf([u8; 64], usize);
+        impl fmt::Write for Buf {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let end = self.1 + s.len();
+                self.0
+                    .get_mut(self.1..end)
+                    .ok_or(fmt::Error)?
+                    .copy_from_slice(s.as_bytes());
+                self.1 = end;
+                Ok(())
+            }
+        }
+        let mut buf = Buf([0; 64], 0);
+        fmt::write(&mut buf, format_args!("{value}")).unwrap();
+        (buf.0, buf.1)
+    }
+
+    fn assert_renders(value: impl fmt::Display, expected: &str) {
+        let (bytes, len) = render(value);
+        assert_eq!(core::str::from_utf8(&bytes[..len]).unwrap(), expected);
+    }
+
+    #[test]
+    fn host_time_formatting() {
+        let t = HostTime(1_234_567);
+        assert_renders(t, "1234567 ticks");
+        assert_renders(format_args!("{t:?}"), "HostTime(1234567)");
+        // Microsecond ticks: 1_234_567 us is 1234.567 ms.
+        assert_renders(t.fmt_with(Timebase::MICROS), "1234.567ms");
+        assert_renders(HostTime(16_667).fmt_with(Timebase::MICROS), "16.667ms");
+        assert_renders(HostTime(5).fmt_with(Timebase::MICROS), "0.005ms");
+        // Sub-microsecond nanoseconds are truncated.
+        assert_renders(HostTime(1_999).fmt_with(Timebase::NANOS), "0.001ms");
+    }
 }
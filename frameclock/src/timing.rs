@@ -35,6 +35,8 @@
 //!    [`Scheduler::observe()`](crate::scheduler::Scheduler::observe) to
 //!    adapt pipeline depth and safety margins.
 
+use alloc::vec::Vec;
+
 pub use crate::demand::{FrameDemand, FrameDemandClass};
 use crate::output::OutputId;
 use crate::time::{Duration, HostTime};
@@ -215,6 +217,9 @@ fn round_up_to_multiple(needed: Duration, interval: Duration) -> Duration {
 /// [`SchedulerConfig`](crate::scheduler::SchedulerConfig). For example, hosts
 /// should normally pair [`PresentationTiming::Estimated`] hints with
 /// [`SchedulerConfig::estimated`](crate::scheduler::SchedulerConfig::estimated).
+///
+/// Modes are ordered by confidence: `Predictive > Estimated > PacingOnly`, so
+/// the weaker of two sources is their minimum.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PresentationTiming {
     /// Strong predicted present time available (e.g. macOS `CVDisplayLink`).
@@ -232,6 +237,54 @@ impl PresentationTiming {
     pub const fn has_target_present(self) -> bool {
         matches!(self, Self::Predictive | Self::Estimated)
     }
+
+    /// Returns the next weaker timing mode.
+    ///
+    /// `Predictive` steps down to `Estimated`, and `Estimated` to
+    /// `PacingOnly`. `PacingOnly` is already the weakest mode and is returned
+    /// unchanged. Backends use this when they lose a source of prediction.
+    #[inline]
+    #[must_use]
+    pub const fn downgrade(self) -> Self {
+        match self {
+            Self::Predictive => Self::Estimated,
+            Self::Estimated | Self::PacingOnly => Self::PacingOnly,
+        }
+    }
+
+    /// Returns the weaker of `self` and `other`.
+    ///
+    /// Equivalent to [`Ord::min`], but usable in `const` contexts.
+    #[inline]
+    #[must_use]
+    pub const fn min(self, other: Self) -> Self {
+        if other.rank() < self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Confidence rank, higher is stronger.
+    const fn rank(self) -> u8 {
+        match self {
+            Self::PacingOnly => 0,
+            Self::Estimated => 1,
+            Self::Predictive => 2,
+        }
+    }
+}
+
+impl PartialOrd for PresentationTiming {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PresentationTiming {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
 }
 
 /// Platform timing facts used to create a [`FrameOpportunity`].
@@ -263,6 +316,137 @@ pub struct FrameTick {
     pub prev_actual_present: Option<HostTime>,
 }
 
+impl FrameTick {
+    /// Starts building a tick.
+    ///
+    /// The builder defaults to `now = HostTime(0)`, frame index 0,
+    /// `OutputId(0)`, and no predicted present, refresh interval, or previous
+    /// present time. This is mostly useful for tests and simulations; backends
+    /// normally fill every field from the platform callback.
+    #[inline]
+    #[must_use]
+    pub const fn builder() -> FrameTickBuilder {
+        FrameTickBuilder {
+            tick: Self {
+                now: HostTime(0),
+                predicted_present: None,
+                refresh_interval: None,
+                frame_index: 0,
+                output: OutputId(0),
+                prev_actual_present: None,
+            },
+        }
+    }
+
+    /// Returns the upcoming predicted present times, earliest first.
+    ///
+    /// The first time is [`predicted_present`](Self::predicted_present), or
+    /// one refresh interval after [`now`](Self::now) when no prediction is
+    /// available. Each later time is one
+    /// [`refresh_interval`](Self::refresh_interval) after the previous one.
+    /// Without a (non-zero) refresh interval, only the platform prediction is
+    /// yielded, if any. The iterator is otherwise unbounded, so callers
+    /// scheduling work "N frames ahead" use `.take(n)` or `.nth(n)`.
+    #[must_use]
+    pub fn predicted_presents(&self) -> PredictedPresents {
+        let interval = self.refresh_interval.filter(|&ticks| ticks > 0);
+        let next = self
+            .predicted_present
+            .or_else(|| Some(self.now + Duration(interval?)));
+        PredictedPresents { next, interval }
+    }
+}
+
+/// Iterator over predicted present times; returned by
+/// [`FrameTick::predicted_presents`].
+#[derive(Clone, Debug)]
+pub struct PredictedPresents {
+    next: Option<HostTime>,
+    interval: Option<u64>,
+}
+
+impl Iterator for PredictedPresents {
+    type Item = HostTime;
+
+    fn next(&mut self) -> Option<HostTime> {
+        let current = self.next?;
+        self.next = self
+            .interval
+            .and_then(|ticks| current.checked_add(Duration(ticks)));
+        Some(current)
+    }
+}
+
+/// Returns the next `count` predicted present times for `tick`, earliest
+/// first.
+///
+/// This collects [`FrameTick::predicted_presents`]: times are spaced by the
+/// tick's refresh interval, starting from its predicted present or, without
+/// one, one interval after `now`. Fewer than `count` times are returned when
+/// the refresh interval is unknown.
+#[must_use]
+pub fn predict_presents(tick: &FrameTick, count: usize) -> Vec<HostTime> {
+    tick.predicted_presents().take(count).collect()
+}
+
+/// Builder for [`FrameTick`].
+///
+/// Created by [`FrameTick::builder`].
+#[derive(Clone, Copy, Debug)]
+pub struct FrameTickBuilder {
+    tick: FrameTick,
+}
+
+impl FrameTickBuilder {
+    /// Sets [`FrameTick::now`].
+    #[must_use]
+    pub const fn now(mut self, now: HostTime) -> Self {
+        self.tick.now = now;
+        self
+    }
+
+    /// Sets [`FrameTick::predicted_present`].
+    #[must_use]
+    pub const fn predicted_present(mut self, predicted_present: Option<HostTime>) -> Self {
+        self.tick.predicted_present = predicted_present;
+        self
+    }
+
+    /// Sets [`FrameTick::refresh_interval`] in host-time ticks.
+    #[must_use]
+    pub const fn refresh_interval(mut self, refresh_interval: Option<u64>) -> Self {
+        self.tick.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Sets [`FrameTick::frame_index`].
+    #[must_use]
+    pub const fn frame_index(mut self, frame_index: u64) -> Self {
+        self.tick.frame_index = frame_index;
+        self
+    }
+
+    /// Sets [`FrameTick::output`].
+    #[must_use]
+    pub const fn output(mut self, output: OutputId) -> Self {
+        self.tick.output = output;
+        self
+    }
+
+    /// Sets [`FrameTick::prev_actual_present`].
+    #[must_use]
+    pub const fn prev_actual_present(mut self, prev_actual_present: Option<HostTime>) -> Self {
+        self.tick.prev_actual_present = prev_actual_present;
+        self
+    }
+
+    /// Returns the configured tick.
+    #[must_use]
+    pub const fn build(self) -> FrameTick {
+        self.tick
+    }
+}
+
 /// Platform frame facts passed to the scheduler or retained driver.
 ///
 /// Hosts construct this from the current display/frame callback. It packages
@@ -380,6 +564,29 @@ pub struct FramePlan {
     pub frame_index: u64,
 }
 
+impl FramePlan {
+    /// Returns the time left at `now` before
+    /// [`commit_deadline`](Self::commit_deadline), saturating at zero.
+    ///
+    /// Frame loops can compare this against the cost of optional work, such
+    /// as prefetching or an extra render pass, before starting it.
+    #[inline]
+    #[must_use]
+    pub const fn budget_remaining(&self, now: HostTime) -> Duration {
+        self.commit_deadline.saturating_sub(now)
+    }
+
+    /// Returns whether `now` is after
+    /// [`commit_deadline`](Self::commit_deadline).
+    ///
+    /// Committing exactly at the deadline is still on time.
+    #[inline]
+    #[must_use]
+    pub const fn is_past_deadline(&self, now: HostTime) -> bool {
+        now.0 > self.commit_deadline.0
+    }
+}
+
 /// Backend submission constraints carried by a [`FrameOpportunity`].
 ///
 /// Backends compute these from the current [`FrameTick`] and their own
@@ -472,6 +679,22 @@ impl PresentHints {
         Self::new(PresentationTiming::PacingOnly, None, latest_commit)
     }
 
+    /// Starts building hints.
+    ///
+    /// The builder defaults to [`PresentationTiming::PacingOnly`], no desired
+    /// present time, and `latest_commit = HostTime(0)`. As with
+    /// [`PresentHints::new`], a desired present time is discarded when the
+    /// final timing mode is pacing-only.
+    #[inline]
+    #[must_use]
+    pub const fn builder() -> PresentHintsBuilder {
+        PresentHintsBuilder {
+            presentation_timing: PresentationTiming::PacingOnly,
+            desired_present: None,
+            latest_commit: HostTime(0),
+        }
+    }
+
     /// Creates feedback hints normalized to an executed [`FramePlan`].
     ///
     /// Use this after scheduling has produced a plan. The scheduler may shift
@@ -489,6 +712,49 @@ impl PresentHints {
     }
 }
 
+/// Builder for [`PresentHints`].
+///
+/// Created by [`PresentHints::builder`].
+#[derive(Clone, Copy, Debug)]
+pub struct PresentHintsBuilder {
+    presentation_timing: PresentationTiming,
+    desired_present: Option<HostTime>,
+    latest_commit: HostTime,
+}
+
+impl PresentHintsBuilder {
+    /// Sets how the desired present time should be interpreted.
+    #[must_use]
+    pub const fn presentation_timing(mut self, presentation_timing: PresentationTiming) -> Self {
+        self.presentation_timing = presentation_timing;
+        self
+    }
+
+    /// Sets the target present time.
+    #[must_use]
+    pub const fn desired_present(mut self, desired_present: Option<HostTime>) -> Self {
+        self.desired_present = desired_present;
+        self
+    }
+
+    /// Sets the latest time by which the frame must be committed.
+    #[must_use]
+    pub const fn latest_commit(mut self, latest_commit: HostTime) -> Self {
+        self.latest_commit = latest_commit;
+        self
+    }
+
+    /// Returns the configured hints.
+    #[must_use]
+    pub const fn build(self) -> PresentHints {
+        PresentHints::new(
+            self.presentation_timing,
+            self.desired_present,
+            self.latest_commit,
+        )
+    }
+}
+
 /// Timing feedback passed to [`Scheduler::observe`](crate::scheduler::Scheduler::observe).
 ///
 /// Low-level scheduler integrations construct this with [`PresentFeedback::new`]
@@ -528,6 +794,11 @@ pub struct PresentFeedback {
     ///
     /// [`missed_deadline`]: Self::missed_deadline
     pub pacing_overrun: Option<bool>,
+    /// Which output the frame was presented on.
+    ///
+    /// The scheduler uses this to update the adaptive state for the same
+    /// output that planned the frame.
+    pub output: OutputId,
 }
 
 impl PresentFeedback {
@@ -547,6 +818,7 @@ impl PresentFeedback {
     ) -> Self {
         Self::from_hints(
             &PresentHints::for_plan(plan),
+            plan.output,
             build_start,
             submitted_at,
             actual_present,
@@ -555,6 +827,8 @@ impl PresentFeedback {
 
     /// Constructs feedback from already-normalized presentation hints.
     ///
+    /// `output` should name the output the frame was planned for.
+    ///
     /// Most integrations should prefer [`Self::new`] with the executed
     /// [`FramePlan`]. Use this only when the caller has intentionally built
     /// hints that already describe the exact target-present and commit timing
@@ -579,6 +853,7 @@ impl PresentFeedback {
     #[must_use]
     pub fn from_hints(
         hints: &PresentHints,
+        output: OutputId,
         build_start: HostTime,
         submitted_at: HostTime,
         actual_present: Option<HostTime>,
@@ -607,8 +882,48 @@ impl PresentFeedback {
             actual_present,
             missed_deadline,
             pacing_overrun,
+            output,
         }
     }
+
+    /// Constructs feedback for `plan` from scripted timing.
+    ///
+    /// The frame starts building at [`FramePlan::frame_start`], is submitted
+    /// `build_cost` later, and presents `present_offset` ticks after
+    /// [`FramePlan::target_present`] (negative offsets present early). Plans
+    /// without a target present time get no actual present time. Deadline
+    /// classification then follows [`Self::new`]; chain
+    /// [`Self::with_missed_deadline`] to override it.
+    ///
+    /// This is meant for deterministic scheduler tests and simulations, where
+    /// deriving host times by hand around the commit deadline is noisy.
+    #[must_use]
+    pub fn scripted(plan: &FramePlan, build_cost: Duration, present_offset: i64) -> Self {
+        let build_start = plan.frame_start;
+        let submitted_at = build_start
+            .checked_add(build_cost)
+            .unwrap_or(HostTime(u64::MAX));
+        let actual_present = plan.target_present.map(|target| {
+            let magnitude = Duration(present_offset.unsigned_abs());
+            if present_offset < 0 {
+                target.checked_sub(magnitude).unwrap_or(HostTime(0))
+            } else {
+                target.checked_add(magnitude).unwrap_or(HostTime(u64::MAX))
+            }
+        });
+        Self::new(plan, build_start, submitted_at, actual_present)
+    }
+
+    /// Overrides the strict deadline signal with `missed`.
+    ///
+    /// Clears [`pacing_overrun`](Self::pacing_overrun), since the scheduler
+    /// only consults it when the deadline outcome is unknown.
+    #[must_use]
+    pub const fn with_missed_deadline(mut self, missed: bool) -> Self {
+        self.missed_deadline = Some(missed);
+        self.pacing_overrun = None;
+        self
+    }
 }
 
 /// Stored submission facts for resolving [`PresentFeedback`] on a later tick.
@@ -621,7 +936,8 @@ impl PresentFeedback {
 /// integrations that receive previous-frame present feedback. Retained
 /// [`FrameDriver`](crate::FrameDriver) integrations can often pass immediate
 /// submission facts through [`FrameSubmission`](crate::FrameSubmission)
-/// instead.
+/// instead. [`Scheduler::submit`](crate::scheduler::Scheduler::submit) holds
+/// one of these internally for hosts that would rather not store it.
 ///
 /// # Usage
 ///
@@ -694,14 +1010,199 @@ mod tests {
         predicted: Option<u64>,
         refresh_interval: Option<u64>,
     ) -> FrameTick {
-        FrameTick {
-            now: HostTime(now),
-            predicted_present: predicted.map(HostTime),
-            refresh_interval,
-            frame_index: 0,
-            output: OutputId(0),
-            prev_actual_present: None,
-        }
+        FrameTick::builder()
+            .now(HostTime(now))
+            .predicted_present(predicted.map(HostTime))
+            .refresh_interval(refresh_interval)
+            .build()
+    }
+
+    #[test]
+    fn budget_remaining_counts_down_to_commit_deadline() {
+        let plan = plan_with_hints(PresentHints::pacing_only(HostTime(15_000)));
+
+        assert_eq!(plan.budget_remaining(HostTime(10_000)), Duration(5_000));
+        assert!(!plan.is_past_deadline(HostTime(10_000)));
+
+        assert_eq!(plan.budget_remaining(HostTime(15_000)), Duration(0));
+        assert!(!plan.is_past_deadline(HostTime(15_000)));
+
+        assert_eq!(plan.budget_remaining(HostTime(16_000)), Duration(0));
+        assert!(plan.is_past_deadline(HostTime(16_000)));
+    }
+
+    #[test]
+    fn scripted_feedback_offsets_actual_present() {
+        let plan = plan_with_hints(PresentHints::predictive(HostTime(20_000), HostTime(15_000)));
+
+        let late = PresentFeedback::scripted(&plan, Duration(4_000), 1_500);
+        assert_eq!(late.build_start, plan.frame_start);
+        assert_eq!(late.submitted_at, HostTime(4_000));
+        assert_eq!(late.expected_present, Some(HostTime(20_000)));
+        assert_eq!(late.actual_present, Some(HostTime(21_500)));
+        assert_eq!(late.missed_deadline, Some(true));
+
+        let early = PresentFeedback::scripted(&plan, Duration(4_000), -500);
+        assert_eq!(early.actual_present, Some(HostTime(19_500)));
+        assert_eq!(early.missed_deadline, Some(false));
+    }
+
+    #[test]
+    fn scripted_feedback_missed_deadline_override() {
+        let plan = plan_with_hints(PresentHints::pacing_only(HostTime(15_000)));
+
+        let feedback = PresentFeedback::scripted(&plan, Duration(20_000), 0);
+        assert_eq!(feedback.actual_present, None);
+        assert_eq!(feedback.missed_deadline, None);
+        assert_eq!(feedback.pacing_overrun, Some(true));
+
+        let forced = feedback.with_missed_deadline(false);
+        assert_eq!(forced.missed_deadline, Some(false));
+        assert_eq!(forced.pacing_overrun, None);
+    }
+
+    #[test]
+    fn presentation_timing_orders_by_confidence() {
+        use PresentationTiming::{Estimated, PacingOnly, Predictive};
+
+        assert!(Predictive > Estimated);
+        assert!(Estimated > PacingOnly);
+        assert!(Predictive > PacingOnly);
+        assert_eq!(Predictive.max(PacingOnly), Predictive);
+        assert_eq!(Predictive.min(Estimated), Estimated);
+        assert_eq!(PacingOnly.min(Predictive), PacingOnly);
+        assert_eq!(Estimated.min(Estimated), Estimated);
+    }
+
+    #[test]
+    fn presentation_timing_downgrade_steps_down() {
+        use PresentationTiming::{Estimated, PacingOnly, Predictive};
+
+        assert_eq!(Predictive.downgrade(), Estimated);
+        assert_eq!(Estimated.downgrade(), PacingOnly);
+        assert_eq!(PacingOnly.downgrade(), PacingOnly);
+    }
+
+    #[test]
+    fn frame_tick_builder_sets_every_field() {
+        let tick = FrameTick::builder()
+            .now(HostTime(1_000))
+            .predicted_present(Some(HostTime(17_667)))
+            .refresh_interval(Some(16_667))
+            .frame_index(42)
+            .output(OutputId(3))
+            .prev_actual_present(Some(HostTime(990)))
+            .build();
+
+        assert_eq!(
+            tick,
+            FrameTick {
+                now: HostTime(1_000),
+                predicted_present: Some(HostTime(17_667)),
+                refresh_interval: Some(16_667),
+                frame_index: 42,
+                output: OutputId(3),
+                prev_actual_present: Some(HostTime(990)),
+            }
+        );
+    }
+
+    #[test]
+    fn frame_tick_builder_defaults() {
+        let tick = FrameTick::builder().build();
+        assert_eq!(tick.now, HostTime(0));
+        assert_eq!(tick.predicted_present, None);
+        assert_eq!(tick.refresh_interval, None);
+        assert_eq!(tick.frame_index, 0);
+        assert_eq!(tick.output, OutputId(0));
+        assert_eq!(tick.prev_actual_present, None);
+    }
+
+    #[test]
+    fn present_hints_builder_matches_constructors() {
+        let predictive = PresentHints::builder()
+            .presentation_timing(PresentationTiming::Predictive)
+            .desired_present(Some(HostTime(20)))
+            .latest_commit(HostTime(15))
+            .build();
+        assert_eq!(
+            predictive,
+            PresentHints::predictive(HostTime(20), HostTime(15))
+        );
+
+        let pacing = PresentHints::builder()
+            .desired_present(Some(HostTime(20)))
+            .latest_commit(HostTime(15))
+            .build();
+        assert_eq!(pacing, PresentHints::pacing_only(HostTime(15)));
+        assert_eq!(pacing.desired_present(), None);
+    }
+
+    #[test]
+    fn predicted_presents_are_evenly_spaced() {
+        let tick = FrameTick::builder()
+            .now(HostTime(1_000))
+            .predicted_present(Some(HostTime(5_000)))
+            .refresh_interval(Some(16_667))
+            .build();
+        let mut presents = tick.predicted_presents();
+        assert_eq!(presents.next(), Some(HostTime(5_000)));
+        assert_eq!(presents.next(), Some(HostTime(21_667)));
+        assert_eq!(presents.next(), Some(HostTime(38_334)));
+        assert_eq!(tick.predicted_presents().nth(10), Some(HostTime(171_670)));
+    }
+
+    #[test]
+    fn predicted_presents_extrapolate_from_now_without_prediction() {
+        let tick = FrameTick::builder()
+            .now(HostTime(1_000))
+            .refresh_interval(Some(10_000))
+            .build();
+        let mut presents = tick.predicted_presents();
+        assert_eq!(presents.next(), Some(HostTime(11_000)));
+        assert_eq!(presents.next(), Some(HostTime(21_000)));
+    }
+
+    #[test]
+    fn predicted_presents_without_refresh_interval() {
+        let predicted = FrameTick::builder()
+            .now(HostTime(1_000))
+            .predicted_present(Some(HostTime(5_000)))
+            .build();
+        let mut presents = predicted.predicted_presents();
+        assert_eq!(presents.next(), Some(HostTime(5_000)));
+        assert_eq!(presents.next(), None, "no interval to step by");
+
+        let unknown = FrameTick::builder()
+            .now(HostTime(1_000))
+            .refresh_interval(Some(0))
+            .build();
+        assert_eq!(unknown.predicted_presents().next(), None);
+
+        let near_end = FrameTick::builder()
+            .predicted_present(Some(HostTime(u64::MAX - 5)))
+            .refresh_interval(Some(10))
+            .build();
+        assert_eq!(
+            near_end.predicted_presents().count(),
+            1,
+            "stepping stops instead of saturating"
+        );
+    }
+
+    #[test]
+    fn predict_presents_collects_count_times() {
+        let tick = FrameTick::builder()
+            .now(HostTime(1_000))
+            .refresh_interval(Some(10_000))
+            .build();
+        assert_eq!(
+            predict_presents(&tick, 3),
+            [HostTime(11_000), HostTime(21_000), HostTime(31_000)]
+        );
+
+        let unknown = FrameTick::builder().now(HostTime(1_000)).build();
+        assert!(predict_presents(&unknown, 3).is_empty());
     }
 
     #[test]
@@ -749,6 +1250,7 @@ mod tests {
         let hints = PresentHints::predictive(HostTime(2_000_000), HostTime(1_800_000));
         let fb = PresentFeedback::from_hints(
             &hints,
+            OutputId(0),
             HostTime(1_700_000),
             HostTime(1_750_000),
             Some(HostTime(2_100_000)),
@@ -761,6 +1263,7 @@ mod tests {
         // On time.
         let fb = PresentFeedback::from_hints(
             &hints,
+            OutputId(0),
             HostTime(1_700_000),
             HostTime(1_750_000),
             Some(HostTime(1_999_000)),
@@ -774,14 +1277,24 @@ mod tests {
         let hints = PresentHints::predictive(HostTime(2_000_000), HostTime(1_800_000));
         // submitted_at > latest_commit is weak pacing evidence until the
         // backend reports actual present.
-        let fb =
-            PresentFeedback::from_hints(&hints, HostTime(1_700_000), HostTime(1_900_000), None);
+        let fb = PresentFeedback::from_hints(
+            &hints,
+            OutputId(0),
+            HostTime(1_700_000),
+            HostTime(1_900_000),
+            None,
+        );
         assert_eq!(fb.missed_deadline, None);
         assert_eq!(fb.pacing_overrun, Some(true));
 
         // submitted_at <= latest_commit is still not presentation truth.
-        let fb =
-            PresentFeedback::from_hints(&hints, HostTime(1_700_000), HostTime(1_750_000), None);
+        let fb = PresentFeedback::from_hints(
+            &hints,
+            OutputId(0),
+            HostTime(1_700_000),
+            HostTime(1_750_000),
+            None,
+        );
         assert_eq!(fb.missed_deadline, None);
         assert_eq!(fb.pacing_overrun, Some(false));
     }
@@ -789,7 +1302,13 @@ mod tests {
     #[test]
     fn new_without_desired_present_is_unknown() {
         let hints = PresentHints::pacing_only(HostTime(1_000_000));
-        let fb = PresentFeedback::from_hints(&hints, HostTime(900_000), HostTime(1_100_000), None);
+        let fb = PresentFeedback::from_hints(
+            &hints,
+            OutputId(0),
+            HostTime(900_000),
+            HostTime(1_100_000),
+            None,
+        );
         assert_eq!(fb.missed_deadline, None);
         assert_eq!(fb.expected_present, None);
         assert_eq!(fb.pacing_overrun, Some(true));
@@ -800,6 +1319,7 @@ mod tests {
         let hints = PresentHints::pacing_only(HostTime(1_000_000));
         let fb = PresentFeedback::from_hints(
             &hints,
+            OutputId(0),
             HostTime(900_000),
             HostTime(1_100_000),
             Some(HostTime(1_200_000)),
@@ -859,7 +1379,13 @@ mod tests {
     #[test]
     fn pacing_only_on_time_submission_reports_no_overrun() {
         let hints = PresentHints::pacing_only(HostTime(1_000_000));
-        let fb = PresentFeedback::from_hints(&hints, HostTime(900_000), HostTime(950_000), None);
+        let fb = PresentFeedback::from_hints(
+            &hints,
+            OutputId(0),
+            HostTime(900_000),
+            HostTime(950_000),
+            None,
+        );
         assert_eq!(fb.missed_deadline, None);
         assert_eq!(fb.pacing_overrun, Some(false));
     }
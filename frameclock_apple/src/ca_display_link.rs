@@ -69,7 +69,10 @@ impl DisplayLinkTarget {
         let ca_now = CACurrentMediaTime();
         let predicted_present =
             mach_time::media_time_to_host_time(target_ts, now, ca_now, ivars.timebase);
-        let refresh_interval = mach_time::seconds_to_ticks(duration, ivars.timebase);
+        let refresh_interval = mach_time::seconds_to_ticks(
+            negotiated_interval_seconds(timestamp, target_ts, duration),
+            ivars.timebase,
+        );
 
         let frame_index = ivars.frame_counter.get();
         ivars.frame_counter.set(frame_index + 1);
@@ -93,17 +96,78 @@ impl DisplayLinkTarget {
     }
 }
 
+/// Returns the interval between the current and the next callback, in seconds.
+///
+/// `duration` is the display's hardware refresh interval. When a preferred
+/// frame-rate range is active, Core Animation may fire less often than that;
+/// the negotiated cadence shows up as the gap between `timestamp` and
+/// `targetTimestamp`. Fall back to `duration` when that gap is unusable.
+fn negotiated_interval_seconds(timestamp: f64, target_timestamp: f64, duration: f64) -> f64 {
+    let interval = target_timestamp - timestamp;
+    if interval.is_finite() && interval > 0.0 {
+        interval
+    } else {
+        duration
+    }
+}
+
+/// Display-link surface used to forward preferred frame-rate requests.
+///
+/// This isolates the Objective-C call so the request path can be exercised
+/// without a live display link.
+trait FrameRateRangeTarget {
+    /// Returns whether `preferredFrameRateRange` is available at runtime.
+    fn supports_preferred_frame_rate_range(&self) -> bool;
+
+    /// Forwards `range` to `preferredFrameRateRange`.
+    fn set_ca_frame_rate_range(&self, range: CAFrameRateRange);
+}
+
+impl FrameRateRangeTarget for CADisplayLinkRaw {
+    fn supports_preferred_frame_rate_range(&self) -> bool {
+        self.respondsToSelector(sel!(setPreferredFrameRateRange:))
+    }
+
+    fn set_ca_frame_rate_range(&self, range: CAFrameRateRange) {
+        self.setPreferredFrameRateRange(range);
+    }
+}
+
+/// Forwards `range` to `target` if the API is available.
+///
+/// Returns `false` without touching the target on OS versions that predate
+/// `preferredFrameRateRange`.
+fn request_frame_rate_range<T: FrameRateRangeTarget + ?Sized>(
+    target: &T,
+    range: PreferredFrameRateRange,
+) -> bool {
+    if !target.supports_preferred_frame_rate_range() {
+        return false;
+    }
+    target.set_ca_frame_rate_range(CAFrameRateRange::new(
+        range.minimum,
+        range.maximum,
+        range.preferred,
+    ));
+    true
+}
+
 /// Safe wrapper around `CADisplayLink` that produces [`FrameTick`] events on
 /// the main thread.
 pub struct DisplayLink {
     raw: Retained<CADisplayLinkRaw>,
     _target: Retained<DisplayLinkTarget>,
+    preferred_frame_rate_range: Cell<Option<PreferredFrameRateRange>>,
 }
 
 impl fmt::Debug for DisplayLink {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("DisplayLink")
             .field("paused", &self.raw.isPaused())
+            .field(
+                "preferred_frame_rate_range",
+                &self.preferred_frame_rate_range.get(),
+            )
             .finish_non_exhaustive()
     }
 }
@@ -141,9 +205,30 @@ impl DisplayLink {
         Self {
             raw,
             _target: target,
+            preferred_frame_rate_range: Cell::new(None),
         }
     }
 
+    /// Creates a new display link that requests `range` from Core Animation.
+    ///
+    /// This is [`new`](Self::new) followed by
+    /// [`set_preferred_frame_rate_range`](Self::set_preferred_frame_rate_range).
+    /// Emitted ticks report the negotiated callback interval in
+    /// [`FrameTick::refresh_interval`].
+    pub fn new_with_preferred_frame_rate_range<F>(
+        callback: F,
+        output: OutputId,
+        range: PreferredFrameRateRange,
+        mtm: MainThreadMarker,
+    ) -> Self
+    where
+        F: Fn(FrameTick) + 'static,
+    {
+        let link = Self::new(callback, output, mtm);
+        link.set_preferred_frame_rate_range(range);
+        link
+    }
+
     /// Starts the display link by adding it to the main run loop.
     pub fn start(&self) {
         let run_loop = NSRunLoop::mainRunLoop();
@@ -182,12 +267,19 @@ impl DisplayLink {
     /// range from a ready frame's selected interval with
     /// [`preferred_frame_rate_range`](crate::preferred_frame_rate_range), apply
     /// it to the display link, and then submit/render normally.
+    ///
+    /// The requested range is remembered even when the running OS predates
+    /// `preferredFrameRateRange`; in that case the request is silently ignored
+    /// and the link keeps firing at the display's native rate.
     pub fn set_preferred_frame_rate_range(&self, range: PreferredFrameRateRange) {
-        self.raw.setPreferredFrameRateRange(CAFrameRateRange::new(
-            range.minimum,
-            range.maximum,
-            range.preferred,
-        ));
+        self.preferred_frame_rate_range.set(Some(range));
+        request_frame_rate_range(&*self.raw, range);
+    }
+
+    /// Returns the most recently requested preferred frame-rate range.
+    #[must_use]
+    pub fn preferred_frame_rate_range(&self) -> Option<PreferredFrameRateRange> {
+        self.preferred_frame_rate_range.get()
     }
 
     /// Computes and applies a preferred frame-rate range for `frame_interval`.
@@ -214,8 +306,73 @@ impl Drop for DisplayLink {
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
+    use core::cell::RefCell;
+
     use super::*;
 
+    struct RecordingTarget {
+        supported: bool,
+        calls: RefCell<Vec<(f32, f32, f32)>>,
+    }
+
+    impl RecordingTarget {
+        fn new(supported: bool) -> Self {
+            Self {
+                supported,
+                calls: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl FrameRateRangeTarget for RecordingTarget {
+        fn supports_preferred_frame_rate_range(&self) -> bool {
+            self.supported
+        }
+
+        fn set_ca_frame_rate_range(&self, range: CAFrameRateRange) {
+            self.calls
+                .borrow_mut()
+                .push((range.minimum, range.maximum, range.preferred));
+        }
+    }
+
+    #[test]
+    fn frame_rate_range_is_forwarded_to_target() {
+        let target = RecordingTarget::new(true);
+        let range = PreferredFrameRateRange {
+            minimum: 80.0,
+            maximum: 120.0,
+            preferred: 120.0,
+        };
+
+        assert!(request_frame_rate_range(&target, range));
+        assert_eq!(*target.calls.borrow(), [(80.0, 120.0, 120.0)]);
+    }
+
+    #[test]
+    fn frame_rate_range_is_skipped_when_unsupported() {
+        let target = RecordingTarget::new(false);
+        let range = PreferredFrameRateRange {
+            minimum: 30.0,
+            maximum: 60.0,
+            preferred: 60.0,
+        };
+
+        assert!(!request_frame_rate_range(&target, range));
+        assert!(target.calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn negotiated_interval_prefers_target_gap() {
+        let duration = 1.0 / 120.0;
+        let interval = negotiated_interval_seconds(10.0, 10.0 + 1.0 / 60.0, duration);
+        assert!((interval - 1.0 / 60.0).abs() < 1e-12);
+
+        assert_eq!(negotiated_interval_seconds(10.0, 10.0, duration), duration);
+        assert_eq!(negotiated_interval_seconds(10.0, f64::NAN, duration), duration);
+    }
+
     #[test]
     fn mach_timebase_available() {
         let tb = mach_time::timebase();
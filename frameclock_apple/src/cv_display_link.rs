@@ -4,6 +4,7 @@
 //! `CVDisplayLink` integration for predictive frame timing.
 
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::ffi::c_void;
 use core::fmt;
 use core::pin::Pin;
@@ -15,7 +16,7 @@ use frameclock::{FrameTick, HostTime, OutputId};
 use objc2_core_foundation::CFRetained;
 use objc2_core_video::{CVDisplayLink as CVDisplayLinkRaw, CVTimeStamp, kCVReturnSuccess};
 
-use crate::threading::TickSender;
+use crate::threading::{TickForwarder, TickSender};
 
 /// Errors from [`DisplayLink`] operations.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -88,6 +89,68 @@ impl DisplayLink {
         reason = "CVDisplayLink API is deprecated by Apple but still functional"
     )]
     pub fn new(sender: TickSender, output: OutputId) -> Result<Self, DisplayLinkError> {
+        Self::create(sender, output, |link_ptr| unsafe {
+            CVDisplayLinkRaw::create_with_active_cg_displays(link_ptr)
+        })
+    }
+
+    /// Creates a display link paced by the display `display_id` (a
+    /// `CGDirectDisplayID`), tagging its ticks with `output`.
+    ///
+    /// On multi-monitor systems, create one link per display, each with a
+    /// distinct [`OutputId`], so layers on each screen are paced by their own
+    /// refresh. See [`TickSender`] for how several links share one frame loop.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DisplayLinkError`] if the underlying `CoreVideo` calls fail,
+    /// including when `display_id` does not name an active display.
+    #[expect(
+        deprecated,
+        reason = "CVDisplayLink API is deprecated by Apple but still functional"
+    )]
+    pub fn for_display(
+        sender: TickSender,
+        display_id: u32,
+        output: OutputId,
+    ) -> Result<Self, DisplayLinkError> {
+        Self::create(sender, output, |link_ptr| unsafe {
+            CVDisplayLinkRaw::create_with_cg_display(display_id, link_ptr)
+        })
+    }
+
+    /// Creates one display link per display in `display_ids`, all feeding
+    /// `forwarder`.
+    ///
+    /// Each link's ticks are tagged with `OutputId(display_id)`, so outputs
+    /// are distinct and stable for as long as the display stays connected.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`DisplayLinkError`]; links created before the
+    /// failure are dropped (and stopped).
+    pub fn for_displays(
+        forwarder: &TickForwarder,
+        display_ids: impl IntoIterator<Item = u32>,
+    ) -> Result<Vec<Self>, DisplayLinkError> {
+        display_ids
+            .into_iter()
+            .map(|display_id| {
+                Self::for_display(forwarder.sender(), display_id, OutputId(display_id))
+            })
+            .collect()
+    }
+
+    /// Shared constructor: `create_raw` fills in the new link pointer.
+    #[expect(
+        deprecated,
+        reason = "CVDisplayLink API is deprecated by Apple but still functional"
+    )]
+    fn create(
+        sender: TickSender,
+        output: OutputId,
+        create_raw: impl FnOnce(NonNull<*mut CVDisplayLinkRaw>) -> i32,
+    ) -> Result<Self, DisplayLinkError> {
         let state = Box::pin(CallbackState {
             sender,
             frame_counter: AtomicU64::new(0),
@@ -95,9 +158,7 @@ impl DisplayLink {
         });
 
         let mut link_ptr: *mut CVDisplayLinkRaw = core::ptr::null_mut();
-        let ret = unsafe {
-            CVDisplayLinkRaw::create_with_active_cg_displays(NonNull::new_unchecked(&mut link_ptr))
-        };
+        let ret = create_raw(NonNull::from(&mut link_ptr));
         if ret != kCVReturnSuccess {
             return Err(DisplayLinkError::CreateFailed(ret));
         }
@@ -210,26 +271,68 @@ unsafe extern "C-unwind" fn display_link_callback(
     let now_ts = unsafe { in_now.as_ref() };
     let out_ts = unsafe { in_output_time.as_ref() };
 
-    let now = HostTime(now_ts.hostTime);
-    let predicted_present = HostTime(out_ts.hostTime);
-    let refresh_interval = if out_ts.hostTime > now_ts.hostTime {
-        Some(out_ts.hostTime - now_ts.hostTime)
-    } else {
-        None
-    };
+    state
+        .sender
+        .send(state.tick(now_ts.hostTime, out_ts.hostTime));
 
-    let frame_index = state.frame_counter.fetch_add(1, Ordering::Relaxed);
+    kCVReturnSuccess
+}
 
-    let tick = FrameTick {
-        now,
-        predicted_present: Some(predicted_present),
-        refresh_interval,
-        frame_index,
-        output: state.output,
-        prev_actual_present: None,
-    };
+impl CallbackState {
+    /// Builds the next tick from the callback's current and output host times.
+    fn tick(&self, now: u64, output_time: u64) -> FrameTick {
+        let refresh_interval = if output_time > now {
+            Some(output_time - now)
+        } else {
+            None
+        };
 
-    state.sender.send(tick);
+        FrameTick {
+            now: HostTime(now),
+            predicted_present: Some(HostTime(output_time)),
+            refresh_interval,
+            frame_index: self.frame_counter.fetch_add(1, Ordering::Relaxed),
+            output: self.output,
+            prev_actual_present: None,
+        }
+    }
+}
 
-    kCVReturnSuccess
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::sync::atomic::AtomicU32;
+
+    use super::*;
+    use crate::threading::TickForwarder;
+
+    fn state(forwarder: &TickForwarder, output: u32) -> CallbackState {
+        CallbackState {
+            sender: forwarder.sender(),
+            frame_counter: AtomicU64::new(0),
+            output: OutputId(output),
+        }
+    }
+
+    #[test]
+    fn two_links_fan_in_with_distinct_outputs() {
+        let seen = Arc::new([AtomicU32::new(0), AtomicU32::new(0)]);
+        let recorded = Arc::clone(&seen);
+        let forwarder = TickForwarder::new(move |tick: FrameTick| {
+            recorded[tick.output.0 as usize - 1].fetch_add(1, Ordering::Relaxed);
+        });
+        let first = state(&forwarder, 1);
+        let second = state(&forwarder, 2);
+
+        for (link, now) in [(&first, 0), (&second, 5), (&first, 16), (&second, 21)] {
+            let tick = link.tick(now, now + 16);
+            assert_eq!(tick.output, link.output);
+            link.sender.deliver(tick);
+        }
+
+        assert_eq!(seen[0].load(Ordering::Relaxed), 2);
+        assert_eq!(seen[1].load(Ordering::Relaxed), 2);
+        assert_eq!(first.tick(32, 48).frame_index, 2);
+        assert_eq!(second.tick(37, 53).frame_index, 2);
+    }
 }
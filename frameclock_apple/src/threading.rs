@@ -39,6 +39,14 @@ impl TickForwarder {
 
 /// A `Send + Sync` handle that dispatches [`FrameTick`] events to the main
 /// thread.
+///
+/// Senders are cheap to clone, and every sender created from one
+/// [`TickForwarder`] feeds the same callback. To drive one frame loop from
+/// several displays, create one [`DisplayLink`](crate::DisplayLink) per
+/// display from senders of a single forwarder. Ticks from all links then
+/// arrive on the main thread in dispatch order, each tagged with its link's
+/// [`OutputId`](frameclock::OutputId); route them to per-output state by
+/// [`FrameTick::output`]. Frame indices are counted per link, not globally.
 #[derive(Clone)]
 pub struct TickSender {
     callback: Arc<dyn Fn(FrameTick) + Send + Sync>,
@@ -52,7 +60,12 @@ impl fmt::Debug for TickSender {
 
 impl TickSender {
     pub(crate) fn send(&self, tick: FrameTick) {
-        let cb = Arc::clone(&self.callback);
-        DispatchQueue::main().exec_async(move || cb(tick));
+        let sender = self.clone();
+        DispatchQueue::main().exec_async(move || sender.deliver(tick));
+    }
+
+    /// Invokes the callback on the current thread.
+    pub(crate) fn deliver(&self, tick: FrameTick) {
+        (self.callback)(tick);
     }
 }
@@ -213,7 +213,7 @@ fn predict_next_present(
 ) -> Option<HostTime> {
     let last = last_actual?;
     let refresh = refresh_interval.filter(|interval| *interval > 0)?;
-    let elapsed = now.ticks().saturating_sub(last.ticks());
+    let elapsed = now.saturating_sub(last).ticks();
     let intervals = elapsed.div_ceil(refresh);
     let advance = intervals.checked_mul(refresh)?;
     last.ticks().checked_add(advance).map(HostTime)
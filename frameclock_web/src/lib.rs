@@ -27,7 +27,7 @@ use frameclock::{
 };
 
 /// Browser host-time conversion: 1 tick = 1 microsecond = 1000 nanoseconds.
-pub const TIMEBASE: Timebase = Timebase::new(1000, 1);
+pub const TIMEBASE: Timebase = Timebase::MICROS;
 
 /// Fallback display interval for browser RAF ticks without an interval.
 ///
@@ -40,14 +40,12 @@ pub fn timebase() -> Timebase {
         freq > 0 && freq <= i64::from(u32::MAX),
         "QPC frequency {freq} out of u32 range"
     );
-    Timebase {
-        numer: 1_000_000_000,
-        #[expect(
-            clippy::cast_possible_truncation,
-            reason = "QPC frequency fits in u32 on all known hardware (typically 10 MHz)"
-        )]
-        denom: freq as u32,
-    }
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "QPC frequency fits in u32 on all known hardware (typically 10 MHz)"
+    )]
+    let hz = freq as u32;
+    Timebase::from_hz(hz)
 }
 
 #[cfg(test)]
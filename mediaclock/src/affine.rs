@@ -18,6 +18,14 @@
 //! media layer feeds observations from the media backend, then queries with a
 //! planned frame's sample or target-present time to choose content for the frame
 //! being prepared.
+//!
+//! A clock can also be slaved to another with [`AffineClock::genlock_to`], so
+//! that, for example, video pacing follows an audio-master clock without
+//! audible or visible jumps.
+//!
+//! With [`AffineClock::set_clamp_monotonic`], [`AffineClock::playhead_at`]
+//! never goes backward, so a playhead driven by the clock stays
+//! non-decreasing while smoothing absorbs noisy observations.
 
 use frameclock::HostTime;
 
@@ -39,6 +47,20 @@ pub enum AffineClockUpdate {
     Reanchored,
 }
 
+/// Media-time prediction returned by [`AffineClock::predict`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClockEstimate {
+    /// Predicted media time in seconds.
+    pub media: f64,
+    /// Smoothed variance of recent prediction residuals, in media seconds
+    /// squared.
+    ///
+    /// This is infinite until the clock has seen at least two observations, and
+    /// spikes when [`AffineClock::update_or_reanchor`] detects a
+    /// discontinuity.
+    pub variance: f64,
+}
+
 /// A smoothed affine mapping from host time to media time seconds.
 ///
 /// Media or playback code creates this when it needs to choose external
@@ -49,6 +71,12 @@ pub enum AffineClockUpdate {
 ///
 /// Ordinary UI/render hosts that only need frame pacing can ignore this type
 /// and use `frameclock` directly.
+///
+/// Offset smoothing can pull the mapping back slightly after an early
+/// observation, so consecutive queries at increasing host times may return a
+/// smaller media time. Enable [`set_clamp_monotonic`](Self::set_clamp_monotonic)
+/// and query with [`playhead_at`](Self::playhead_at) when the output drives a
+/// strictly forward playhead.
 #[derive(Clone, Debug)]
 pub struct AffineClock {
     /// Current estimated rate (media seconds per host tick).
@@ -69,9 +97,25 @@ pub struct AffineClock {
     last_host: u64,
     /// Last media time observation.
     last_media: f64,
+    /// EMA of squared prediction residuals, in media seconds squared.
+    residual_variance: f64,
+    /// Whether [`playhead_at`](Self::playhead_at) never goes backward.
+    clamp_monotonic: bool,
+    /// Largest playhead returned while clamping, cleared by reanchoring.
+    last_output: Option<f64>,
+    /// How far the last playhead query was raised, in media seconds.
+    clamp_amount: f64,
 }
 
 impl AffineClock {
+    /// Residual variance at or below which [`is_locked`](Self::is_locked)
+    /// reports a converged clock: one millisecond of RMS error.
+    pub const LOCKED_VARIANCE: f64 = 1e-6;
+
+    /// Default maximum slew for [`genlock_to`](Self::genlock_to): 0.5% of the
+    /// master's rate, or 5 ms of correction per second of media.
+    pub const DEFAULT_GENLOCK_SLEW: f64 = 0.005;
+
     /// Creates a new media-clock mapper.
     ///
     /// `initial_rate` is in media-seconds per host-tick (e.g. for nanosecond
@@ -90,11 +134,51 @@ impl AffineClock {
             initialized: false,
             last_host: 0,
             last_media: 0.0,
+            residual_variance: f64::INFINITY,
+            clamp_monotonic: false,
+            last_output: None,
+            clamp_amount: 0.0,
         }
     }
 
+    /// Sets whether [`playhead_at`](Self::playhead_at) may return less than it
+    /// did before.
+    ///
+    /// When enabled, a playhead query that the mapping would place before the
+    /// largest value already returned yields that value instead, and
+    /// [`clamp_amount`](Self::clamp_amount) reports the difference. Only
+    /// [`reanchor`](Self::reanchor), a reanchoring
+    /// [`update_or_reanchor`](Self::update_or_reanchor), and
+    /// [`reset`](Self::reset) release the hold, so seeks and loops still move
+    /// the output backward.
+    pub fn set_clamp_monotonic(&mut self, enabled: bool) {
+        self.clamp_monotonic = enabled;
+        self.release_output();
+    }
+
+    /// Returns whether playhead queries are clamped to be non-decreasing.
+    #[must_use]
+    pub const fn clamp_monotonic(&self) -> bool {
+        self.clamp_monotonic
+    }
+
+    /// Returns how far, in media seconds, the most recent
+    /// [`playhead_at`](Self::playhead_at) query was raised to keep the output
+    /// non-decreasing.
+    ///
+    /// This is 0 when the last query needed no clamping or clamping is
+    /// disabled. A positive value means the output is holding while the
+    /// mapping catches up.
+    #[must_use]
+    pub const fn clamp_amount(&self) -> f64 {
+        self.clamp_amount
+    }
+
     /// Queries the estimated media time at a host time.
     ///
+    /// This is the unclamped mapping, so it is unaffected by
+    /// [`set_clamp_monotonic`](Self::set_clamp_monotonic).
+    ///
     /// Returns `None` if no observations have been fed yet.
     #[must_use]
     pub fn media_time_at(&self, host: HostTime) -> Option<f64> {
@@ -104,6 +188,47 @@ impl AffineClock {
         Some(self.media_time_at_initialized(host.ticks()))
     }
 
+    /// Queries the media time at a host time for driving a playhead.
+    ///
+    /// Without [`clamp_monotonic`](Self::set_clamp_monotonic), this is the
+    /// same as [`media_time_at`](Self::media_time_at). With it, the result
+    /// never drops below the largest value this method has returned since the
+    /// hold was last released.
+    ///
+    /// Returns `None` if no observations have been fed yet.
+    pub fn playhead_at(&mut self, host: HostTime) -> Option<f64> {
+        let media = self.media_time_at(host)?;
+        if !self.clamp_monotonic {
+            return Some(media);
+        }
+        let output = self.last_output.map_or(media, |floor| media.max(floor));
+        self.clamp_amount = output - media;
+        self.last_output = Some(output);
+        Some(output)
+    }
+
+    /// Predicts the media time at a host time along with its confidence.
+    ///
+    /// Returns `None` if no observations have been fed yet.
+    #[must_use]
+    pub fn predict(&self, host: HostTime) -> Option<ClockEstimate> {
+        let media = self.media_time_at(host)?;
+        Some(ClockEstimate {
+            media,
+            variance: self.residual_variance,
+        })
+    }
+
+    /// Returns whether recent prediction residuals are small enough to trust
+    /// the mapping.
+    ///
+    /// This compares the smoothed residual variance against
+    /// [`LOCKED_VARIANCE`](Self::LOCKED_VARIANCE).
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.initialized && self.residual_variance <= Self::LOCKED_VARIANCE
+    }
+
     /// Returns the current effective media-seconds-per-host-tick rate.
     ///
     /// This includes both commanded rate changes made through
@@ -113,6 +238,28 @@ impl AffineClock {
         self.rate
     }
 
+    /// Returns the baseline rate restored by [`reset`](Self::reset).
+    ///
+    /// This is the `initial_rate` passed to [`new`](Self::new), or the most
+    /// recent rate commanded through [`set_rate`](Self::set_rate). Comparing it
+    /// with [`rate`](Self::rate) shows how much drift has been learned.
+    #[must_use]
+    pub const fn baseline_rate(&self) -> f64 {
+        self.initial_rate
+    }
+
+    /// Returns the current mapping anchor as `(host_time, media_time)`.
+    ///
+    /// Together with [`rate`](Self::rate), this fully describes the mapping.
+    /// Returns `None` if no observations have been fed yet.
+    #[must_use]
+    pub const fn anchor(&self) -> Option<(HostTime, f64)> {
+        if !self.initialized {
+            return None;
+        }
+        Some((HostTime(self.epoch_host), self.epoch_media))
+    }
+
     /// Feeds a `(host_time, media_time_seconds)` observation to update the
     /// mapping.
     ///
@@ -150,8 +297,10 @@ impl AffineClock {
         if self.initialized && discontinuity_threshold.is_finite() && discontinuity_threshold >= 0.0
         {
             let predicted = self.media_time_at_initialized(host.ticks());
-            if (media_time - predicted).abs() > discontinuity_threshold {
+            let error = media_time - predicted;
+            if error.abs() > discontinuity_threshold {
                 self.reanchor(host, media_time);
+                self.residual_variance = error * error;
                 return AffineClockUpdate::Reanchored;
             }
         }
@@ -159,6 +308,69 @@ impl AffineClock {
         self.update_inner(host.ticks(), media_time)
     }
 
+    /// Nudges this clock toward `master`'s timeline at `host`.
+    ///
+    /// Call this once per frame (or per media callback) with monotonically
+    /// increasing host times. Each call adopts the master's current rate and
+    /// moves this clock's media time at `host` toward the master's by at most
+    /// `max_slew * |master rate| * elapsed ticks` since the previous sync
+    /// point, never past the master. `max_slew` is therefore the maximum
+    /// relative rate error: with the default
+    /// [`DEFAULT_GENLOCK_SLEW`](Self::DEFAULT_GENLOCK_SLEW) of `0.005`, the
+    /// slave advances between 0.995× and 1.005× as fast as the master while
+    /// it catches up, and a 50 ms offset closes in 10 s of media.
+    ///
+    /// An uninitialized slave snaps to the master exactly and returns
+    /// [`AffineClockUpdate::Initialized`]. The call is
+    /// [`Ignored`](AffineClockUpdate::Ignored) when the master has no
+    /// observations yet, when `host` is not newer than this clock's last
+    /// sync point, or when `max_slew` is negative or non-finite. The
+    /// residual variance tracks the pre-correction error, so
+    /// [`is_locked`](Self::is_locked) reports a converged genlock.
+    #[must_use]
+    pub fn genlock_to(
+        &mut self,
+        master: &Self,
+        host: HostTime,
+        max_slew: f64,
+    ) -> AffineClockUpdate {
+        if !max_slew.is_finite() || max_slew < 0.0 {
+            return AffineClockUpdate::Ignored;
+        }
+        if !master.initialized {
+            return AffineClockUpdate::Ignored;
+        }
+        let target = master.media_time_at_initialized(host.ticks());
+
+        if !self.initialized {
+            self.rate = master.rate;
+            self.reanchor(host, target);
+            return AffineClockUpdate::Initialized;
+        }
+
+        let host_ticks = host.ticks();
+        if host_ticks <= self.last_host {
+            return AffineClockUpdate::Ignored;
+        }
+
+        let current = self.media_time_at_initialized(host_ticks);
+        let error = target - current;
+        let max_step = max_slew * master.rate.abs() * (host_ticks - self.last_host) as f64;
+        let step = error.clamp(-max_step, max_step);
+
+        let squared_error = error * error;
+        self.residual_variance = if self.residual_variance.is_finite() {
+            self.offset_alpha * squared_error + (1.0 - self.offset_alpha) * self.residual_variance
+        } else {
+            squared_error
+        };
+
+        self.rate = master.rate;
+        // Slewing is continuous, so it keeps any monotonic hold.
+        self.set_anchor(host_ticks, current + step);
+        AffineClockUpdate::Smoothed
+    }
+
     fn update_inner(&mut self, host_ticks: u64, media_time: f64) -> AffineClockUpdate {
         if !media_time.is_finite() {
             return AffineClockUpdate::Ignored;
@@ -185,6 +397,14 @@ impl AffineClock {
         let predicted_media = self.media_time_at_initialized(host_ticks);
         let offset_error = media_time - predicted_media;
 
+        // Track residual variance; the first residual seeds it directly.
+        let squared_error = offset_error * offset_error;
+        self.residual_variance = if self.residual_variance.is_finite() {
+            self.offset_alpha * squared_error + (1.0 - self.offset_alpha) * self.residual_variance
+        } else {
+            squared_error
+        };
+
         // Smooth offset correction.
         self.epoch_media += self.offset_alpha * offset_error;
 
@@ -195,21 +415,36 @@ impl AffineClock {
 
     /// Reanchors the mapping exactly at `(host_time, media_time)`.
     ///
-    /// This keeps the current rate but resets accumulated offset state. Use it
-    /// for known timeline discontinuities such as seek, loop, and pause/resume
-    /// points.
+    /// This keeps the current rate, including any learned drift, but resets
+    /// accumulated offset state. Use it for known timeline discontinuities such
+    /// as seek, loop, and pause/resume points instead of reconstructing the
+    /// clock, which would discard the learned rate.
+    ///
+    /// This also releases a [monotonic](Self::set_clamp_monotonic) hold, so
+    /// the next query may return less than the previous one.
     pub fn reanchor(&mut self, host: HostTime, media_time: f64) {
         if !media_time.is_finite() {
             return;
         }
 
-        self.epoch_host = host.ticks();
+        self.set_anchor(host.ticks(), media_time);
+        self.release_output();
+    }
+
+    fn set_anchor(&mut self, host_ticks: u64, media_time: f64) {
+        self.epoch_host = host_ticks;
         self.epoch_media = media_time;
-        self.last_host = host.ticks();
+        self.last_host = host_ticks;
         self.last_media = media_time;
         self.initialized = true;
     }
 
+    /// Forgets the monotonic floor and any active clamp.
+    fn release_output(&mut self) {
+        self.last_output = None;
+        self.clamp_amount = 0.0;
+    }
+
     /// Sets the commanded host-to-media rate immediately.
     ///
     /// This is for known playback-rate changes, not clock drift. If the clock
@@ -254,6 +489,8 @@ impl AffineClock {
         self.initialized = false;
         self.last_host = 0;
         self.last_media = 0.0;
+        self.residual_variance = f64::INFINITY;
+        self.release_output();
     }
 }
 
@@ -265,6 +502,70 @@ mod tests {
         HostTime(ticks)
     }
 
+    /// Feeds 10 ms observations with ±20 ms of alternating noise and returns
+    /// the media time queried 1 ms after each one.
+    fn noisy_outputs(clamp_monotonic: bool) -> ([f64; 40], AffineClock) {
+        let mut clock = AffineClock::new(1e-9, 0.1, 0.8);
+        clock.set_clamp_monotonic(clamp_monotonic);
+        let mut outputs = [0.0; 40];
+        for (i, out) in outputs.iter_mut().enumerate() {
+            let ticks = i as u64 * 10_000_000;
+            let noise = if i % 2 == 0 { 0.02 } else { -0.02 };
+            clock.update(host(ticks), i as f64 * 0.01 + noise);
+            *out = clock.playhead_at(host(ticks + 1_000_000)).unwrap();
+        }
+        (outputs, clock)
+    }
+
+    #[test]
+    fn noisy_observations_can_move_output_backward() {
+        let (outputs, clock) = noisy_outputs(false);
+        assert!(outputs.windows(2).any(|w| w[1] < w[0]));
+        assert_eq!(clock.clamp_amount(), 0.0);
+    }
+
+    #[test]
+    fn clamp_monotonic_keeps_output_non_decreasing() {
+        let (outputs, mut clock) = noisy_outputs(true);
+        assert!(
+            outputs.windows(2).all(|w| w[1] >= w[0]),
+            "outputs went backward: {outputs:?}"
+        );
+
+        // Querying earlier than the last output holds it and reports the gap.
+        let held = clock.playhead_at(host(0)).unwrap();
+        assert_eq!(held, outputs[39]);
+        assert!(clock.clamp_amount() > 0.0);
+        // The raw mapping is not held.
+        assert!(clock.media_time_at(host(0)).unwrap() < held);
+
+        // A seek backward releases the hold.
+        clock.reanchor(host(500_000_000), 0.0);
+        assert_eq!(clock.playhead_at(host(500_000_000)), Some(0.0));
+        assert_eq!(clock.clamp_amount(), 0.0);
+    }
+
+    #[test]
+    fn genlock_reads_master_mapping_not_playhead() {
+        let (_, master) = noisy_outputs(true);
+        let mut slave = AffineClock::new(1e-9, 0.1, 0.8);
+        assert_eq!(
+            slave.genlock_to(&master, host(0), 0.01),
+            AffineClockUpdate::Initialized
+        );
+
+        let expected = master.media_time_at(host(0)).unwrap();
+        let actual = slave.media_time_at(host(0)).unwrap();
+        assert!((actual - expected).abs() < 1e-9, "{actual} vs {expected}");
+    }
+
+    #[test]
+    fn clocks_are_send_and_sync() {
+        fn check<T: Send + Sync>() {}
+        check::<AffineClock>();
+        check::<crate::MediaTimeline>();
+    }
+
     #[test]
     fn uninitialized_returns_none() {
         let clock = AffineClock::new(1e-9, 0.1, 0.1);
@@ -352,6 +653,48 @@ mod tests {
         assert!((clock.media_time_at(host(3_000_000_000)).unwrap() - 11.0).abs() < 1e-12);
     }
 
+    #[test]
+    fn reanchor_preserves_learned_rate() {
+        let mut clock = AffineClock::new(1e-9, 0.5, 0.5);
+        clock.update(host(0), 0.0);
+        for i in 1..=20_u64 {
+            clock.update(host(i * 1_000_000_000), i as f64 * 1.001);
+        }
+        let learned = clock.rate();
+        assert!(
+            (learned - clock.baseline_rate()).abs() > 1e-13,
+            "test setup must learn some drift"
+        );
+
+        clock.reanchor(host(30_000_000_000), 5.0);
+
+        assert_eq!(clock.rate(), learned, "reanchor must keep the learned rate");
+        assert_eq!(
+            clock.media_time_at(host(30_000_000_000)),
+            Some(5.0),
+            "reanchor must map the anchor host exactly"
+        );
+        assert_eq!(
+            clock.anchor(),
+            Some((host(30_000_000_000), 5.0)),
+            "anchor must report the reanchored point"
+        );
+    }
+
+    #[test]
+    fn anchor_is_none_until_initialized() {
+        let mut clock = AffineClock::new(1e-9, 0.1, 0.1);
+        assert_eq!(clock.anchor(), None, "unanchored clock has no anchor");
+        clock.update(host(7), 1.5);
+        assert_eq!(
+            clock.anchor(),
+            Some((host(7), 1.5)),
+            "first observation sets the anchor"
+        );
+        clock.reset();
+        assert_eq!(clock.anchor(), None, "reset clears the anchor");
+    }
+
     #[test]
     fn update_or_reanchor_snaps_large_error() {
         let mut clock = AffineClock::new(1e-9, 0.1, 0.1);
@@ -372,6 +715,93 @@ mod tests {
         assert!((clock.media_time_at(host(2_000_000_000)).unwrap() - 10.0).abs() < 1e-12);
     }
 
+    #[test]
+    fn update_or_reanchor_smooths_stream_and_snaps_jump() {
+        let mut clock = AffineClock::new(1e-9, 0.1, 0.1);
+        assert_eq!(
+            clock.update_or_reanchor(host(0), 0.0, 0.25),
+            AffineClockUpdate::Initialized
+        );
+        for i in 1..=10_u64 {
+            // 1 ms of jitter per observation stays well under the threshold.
+            let media = i as f64 * 0.1 + if i % 2 == 0 { 0.001 } else { -0.001 };
+            assert_eq!(
+                clock.update_or_reanchor(host(i * 100_000_000), media, 0.25),
+                AffineClockUpdate::Smoothed,
+                "observation {i} of a smooth stream must not reanchor"
+            );
+        }
+
+        // A 5-second scrub forward.
+        assert_eq!(
+            clock.update_or_reanchor(host(1_100_000_000), 6.1, 0.25),
+            AffineClockUpdate::Reanchored,
+            "a 5 s jump must be treated as a discontinuity"
+        );
+        assert_eq!(
+            clock.media_time_at(host(1_100_000_000)),
+            Some(6.1),
+            "reanchor must map the jump observation exactly"
+        );
+    }
+
+    #[test]
+    fn predict_variance_shrinks_with_consistent_samples() {
+        let mut clock = AffineClock::new(1e-9, 0.1, 0.2);
+        assert_eq!(
+            clock.predict(host(0)),
+            None,
+            "unanchored clock predicts nothing"
+        );
+
+        clock.update(host(0), 0.0);
+        let first = clock.predict(host(0)).unwrap();
+        assert!(
+            first.variance.is_infinite(),
+            "one sample gives no confidence"
+        );
+        assert!(!clock.is_locked(), "one sample must not lock");
+
+        // The anchor was 20 ms off; later samples are consistent.
+        clock.update(host(100_000_000), 0.12);
+        let early = clock.predict(host(100_000_000)).unwrap().variance;
+        for i in 2..=40_u64 {
+            clock.update(host(i * 100_000_000), i as f64 * 0.1 + 0.02);
+        }
+        let late = clock.predict(host(4_000_000_000)).unwrap().variance;
+        assert!(
+            late < early,
+            "variance must shrink as consistent samples arrive: {early} -> {late}"
+        );
+        assert!(clock.is_locked(), "a consistent stream must lock");
+    }
+
+    #[test]
+    fn predict_variance_spikes_after_discontinuity() {
+        let mut clock = AffineClock::new(1e-9, 0.1, 0.2);
+        for i in 0..=20_u64 {
+            _ = clock.update_or_reanchor(host(i * 100_000_000), i as f64 * 0.1, 0.25);
+        }
+        assert!(clock.is_locked(), "a clean stream must lock");
+
+        assert_eq!(
+            clock.update_or_reanchor(host(2_100_000_000), 7.1, 0.25),
+            AffineClockUpdate::Reanchored,
+            "a 5 s jump must reanchor"
+        );
+        let estimate = clock.predict(host(2_100_000_000)).unwrap();
+        assert!(
+            (estimate.media - 7.1).abs() < 1e-12,
+            "prediction must follow the reanchor"
+        );
+        assert!(
+            estimate.variance > 1.0,
+            "variance must spike after a discontinuity, got {}",
+            estimate.variance
+        );
+        assert!(!clock.is_locked(), "a discontinuity must drop the lock");
+    }
+
     #[test]
     fn set_rate_applies_commanded_rate_without_drift_learning() {
         let mut clock = AffineClock::new(1e-9, 0.1, 0.1);
@@ -443,4 +873,96 @@ mod tests {
 
         assert_eq!(clock.media_time_at(host(2_000_000_000)).unwrap(), before);
     }
+
+    fn locked_master(rate: f64) -> AffineClock {
+        let mut master = AffineClock::new(rate, 0.1, 0.1);
+        master.update(host(0), 0.0);
+        master
+    }
+
+    #[test]
+    fn genlock_converges_without_overshoot() {
+        let master = locked_master(1e-9);
+        let mut slave = AffineClock::new(1e-9, 0.1, 0.1);
+        slave.update(host(0), -0.05);
+        let slew = AffineClock::DEFAULT_GENLOCK_SLEW;
+
+        let step_ticks = 10_000_000_u64;
+        let mut previous = -0.05;
+        let mut converged_at = None;
+        for i in 1..=2_000_u64 {
+            let now = host(i * step_ticks);
+            assert_eq!(
+                slave.genlock_to(&master, now, slew),
+                AffineClockUpdate::Smoothed
+            );
+            let slave_media = slave.media_time_at(now).unwrap();
+            let master_media = master.media_time_at(now).unwrap();
+            let error = master_media - slave_media;
+            assert!(
+                error >= -1e-12,
+                "slave overshot the master at step {i}: {error}"
+            );
+
+            // Relative to the master, the slave never runs faster than the
+            // slew bound allows.
+            let advance = slave_media - previous;
+            let master_advance = step_ticks as f64 * 1e-9;
+            assert!(
+                (advance - master_advance).abs() <= slew * master_advance + 1e-12,
+                "slew bound exceeded at step {i}: {advance}"
+            );
+            previous = slave_media;
+
+            if converged_at.is_none() && error.abs() < 1e-9 {
+                converged_at = Some(i);
+            }
+        }
+        // 50 ms at 0.5% slew closes in 10 s of media, i.e. 1000 steps.
+        let converged_at = converged_at.expect("slave must converge");
+        assert!(
+            (999..=1_001).contains(&converged_at),
+            "converged at {converged_at}"
+        );
+        assert!(slave.is_locked(), "a converged genlock must report lock");
+    }
+
+    #[test]
+    fn genlock_adopts_master_rate_and_snaps_when_uninitialized() {
+        let master = locked_master(2e-9);
+        let mut slave = AffineClock::new(1e-9, 0.1, 0.1);
+        assert_eq!(
+            slave.genlock_to(&master, host(1_000_000_000), 0.01),
+            AffineClockUpdate::Initialized
+        );
+        assert_eq!(slave.media_time_at(host(1_000_000_000)), Some(2.0));
+        assert_eq!(slave.rate(), 2e-9, "slave must follow the master's rate");
+        assert_eq!(slave.baseline_rate(), 1e-9, "genlock keeps the baseline");
+    }
+
+    #[test]
+    fn genlock_ignores_unusable_inputs() {
+        let unanchored = AffineClock::new(1e-9, 0.1, 0.1);
+        let master = locked_master(1e-9);
+        let mut slave = AffineClock::new(1e-9, 0.1, 0.1);
+        assert_eq!(
+            slave.genlock_to(&unanchored, host(1), 0.01),
+            AffineClockUpdate::Ignored
+        );
+        assert!(slave.media_time_at(host(1)).is_none());
+
+        slave.update(host(1_000), 0.5);
+        for slew in [f64::NAN, f64::INFINITY, -0.1] {
+            assert_eq!(
+                slave.genlock_to(&master, host(2_000), slew),
+                AffineClockUpdate::Ignored
+            );
+        }
+        assert_eq!(
+            slave.genlock_to(&master, host(1_000), 0.01),
+            AffineClockUpdate::Ignored,
+            "a sync point that is not newer must be ignored"
+        );
+        assert_eq!(slave.media_time_at(host(1_000)), Some(0.5));
+    }
 }
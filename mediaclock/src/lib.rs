@@ -32,5 +32,5 @@
 mod affine;
 mod timeline;
 
-pub use affine::{AffineClock, AffineClockUpdate};
+pub use affine::{AffineClock, AffineClockUpdate, ClockEstimate};
 pub use timeline::MediaTimeline;
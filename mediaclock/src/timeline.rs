@@ -70,6 +70,12 @@ impl MediaTimeline {
         }
     }
 
+    /// Returns the host-tick-to-seconds scale passed at construction.
+    #[must_use]
+    pub const fn seconds_per_host_tick(&self) -> f64 {
+        self.seconds_per_host_tick
+    }
+
     /// Returns the current commanded playback rate.
     ///
     /// This is the host-requested playback rate. The effective affine mapping
@@ -10,7 +10,8 @@
 //! [`FrameChanges`]: subduction_core::layer::FrameChanges
 
 use alloc::vec::Vec;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
+use kurbo::Size;
 
 use objc2::rc::Retained;
 use objc2_core_foundation::{CGPoint, CGRect, CGSize};
@@ -20,6 +21,7 @@ use objc2_quartz_core::{CALayer, CATransaction, CATransform3D};
 use subduction_core::backend::Presenter;
 use subduction_core::layer::{ClipShape, FrameChanges, LayerStore};
 use subduction_core::output::{Backdrop, Color};
+use subduction_core::timing::FrameTick;
 use subduction_core::transform::Transform3d;
 
 #[cfg(feature = "appkit")]
@@ -87,16 +89,82 @@ impl LayerRoot {
     }
 }
 
+/// How [`LayerPresenter`] maps subduction's Y-down coordinates onto the root
+/// `CALayer`.
+///
+/// Subduction uses a top-left origin with Y pointing down, so a layer at world
+/// `(0, 0)` belongs in the top-left corner of the root. `CALayer` geometry is
+/// Y-up on macOS unless the layer is geometry-flipped, and Y-down on iOS-family
+/// platforms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoordinateConvention {
+    /// Use world coordinates as-is and leave the root layer untouched.
+    ///
+    /// Correct when the root layer is already Y-down (the default on iOS,
+    /// tvOS, and visionOS, or a layer the host has flipped itself).
+    Native,
+    /// Set `geometryFlipped` on the root layer so its sublayers are laid out
+    /// Y-down.
+    ///
+    /// This is the default on macOS.
+    FlipRoot,
+    /// Leave the root Y-up and flip Y in each applied transform instead,
+    /// measuring positions from the top of the root layer's bounds.
+    ///
+    /// Use this when something else relies on the root staying unflipped.
+    FlipTransform,
+}
+
+impl Default for CoordinateConvention {
+    fn default() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::FlipRoot
+        } else {
+            Self::Native
+        }
+    }
+}
+
 /// Maps a [`LayerStore`] to a live `CALayer` tree, applying incremental
 /// updates from [`FrameChanges`].
 ///
 /// The presenter owns a [`LayerRoot`] to which child layers are added and
 /// removed. Call [`apply`](Self::apply) each frame with the latest
 /// `FrameChanges` to synchronize the `CALayer` tree with the store.
+///
+/// By default each `apply` runs inside a `CATransaction` with actions disabled
+/// and a zero animation duration, so Core Animation's implicit animations do
+/// not fight subduction's per-frame updates. Use
+/// [`with_implicit_animations`](Self::with_implicit_animations) to keep them.
+/// Between [`begin_frame`](Presenter::begin_frame) and
+/// [`end_frame`](Presenter::end_frame), a single transaction spans every
+/// `apply` of the frame instead.
+///
+/// Core Animation and AppKit have no per-layer switch for hit-testing, so
+/// the presenter records which slots are flagged
+/// [`input_transparent`](subduction_core::layer::LayerFlags::input_transparent)
+/// and a host's `hitTest:` override skips the layers or attached views for
+/// which [`is_input_transparent`](Self::is_input_transparent) returns `true`.
+///
+/// World `(0, 0)` maps to the top-left corner of the root layer; see
+/// [`CoordinateConvention`] for how that is achieved on each platform.
+///
+/// Layer bounds are in points. Every presented layer gets the presenter's
+/// [content scale](Self::content_scale) as its `contentsScale`, so its backing
+/// store has that many pixels per point. It defaults to the root layer's
+/// `contentsScale`.
 #[derive(Debug)]
 pub struct LayerPresenter {
     root: LayerRoot,
+    implicit_animations: bool,
+    /// Whether `begin_frame` opened a transaction that `end_frame` commits.
+    frame_open: bool,
+    convention: CoordinateConvention,
+    /// `contentsScale` applied to every presented layer.
+    content_scale: f64,
     layers: HashMap<u32, Retained<CALayer>>,
+    /// Slots whose layers ignore pointer input.
+    input_transparent: HashSet<u32>,
     #[cfg(feature = "appkit")]
     views: HashMap<u32, Retained<NSView>>,
 }
@@ -105,12 +173,81 @@ impl LayerPresenter {
     /// Creates a new presenter that manages sublayers of `root`.
     #[must_use]
     pub fn new(root: LayerRoot) -> Self {
+        let content_scale = root.layer().contentsScale();
         Self {
             root,
+            implicit_animations: false,
+            frame_open: false,
+            convention: CoordinateConvention::Native,
+            content_scale,
             layers: HashMap::new(),
+            input_transparent: HashSet::new(),
             #[cfg(feature = "appkit")]
             views: HashMap::new(),
         }
+        .with_coordinate_convention(CoordinateConvention::default())
+    }
+
+    /// Returns this presenter using the given coordinate convention.
+    ///
+    /// [`FlipRoot`](CoordinateConvention::FlipRoot) sets `geometryFlipped` on
+    /// the root layer and [`FlipTransform`](CoordinateConvention::FlipTransform)
+    /// clears it; [`Native`](CoordinateConvention::Native) leaves it alone.
+    /// Set this before the first [`apply`](Self::apply), since layers already
+    /// positioned are not moved until their transforms change.
+    #[must_use]
+    pub fn with_coordinate_convention(mut self, convention: CoordinateConvention) -> Self {
+        match convention {
+            CoordinateConvention::Native => {}
+            CoordinateConvention::FlipRoot => self.root.layer().setGeometryFlipped(true),
+            CoordinateConvention::FlipTransform => self.root.layer().setGeometryFlipped(false),
+        }
+        self.convention = convention;
+        self
+    }
+
+    /// Returns the coordinate convention in use.
+    #[must_use]
+    pub fn coordinate_convention(&self) -> CoordinateConvention {
+        self.convention
+    }
+
+    /// Returns this presenter with Core Animation implicit animations enabled
+    /// or disabled.
+    ///
+    /// When enabled, property changes made by [`apply`](Self::apply) animate
+    /// with the layers' default actions instead of snapping to the new value.
+    /// Disabled by default.
+    #[must_use]
+    pub fn with_implicit_animations(mut self, enabled: bool) -> Self {
+        self.implicit_animations = enabled;
+        self
+    }
+
+    /// Returns whether implicit animations are enabled.
+    #[must_use]
+    pub fn implicit_animations(&self) -> bool {
+        self.implicit_animations
+    }
+
+    /// Returns the pixels-per-point scale given to presented layers.
+    #[must_use]
+    pub fn content_scale(&self) -> f64 {
+        self.content_scale
+    }
+
+    /// Sets the pixels-per-point scale of the root and every presented layer.
+    ///
+    /// Call this when the hosting window moves to a display with a different
+    /// backing scale factor. Bounds and transforms stay in points.
+    ///
+    /// Must be called on the main thread.
+    pub fn set_content_scale(&mut self, scale: f64) {
+        self.content_scale = scale;
+        self.root.layer().setContentsScale(scale);
+        for layer in self.layers.values() {
+            layer.setContentsScale(scale);
+        }
     }
 
     /// Returns the scene root.
@@ -130,11 +267,27 @@ impl LayerPresenter {
         self.layers.get(&idx).map(|r| &**r)
     }
 
+    /// Returns whether the layer at the given slot index should be skipped
+    /// by hit-testing, as of the last [`apply`](Self::apply).
+    #[must_use]
+    pub fn is_input_transparent(&self, idx: u32) -> bool {
+        self.input_transparent.contains(&idx)
+    }
+
+    /// Records the input transparency of slot `idx` from the store.
+    fn sync_input_transparency(&mut self, store: &LayerStore, idx: u32) {
+        if store.flags_at(idx).input_transparent {
+            self.input_transparent.insert(idx);
+        } else {
+            self.input_transparent.remove(&idx);
+        }
+    }
+
     /// Attaches an `NSView` to the given slot index.
     ///
     /// On each [`apply`](Self::apply), the presenter will sync the view's
     /// frame origin (centered on the layer's world-transform position) and
-    /// alpha value from the store's effective opacity.
+    /// alpha value from the store's ungrouped opacity.
     ///
     /// The caller is responsible for creating the view and adding it as a
     /// subview of the appropriate parent. The presenter does **not** call
@@ -147,13 +300,23 @@ impl LayerPresenter {
         self.views.insert(idx, view);
     }
 
-    /// Detaches and returns the `NSView` for the given slot index, if any.
+    /// Detaches the `NSView` attached to the given slot index, if any.
+    ///
+    /// The view is removed from its superview and the presenter's retained
+    /// reference is dropped. The slot goes back to being managed purely as a
+    /// `CALayer`. Returns `true` if a view was attached.
     ///
-    /// The view is **not** removed from its superview — the caller manages
-    /// that.
+    /// [`apply`](Self::apply) calls this automatically for slots listed in
+    /// [`FrameChanges::removed`].
     #[cfg(feature = "appkit")]
-    pub fn detach_view(&mut self, idx: u32) -> Option<Retained<NSView>> {
-        self.views.remove(&idx)
+    pub fn detach_view(&mut self, idx: u32) -> bool {
+        match self.views.remove(&idx) {
+            Some(view) => {
+                view.removeFromSuperview();
+                true
+            }
+            None => false,
+        }
     }
 
     /// Returns a reference to the attached `NSView` for the given slot index.
@@ -163,6 +326,41 @@ impl LayerPresenter {
         self.views.get(&idx).map(|r| &**r)
     }
 
+    /// Creates the `CALayer` for slot `idx` and appends it to the root layer.
+    fn create_layer(&mut self, store: &LayerStore, idx: u32) -> &CALayer {
+        let layer = CALayer::new();
+        // Center anchor point (default) — position sets the center.
+        layer.setAnchorPoint(CGPoint::new(0.5, 0.5));
+        layer.setContentsScale(self.content_scale);
+        if store.effective_hidden_at(idx) {
+            layer.setHidden(true);
+        }
+        self.root.layer().addSublayer(&layer);
+        self.sync_input_transparency(store, idx);
+        self.layers.insert(idx, layer);
+        &self.layers[&idx]
+    }
+
+    /// Returns the root height that world transforms are flipped against,
+    /// if the convention flips them.
+    fn flip_height(&self) -> Option<f64> {
+        (self.convention == CoordinateConvention::FlipTransform)
+            .then(|| self.root.layer().bounds().size.height)
+    }
+
+    /// Syncs the position and opacity of every attached `NSView`.
+    #[cfg(feature = "appkit")]
+    fn sync_views(&self, store: &LayerStore) {
+        for (&idx, view) in &self.views {
+            let world = store.world_transform_at(idx);
+            let tx = world.cols[3][0];
+            let ty = world.cols[3][1];
+            let size = view.frame().size;
+            view.setFrameOrigin(CGPoint::new(tx - size.width / 2.0, ty - size.height / 2.0));
+            view.setAlphaValue(f64::from(store.ungrouped_opacity_at(idx)));
+        }
+    }
+
     /// Reorders sublayers to match the store's traversal order.
     fn reorder_sublayers(&self, store: &LayerStore) {
         let order = store.traversal_order();
@@ -190,46 +388,50 @@ impl Presenter for LayerPresenter {
     /// tree.
     ///
     /// Must be called on the main thread. Wraps all mutations in a
-    /// `CATransaction` with implicit animations disabled.
+    /// `CATransaction`, with implicit animations disabled unless enabled via
+    /// [`with_implicit_animations`](LayerPresenter::with_implicit_animations).
+    /// Inside a frame bracket, the frame's transaction is used instead.
     fn apply(&mut self, store: &LayerStore, changes: &FrameChanges) {
-        CATransaction::begin();
-        CATransaction::setDisableActions(true);
+        // 0. Full rebuild: every live layer is re-reported, so build them
+        // all from the store instead.
+        if changes.full_rebuild {
+            self.apply_full(store);
+            return;
+        }
+
+        let own_transaction = !self.frame_open;
+        if own_transaction {
+            begin_transaction(self.implicit_animations);
+        }
 
         // 1. Removals
         for &idx in &changes.removed {
             if let Some(layer) = self.layers.remove(&idx) {
                 layer.removeFromSuperlayer();
             }
+            self.input_transparent.remove(&idx);
             #[cfg(feature = "appkit")]
-            if let Some(view) = self.views.remove(&idx) {
-                view.removeFromSuperview();
-            }
+            self.detach_view(idx);
         }
 
         // 2. Additions
         for &idx in &changes.added {
-            let layer = CALayer::new();
-            // Center anchor point (default) — position sets the center.
-            layer.setAnchorPoint(CGPoint::new(0.5, 0.5));
-            if store.effective_hidden_at(idx) {
-                layer.setHidden(true);
-            }
-            self.root.layer().addSublayer(&layer);
-            self.layers.insert(idx, layer);
+            self.create_layer(store, idx);
         }
 
-        // 3. Transforms
+        // 3. Transforms (flag changes are reported here too)
+        let flip_height = self.flip_height();
         for &idx in &changes.transforms {
+            self.sync_input_transparency(store, idx);
             if let Some(layer) = self.layers.get(&idx) {
-                let world = store.world_transform_at(idx);
-                apply_transform(layer, world);
+                apply_transform(layer, world_transform(store, idx, flip_height));
             }
         }
 
         // 4. Opacities
         for &idx in &changes.opacities {
             if let Some(layer) = self.layers.get(&idx) {
-                let opacity = store.effective_opacity_at(idx);
+                let opacity = store.ungrouped_opacity_at(idx);
                 layer.setOpacity(opacity);
             }
         }
@@ -257,11 +459,7 @@ impl Presenter for LayerPresenter {
         // 6. Bounds
         for &idx in &changes.bounds {
             if let Some(layer) = self.layers.get(&idx) {
-                let bounds = store.bounds_at(idx);
-                layer.setBounds(CGRect::new(
-                    CGPoint::new(0.0, 0.0),
-                    CGSize::new(bounds.width, bounds.height),
-                ));
+                apply_bounds(layer, store.bounds_at(idx));
             }
         }
 
@@ -280,16 +478,102 @@ impl Presenter for LayerPresenter {
 
         // 9. Sync attached NSViews (position + opacity).
         #[cfg(feature = "appkit")]
-        for (&idx, view) in &self.views {
-            let world = store.world_transform_at(idx);
-            let tx = world.cols[3][0];
-            let ty = world.cols[3][1];
-            let size = view.frame().size;
-            view.setFrameOrigin(CGPoint::new(tx - size.width / 2.0, ty - size.height / 2.0));
-            view.setAlphaValue(f64::from(store.effective_opacity_at(idx)));
+        self.sync_views(store);
+
+        if own_transaction {
+            CATransaction::commit();
         }
+    }
 
-        CATransaction::commit();
+    /// Discards every `CALayer` and recreates one per live layer, in
+    /// [`traversal_order`](LayerStore::traversal_order), with properties read
+    /// from the current state of `store`.
+    ///
+    /// Attached views belong to the host and stay attached. Must be called
+    /// on the main thread; transactions work as in
+    /// [`apply`](Self::apply).
+    fn apply_full(&mut self, store: &LayerStore) {
+        let own_transaction = !self.frame_open;
+        if own_transaction {
+            begin_transaction(self.implicit_animations);
+        }
+
+        for (_, layer) in self.layers.drain() {
+            layer.removeFromSuperlayer();
+        }
+        self.input_transparent.clear();
+
+        // Appending in traversal order leaves the sublayers in paint order.
+        let flip_height = self.flip_height();
+        for &idx in store.traversal_order() {
+            let layer = self.create_layer(store, idx);
+            apply_transform(layer, world_transform(store, idx, flip_height));
+            layer.setOpacity(store.ungrouped_opacity_at(idx));
+            apply_bounds(layer, store.bounds_at(idx));
+            if let Some(clip) = store.clip_at(idx) {
+                apply_clip(layer, Some(clip));
+            }
+        }
+
+        #[cfg(feature = "appkit")]
+        {
+            for (&idx, view) in &self.views {
+                view.setHidden(store.effective_hidden_at(idx));
+            }
+            self.sync_views(store);
+        }
+
+        if own_transaction {
+            CATransaction::commit();
+        }
+    }
+
+    /// Opens the `CATransaction` that batches every `apply` of this frame.
+    ///
+    /// Must be called on the main thread.
+    fn begin_frame(&mut self, _tick: &FrameTick) {
+        if !self.frame_open {
+            begin_transaction(self.implicit_animations);
+            self.frame_open = true;
+        }
+    }
+
+    /// Commits the transaction opened by `begin_frame`.
+    fn end_frame(&mut self) {
+        if self.frame_open {
+            CATransaction::commit();
+            self.frame_open = false;
+        }
+    }
+}
+
+/// Returns the world transform of slot `idx`, flipped against
+/// `flip_height` if given.
+fn world_transform(store: &LayerStore, idx: u32, flip_height: Option<f64>) -> Transform3d {
+    let world = store.world_transform_at(idx);
+    match flip_height {
+        Some(height) => flip_y(world, height),
+        None => world,
+    }
+}
+
+/// Sets a layer's bounds to `size`, anchored at its origin.
+fn apply_bounds(layer: &CALayer, size: Size) {
+    layer.setBounds(CGRect::new(
+        CGPoint::new(0.0, 0.0),
+        CGSize::new(size.width, size.height),
+    ));
+}
+
+/// Opens the transaction that batches one `apply`.
+///
+/// Unless `implicit_animations` is set, disables actions and zeroes the
+/// animation duration so property changes take effect immediately.
+fn begin_transaction(implicit_animations: bool) {
+    CATransaction::begin();
+    if !implicit_animations {
+        CATransaction::setDisableActions(true);
+        CATransaction::setAnimationDuration(0.0);
     }
 }
 
@@ -308,6 +592,23 @@ fn apply_transform(layer: &CALayer, world: Transform3d) {
     layer.setTransform(transform3d_to_ca(&m));
 }
 
+/// Re-expresses a Y-down world transform in a Y-up space of the given height.
+///
+/// Computes `F * world * F` for the Y reflection `F`, then moves the origin to
+/// the top edge.
+fn flip_y(world: Transform3d, height: f64) -> Transform3d {
+    let mut m = world;
+    for (c, col) in m.cols.iter_mut().enumerate() {
+        for (r, value) in col.iter_mut().enumerate() {
+            if (c == 1) != (r == 1) {
+                *value = -*value;
+            }
+        }
+    }
+    m.cols[3][1] += height * m.cols[3][3];
+    m
+}
+
 /// Applies a clip shape (or clears clipping) on a `CALayer`.
 fn apply_clip(layer: &CALayer, clip: Option<ClipShape>) {
     match clip {
@@ -337,6 +638,17 @@ fn apply_clip(layer: &CALayer, clip: Option<ClipShape>) {
                 .unwrap_or_else(|| radii_max(&radii));
             layer.setCornerRadius(max_r);
         }
+        Some(clip @ ClipShape::Ellipse(_)) => {
+            layer.setMasksToBounds(true);
+            let rect = clip.bounding_box();
+            layer.setBounds(CGRect::new(
+                CGPoint::new(rect.x0, rect.y0),
+                CGSize::new(rect.width(), rect.height()),
+            ));
+            // A uniform corner radius of half the short side is exact for
+            // circles and approximates other ellipses as a capsule.
+            layer.setCornerRadius(rect.width().min(rect.height()) / 2.0);
+        }
     }
 }
 
@@ -430,6 +742,263 @@ mod tests {
         assert!(presenter.root().layer().backgroundColor().is_none());
     }
 
+    /// Runs `begin_transaction` and records the transaction flags in effect
+    /// for the mutation batch.
+    fn record_transaction(implicit_animations: bool) -> (bool, f64) {
+        begin_transaction(implicit_animations);
+        let recorded = (
+            CATransaction::disableActions(),
+            CATransaction::animationDuration(),
+        );
+        CATransaction::commit();
+        recorded
+    }
+
+    #[test]
+    fn apply_transaction_disables_actions_by_default() {
+        let presenter = LayerPresenter::new(LayerRoot::new(CALayer::new()));
+        assert!(!presenter.implicit_animations());
+
+        let (disable_actions, duration) = record_transaction(presenter.implicit_animations());
+        assert!(disable_actions);
+        assert_eq!(duration, 0.0);
+    }
+
+    #[test]
+    fn implicit_animations_leave_transaction_defaults() {
+        let presenter =
+            LayerPresenter::new(LayerRoot::new(CALayer::new())).with_implicit_animations(true);
+        assert!(presenter.implicit_animations());
+
+        let (disable_actions, duration) = record_transaction(presenter.implicit_animations());
+        assert!(!disable_actions);
+        assert!(
+            duration > 0.0,
+            "default implicit animation duration is kept"
+        );
+    }
+
+    #[test]
+    fn apply_commits_transform_without_animation() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_transform(id, Transform3d::from_translation(12.0, 34.0, 0.0));
+        let changes = store.evaluate();
+
+        let mut presenter = LayerPresenter::new(LayerRoot::new(CALayer::new()));
+        presenter.apply(&store, &changes);
+
+        let layer = presenter.get_layer(id.index()).expect("layer was added");
+        let position = layer.position();
+        assert_eq!((position.x, position.y), (12.0, 34.0));
+        assert!(layer.animationKeys().is_none(), "no implicit animations");
+    }
+
+    #[test]
+    fn frame_bracket_shares_one_transaction() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_transform(id, Transform3d::from_translation(5.0, 6.0, 0.0));
+        let changes = store.evaluate();
+
+        let mut presenter = LayerPresenter::new(LayerRoot::new(CALayer::new()));
+        presenter.begin_frame(&FrameTick::builder().build());
+        assert!(presenter.frame_open);
+        assert!(CATransaction::disableActions(), "frame transaction is open");
+        presenter.apply(&store, &changes);
+        assert!(presenter.frame_open, "apply leaves the frame open");
+        presenter.end_frame();
+        assert!(!presenter.frame_open);
+
+        let position = presenter.get_layer(id.index()).unwrap().position();
+        assert_eq!((position.x, position.y), (5.0, 6.0));
+    }
+
+    #[test]
+    fn apply_tracks_input_transparent_layers() {
+        use subduction_core::layer::LayerFlags;
+
+        let mut store = LayerStore::new();
+        let layer = store.create_layer();
+        store.set_flags(
+            layer,
+            LayerFlags {
+                input_transparent: true,
+                ..LayerFlags::default()
+            },
+        );
+        let mut presenter = LayerPresenter::new(LayerRoot::new(CALayer::new()));
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+        assert!(presenter.is_input_transparent(layer.index()));
+
+        store.set_flags(layer, LayerFlags::default());
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+        assert!(!presenter.is_input_transparent(layer.index()));
+    }
+
+    #[test]
+    fn apply_full_replaces_every_layer() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(root, child);
+        store.set_opacity(child, 0.25);
+        let changes = store.evaluate();
+
+        let mut presenter = LayerPresenter::new(LayerRoot::new(CALayer::new()));
+        presenter.apply(&store, &changes);
+        let before = presenter.layers[&child.index()].clone();
+
+        presenter.apply_full(&store);
+        assert_eq!(presenter.layers.len(), 2);
+        let sublayers = presenter.root().layer().sublayers().unwrap();
+        assert_eq!(sublayers.count(), 2, "old layers were removed");
+        let after = presenter.get_layer(child.index()).unwrap();
+        assert!(!core::ptr::eq(&*before, after), "layer was recreated");
+        assert!(before.superlayer().is_none());
+        assert_eq!(after.opacity(), 0.25);
+    }
+
+    /// Presents one translate-only layer at world `(10, 20)` inside a
+    /// 100×200 root and returns the root and the layer's position.
+    fn present_translated(convention: CoordinateConvention) -> (LayerPresenter, (f64, f64)) {
+        let root_layer = CALayer::new();
+        root_layer.setBounds(CGRect::new(
+            CGPoint::new(0.0, 0.0),
+            CGSize::new(100.0, 200.0),
+        ));
+        let mut presenter =
+            LayerPresenter::new(LayerRoot::new(root_layer)).with_coordinate_convention(convention);
+
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_transform(id, Transform3d::from_translation(10.0, 20.0, 0.0));
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+
+        let position = presenter.get_layer(id.index()).unwrap().position();
+        (presenter, (position.x, position.y))
+    }
+
+    #[test]
+    fn flip_root_sets_geometry_flipped() {
+        let (presenter, position) = present_translated(CoordinateConvention::FlipRoot);
+        assert!(presenter.root().layer().isGeometryFlipped());
+        assert_eq!(position, (10.0, 20.0));
+    }
+
+    #[test]
+    fn flip_transform_measures_from_top() {
+        let (presenter, position) = present_translated(CoordinateConvention::FlipTransform);
+        assert!(!presenter.root().layer().isGeometryFlipped());
+        assert_eq!(position, (10.0, 180.0));
+    }
+
+    #[test]
+    fn native_uses_world_coordinates() {
+        let (presenter, position) = present_translated(CoordinateConvention::Native);
+        assert_eq!(
+            presenter.coordinate_convention(),
+            CoordinateConvention::Native
+        );
+        assert_eq!(position, (10.0, 20.0));
+    }
+
+    #[test]
+    fn flip_y_reverses_rotation_direction() {
+        let angle = 0.3;
+        let flipped = flip_y(Transform3d::from_rotation_z(angle), 50.0);
+        let expected =
+            Transform3d::from_translation(0.0, 50.0, 0.0) * Transform3d::from_rotation_z(-angle);
+        for (a, b) in flipped
+            .cols
+            .iter()
+            .flatten()
+            .zip(expected.cols.iter().flatten())
+        {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn content_scale_doubles_backing_pixels() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_bounds(id, Size::new(100.0, 50.0));
+        store.set_transform(id, Transform3d::from_translation(12.0, 34.0, 0.0));
+        let changes = store.evaluate();
+
+        let present = |scale: f64| {
+            let mut presenter = LayerPresenter::new(LayerRoot::new(CALayer::new()))
+                .with_coordinate_convention(CoordinateConvention::Native);
+            presenter.set_content_scale(scale);
+            presenter.apply(&store, &changes);
+            let layer = presenter.get_layer(id.index()).unwrap();
+            let size = layer.bounds().size;
+            let position = layer.position();
+            (
+                (
+                    size.width * layer.contentsScale(),
+                    size.height * layer.contentsScale(),
+                ),
+                (size.width, size.height),
+                (position.x, position.y),
+            )
+        };
+
+        let (pixels_1x, points_1x, position_1x) = present(1.0);
+        let (pixels_2x, points_2x, position_2x) = present(2.0);
+        assert_eq!(pixels_1x, (100.0, 50.0));
+        assert_eq!(pixels_2x, (200.0, 100.0));
+        assert_eq!(points_2x, points_1x);
+        assert_eq!(position_2x, position_1x);
+    }
+
+    #[test]
+    fn set_content_scale_updates_existing_layers() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        let changes = store.evaluate();
+        let mut presenter = LayerPresenter::new(LayerRoot::new(CALayer::new()));
+        presenter.apply(&store, &changes);
+        assert_eq!(
+            presenter.content_scale(),
+            presenter.root().layer().contentsScale()
+        );
+
+        presenter.set_content_scale(3.0);
+        assert_eq!(presenter.content_scale(), 3.0);
+        assert_eq!(presenter.root().layer().contentsScale(), 3.0);
+        assert_eq!(
+            presenter.get_layer(id.index()).unwrap().contentsScale(),
+            3.0
+        );
+    }
+
+    #[cfg(feature = "appkit")]
+    #[test]
+    fn detach_view_removes_attached_view() {
+        use objc2::MainThreadMarker;
+
+        // SAFETY: the view is never added to a window or displayed, so no
+        // main-thread-only AppKit state is touched.
+        let mtm = unsafe { MainThreadMarker::new_unchecked() };
+        let mut presenter = LayerPresenter::new(LayerRoot::new(CALayer::new()));
+        presenter.attach_view(3, NSView::new(mtm));
+        presenter.attach_view(7, NSView::new(mtm));
+        assert_eq!(presenter.views.len(), 2);
+
+        assert!(presenter.detach_view(3));
+        assert_eq!(presenter.views.len(), 1);
+        assert!(presenter.get_view(3).is_none());
+        assert!(presenter.get_view(7).is_some());
+
+        assert!(!presenter.detach_view(3), "second detach is a no-op");
+        assert_eq!(presenter.views.len(), 1);
+    }
+
     fn cg_color_components(color: &CGColor) -> [f64; 4] {
         let count = CGColor::number_of_components(Some(color));
         assert_eq!(count, 4, "expected RGBA color");
@@ -25,6 +25,6 @@ extern crate alloc;
 mod calayer;
 mod cametal;
 
-pub use calayer::{LayerPresenter, LayerRoot};
+pub use calayer::{CoordinateConvention, LayerPresenter, LayerRoot};
 pub use cametal::MetalLayerPresenter;
 pub use subduction_core::backend::Presenter;
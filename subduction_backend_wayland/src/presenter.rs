@@ -311,6 +311,12 @@ where
         + 'static,
 {
     fn apply(&mut self, store: &LayerStore, changes: &FrameChanges) {
+        // 0. Full rebuild: every live layer is re-reported as added, so drop
+        // the existing subsurfaces and content mappings first.
+        if changes.full_rebuild {
+            self.destroy();
+        }
+
         // 1. Removals
         for &slot in &changes.removed {
             self.remove_surface_mapping_for_slot(slot);
@@ -483,6 +489,27 @@ mod tests {
         assert!(p.get_surface(root.index()).is_some());
     }
 
+    #[test]
+    fn apply_full_replaces_entries() {
+        let mut p = test_presenter();
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(root, child);
+        let sid = surface_id(3);
+        store.set_content(child, Some(sid));
+        let changes = store.evaluate();
+        p.apply(&store, &changes);
+
+        p.apply_full(&store);
+
+        assert_eq!(p.entries.iter().flatten().count(), 2);
+        assert_eq!(
+            p.surface_for_content(sid).map(|s| s.id()),
+            p.get_surface(child.index()).map(|s| s.id()),
+        );
+    }
+
     #[test]
     fn apply_content_mapping_basic() {
         let mut p = test_presenter();
@@ -588,7 +615,13 @@ mod tests {
         let mut store = LayerStore::new();
         use subduction_core::layer::LayerFlags;
         let layer = store.create_layer();
-        store.set_flags(layer, LayerFlags { hidden: true });
+        store.set_flags(
+            layer,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
 
         // Should not panic — detach_buffer on an inert proxy is a no-op send.
@@ -606,7 +639,13 @@ mod tests {
         p.apply(&store, &changes);
 
         // Transition to hidden.
-        store.set_flags(layer, LayerFlags { hidden: true });
+        store.set_flags(
+            layer,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
 
         // Should not panic; the hidden slot list should include our layer.
@@ -620,12 +659,24 @@ mod tests {
         let mut store = LayerStore::new();
         use subduction_core::layer::LayerFlags;
         let layer = store.create_layer();
-        store.set_flags(layer, LayerFlags { hidden: true });
+        store.set_flags(
+            layer,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
         p.apply(&store, &changes);
 
         // Unhide.
-        store.set_flags(layer, LayerFlags { hidden: false });
+        store.set_flags(
+            layer,
+            LayerFlags {
+                hidden: false,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
         assert!(changes.unhidden.contains(&layer.index()));
         p.apply(&store, &changes);
@@ -7,6 +7,7 @@
 //!
 //! - [`LayerRoot`]: root DOM container for a scene
 //! - [`DomPresenter`]: DOM element management
+//! - [`ElementSpec`]: per-layer element tag and attributes
 //!
 //! Browser frame timing lives in `frameclock_web`. Use that crate for
 //! `requestAnimationFrame` ticks and retained `frameclock` driver integration.
@@ -17,5 +18,5 @@ extern crate alloc;
 
 mod presenter;
 
-pub use presenter::{DomPresenter, LayerRoot};
+pub use presenter::{DomPresenter, ElementSpec, LayerRoot};
 pub use subduction_core::backend::Presenter;
@@ -13,12 +13,13 @@ use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use kurbo::Size;
 use subduction_core::backend::Presenter;
-use subduction_core::layer::{ClipShape, FrameChanges, LayerStore};
+use subduction_core::layer::{ClipShape, FrameChanges, LayerId, LayerStore};
 use subduction_core::output::{Backdrop, Color};
 use subduction_core::transform::Transform3d;
 use wasm_bindgen::JsCast as _;
-use web_sys::HtmlElement;
+use web_sys::{CssStyleDeclaration, HtmlElement, Node};
 
 /// Root DOM container for a presented scene.
 pub struct LayerRoot {
@@ -90,15 +91,111 @@ impl LayerRoot {
     }
 }
 
+/// The element [`DomPresenter`] creates for a layer: a tag name and initial
+/// attributes.
+///
+/// The default is a plain `<div>`. Register a different spec with
+/// [`DomPresenter::set_element_spec`] so that a content layer can itself be a
+/// `<canvas>`, `<video>`, or `<img>` instead of a `<div>` wrapping one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElementSpec {
+    tag: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl ElementSpec {
+    /// Creates a spec for elements with the given tag name and no attributes.
+    #[must_use]
+    pub fn new(tag: &str) -> Self {
+        Self {
+            tag: String::from(tag),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Returns this spec with an additional initial attribute.
+    #[must_use]
+    pub fn with_attribute(mut self, name: &str, value: &str) -> Self {
+        self.attributes
+            .push((String::from(name), String::from(value)));
+        self
+    }
+
+    /// Returns the tag name.
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns the initial attributes, in insertion order.
+    #[must_use]
+    pub fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+}
+
+impl Default for ElementSpec {
+    fn default() -> Self {
+        Self::new("div")
+    }
+}
+
 /// Maps a [`LayerStore`] to live DOM elements, applying incremental updates
 /// from [`FrameChanges`].
 ///
 /// The presenter owns a [`LayerRoot`] whose container receives child `<div>`
 /// elements. Call [`apply`](Self::apply) each frame with the latest
 /// `FrameChanges` to synchronize the DOM with the store.
+///
+/// By default every reported transform and opacity change is written. With
+/// [`with_write_epsilon`](Self::with_write_epsilon), writes whose value is
+/// within the epsilon of the last value written to that element are skipped,
+/// which avoids style recalculation for changes too small to see.
+///
+/// With [`with_batched_css_text`](Self::with_batched_css_text), each changed
+/// element receives a single `cssText` assignment per `apply` instead of one
+/// `setProperty` call per property.
+///
+/// Elements are `<div>`s unless a different [`ElementSpec`] was registered
+/// for the layer with [`set_element_spec`](Self::set_element_spec).
+/// Elements of layers flagged
+/// [`input_transparent`](subduction_core::layer::LayerFlags::input_transparent)
+/// get `pointer-events: none`, so input reaches whatever is beneath them.
+///
+/// Layer sizes are CSS pixels. A `<canvas>` element additionally gets its
+/// `width` and `height` attributes (its backing store) set to the layer size
+/// times the [content scale](Self::content_scale), which defaults to the
+/// window's `devicePixelRatio`, so canvas content stays sharp on
+/// high-density displays.
+///
+/// # Stacking order
+///
+/// All elements are siblings under the root container, and paint order
+/// follows their document order. When topology changes, the presenter moves
+/// elements so that document order matches
+/// [`traversal_order`](LayerStore::traversal_order), rather than assigning
+/// `z-index`. Only elements that are out of order relative to the rest are
+/// moved, so raising one layer moves one element. A `z-index` scheme would
+/// avoid DOM mutations entirely, but inserting a layer shifts the index of
+/// every later sibling (rewriting many styles), and a `z-index` on each
+/// element competes with stacking contexts the host sets up itself.
 pub struct DomPresenter {
     root: LayerRoot,
     elements: Vec<Option<HtmlElement>>,
+    /// Slots of the root container's managed children, in document order.
+    dom_order: Vec<u32>,
+    /// Registered non-default element specs.
+    specs: ElementSpecs,
+    gate: WriteGate,
+    batch_css_text: bool,
+    /// Managed properties per slot, used when `batch_css_text` is set.
+    managed: Vec<ManagedStyle>,
+    /// Slots whose managed properties changed during the current `apply`.
+    batch_dirty: Vec<u32>,
+    /// Device pixels per CSS pixel for canvas backing stores.
+    content_scale: f64,
+    /// Whether every canvas must be resized on the next `apply`.
+    content_scale_changed: bool,
 }
 
 impl core::fmt::Debug for DomPresenter {
@@ -106,7 +203,11 @@ impl core::fmt::Debug for DomPresenter {
         f.debug_struct("DomPresenter")
             .field("root", &self.root)
             .field("elements_len", &self.elements.len())
-            .finish()
+            .field("write_epsilon", &self.gate.epsilon)
+            .field("skipped_writes", &self.gate.skipped)
+            .field("batch_css_text", &self.batch_css_text)
+            .field("content_scale", &self.content_scale)
+            .finish_non_exhaustive()
     }
 }
 
@@ -114,12 +215,108 @@ impl DomPresenter {
     /// Creates a new presenter that manages child elements of `root`.
     #[must_use]
     pub fn new(root: LayerRoot) -> Self {
+        let content_scale = root
+            .container()
+            .owner_document()
+            .and_then(|doc| doc.default_view())
+            .map_or(1.0, |window| window.device_pixel_ratio());
         Self {
             root,
             elements: Vec::new(),
+            dom_order: Vec::new(),
+            specs: ElementSpecs::default(),
+            gate: WriteGate::default(),
+            batch_css_text: false,
+            managed: Vec::new(),
+            batch_dirty: Vec::new(),
+            content_scale,
+            content_scale_changed: false,
+        }
+    }
+
+    /// Returns the number of device pixels per CSS pixel used to size canvas
+    /// backing stores.
+    #[must_use]
+    pub fn content_scale(&self) -> f64 {
+        self.content_scale
+    }
+
+    /// Sets the number of device pixels per CSS pixel used to size canvas
+    /// backing stores.
+    ///
+    /// Call this when `devicePixelRatio` changes, for example when the window
+    /// moves to a display with a different density. Every canvas is resized
+    /// on the next [`apply`](Self::apply); CSS sizes and transforms are not
+    /// affected.
+    pub fn set_content_scale(&mut self, scale: f64) {
+        if scale != self.content_scale {
+            self.content_scale = scale;
+            self.content_scale_changed = true;
         }
     }
 
+    /// Returns this presenter writing each changed element's style with a
+    /// single `cssText` assignment.
+    ///
+    /// The presenter remembers the properties it owns (position, transform,
+    /// opacity, display, size, and clipping) for every element. Any other
+    /// inline declarations are read once when the element is created (for
+    /// example from an [`ElementSpec`] `style` attribute) and carried into
+    /// every composed `cssText`; the presenter never reads styles back per
+    /// frame. After changing an element's inline style directly, call
+    /// [`capture_host_style`](Self::capture_host_style) so the change is not
+    /// overwritten by the next flush.
+    #[must_use]
+    pub fn with_batched_css_text(mut self, batched: bool) -> Self {
+        self.batch_css_text = batched;
+        self
+    }
+
+    /// Returns this presenter skipping transform and opacity writes that are
+    /// within `epsilon` of the last value written to the same element.
+    ///
+    /// Each matrix entry and the opacity are compared separately against the
+    /// last *written* value, so a slow drift still gets written once it
+    /// accumulates past `epsilon`.
+    #[must_use]
+    pub fn with_write_epsilon(mut self, epsilon: f64) -> Self {
+        self.gate.epsilon = Some(epsilon);
+        self
+    }
+
+    /// Returns the number of transform and opacity writes skipped because
+    /// the value had not changed by more than the write epsilon.
+    #[must_use]
+    pub fn skipped_writes(&self) -> u64 {
+        self.gate.skipped
+    }
+
+    /// Records the current inline declarations of slot `idx`'s element that
+    /// the presenter does not manage.
+    ///
+    /// Only needed with [batched `cssText`](Self::with_batched_css_text): the
+    /// recorded declarations are written back with every composed `cssText`,
+    /// replacing whatever was recorded before. Does nothing if the slot has
+    /// no element.
+    pub fn capture_host_style(&mut self, idx: u32) {
+        let Some(el) = self.get_element(idx) else {
+            return;
+        };
+        let host = host_declarations(&el.style());
+        self.managed_mut(idx).host = host;
+    }
+
+    /// Sets (or with `None`, clears) the element spec used when the element
+    /// for layer `id` is created.
+    ///
+    /// Register the spec after creating the layer and before the
+    /// [`apply`](Self::apply) that reports it as added; an element that
+    /// already exists is not recreated. The registration is dropped once the
+    /// layer is destroyed, so a reused slot gets a `<div>` again.
+    pub fn set_element_spec(&mut self, id: LayerId, spec: Option<ElementSpec>) {
+        self.specs.set(id, spec);
+    }
+
     /// Returns the scene root.
     #[must_use]
     pub fn root(&self) -> &LayerRoot {
@@ -152,101 +349,546 @@ impl DomPresenter {
         }
         self.elements[slot] = Some(el);
     }
+
+    /// Writes managed style properties for slot `idx`, either directly or
+    /// into the pending `cssText` batch.
+    fn write_style(&mut self, idx: u32, f: impl FnOnce(&mut dyn StyleSink)) {
+        let Some(el) = self.elements.get(idx as usize).and_then(Option::as_ref) else {
+            return;
+        };
+        if !self.batch_css_text {
+            f(&mut el.style());
+            return;
+        }
+        let style = self.managed_mut(idx);
+        f(style);
+        if !style.dirty {
+            style.dirty = true;
+            self.batch_dirty.push(idx);
+        }
+    }
+
+    /// Returns the managed properties of slot `idx`, growing the table if
+    /// needed.
+    fn managed_mut(&mut self, idx: u32) -> &mut ManagedStyle {
+        let slot = idx as usize;
+        if self.managed.len() <= slot {
+            self.managed.resize_with(slot + 1, ManagedStyle::default);
+        }
+        &mut self.managed[slot]
+    }
+
+    /// Sizes the backing store of slot `idx` if its element is a `<canvas>`.
+    fn size_canvas(&self, idx: u32, bounds: Size) {
+        let Some(el) = self.get_element(idx) else {
+            return;
+        };
+        if !el.tag_name().eq_ignore_ascii_case("canvas") {
+            return;
+        }
+        let (width, height) = device_pixel_size(bounds, self.content_scale);
+        let _ = el.set_attribute("width", &format!("{width}"));
+        let _ = el.set_attribute("height", &format!("{height}"));
+    }
+
+    /// Forgets the managed properties recorded for slot `idx`.
+    fn forget_managed(&mut self, idx: u32) {
+        if let Some(style) = self.managed.get_mut(idx as usize) {
+            *style = ManagedStyle::default();
+        }
+    }
+
+    /// Creates the element for slot `idx` and appends it to the root
+    /// container, forgetting anything written to the slot before.
+    ///
+    /// Only the element's input transparency is recorded as written; the
+    /// caller writes its styles.
+    fn create_element(&mut self, store: &LayerStore, idx: u32) {
+        let doc = self
+            .root
+            .container()
+            .owner_document()
+            .expect("no owner document");
+        let spec = self.specs.get(store.id_at(idx));
+        let el: HtmlElement = doc
+            .create_element(spec.map_or("div", ElementSpec::tag))
+            .expect("create_element failed")
+            .unchecked_into();
+        for (name, value) in spec.map_or(&[][..], ElementSpec::attributes) {
+            let _ = el.set_attribute(name, value);
+        }
+        let _ = self.root.container().append_child(&el);
+        self.put_element(idx, el);
+        self.dom_order.push(idx);
+        self.gate.forget(idx);
+        self.forget_managed(idx);
+        if self.batch_css_text {
+            self.capture_host_style(idx);
+        }
+        self.gate
+            .input_transparency_changed(idx, store.flags_at(idx).input_transparent);
+    }
+
+    /// Assigns the composed `cssText` of every slot written during this
+    /// `apply`.
+    fn flush_css_text(&mut self) {
+        for idx in self.batch_dirty.drain(..) {
+            let slot = idx as usize;
+            let style = &mut self.managed[slot];
+            style.dirty = false;
+            if let Some(el) = self.elements.get(slot).and_then(Option::as_ref) {
+                let host = style.host.iter();
+                let css_text =
+                    style.compose(host.map(|(n, v, p)| (n.as_str(), v.as_str(), p.as_str())));
+                el.style().set_css_text(&css_text);
+            }
+        }
+    }
 }
 
 impl Presenter for DomPresenter {
     /// Applies incremental changes from a [`FrameChanges`] to the DOM.
     fn apply(&mut self, store: &LayerStore, changes: &FrameChanges) {
+        // 0. Full rebuild: every live layer is re-reported, so build them
+        // all from the store instead.
+        if changes.full_rebuild {
+            self.apply_full(store);
+            return;
+        }
+
         // 1. Removals
         for &idx in &changes.removed {
             if let Some(el) = self.take_element(idx) {
                 el.remove();
             }
+            self.gate.forget(idx);
+            self.forget_managed(idx);
+        }
+        if !changes.removed.is_empty() {
+            self.specs.prune(store);
+            let elements = &self.elements;
+            self.dom_order
+                .retain(|&idx| matches!(elements.get(idx as usize), Some(Some(_))));
         }
 
         // 2. Additions
         for &idx in &changes.added {
-            let doc = self
-                .root
-                .container()
-                .owner_document()
-                .expect("no owner document");
-            let el: HtmlElement = doc
-                .create_element("div")
-                .expect("create_element failed")
-                .unchecked_into();
-            let s = el.style();
-            let _ = s.set_property("position", "absolute");
-            let _ = s.set_property("left", "0");
-            let _ = s.set_property("top", "0");
-            let _ = s.set_property("transform-origin", "0 0");
-            if store.effective_hidden_at(idx) {
-                let _ = s.set_property("display", "none");
-            }
-            let _ = self.root.container().append_child(&el);
-            self.put_element(idx, el);
+            self.create_element(store, idx);
+            let hidden = store.effective_hidden_at(idx);
+            let input_transparent = store.flags_at(idx).input_transparent;
+            self.write_style(idx, |s| {
+                apply_css_base(s, hidden, input_transparent);
+            });
         }
 
-        // 3. Transforms
+        // 3. Transforms (flag changes are reported here too)
         for &idx in &changes.transforms {
-            if let Some(el) = self.get_element(idx) {
-                let world = store.world_transform_at(idx);
-                apply_css_transform(el, &world);
+            if self.get_element(idx).is_some()
+                && let Some(write) = self.gate.input_transparency_write(store, idx)
+            {
+                self.write_style(idx, write);
+            }
+            let world = store.world_transform_at(idx);
+            if self.get_element(idx).is_some() && self.gate.transform_changed(idx, &world) {
+                self.write_style(idx, |s| apply_css_transform(s, &world));
             }
         }
 
         // 4. Opacities
         for &idx in &changes.opacities {
-            if let Some(el) = self.get_element(idx) {
-                let opacity = store.effective_opacity_at(idx);
-                let _ = el.style().set_property("opacity", &format!("{opacity}"));
+            let opacity = store.ungrouped_opacity_at(idx);
+            if self.get_element(idx).is_some() && self.gate.opacity_changed(idx, opacity) {
+                self.write_style(idx, |s| s.set("opacity", &format!("{opacity}")));
             }
         }
 
         // 5. Hidden/unhidden
         for &idx in &changes.hidden {
-            if let Some(el) = self.get_element(idx) {
-                let _ = el.style().set_property("display", "none");
-            }
+            self.write_style(idx, |s| s.set("display", "none"));
         }
         for &idx in &changes.unhidden {
-            if let Some(el) = self.get_element(idx) {
-                let _ = el.style().remove_property("display");
-            }
+            self.write_style(idx, |s| s.remove("display"));
         }
 
         // 6. Bounds
         for &idx in &changes.bounds {
-            if let Some(el) = self.get_element(idx) {
-                let bounds = store.bounds_at(idx);
-                let s = el.style();
-                if bounds.width > 0.0 && bounds.height > 0.0 {
-                    let _ = s.set_property("width", &format!("{}px", bounds.width));
-                    let _ = s.set_property("height", &format!("{}px", bounds.height));
-                } else {
-                    let _ = s.remove_property("width");
-                    let _ = s.remove_property("height");
-                }
+            let bounds = store.bounds_at(idx);
+            self.write_style(idx, |s| apply_css_size(s, bounds));
+            self.size_canvas(idx, bounds);
+        }
+        if self.content_scale_changed {
+            self.content_scale_changed = false;
+            for &idx in &self.dom_order {
+                self.size_canvas(idx, store.bounds_at(idx));
             }
         }
 
         // 7. Clips
         for &idx in &changes.clips {
-            if let Some(el) = self.get_element(idx) {
-                let clip = store.clip_at(idx);
-                apply_css_clip(el, clip);
-            }
+            let clip = store.clip_at(idx);
+            self.write_style(idx, |s| apply_css_clip(s, clip));
         }
 
         // 8. Topology reorder
         if changes.topology_changed {
-            for &idx in store.traversal_order() {
+            let target: Vec<u32> = store
+                .traversal_order()
+                .iter()
+                .copied()
+                .filter(|&idx| self.get_element(idx).is_some())
+                .collect();
+            for (idx, before) in reorder_plan(&self.dom_order, &target) {
                 if let Some(el) = self.get_element(idx) {
-                    // DOM re-append moves an existing child, reordering it.
-                    let _ = self.root.container().append_child(el);
+                    let before = before
+                        .and_then(|b| self.get_element(b))
+                        .map(AsRef::<Node>::as_ref);
+                    // Inserting an existing child moves it.
+                    let _ = self.root.container().insert_before(el, before);
                 }
             }
+            self.dom_order = target;
         }
+
+        // 9. Batched cssText
+        self.flush_css_text();
+    }
+
+    /// Discards every element and recreates one per live layer, in
+    /// [`traversal_order`](LayerStore::traversal_order), with styles read
+    /// from the current state of `store`.
+    fn apply_full(&mut self, store: &LayerStore) {
+        for el in self.elements.drain(..).flatten() {
+            el.remove();
+        }
+        self.dom_order.clear();
+        self.gate.clear();
+        self.managed.clear();
+        self.batch_dirty.clear();
+        self.specs.prune(store);
+        self.content_scale_changed = false;
+
+        for &idx in store.traversal_order() {
+            self.create_element(store, idx);
+            // Record the written values so later gated writes compare
+            // against them.
+            self.gate
+                .transform_changed(idx, &store.world_transform_at(idx));
+            self.gate
+                .opacity_changed(idx, store.ungrouped_opacity_at(idx));
+            self.write_style(idx, |s| apply_css_layer(s, store, idx));
+            self.size_canvas(idx, store.bounds_at(idx));
+        }
+        self.flush_css_text();
+    }
+}
+
+/// Element specs registered per layer.
+///
+/// Few layers use a non-default element, so this is a small list rather than
+/// a per-slot table.
+#[derive(Debug, Default)]
+struct ElementSpecs {
+    entries: Vec<(LayerId, ElementSpec)>,
+}
+
+impl ElementSpecs {
+    fn set(&mut self, id: LayerId, spec: Option<ElementSpec>) {
+        self.entries.retain(|(entry, _)| *entry != id);
+        if let Some(spec) = spec {
+            self.entries.push((id, spec));
+        }
+    }
+
+    fn get(&self, id: LayerId) -> Option<&ElementSpec> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| *entry == id)
+            .map(|(_, spec)| spec)
+    }
+
+    /// Drops registrations for layers that are no longer alive.
+    fn prune(&mut self, store: &LayerStore) {
+        self.entries.retain(|(id, _)| store.is_alive(*id));
+    }
+}
+
+/// Returns the moves that turn `current` into `target`, as
+/// `(slot, insert_before)` pairs where `None` means "append".
+///
+/// Both slices must hold the same slots. Slots on a longest subsequence of
+/// `current` that is already in `target` order stay put; every other slot is
+/// moved, in reverse target order, in front of its final successor.
+fn reorder_plan(current: &[u32], target: &[u32]) -> Vec<(u32, Option<u32>)> {
+    let max_slot = target.iter().copied().max().map_or(0, |m| m as usize + 1);
+    let mut target_pos = alloc::vec![usize::MAX; max_slot];
+    for (pos, &idx) in target.iter().enumerate() {
+        target_pos[idx as usize] = pos;
+    }
+
+    // Longest increasing run of target positions, in `current` order
+    // (patience sorting with predecessor links).
+    let positions: Vec<usize> = current
+        .iter()
+        .filter_map(|&idx| target_pos.get(idx as usize).copied())
+        .filter(|&pos| pos != usize::MAX)
+        .collect();
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev = alloc::vec![usize::MAX; positions.len()];
+    for (i, &pos) in positions.iter().enumerate() {
+        let len = tails.partition_point(|&t| positions[t] < pos);
+        if len > 0 {
+            prev[i] = tails[len - 1];
+        }
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+    let mut stable = alloc::vec![false; target.len()];
+    let mut i = tails.last().copied().unwrap_or(usize::MAX);
+    while i != usize::MAX {
+        stable[positions[i]] = true;
+        i = prev[i];
+    }
+
+    let mut moves = Vec::new();
+    let mut next = None;
+    for (pos, &idx) in target.iter().enumerate().rev() {
+        if !stable[pos] {
+            moves.push((idx, next));
+        }
+        next = Some(idx);
+    }
+    moves
+}
+
+/// Destination for the inline style properties the presenter manages.
+trait StyleSink {
+    fn set(&mut self, name: &'static str, value: &str);
+    fn remove(&mut self, name: &'static str);
+}
+
+impl StyleSink for CssStyleDeclaration {
+    fn set(&mut self, name: &'static str, value: &str) {
+        let _ = self.set_property(name, value);
+    }
+
+    fn remove(&mut self, name: &'static str) {
+        let _ = self.remove_property(name);
+    }
+}
+
+/// Inline style properties owned by the presenter, in `cssText` order.
+const MANAGED_PROPERTIES: [&str; 12] = [
+    "position",
+    "left",
+    "top",
+    "transform-origin",
+    "transform",
+    "opacity",
+    "display",
+    "width",
+    "height",
+    "overflow",
+    "border-radius",
+    "pointer-events",
+];
+
+/// Returns whether `name` is a managed property or a longhand of one.
+///
+/// The browser enumerates inline declarations as longhands, so a managed
+/// `overflow` shows up as `overflow-x` and `overflow-y`.
+fn is_managed_property(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    MANAGED_PROPERTIES.contains(&name.as_str())
+        || name.starts_with("overflow-")
+        || (name.starts_with("border-") && name.ends_with("-radius"))
+}
+
+/// Returns the inline declarations of `style` that the presenter does not
+/// manage, as `(name, value, priority)`.
+fn host_declarations(style: &CssStyleDeclaration) -> Vec<(String, String, String)> {
+    (0..style.length())
+        .map(|i| style.item(i))
+        .filter(|name| !is_managed_property(name))
+        .map(|name| {
+            let value = style.get_property_value(&name).unwrap_or_default();
+            let priority = style.get_property_priority(&name);
+            (name, value, priority)
+        })
+        .collect()
+}
+
+/// Current values of the managed properties of one element.
+#[derive(Clone, Debug, Default)]
+struct ManagedStyle {
+    values: [Option<String>; MANAGED_PROPERTIES.len()],
+    /// Unmanaged inline declarations, as `(name, value, priority)`, written
+    /// ahead of the managed ones.
+    host: Vec<(String, String, String)>,
+    /// Whether this slot is queued for the current `cssText` flush.
+    dirty: bool,
+}
+
+impl ManagedStyle {
+    fn value_mut(&mut self, name: &str) -> &mut Option<String> {
+        let i = MANAGED_PROPERTIES
+            .iter()
+            .position(|&managed| managed == name)
+            .expect("property is not managed by the presenter");
+        &mut self.values[i]
+    }
+
+    /// Composes a `cssText` value: the `existing` declarations, as
+    /// `(name, value, priority)`, that the presenter does not manage,
+    /// followed by the managed properties that are set.
+    ///
+    /// Declarations are taken whole rather than re-parsed from `cssText`, so
+    /// values containing `;` (such as data URLs) survive.
+    fn compose<S: AsRef<str>>(&self, existing: impl IntoIterator<Item = (S, S, S)>) -> String {
+        let mut out = String::new();
+        let mut push = |name: &str, value: &str, priority: &str| {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(name);
+            out.push_str(": ");
+            out.push_str(value);
+            if !priority.is_empty() {
+                out.push_str(" !");
+                out.push_str(priority);
+            }
+            out.push(';');
+        };
+        for (name, value, priority) in existing {
+            let name = name.as_ref();
+            if !is_managed_property(name) {
+                push(name, value.as_ref(), priority.as_ref());
+            }
+        }
+        for (name, value) in MANAGED_PROPERTIES.iter().zip(&self.values) {
+            if let Some(value) = value {
+                push(name, value, "");
+            }
+        }
+        out
+    }
+}
+
+impl StyleSink for ManagedStyle {
+    fn set(&mut self, name: &'static str, value: &str) {
+        *self.value_mut(name) = Some(String::from(value));
+    }
+
+    fn remove(&mut self, name: &'static str) {
+        *self.value_mut(name) = None;
+    }
+}
+
+/// Last transform, opacity, and input transparency written to one element.
+#[derive(Clone, Copy, Debug, Default)]
+struct AppliedStyle {
+    transform: Option<Transform3d>,
+    opacity: Option<f32>,
+    input_transparent: bool,
+}
+
+/// Decides whether a transform or opacity write is needed, per slot.
+#[derive(Debug, Default)]
+struct WriteGate {
+    /// `None` disables gating: every write goes through.
+    epsilon: Option<f64>,
+    applied: Vec<AppliedStyle>,
+    skipped: u64,
+}
+
+impl WriteGate {
+    /// Forgets everything written to slot `idx`.
+    fn forget(&mut self, idx: u32) {
+        if let Some(style) = self.applied.get_mut(idx as usize) {
+            *style = AppliedStyle::default();
+        }
+    }
+
+    /// Forgets everything written to every slot.
+    fn clear(&mut self) {
+        self.applied.clear();
+    }
+
+    fn slot(&mut self, idx: u32) -> &mut AppliedStyle {
+        let slot = idx as usize;
+        if self.applied.len() <= slot {
+            self.applied.resize(slot + 1, AppliedStyle::default());
+        }
+        &mut self.applied[slot]
+    }
+
+    /// Returns whether `xf` must be written to slot `idx`, recording it as
+    /// written if so.
+    fn transform_changed(&mut self, idx: u32, xf: &Transform3d) -> bool {
+        let Some(epsilon) = self.epsilon else {
+            return true;
+        };
+        let style = self.slot(idx);
+        if let Some(last) = &style.transform
+            && last
+                .cols
+                .iter()
+                .flatten()
+                .zip(xf.cols.iter().flatten())
+                .all(|(a, b)| (a - b).abs() <= epsilon)
+        {
+            self.skipped += 1;
+            return false;
+        }
+        style.transform = Some(*xf);
+        true
+    }
+
+    /// Returns whether `opacity` must be written to slot `idx`, recording it
+    /// as written if so.
+    fn opacity_changed(&mut self, idx: u32, opacity: f32) -> bool {
+        let Some(epsilon) = self.epsilon else {
+            return true;
+        };
+        let style = self.slot(idx);
+        if let Some(last) = style.opacity
+            && (f64::from(last) - f64::from(opacity)).abs() <= epsilon
+        {
+            self.skipped += 1;
+            return false;
+        }
+        style.opacity = Some(opacity);
+        true
+    }
+
+    /// Returns whether input transparency must be written to slot `idx`,
+    /// recording it as written if so.
+    ///
+    /// Unlike transforms and opacity this is always gated, since the flag
+    /// changes rarely but its slot is re-reported on every transform change.
+    fn input_transparency_changed(&mut self, idx: u32, input_transparent: bool) -> bool {
+        let style = self.slot(idx);
+        if style.input_transparent == input_transparent {
+            return false;
+        }
+        style.input_transparent = input_transparent;
+        true
+    }
+
+    /// Returns the style write that brings slot `idx`'s input transparency
+    /// up to date with `store`, or `None` if it is already written.
+    fn input_transparency_write(
+        &mut self,
+        store: &LayerStore,
+        idx: u32,
+    ) -> Option<impl FnOnce(&mut dyn StyleSink) + use<>> {
+        let input_transparent = store.flags_at(idx).input_transparent;
+        self.input_transparency_changed(idx, input_transparent)
+            .then_some(move |s: &mut dyn StyleSink| {
+                apply_css_input_transparency(s, input_transparent);
+            })
     }
 }
 
@@ -277,8 +919,34 @@ fn backdrop_css_value(backdrop: Backdrop) -> Option<String> {
     }
 }
 
+/// Applies a layer size as CSS `width` and `height`, or clears them for an
+/// empty size.
+fn apply_css_size(s: &mut dyn StyleSink, bounds: Size) {
+    if bounds.width > 0.0 && bounds.height > 0.0 {
+        s.set("width", &format!("{}px", bounds.width));
+        s.set("height", &format!("{}px", bounds.height));
+    } else {
+        s.remove("width");
+        s.remove("height");
+    }
+}
+
+/// Returns the backing-store size in device pixels of a layer of logical
+/// size `bounds` at the given content scale.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "float-to-int casts saturate; canvas sizes are small and non-negative"
+)]
+fn device_pixel_size(bounds: Size, scale: f64) -> (u32, u32) {
+    (
+        (bounds.width * scale).round() as u32,
+        (bounds.height * scale).round() as u32,
+    )
+}
+
 /// Applies a world transform as a CSS `matrix3d()` value.
-fn apply_css_transform(el: &HtmlElement, xf: &Transform3d) {
+fn apply_css_transform(s: &mut dyn StyleSink, xf: &Transform3d) {
     let c0 = xf.col(0);
     let c1 = xf.col(1);
     let c2 = xf.col(2);
@@ -304,32 +972,70 @@ fn apply_css_transform(el: &HtmlElement, xf: &Transform3d) {
         c3[3],
     );
 
-    let _ = el.style().set_property("transform", &css);
+    s.set("transform", &css);
+}
+
+/// Writes the properties every new element starts with.
+fn apply_css_base(s: &mut dyn StyleSink, hidden: bool, input_transparent: bool) {
+    s.set("position", "absolute");
+    s.set("left", "0");
+    s.set("top", "0");
+    s.set("transform-origin", "0 0");
+    if hidden {
+        s.set("display", "none");
+    }
+    if input_transparent {
+        apply_css_input_transparency(s, true);
+    }
+}
+
+/// Writes every managed property of slot `idx` from the current state of
+/// `store`.
+fn apply_css_layer(s: &mut dyn StyleSink, store: &LayerStore, idx: u32) {
+    apply_css_base(
+        s,
+        store.effective_hidden_at(idx),
+        store.flags_at(idx).input_transparent,
+    );
+    apply_css_transform(s, &store.world_transform_at(idx));
+    s.set("opacity", &format!("{}", store.ungrouped_opacity_at(idx)));
+    apply_css_size(s, store.bounds_at(idx));
+    if let Some(clip) = store.clip_at(idx) {
+        apply_css_clip(s, Some(clip));
+    }
+}
+
+/// Applies a layer's input transparency as CSS `pointer-events`.
+fn apply_css_input_transparency(s: &mut dyn StyleSink, input_transparent: bool) {
+    if input_transparent {
+        s.set("pointer-events", "none");
+    } else {
+        s.remove("pointer-events");
+    }
 }
 
 /// Applies a clip shape (or clears clipping) as CSS properties.
-fn apply_css_clip(el: &HtmlElement, clip: Option<ClipShape>) {
-    let s = el.style();
+fn apply_css_clip(s: &mut dyn StyleSink, clip: Option<ClipShape>) {
     match clip {
         None => {
-            let _ = s.set_property("overflow", "visible");
-            let _ = s.remove_property("width");
-            let _ = s.remove_property("height");
-            let _ = s.remove_property("border-radius");
+            s.set("overflow", "visible");
+            s.remove("width");
+            s.remove("height");
+            s.remove("border-radius");
         }
         Some(ClipShape::Rect(rect)) => {
-            let _ = s.set_property("overflow", "hidden");
-            let _ = s.set_property("width", &format!("{}px", rect.width()));
-            let _ = s.set_property("height", &format!("{}px", rect.height()));
-            let _ = s.set_property("border-radius", "0");
+            s.set("overflow", "hidden");
+            s.set("width", &format!("{}px", rect.width()));
+            s.set("height", &format!("{}px", rect.height()));
+            s.set("border-radius", "0");
         }
         Some(ClipShape::RoundedRect(rrect)) => {
             let rect = rrect.rect();
-            let _ = s.set_property("overflow", "hidden");
-            let _ = s.set_property("width", &format!("{}px", rect.width()));
-            let _ = s.set_property("height", &format!("{}px", rect.height()));
+            s.set("overflow", "hidden");
+            s.set("width", &format!("{}px", rect.width()));
+            s.set("height", &format!("{}px", rect.height()));
             let radii = rrect.radii();
-            let _ = s.set_property(
+            s.set(
                 "border-radius",
                 &format!(
                     "{}px {}px {}px {}px",
@@ -337,6 +1043,15 @@ fn apply_css_clip(el: &HtmlElement, clip: Option<ClipShape>) {
                 ),
             );
         }
+        Some(clip @ ClipShape::Ellipse(_)) => {
+            // CSS clips in the element's axis-aligned box, so a rotated
+            // ellipse is approximated by its bounding box.
+            let rect = clip.bounding_box();
+            s.set("overflow", "hidden");
+            s.set("width", &format!("{}px", rect.width()));
+            s.set("height", &format!("{}px", rect.height()));
+            s.set("border-radius", "50%");
+        }
     }
 }
 
@@ -344,6 +1059,334 @@ fn apply_css_clip(el: &HtmlElement, clip: Option<ClipShape>) {
 mod tests {
     use super::*;
 
+    /// Runs every slot's current transform and opacity through the gate and
+    /// returns how many writes it let through.
+    fn gated_writes(gate: &mut WriteGate, store: &LayerStore) -> usize {
+        let mut writes = 0;
+        for &idx in store.traversal_order() {
+            writes += usize::from(gate.transform_changed(idx, &store.world_transform_at(idx)));
+            writes += usize::from(gate.opacity_changed(idx, store.ungrouped_opacity_at(idx)));
+        }
+        writes
+    }
+
+    #[test]
+    fn identical_reevaluation_writes_nothing() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(root, child);
+        store.set_transform(child, Transform3d::from_translation(4.0, 2.0, 0.0));
+        store.set_opacity(child, 0.9);
+        let _ = store.evaluate();
+
+        let mut gate = WriteGate {
+            epsilon: Some(1e-3),
+            ..WriteGate::default()
+        };
+        assert_eq!(gated_writes(&mut gate, &store), 4);
+
+        store.mark_all_dirty();
+        let _ = store.evaluate();
+        assert_eq!(gated_writes(&mut gate, &store), 0);
+        assert_eq!(gate.skipped, 4);
+    }
+
+    #[test]
+    fn write_gate_skips_changes_within_epsilon() {
+        let mut gate = WriteGate {
+            epsilon: Some(1e-3),
+            ..WriteGate::default()
+        };
+        assert!(gate.opacity_changed(3, 0.9));
+        assert!(!gate.opacity_changed(3, 0.900_01));
+        assert!(gate.opacity_changed(3, 0.95));
+
+        let xf = Transform3d::from_translation(10.0, 0.0, 0.0);
+        assert!(gate.transform_changed(3, &xf));
+        assert!(!gate.transform_changed(3, &Transform3d::from_translation(10.0001, 0.0, 0.0)));
+        assert!(gate.transform_changed(3, &Transform3d::from_translation(10.1, 0.0, 0.0)));
+
+        gate.forget(3);
+        assert!(gate.opacity_changed(3, 0.95));
+        assert_eq!(gate.skipped, 2);
+    }
+
+    #[test]
+    fn write_gate_disabled_by_default() {
+        let mut gate = WriteGate::default();
+        assert!(gate.opacity_changed(0, 0.5));
+        assert!(gate.opacity_changed(0, 0.5));
+        assert_eq!(gate.skipped, 0);
+    }
+
+    #[test]
+    fn element_spec_defaults_to_div() {
+        let spec = ElementSpec::default();
+        assert_eq!(spec.tag(), "div");
+        assert!(spec.attributes().is_empty());
+
+        let canvas = ElementSpec::new("canvas").with_attribute("width", "64");
+        assert_eq!(canvas.tag(), "canvas");
+        assert_eq!(
+            canvas.attributes(),
+            [(String::from("width"), String::from("64"))]
+        );
+    }
+
+    #[test]
+    fn element_specs_follow_layer_identity() {
+        let mut store = LayerStore::new();
+        let canvas_layer = store.create_layer();
+        let plain = store.create_layer();
+
+        let mut specs = ElementSpecs::default();
+        specs.set(canvas_layer, Some(ElementSpec::new("canvas")));
+        assert_eq!(
+            specs.get(canvas_layer).map(ElementSpec::tag),
+            Some("canvas")
+        );
+        assert_eq!(specs.get(plain), None);
+
+        // A destroyed layer's slot is reused without inheriting its spec.
+        store.destroy_layer(canvas_layer);
+        let reused = store.create_layer();
+        assert_eq!(reused.index(), canvas_layer.index());
+        assert_eq!(specs.get(reused), None);
+        specs.prune(&store);
+        assert!(specs.entries.is_empty());
+
+        specs.set(plain, Some(ElementSpec::new("img")));
+        specs.set(plain, None);
+        assert_eq!(specs.get(plain), None);
+    }
+
+    /// Applies a [`reorder_plan`] to a simulated child list.
+    fn simulate_moves(children: &mut Vec<u32>, moves: &[(u32, Option<u32>)]) {
+        for &(idx, before) in moves {
+            children.retain(|&c| c != idx);
+            let at = before.map_or(children.len(), |b| {
+                children.iter().position(|&c| c == b).unwrap()
+            });
+            children.insert(at, idx);
+        }
+    }
+
+    #[test]
+    fn reorder_after_insert_before_moves_one_element() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        let c = store.create_layer();
+        for id in [a, b, c] {
+            store.add_child(root, id);
+        }
+        let _ = store.evaluate();
+        let mut children = store.traversal_order().to_vec();
+
+        // Raise `a` above its siblings.
+        store.remove_from_parent(a);
+        store.add_child(root, a);
+        let changes = store.evaluate();
+        assert!(changes.topology_changed);
+
+        let target = store.traversal_order();
+        let moves = reorder_plan(&children, target);
+        assert_eq!(moves, [(a.index(), None)]);
+        simulate_moves(&mut children, &moves);
+        assert_eq!(children, target);
+
+        // Lower `a` back below `b`.
+        store.remove_from_parent(a);
+        store.insert_before(a, b);
+        let _ = store.evaluate();
+        let target = store.traversal_order();
+        let moves = reorder_plan(&children, target);
+        assert_eq!(moves, [(a.index(), Some(b.index()))]);
+        simulate_moves(&mut children, &moves);
+        assert_eq!(children, target);
+    }
+
+    #[test]
+    fn reorder_plan_handles_reversal() {
+        let mut children = alloc::vec![0, 1, 2, 3, 4];
+        let target = [4, 3, 2, 1, 0];
+        let moves = reorder_plan(&children, &target);
+        assert_eq!(moves.len(), 4);
+        simulate_moves(&mut children, &moves);
+        assert_eq!(children, target);
+        assert!(reorder_plan(&target, &target).is_empty());
+    }
+
+    /// An element with no inline declarations.
+    const NO_DECLARATIONS: [(&str, &str, &str); 0] = [];
+
+    #[test]
+    fn composed_css_text_replaces_only_managed_properties() {
+        let mut style = ManagedStyle::default();
+        style.set("position", "absolute");
+        style.set("opacity", "0.5");
+        style.set("width", "10px");
+        style.set("display", "none");
+        style.remove("display");
+        apply_css_transform(&mut style, &Transform3d::from_translation(3.0, 4.0, 0.0));
+
+        let existing = [
+            ("background-color", "red", ""),
+            ("opacity", "1", ""),
+            ("transform", "none", ""),
+            ("Width", "2px", ""),
+            ("color", "rgb(1, 2, 3)", "important"),
+            (
+                "background-image",
+                "url(\"data:image/png;base64,AAAA\")",
+                "",
+            ),
+        ];
+        assert_eq!(
+            style.compose(existing),
+            "background-color: red; color: rgb(1, 2, 3) !important; \
+             background-image: url(\"data:image/png;base64,AAAA\"); position: absolute; \
+             transform: matrix3d(1,0,0,0,0,1,0,0,0,0,1,0,3,4,0,1); opacity: 0.5; width: 10px;"
+        );
+    }
+
+    #[test]
+    fn full_rebuild_styles_every_live_layer() {
+        use subduction_core::layer::LayerFlags;
+
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let a = store.create_layer();
+        let hidden = store.create_layer();
+        let gone = store.create_layer();
+        store.add_child(root, a);
+        store.add_child(root, hidden);
+        store.add_child(root, gone);
+        store.set_bounds(a, Size::new(10.0, 20.0));
+        store.set_opacity(a, 0.5);
+        store.set_transform(a, Transform3d::from_translation(3.0, 4.0, 0.0));
+        store.set_flags(
+            hidden,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
+        store.destroy_layer(gone);
+        let _ = store.evaluate();
+
+        // `apply_full` creates one element per slot in this order and
+        // styles it with `apply_css_layer`.
+        let styles: Vec<(u32, String)> = store
+            .traversal_order()
+            .iter()
+            .map(|&idx| {
+                let mut style = ManagedStyle::default();
+                apply_css_layer(&mut style, &store, idx);
+                (idx, style.compose(NO_DECLARATIONS))
+            })
+            .collect();
+        let slots: Vec<u32> = styles.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(slots, [root.index(), a.index(), hidden.index()]);
+        assert_eq!(
+            styles[1].1,
+            "position: absolute; left: 0; top: 0; transform-origin: 0 0; \
+             transform: matrix3d(1,0,0,0,0,1,0,0,0,0,1,0,3,4,0,1); opacity: 0.5; \
+             width: 10px; height: 20px;"
+        );
+        assert!(styles[2].1.contains("display: none;"));
+    }
+
+    #[test]
+    fn input_transparent_layer_gets_pointer_events_none() {
+        use subduction_core::layer::LayerFlags;
+
+        fn apply_flags(
+            gate: &mut WriteGate,
+            style: &mut ManagedStyle,
+            store: &LayerStore,
+            transforms: &[u32],
+        ) {
+            for &idx in transforms {
+                if let Some(write) = gate.input_transparency_write(store, idx) {
+                    write(style);
+                }
+            }
+        }
+
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        let _ = store.evaluate();
+        let mut gate = WriteGate::default();
+        let mut style = ManagedStyle::default();
+
+        store.set_flags(
+            id,
+            LayerFlags {
+                input_transparent: true,
+                ..LayerFlags::default()
+            },
+        );
+        let changes = store.evaluate();
+        assert!(changes.transforms.contains(&id.index()));
+        apply_flags(&mut gate, &mut style, &store, &changes.transforms);
+        assert_eq!(style.compose(NO_DECLARATIONS), "pointer-events: none;");
+
+        // A transform change re-reports the slot without rewriting the flag.
+        store.set_transform(id, Transform3d::from_translation(1.0, 0.0, 0.0));
+        let changes = store.evaluate();
+        assert!(changes.transforms.contains(&id.index()));
+        assert!(gate.input_transparency_write(&store, id.index()).is_none());
+
+        store.set_flags(id, LayerFlags::default());
+        let changes = store.evaluate();
+        apply_flags(&mut gate, &mut style, &store, &changes.transforms);
+        assert_eq!(style.compose(NO_DECLARATIONS), "");
+    }
+
+    #[test]
+    fn composed_css_text_clears_removed_clip() {
+        let mut style = ManagedStyle::default();
+        apply_css_clip(
+            &mut style,
+            Some(ClipShape::Rect(kurbo::Rect::new(0.0, 0.0, 8.0, 6.0))),
+        );
+        assert_eq!(
+            style.compose(NO_DECLARATIONS),
+            "width: 8px; height: 6px; overflow: hidden; border-radius: 0;"
+        );
+
+        apply_css_clip(&mut style, None);
+        assert_eq!(
+            style.compose([
+                ("overflow-x", "hidden", ""),
+                ("overflow-y", "hidden", ""),
+                ("width", "8px", ""),
+                ("cursor", "pointer", ""),
+            ]),
+            "cursor: pointer; overflow: visible;"
+        );
+    }
+
+    #[test]
+    fn content_scale_doubles_canvas_pixels_only() {
+        let bounds = Size::new(100.0, 50.0);
+        assert_eq!(device_pixel_size(bounds, 1.0), (100, 50));
+        assert_eq!(device_pixel_size(bounds, 2.0), (200, 100));
+        assert_eq!(device_pixel_size(bounds, 1.5), (150, 75));
+
+        // CSS size and transform stay in logical pixels.
+        let mut style = ManagedStyle::default();
+        apply_css_size(&mut style, bounds);
+        apply_css_transform(&mut style, &Transform3d::from_translation(3.0, 4.0, 0.0));
+        assert_eq!(
+            style.compose(NO_DECLARATIONS),
+            "transform: matrix3d(1,0,0,0,0,1,0,0,0,0,1,0,3,4,0,1); width: 100px; height: 50px;"
+        );
+    }
+
     #[test]
     fn backdrop_none_has_no_css_background() {
         assert_eq!(backdrop_css_value(Backdrop::None), None);
@@ -0,0 +1,430 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! [`LayerSurfacePresenter`]: one host-owned render target per content layer.
+//!
+//! Unlike [`WgpuPresenter`](crate::WgpuPresenter), which composites
+//! presenter-owned textures into a single output, this presenter is for hosts
+//! where each content-bearing layer already has its own presentable surface
+//! (a `<canvas>` per DOM element, a `CAMetalLayer` per `CALayer`). It keeps
+//! each surface configured to its layer's bounds and hands every surface to a
+//! render hook in traversal order.
+
+use std::collections::HashMap;
+
+use subduction_core::backend::Presenter;
+use subduction_core::layer::{FrameChanges, LayerStore};
+use subduction_core::transform::Transform3d;
+
+/// A render target that [`LayerSurfacePresenter`] keeps sized to its layer.
+pub trait LayerSurface {
+    /// Reconfigures the surface for a new size in physical pixels.
+    ///
+    /// Both dimensions are at least 1.
+    fn configure(&mut self, width: u32, height: u32);
+}
+
+/// A [`LayerSurface`] backed by a [`wgpu::Surface`].
+///
+/// Holds a clone of the device and queue so render hooks can encode and
+/// submit work without threading them separately.
+#[derive(Debug)]
+pub struct WgpuLayerSurface {
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+}
+
+impl WgpuLayerSurface {
+    /// Wraps `surface`, configuring it immediately with `config`.
+    ///
+    /// The width and height of `config` are replaced on every
+    /// [`configure`](LayerSurface::configure); the remaining fields are kept.
+    #[must_use]
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        surface: wgpu::Surface<'static>,
+        config: wgpu::SurfaceConfiguration,
+    ) -> Self {
+        surface.configure(device, &config);
+        Self {
+            surface,
+            device: device.clone(),
+            queue: queue.clone(),
+            config,
+        }
+    }
+
+    /// Returns the underlying wgpu surface.
+    #[must_use]
+    pub fn surface(&self) -> &wgpu::Surface<'static> {
+        &self.surface
+    }
+
+    /// Returns the device this surface is configured with.
+    #[must_use]
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    /// Returns the queue associated with [`device`](Self::device).
+    #[must_use]
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+
+    /// Returns the current surface configuration.
+    #[must_use]
+    pub fn config(&self) -> &wgpu::SurfaceConfiguration {
+        &self.config
+    }
+}
+
+impl LayerSurface for WgpuLayerSurface {
+    fn configure(&mut self, width: u32, height: u32) {
+        self.config.width = width;
+        self.config.height = height;
+        self.surface.configure(&self.device, &self.config);
+    }
+}
+
+/// One layer's surface, passed to the [`LayerSurfacePresenter::render`] hook.
+#[derive(Debug)]
+pub struct LayerFrame<'a, S> {
+    /// Raw slot index of the layer.
+    pub slot: u32,
+    /// The layer's surface, already configured to [`size`](Self::size).
+    pub surface: &'a mut S,
+    /// Configured surface size in physical pixels.
+    pub size: (u32, u32),
+    /// The layer's evaluated world transform.
+    pub world_transform: Transform3d,
+    /// The layer's [ungrouped opacity](LayerStore::ungrouped_opacity), to
+    /// apply when drawing its surface.
+    pub opacity: f32,
+}
+
+/// Per-slot surface state.
+#[derive(Debug)]
+struct SurfaceSlot<S> {
+    surface: S,
+    /// Size passed to the last `configure`, or `None` if never configured.
+    configured: Option<(u32, u32)>,
+}
+
+/// Keeps one host-owned [`LayerSurface`] per content-bearing layer sized to
+/// that layer's bounds, and drives rendering in traversal order.
+///
+/// Surfaces are attached by raw slot index with
+/// [`attach`](Self::attach). [`apply`](Presenter::apply) reconfigures any
+/// attached surface whose pixel size (bounds × scale factor, rounded up)
+/// differs from the size it was last configured with, and drops surfaces of
+/// removed layers. [`render`](Self::render) then calls the hook once per
+/// visible attached layer, back to front.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let mut presenter = LayerSurfacePresenter::new().with_scale_factor(dpr);
+/// presenter.attach(
+///     layer.index(),
+///     WgpuLayerSurface::new(&device, &queue, surface, config),
+/// );
+///
+/// let changes = store.evaluate();
+/// presenter.apply(&store, &changes);
+/// presenter.render(&store, |frame| {
+///     draw_layer(frame.surface, frame.world_transform, frame.opacity);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct LayerSurfacePresenter<S> {
+    slots: HashMap<u32, SurfaceSlot<S>>,
+    scale_factor: f64,
+}
+
+impl<S> Default for LayerSurfacePresenter<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> LayerSurfacePresenter<S> {
+    /// Creates a presenter with no attached surfaces and a scale factor of 1.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            slots: HashMap::new(),
+            scale_factor: 1.0,
+        }
+    }
+
+    /// Returns this presenter using the given physical-pixels-per-point
+    /// scale factor.
+    #[must_use]
+    pub fn with_scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Returns the physical-pixels-per-point scale factor.
+    #[must_use]
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Updates the scale factor.
+    ///
+    /// Every attached surface is reconfigured on the next
+    /// [`apply`](Presenter::apply).
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        for slot in self.slots.values_mut() {
+            slot.configured = None;
+        }
+    }
+
+    /// Attaches `surface` to the layer in slot `idx`, returning the surface it
+    /// replaces.
+    ///
+    /// The surface is configured on the next [`apply`](Presenter::apply).
+    pub fn attach(&mut self, idx: u32, surface: S) -> Option<S> {
+        self.slots
+            .insert(
+                idx,
+                SurfaceSlot {
+                    surface,
+                    configured: None,
+                },
+            )
+            .map(|slot| slot.surface)
+    }
+
+    /// Detaches and returns the surface attached to slot `idx`.
+    pub fn detach(&mut self, idx: u32) -> Option<S> {
+        self.slots.remove(&idx).map(|slot| slot.surface)
+    }
+
+    /// Returns the surface attached to slot `idx`.
+    #[must_use]
+    pub fn surface(&self, idx: u32) -> Option<&S> {
+        self.slots.get(&idx).map(|slot| &slot.surface)
+    }
+
+    /// Returns the size slot `idx`'s surface was last configured with.
+    #[must_use]
+    pub fn configured_size(&self, idx: u32) -> Option<(u32, u32)> {
+        self.slots.get(&idx).and_then(|slot| slot.configured)
+    }
+
+    /// Returns the number of attached surfaces.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns whether no surfaces are attached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Calls `f` for every attached, visible layer in traversal order
+    /// (back to front).
+    ///
+    /// Surfaces that have not been configured yet are skipped; call
+    /// [`apply`](Presenter::apply) first.
+    pub fn render(&mut self, store: &LayerStore, mut f: impl FnMut(LayerFrame<'_, S>)) {
+        for &idx in store.traversal_order() {
+            let Some(slot) = self.slots.get_mut(&idx) else {
+                continue;
+            };
+            let Some(size) = slot.configured else {
+                continue;
+            };
+            if store.effective_hidden_at(idx) {
+                continue;
+            }
+            f(LayerFrame {
+                slot: idx,
+                surface: &mut slot.surface,
+                size,
+                world_transform: store.world_transform_at(idx),
+                opacity: store.ungrouped_opacity_at(idx),
+            });
+        }
+    }
+}
+
+impl<S: LayerSurface> Presenter for LayerSurfacePresenter<S> {
+    fn apply(&mut self, store: &LayerStore, changes: &FrameChanges) {
+        if changes.full_rebuild {
+            for slot in self.slots.values_mut() {
+                slot.configured = None;
+            }
+        }
+        for &idx in &changes.removed {
+            self.slots.remove(&idx);
+        }
+
+        let scale_factor = self.scale_factor;
+        for (&idx, slot) in &mut self.slots {
+            let bounds = store.bounds_at(idx);
+            let size = (
+                to_pixels(bounds.width * scale_factor),
+                to_pixels(bounds.height * scale_factor),
+            );
+            if slot.configured != Some(size) {
+                slot.surface.configure(size.0, size.1);
+                slot.configured = Some(size);
+            }
+        }
+    }
+}
+
+/// Rounds a physical length up to whole pixels, clamped to `1..=u32::MAX`.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "value is clamped to the u32 range first"
+)]
+fn to_pixels(length: f64) -> u32 {
+    length.ceil().clamp(1.0, f64::from(u32::MAX)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Size;
+    use subduction_core::layer::LayerFlags;
+
+    /// Records every `configure` call.
+    #[derive(Debug, Default)]
+    struct MockSurface {
+        configures: Vec<(u32, u32)>,
+    }
+
+    impl LayerSurface for MockSurface {
+        fn configure(&mut self, width: u32, height: u32) {
+            self.configures.push((width, height));
+        }
+    }
+
+    fn configures(presenter: &LayerSurfacePresenter<MockSurface>, idx: u32) -> &[(u32, u32)] {
+        &presenter.surface(idx).unwrap().configures
+    }
+
+    #[test]
+    fn reconfigures_only_on_resize() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let layer = store.create_layer();
+        store.add_child(root, layer);
+        store.set_bounds(layer, Size::new(100.0, 50.5));
+
+        let mut presenter = LayerSurfacePresenter::new().with_scale_factor(2.0);
+        presenter.attach(layer.index(), MockSurface::default());
+
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+        assert_eq!(configures(&presenter, layer.index()), [(200, 101)]);
+
+        // Unrelated change: no reconfigure.
+        store.set_opacity(layer, 0.5);
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+        assert_eq!(configures(&presenter, layer.index()), [(200, 101)]);
+
+        store.set_bounds(layer, Size::new(30.0, 40.0));
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+        assert_eq!(
+            configures(&presenter, layer.index()),
+            [(200, 101), (60, 80)]
+        );
+        assert_eq!(presenter.configured_size(layer.index()), Some((60, 80)));
+
+        presenter.set_scale_factor(1.0);
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+        assert_eq!(
+            configures(&presenter, layer.index()),
+            [(200, 101), (60, 80), (30, 40)]
+        );
+    }
+
+    #[test]
+    fn empty_bounds_configure_one_pixel() {
+        let mut store = LayerStore::new();
+        let layer = store.create_layer();
+        let mut presenter = LayerSurfacePresenter::new();
+        presenter.attach(layer.index(), MockSurface::default());
+
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+        assert_eq!(configures(&presenter, layer.index()), [(1, 1)]);
+    }
+
+    #[test]
+    fn removed_layers_drop_surfaces() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let layer = store.create_layer();
+        store.add_child(root, layer);
+        let mut presenter = LayerSurfacePresenter::new();
+        presenter.attach(layer.index(), MockSurface::default());
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+
+        store.destroy_layer(layer);
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+        assert!(presenter.is_empty());
+    }
+
+    #[test]
+    fn render_visits_visible_surfaces_in_traversal_order() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let back = store.create_layer();
+        let hidden = store.create_layer();
+        let front = store.create_layer();
+        store.add_child(root, back);
+        store.add_child(root, hidden);
+        store.add_child(root, front);
+        store.set_opacity(root, 0.5);
+        store.set_transform(front, Transform3d::from_translation(5.0, 0.0, 0.0));
+        store.set_flags(
+            hidden,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
+
+        let mut presenter = LayerSurfacePresenter::new();
+        // Attach out of order; rendering follows the tree, not attachment.
+        for id in [front, hidden, back] {
+            presenter.attach(id.index(), MockSurface::default());
+        }
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+
+        let mut seen = Vec::new();
+        presenter.render(&store, |frame| {
+            seen.push((frame.slot, frame.opacity, frame.world_transform));
+        });
+        assert_eq!(
+            seen,
+            [
+                (back.index(), 0.5, Transform3d::IDENTITY),
+                (
+                    front.index(),
+                    0.5,
+                    Transform3d::from_translation(5.0, 0.0, 0.0)
+                ),
+            ]
+        );
+    }
+}
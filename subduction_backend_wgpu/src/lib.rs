@@ -14,11 +14,18 @@
 //! [`LayerRoot`] describes the final compositing target, while
 //! [`WgpuPresenter`] owns per-surface textures and composites into that root.
 //!
+//! For hosts that give each content layer its own presentable surface (a
+//! canvas per element, a `CAMetalLayer` per layer),
+//! [`LayerSurfacePresenter`] keeps those surfaces sized to their layers and
+//! drives rendering in traversal order instead of compositing.
+//!
 //! [`SurfaceId`]: subduction_core::layer::SurfaceId
 
+mod layer_surface;
 mod pipeline;
 mod presenter;
 mod shader;
 
+pub use layer_surface::{LayerFrame, LayerSurface, LayerSurfacePresenter, WgpuLayerSurface};
 pub use presenter::{LayerRoot, WgpuPresenter, WgpuPresenterConfig, WgpuSurfaceTarget};
 pub use subduction_core::backend::Presenter;
@@ -6,7 +6,8 @@
 use std::collections::HashMap;
 
 use color::LinearSrgb;
-use subduction_core::backend::Presenter;
+use kurbo::Size;
+use subduction_core::backend::{Presenter, SurfaceResolver};
 use subduction_core::layer::{ClipShape, FrameChanges, LayerStore, SurfaceId};
 use subduction_core::output::{Backdrop, Color};
 use subduction_core::transform::Transform3d;
@@ -21,7 +22,7 @@ const REQUIRED_SURFACE_USAGE: wgpu::TextureUsages = wgpu::TextureUsages::TEXTURE
 /// Per-layer uniform data uploaded to the GPU.
 ///
 /// `transform` is the pre-multiplied `ortho * world_transform * scale_to_layer_size` matrix.
-/// `opacity` is the effective opacity. Padding fills to 16-byte alignment.
+/// `opacity` is the ungrouped opacity. Padding fills to 16-byte alignment.
 #[repr(C)]
 #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct LayerUniforms {
@@ -511,7 +512,7 @@ impl WgpuPresenter {
             };
             let scale = Transform3d::from_scale(sw, sh, 1.0);
             let combined = ortho * world * scale;
-            let opacity = store.effective_opacity_at(idx);
+            let opacity = store.ungrouped_opacity_at(idx);
 
             let uniforms = LayerUniforms {
                 transform: transform_to_f32(&combined),
@@ -663,20 +664,18 @@ impl WgpuPresenter {
         }
     }
 
-    fn surface_size_for_slot(&self, store: &LayerStore, slot: u32) -> (u32, u32) {
-        let bounds = store.bounds_at(slot);
-        if bounds.width > 0.0 && bounds.height > 0.0 {
-            #[expect(
-                clippy::cast_possible_truncation,
-                clippy::cast_sign_loss,
-                reason = "bounds are non-negative pixel dimensions that fit in u32"
-            )]
-            {
-                (bounds.width as u32, bounds.height as u32)
-            }
-        } else {
-            self.default_surface_size
-        }
+    fn surface_size_for_slot(
+        &self,
+        store: &LayerStore,
+        slot: u32,
+        surface_id: SurfaceId,
+        resolved: impl Fn(SurfaceId) -> Option<(u32, u32)>,
+    ) -> (u32, u32) {
+        surface_texture_size(
+            resolved(surface_id),
+            store.bounds_at(slot),
+            self.default_surface_size,
+        )
     }
 
     fn ensure_surface_entry(&mut self, surface_id: SurfaceId, size: (u32, u32)) {
@@ -703,8 +702,29 @@ impl WgpuPresenter {
     }
 }
 
-impl Presenter for WgpuPresenter {
-    fn apply(&mut self, store: &LayerStore, changes: &FrameChanges) {
+impl WgpuPresenter {
+    /// Applies `changes` like [`apply`](Presenter::apply), sizing surface
+    /// textures from `resolver`.
+    ///
+    /// A surface the resolver knows gets a texture of its reported pixel
+    /// size instead of one sized from the layer bounds, so host content is
+    /// rendered 1:1 and scaled to the layer during composition. Surfaces the
+    /// resolver does not know fall back to the layer bounds.
+    pub fn apply_with_resolver<R: SurfaceResolver + ?Sized>(
+        &mut self,
+        store: &LayerStore,
+        changes: &FrameChanges,
+        resolver: &R,
+    ) {
+        self.apply_sized(store, changes, |surface| resolver.size(surface));
+    }
+
+    fn apply_sized(
+        &mut self,
+        store: &LayerStore,
+        changes: &FrameChanges,
+        resolved: impl Fn(SurfaceId) -> Option<(u32, u32)>,
+    ) {
         // Removals detach slots. Surface resources are released explicitly.
         for &idx in &changes.removed {
             self.attachments.detach_slot(idx);
@@ -715,7 +735,7 @@ impl Presenter for WgpuPresenter {
             self.attachments.detach_slot(idx);
             if let Some(surface_id) = store.content_at(idx) {
                 self.attachments.attach(surface_id, idx);
-                let size = self.surface_size_for_slot(store, idx);
+                let size = self.surface_size_for_slot(store, idx, surface_id, &resolved);
                 self.ensure_surface_entry(surface_id, size);
             }
         }
@@ -723,7 +743,7 @@ impl Presenter for WgpuPresenter {
         // Bounds changes resize the attached surface texture, if any.
         for &idx in &changes.bounds {
             if let Some(surface_id) = self.attachments.surface_for_slot(idx) {
-                let size = self.surface_size_for_slot(store, idx);
+                let size = self.surface_size_for_slot(store, idx, surface_id, &resolved);
                 self.resize_surface_entry(surface_id, size);
             }
         }
@@ -733,6 +753,39 @@ impl Presenter for WgpuPresenter {
     }
 }
 
+impl Presenter for WgpuPresenter {
+    fn apply(&mut self, store: &LayerStore, changes: &FrameChanges) {
+        self.apply_sized(store, changes, |_| None);
+    }
+}
+
+/// Returns the texture size for a surface: its `resolved` pixel size, else
+/// the layer `bounds`, else `default`.
+fn surface_texture_size(
+    resolved: Option<(u32, u32)>,
+    bounds: Size,
+    default: (u32, u32),
+) -> (u32, u32) {
+    if let Some((width, height)) = resolved
+        && width > 0
+        && height > 0
+    {
+        return (width, height);
+    }
+    if bounds.width > 0.0 && bounds.height > 0.0 {
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "bounds are non-negative pixel dimensions that fit in u32"
+        )]
+        {
+            (bounds.width as u32, bounds.height as u32)
+        }
+    } else {
+        default
+    }
+}
+
 /// Returns the dynamic uniform buffer stride (aligned to 256 bytes).
 fn uniform_stride() -> u64 {
     let raw = size_of::<LayerUniforms>() as u64;
@@ -828,10 +881,7 @@ fn clip_to_scissor(
         "clip_to_scissor does not support perspective transforms"
     );
 
-    let rect = match clip {
-        ClipShape::Rect(r) => r,
-        ClipShape::RoundedRect(rr) => rr.rect(),
-    };
+    let rect = clip.bounding_box();
 
     // Transform the four corners and take the AABB.
     let corners = [
@@ -932,6 +982,20 @@ mod tests {
         assert!(attachments.is_attached_to_slot(surface, 20));
     }
 
+    #[test]
+    fn resolved_surface_size_wins_over_bounds() {
+        let bounds = Size::new(100.0, 50.0);
+        assert_eq!(
+            surface_texture_size(Some((640, 360)), bounds, (256, 256)),
+            (640, 360)
+        );
+        assert_eq!(surface_texture_size(None, bounds, (256, 256)), (100, 50));
+        assert_eq!(
+            surface_texture_size(Some((0, 360)), Size::ZERO, (256, 256)),
+            (256, 256)
+        );
+    }
+
     #[test]
     fn layer_root_mutates_in_place() {
         let mut root = LayerRoot::new(wgpu::TextureFormat::Bgra8Unorm, (640, 480));
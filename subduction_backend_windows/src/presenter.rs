@@ -330,6 +330,23 @@ fn apply_clip(
                 radii.bottom_left as f32,
             )
         }
+        ClipShape::Ellipse(_) => {
+            // DComp rounded clips take one radius per corner, so this is exact
+            // for circles and approximates other ellipses as a capsule.
+            let r = clip.bounding_box();
+            let radius = (r.width().min(r.height()) / 2.0) as f32;
+            composition.set_rounded_clip(
+                layer_id,
+                r.x0 as f32,
+                r.y0 as f32,
+                r.x1 as f32,
+                r.y1 as f32,
+                radius,
+                radius,
+                radius,
+                radius,
+            )
+        }
     }
 }
 
@@ -342,6 +359,25 @@ impl Presenter for DCompPresenter {
             return;
         }
 
+        // ── Full rebuild: every live layer is re-reported as added ──
+        if changes.full_rebuild {
+            for idx in 0..self.layer_map.len() {
+                let Some(layer_id) = self.layer_map[idx].take() else {
+                    continue;
+                };
+                // Nested visuals leave the tree with their top-level
+                // ancestor, so only top-level ones are detached.
+                let top_level = self.layer_parents[idx].flatten().is_none();
+                let r = self.composition.destroy_layer(layer_id, None, top_level);
+                if !self.check(r) {
+                    return;
+                }
+            }
+            self.layer_parents.fill(None);
+            self.surface_to_slot.clear();
+            self.slot_to_surface.clear();
+        }
+
         // ── Structural: added layers ────────────────────────────────
         for &idx in &changes.added {
             self.ensure_slot(idx);
@@ -420,7 +456,7 @@ impl Presenter for DCompPresenter {
                 return;
             }
             if let Some(layer_id) = self.mapped_id(idx) {
-                let t = store.local_transform_at(idx);
+                let t = store.anchored_transform_at(idx);
                 let cols = t.to_cols_array_2d();
 
                 #[expect(
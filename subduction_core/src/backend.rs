@@ -10,6 +10,9 @@
 //!   frame changes to a platform-native tree, such as `CALayer`, DOM elements,
 //!   DirectComposition visuals, or Wayland subsurfaces.
 //!
+//! Hosts describe their surfaces with a [`SurfaceResolver`], which maps the
+//! [`SurfaceId`] tokens attached to layers to pixel sizes and native handles.
+//!
 //! Frame timing is owned by `frameclock` and adapter crates such as
 //! `frameclock_apple` and `frameclock_web`. Those crates produce
 //! `frameclock::FrameTick` values, compute present hints, expose host-time
@@ -24,7 +27,9 @@
 //! presenter backend, and one timing adapter, then wires them together in a
 //! frame loop.
 
-use crate::layer::{FrameChanges, LayerStore};
+use frameclock::FrameTick;
+
+use crate::layer::{FrameChanges, LayerStore, SurfaceId};
 
 /// Applies evaluated frame changes to a platform-native presentation tree.
 ///
@@ -50,15 +55,197 @@ use crate::layer::{FrameChanges, LayerStore};
 ///     // Evaluate: drain dirty channels, recompute world properties
 ///     let changes = store.evaluate();
 ///
-///     // Present: apply incremental changes to the native tree
-///     presenter.apply(&store, &changes);
+///     // Present: apply incremental changes to the native tree, bracketed
+///     // by the presenter's frame hooks.
+///     presenter.present_frame(&frame.tick(), &store, &changes);
 ///
 ///     // Feedback: report submission through the timing adapter.
 ///     frame_clock.submit_frame(frame, FrameSubmission::new(now(), actual));
 /// }
 /// ```
+///
+/// # Frame hooks
+///
+/// [`begin_frame`](Self::begin_frame) and [`end_frame`](Self::end_frame)
+/// bracket the work for one display frame, so a backend can scope a native
+/// transaction, accumulate damage, or batch surface commits across every
+/// `apply` in between. Both default to no-ops. Presenters must still accept
+/// a bare `apply` outside a bracket, as used for the initial scene.
+/// [`present_frame`](Self::present_frame) calls all three in order.
 pub trait Presenter {
     /// Applies the given [`FrameChanges`] to the backing presentation tree,
     /// reading current property values from `store` as needed.
     fn apply(&mut self, store: &LayerStore, changes: &FrameChanges);
+
+    /// Starts the frame described by `tick`.
+    ///
+    /// Called before the frame's [`apply`](Self::apply) calls. The default
+    /// does nothing.
+    fn begin_frame(&mut self, tick: &FrameTick) {
+        _ = tick;
+    }
+
+    /// Finishes the frame opened by [`begin_frame`](Self::begin_frame).
+    ///
+    /// The default does nothing.
+    fn end_frame(&mut self) {}
+
+    /// Rebuilds all native state from the current contents of `store`.
+    ///
+    /// Use this after the presentation target was replaced or lost (a new
+    /// GPU device, a fresh DOM container) instead of an incremental
+    /// [`apply`](Self::apply). Unlike
+    /// [`LayerStore::mark_all_dirty`], it leaves the store's pending changes
+    /// untouched, so the next [`evaluate`](LayerStore::evaluate) still
+    /// reports them.
+    ///
+    /// The default applies [`FrameChanges::full`], which sets
+    /// [`full_rebuild`](FrameChanges::full_rebuild); presenters relying on it
+    /// must discard their per-layer native state when that flag is set.
+    /// `store` must have been evaluated since its last mutation.
+    fn apply_full(&mut self, store: &LayerStore) {
+        self.apply(store, &FrameChanges::full(store));
+    }
+
+    /// Presents one frame: [`begin_frame`](Self::begin_frame), then
+    /// [`apply`](Self::apply), then [`end_frame`](Self::end_frame).
+    fn present_frame(&mut self, tick: &FrameTick, store: &LayerStore, changes: &FrameChanges) {
+        self.begin_frame(tick);
+        self.apply(store, changes);
+        self.end_frame();
+    }
+}
+
+/// Resolves [`SurfaceId`] tokens to host-owned surface facts.
+///
+/// [`LayerStore`] stores only surface tokens, so neither evaluation nor
+/// presenters know how large a surface is or which native object backs it.
+/// Hosts implement this over their surface registry (a decoded-image cache, a
+/// video-frame pool) and pass it to [`LayerStore::size_from_surfaces`] and to
+/// presenters that accept one, instead of hard-coding layer sizes.
+pub trait SurfaceResolver {
+    /// Native handle type presenters attach as layer content, such as an
+    /// `IOSurface` reference or a texture.
+    ///
+    /// Hosts that only provide sizes can use `()`.
+    type Handle;
+
+    /// Returns the pixel size of `surface` as `(width, height)`, or `None` if
+    /// the surface is unknown.
+    fn size(&self, surface: SurfaceId) -> Option<(u32, u32)>;
+
+    /// Returns the native handle for `surface`, if there is one.
+    ///
+    /// The default returns `None`, for hosts that only provide sizes.
+    fn native_handle(&self, surface: SurfaceId) -> Option<Self::Handle> {
+        _ = surface;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Call {
+        Begin(u64),
+        Apply(usize),
+        End,
+    }
+
+    #[derive(Default)]
+    struct MockPresenter {
+        calls: Vec<Call>,
+    }
+
+    impl Presenter for MockPresenter {
+        fn apply(&mut self, _: &LayerStore, changes: &FrameChanges) {
+            self.calls.push(Call::Apply(changes.added.len()));
+        }
+
+        fn begin_frame(&mut self, tick: &FrameTick) {
+            self.calls.push(Call::Begin(tick.frame_index));
+        }
+
+        fn end_frame(&mut self) {
+            self.calls.push(Call::End);
+        }
+    }
+
+    #[test]
+    fn apply_full_touches_every_live_layer() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        let gone = store.create_layer();
+        store.add_child(root, a);
+        store.add_child(a, b);
+        store.destroy_layer(gone);
+        let _ = store.evaluate();
+
+        #[derive(Default)]
+        struct Rebuild {
+            changes: Option<FrameChanges>,
+        }
+        impl Presenter for Rebuild {
+            fn apply(&mut self, _: &LayerStore, changes: &FrameChanges) {
+                self.changes = Some(changes.clone());
+            }
+        }
+
+        let mut presenter = Rebuild::default();
+        presenter.apply_full(&store);
+        let changes = presenter.changes.take().unwrap();
+        let live = [root.index(), a.index(), b.index()];
+        assert!(changes.full_rebuild && changes.topology_changed);
+        assert_eq!(changes.added, live);
+        for list in [
+            &changes.transforms,
+            &changes.opacities,
+            &changes.clips,
+            &changes.content,
+            &changes.bounds,
+            &changes.blend_modes,
+        ] {
+            assert_eq!(list, &live);
+        }
+        assert!(changes.removed.is_empty());
+
+        // The store's own pending state is untouched.
+        store.set_opacity(b, 0.5);
+        presenter.apply_full(&store);
+        assert_eq!(store.evaluate().opacities, [b.index()]);
+    }
+
+    #[test]
+    fn present_frame_brackets_apply_once_per_frame() {
+        let mut store = LayerStore::new();
+        let _ = store.create_layer();
+        let mut presenter = MockPresenter::default();
+
+        for frame_index in 0..3 {
+            let tick = FrameTick::builder().frame_index(frame_index).build();
+            let changes = store.evaluate();
+            presenter.present_frame(&tick, &store, &changes);
+        }
+
+        assert_eq!(
+            presenter.calls,
+            [
+                Call::Begin(0),
+                Call::Apply(1),
+                Call::End,
+                Call::Begin(1),
+                Call::Apply(0),
+                Call::End,
+                Call::Begin(2),
+                Call::Apply(0),
+                Call::End,
+            ]
+        );
+    }
 }
@@ -11,19 +11,19 @@
 //!
 //! Channels differ in whether dirtiness propagates to descendants:
 //!
-//! - **Propagating** — [`TRANSFORM`] and [`OPACITY`] use
+//! - **Propagating** — [`TRANSFORM`], [`OPACITY`], and [`CLIP`] use
 //!   [`EagerPolicy`](invalidation::EagerPolicy) and have dependency
 //!   edges from child to parent. Marking a parent dirty automatically marks
-//!   all descendants, because world transforms, effective opacities, and
-//!   effective hidden state are inherited properties. (Hidden-flag changes
-//!   are routed through [`TRANSFORM`] so that the same drain pass
-//!   recomputes both world transforms and `effective_hidden`.)
+//!   all descendants, because world transforms, effective opacities,
+//!   effective clips, and effective hidden state are inherited properties.
+//!   (Hidden-flag changes are routed through [`TRANSFORM`] so that the same
+//!   drain pass recomputes both world transforms and `effective_hidden`.)
 //!
-//! - **Local-only** — [`CLIP`], [`CONTENT`], and [`BOUNDS`] are marked with
+//! - **Local-only** — [`CONTENT`], [`BOUNDS`], and [`BLEND`] are marked with
 //!   [`InvalidationTracker::mark`](invalidation::InvalidationTracker::mark).
-//!   Only the explicitly marked layer appears in the drain output, since clip
-//!   shapes, surface content, and bounds are per-layer properties. `mark` does
-//!   not follow graph dependents or cross-channel edges.
+//!   Only the explicitly marked layer appears in the drain output, since
+//!   surface content, bounds, and blend modes are per-layer properties.
+//!   `mark` does not follow graph dependents or cross-channel edges.
 //!
 //! - **Structural** — [`TOPOLOGY`] is marked on topology mutations
 //!   (add/remove child, create/destroy layer). It triggers a traversal-order
@@ -36,6 +36,8 @@
 //! all channels and surfaces the results as
 //! [`FrameChanges`](crate::layer::FrameChanges), which backends
 //! [consume](crate::backend::Presenter::apply) to apply incremental updates.
+//! To ask whether the next evaluation would report anything without draining,
+//! use [`LayerStore::has_pending_changes`](crate::layer::LayerStore::has_pending_changes).
 
 use invalidation::Channel;
 
@@ -46,7 +48,7 @@ pub const TRANSFORM: Channel = Channel::new(0);
 /// Opacity changed — requires effective opacity recomputation for descendants.
 pub const OPACITY: Channel = Channel::new(1);
 
-/// Clip shape changed — no propagation needed.
+/// Clip shape changed — requires effective clip recomputation for descendants.
 pub const CLIP: Channel = Channel::new(2);
 
 /// Surface content changed — no propagation needed.
@@ -57,3 +59,6 @@ pub const TOPOLOGY: Channel = Channel::new(4);
 
 /// Layer bounds changed — no propagation needed.
 pub const BOUNDS: Channel = Channel::new(5);
+
+/// Layer blend mode changed — no propagation needed.
+pub const BLEND: Channel = Channel::new(6);
@@ -0,0 +1,17 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Blend modes for layer compositing.
+
+/// Blend mode for compositing a layer over the content behind it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendMode {
+    /// Standard source-over alpha compositing.
+    #[default]
+    SourceOver,
+    /// Multiply blend.
+    Multiply,
+    /// Screen blend.
+    Screen,
+}
@@ -5,11 +5,14 @@
 
 /// A shape used to clip a layer's content and descendants.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClipShape {
     /// An axis-aligned rectangle.
     Rect(kurbo::Rect),
     /// A rectangle with rounded corners.
     RoundedRect(kurbo::RoundedRect),
+    /// An ellipse, which may be rotated.
+    Ellipse(kurbo::Ellipse),
 }
 
 impl ClipShape {
@@ -22,14 +25,49 @@ impl ClipShape {
                 use kurbo::Shape;
                 rr.contains(point)
             }
+            Self::Ellipse(e) => {
+                use kurbo::Shape;
+                e.contains(point)
+            }
         }
     }
+
+    /// Returns the axis-aligned bounding box of this clip shape in layer-local
+    /// coordinates.
+    ///
+    /// Presenters without native support for a shape can fall back to this
+    /// rectangle.
+    #[must_use]
+    pub fn bounding_box(&self) -> kurbo::Rect {
+        match self {
+            Self::Rect(r) => *r,
+            Self::RoundedRect(rr) => rr.rect(),
+            Self::Ellipse(e) => {
+                use kurbo::Shape;
+                e.bounding_box()
+            }
+        }
+    }
+
+    /// Returns the intersection of two clip shapes in the same coordinate
+    /// space, or `None` when nothing is left (the content is fully clipped).
+    ///
+    /// Two rectangles intersect exactly. Any intersection involving a rounded
+    /// rectangle or an ellipse is approximated by the intersection of the
+    /// shapes' [bounding boxes](Self::bounding_box), which is conservative: it
+    /// may keep area that the exact shape would clip, but never removes area
+    /// the exact shape keeps. Rectangles that only touch count as empty.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let rect = self.bounding_box().intersect(other.bounding_box());
+        (!rect.is_zero_area()).then_some(Self::Rect(rect))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use kurbo::{Point, Rect, RoundedRect};
+    use kurbo::{Ellipse, Point, Rect, RoundedRect};
 
     #[test]
     fn rect_contains_inside() {
@@ -71,4 +109,63 @@ mod tests {
         ));
         assert!(!clip.contains(Point::new(-5.0, 50.0)));
     }
+
+    #[test]
+    fn ellipse_contains_center_and_rejects_corner() {
+        let clip = ClipShape::Ellipse(Ellipse::from_rect(Rect::new(0.0, 0.0, 100.0, 50.0)));
+        assert!(clip.contains(Point::new(50.0, 25.0)));
+        assert!(clip.contains(Point::new(95.0, 25.0)));
+        // Inside the bounding rect but outside the ellipse.
+        assert!(!clip.contains(Point::new(5.0, 5.0)));
+    }
+
+    #[test]
+    fn intersect_overlapping_rects() {
+        let a = ClipShape::Rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let b = ClipShape::Rect(Rect::new(50.0, 25.0, 150.0, 75.0));
+        let expected = Some(ClipShape::Rect(Rect::new(50.0, 25.0, 100.0, 75.0)));
+        assert_eq!(a.intersect(&b), expected);
+        assert_eq!(b.intersect(&a), expected);
+    }
+
+    #[test]
+    fn intersect_disjoint_rects_is_empty() {
+        let a = ClipShape::Rect(Rect::new(0.0, 0.0, 10.0, 10.0));
+        let b = ClipShape::Rect(Rect::new(20.0, 0.0, 30.0, 10.0));
+        assert_eq!(a.intersect(&b), None);
+        // Shared edges leave no area either.
+        let touching = ClipShape::Rect(Rect::new(10.0, 0.0, 20.0, 10.0));
+        assert_eq!(a.intersect(&touching), None);
+    }
+
+    #[test]
+    fn intersect_rounded_rect_uses_bounding_box() {
+        let rect = ClipShape::Rect(Rect::new(0.0, 0.0, 60.0, 60.0));
+        let rounded = ClipShape::RoundedRect(RoundedRect::from_rect(
+            Rect::new(40.0, 40.0, 100.0, 100.0),
+            10.0,
+        ));
+        assert_eq!(
+            rect.intersect(&rounded),
+            Some(ClipShape::Rect(Rect::new(40.0, 40.0, 60.0, 60.0)))
+        );
+    }
+
+    #[test]
+    fn bounding_box_covers_each_variant() {
+        let rect = Rect::new(10.0, 20.0, 110.0, 70.0);
+        assert_eq!(ClipShape::Rect(rect).bounding_box(), rect);
+        assert_eq!(
+            ClipShape::RoundedRect(RoundedRect::from_rect(rect, 8.0)).bounding_box(),
+            rect
+        );
+        let ellipse = ClipShape::Ellipse(Ellipse::from_rect(rect)).bounding_box();
+        assert!(
+            (ellipse.x0 - rect.x0).abs() < 1e-9
+                && (ellipse.y0 - rect.y0).abs() < 1e-9
+                && (ellipse.x1 - rect.x1).abs() < 1e-9
+                && (ellipse.y1 - rect.y1).abs() < 1e-9,
+            "ellipse bounding box should match its source rect, got {ellipse:?}"
+        );
+    }
 }
@@ -0,0 +1,189 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Viewport culling.
+//!
+//! Large scenes often contain many layers that lie entirely outside the
+//! visible area. [`LayerStore::visible_in`] reports only the layers whose
+//! evaluated world bounds, after clipping, overlap a viewport, so presenters
+//! can skip work for everything else.
+
+use alloc::vec::Vec;
+
+use kurbo::Rect;
+
+use super::id::INVALID;
+use super::store::LayerStore;
+
+impl LayerStore {
+    /// Returns the slot indices of layers that overlap `viewport` (in world
+    /// coordinates), in back-to-front traversal order.
+    ///
+    /// A layer is reported when it is not effectively hidden and its world
+    /// bounds, intersected with its effective clip, overlap `viewport` with
+    /// non-zero area. Layers without bounds are never reported. When a
+    /// layer's effective clip misses the viewport, its whole subtree is
+    /// skipped, since descendants are clipped at least as tightly.
+    ///
+    /// Only valid after [`evaluate`](Self::evaluate) has been called.
+    #[must_use]
+    pub fn visible_in(&self, viewport: Rect) -> Vec<u32> {
+        let mut out = Vec::new();
+        self.visible_in_into(viewport, &mut out);
+        out
+    }
+
+    /// Like [`visible_in`](Self::visible_in), but reuses a caller-provided
+    /// buffer to avoid allocation on repeated queries.
+    pub fn visible_in_into(&self, viewport: Rect, out: &mut Vec<u32>) {
+        out.clear();
+
+        let mut i = 0;
+        while i < self.traversal_order.len() {
+            let idx = self.traversal_order[i];
+            let slot = idx as usize;
+            i += 1;
+
+            if self.effective_hidden[slot] {
+                continue;
+            }
+            let area = match self.effective_clip[slot] {
+                Some(clip) => {
+                    let area = clip.intersect(viewport);
+                    if !has_area(area) {
+                        i = self.skip_subtree(i, idx);
+                        continue;
+                    }
+                    area
+                }
+                None => viewport,
+            };
+            if let Some(bounds) = self.world_bounds[slot]
+                && has_area(bounds.intersect(area))
+            {
+                out.push(idx);
+            }
+        }
+    }
+
+    /// Advances `pos` in the traversal order past every descendant of `idx`.
+    ///
+    /// `pos` must be the position just after `idx`.
+    fn skip_subtree(&self, mut pos: usize, idx: u32) -> usize {
+        while let Some(&next) = self.traversal_order.get(pos) {
+            if !self.is_descendant_slot(next, idx) {
+                break;
+            }
+            pos += 1;
+        }
+        pos
+    }
+
+    /// Returns whether slot `idx` lies strictly below slot `ancestor`.
+    fn is_descendant_slot(&self, idx: u32, ancestor: u32) -> bool {
+        let mut cur = self.parent[idx as usize];
+        while cur != INVALID {
+            if cur == ancestor {
+                return true;
+            }
+            cur = self.parent[cur as usize];
+        }
+        false
+    }
+}
+
+/// Returns whether `rect` has positive width and height.
+fn has_area(rect: Rect) -> bool {
+    rect.width() > 0.0 && rect.height() > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use kurbo::Size;
+
+    use super::*;
+    use crate::layer::{ClipShape, LayerFlags, LayerId};
+    use crate::transform::Transform3d;
+
+    const VIEWPORT: Rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+    fn place(store: &mut LayerStore, parent: LayerId, x: f64, y: f64) -> LayerId {
+        let id = store.create_layer();
+        store.add_child(parent, id);
+        store.set_bounds(id, Size::new(20.0, 20.0));
+        store.set_transform(id, Transform3d::from_translation(x, y, 0.0));
+        id
+    }
+
+    #[test]
+    fn reports_only_intersecting_layers() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let inside = place(&mut store, root, 10.0, 10.0);
+        let outside = place(&mut store, root, 200.0, 10.0);
+        let partial = place(&mut store, root, 90.0, 90.0);
+        let touching = place(&mut store, root, 100.0, 0.0);
+        let _ = store.evaluate();
+
+        let visible = store.visible_in(VIEWPORT);
+        assert_eq!(visible, [inside.index(), partial.index()]);
+        assert!(!visible.contains(&outside.index()));
+        assert!(!visible.contains(&touching.index()));
+    }
+
+    #[test]
+    fn skips_hidden_layers() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let group = place(&mut store, root, 0.0, 0.0);
+        let child = place(&mut store, group, 5.0, 5.0);
+        store.set_flags(
+            group,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
+        let _ = store.evaluate();
+
+        let visible = store.visible_in(VIEWPORT);
+        assert!(!visible.contains(&group.index()));
+        assert!(!visible.contains(&child.index()));
+    }
+
+    #[test]
+    fn skips_subtrees_clipped_out_of_viewport() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let group = store.create_layer();
+        store.add_child(root, group);
+        store.set_clip(
+            group,
+            Some(ClipShape::Rect(Rect::new(200.0, 200.0, 300.0, 300.0))),
+        );
+        // Inside the viewport, but outside the ancestor's clip.
+        let child = place(&mut store, group, 10.0, 10.0);
+        let sibling = place(&mut store, root, 10.0, 10.0);
+        let _ = store.evaluate();
+
+        assert_eq!(store.visible_in(VIEWPORT), [sibling.index()]);
+        assert!(store.world_bounds_at(child.index()).is_some());
+    }
+
+    #[test]
+    fn clip_trims_partial_overlap() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        store.set_clip(root, Some(ClipShape::Rect(Rect::new(0.0, 0.0, 50.0, 50.0))));
+        let cut = place(&mut store, root, 60.0, 10.0);
+        let kept = place(&mut store, root, 40.0, 40.0);
+        let _ = store.evaluate();
+
+        let mut out = vec![u32::MAX];
+        store.visible_in_into(VIEWPORT, &mut out);
+        assert_eq!(out, [kept.index()]);
+        assert!(!out.contains(&cut.index()));
+    }
+}
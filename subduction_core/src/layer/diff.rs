@@ -0,0 +1,261 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Structural comparison between two layer stores.
+//!
+//! [`LayerStore::diff`] walks both stores slot by slot and reports which
+//! layers exist in only one of them and which local properties differ for
+//! layers present in both. Two layers are "the same" only when both their
+//! slot index and generation match, so a slot that was destroyed and reused
+//! shows up as a removal plus an addition.
+
+use alloc::vec::Vec;
+
+use super::id::LayerId;
+use super::store::LayerStore;
+
+/// Local properties that differ between two versions of the same layer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LayerDelta {
+    /// The parent link differs.
+    pub parent: bool,
+    /// The layer's position among its siblings differs.
+    ///
+    /// Set when the previous sibling differs, so a reordering reports every
+    /// layer whose predecessor changed.
+    pub order: bool,
+    /// The local transform differs.
+    pub transform: bool,
+    /// The local opacity differs.
+    pub opacity: bool,
+    /// The clip shape differs.
+    pub clip: bool,
+    /// The attached content differs.
+    pub content: bool,
+    /// The layer flags differ.
+    pub flags: bool,
+    /// The bounds differ.
+    pub bounds: bool,
+    /// The blend mode differs.
+    pub blend_mode: bool,
+    /// The anchor point differs.
+    pub anchor_point: bool,
+    /// The hit region or hit policy differs.
+    pub hit_test: bool,
+    /// The application tag differs.
+    pub tag: bool,
+}
+
+impl LayerDelta {
+    /// Returns whether no property differs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The result of [`LayerStore::diff`].
+///
+/// `removed` handles are valid against the baseline store, `added` handles
+/// against the other store, and `modified` handles against both.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StoreDiff {
+    /// Layers live in the other store but not in the baseline.
+    pub added: Vec<LayerId>,
+    /// Layers live in the baseline but not in the other store.
+    pub removed: Vec<LayerId>,
+    /// Layers live in both stores whose local properties differ.
+    pub modified: Vec<(LayerId, LayerDelta)>,
+}
+
+impl StoreDiff {
+    /// Returns whether the two stores were structurally identical.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+impl LayerStore {
+    /// Compares `self` (the baseline) against `other`.
+    ///
+    /// Only caller-set state is compared: topology (parent links and sibling
+    /// order), local transform, opacity, clip, content, flags, bounds, blend
+    /// mode, anchor point, hit region and policy, and tag. Names and computed
+    /// properties are ignored, so neither store needs to have been evaluated. Slots whose
+    /// generation differs between the stores are reported as a removal plus
+    /// an addition. Results are ordered by slot index.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> StoreDiff {
+        let mut out = StoreDiff::default();
+        let self_live = self.live_slot_mask();
+        let other_live = other.live_slot_mask();
+
+        for idx in 0..self.len.max(other.len) {
+            let i = idx as usize;
+            let before = self_live.get(i).copied().unwrap_or(false);
+            let after = other_live.get(i).copied().unwrap_or(false);
+            let old_id = before.then(|| self.id_at(idx));
+            let new_id = after.then(|| other.id_at(idx));
+
+            match (old_id, new_id) {
+                (Some(old), Some(new)) if old == new => {
+                    let delta = LayerDelta {
+                        parent: self.parent(old) != other.parent(new),
+                        order: self.prev_sibling[i] != other.prev_sibling[i],
+                        transform: self.local_transform[i] != other.local_transform[i],
+                        opacity: self.local_opacity[i] != other.local_opacity[i],
+                        clip: self.clip[i] != other.clip[i],
+                        content: self.content[i] != other.content[i],
+                        flags: self.flags[i] != other.flags[i],
+                        bounds: self.bounds[i] != other.bounds[i],
+                        blend_mode: self.blend_mode[i] != other.blend_mode[i],
+                        anchor_point: self.anchor_point[i] != other.anchor_point[i],
+                        hit_test: self.hit_region[i] != other.hit_region[i]
+                            || self.hit_policy[i] != other.hit_policy[i],
+                        tag: self.tag[i] != other.tag[i],
+                    };
+                    if !delta.is_empty() {
+                        out.modified.push((new, delta));
+                    }
+                }
+                (old, new) => {
+                    if let Some(old) = old {
+                        out.removed.push(old);
+                    }
+                    if let Some(new) = new {
+                        out.added.push(new);
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Returns a per-slot mask of which slots hold live layers.
+    fn live_slot_mask(&self) -> Vec<bool> {
+        let mut live = alloc::vec![true; self.len as usize];
+        for &idx in &self.free_list {
+            live[idx as usize] = false;
+        }
+        live
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::{BlendMode, ClipShape, HitPolicy, LayerFlags, SurfaceId};
+    use crate::transform::Transform3d;
+    use kurbo::{Rect, Size};
+
+    fn sample() -> (LayerStore, LayerId, LayerId, LayerId) {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        store.add_child(root, a);
+        store.add_child(root, b);
+        store.set_transform(a, Transform3d::from_translation(10.0, 0.0, 0.0));
+        (store, root, a, b)
+    }
+
+    #[test]
+    fn identical_stores_diff_empty() {
+        let (store, ..) = sample();
+        let (other, ..) = sample();
+        assert!(store.diff(&other).is_empty());
+        assert!(store.diff(&store.clone()).is_empty());
+    }
+
+    #[test]
+    fn moved_layer_shows_transform_delta() {
+        let (store, _, a, _) = sample();
+        let mut other = store.clone();
+        other.set_transform(a, Transform3d::from_translation(20.0, 5.0, 0.0));
+
+        let diff = store.diff(&other);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(
+            diff.modified,
+            [(
+                a,
+                LayerDelta {
+                    transform: true,
+                    ..LayerDelta::default()
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn recreated_slot_shows_remove_and_add() {
+        let (store, _, _, b) = sample();
+        let mut other = store.clone();
+        other.destroy_layer(b);
+        let reused = other.create_layer();
+        assert_eq!(reused.index(), b.index());
+
+        let diff = store.diff(&other);
+        assert_eq!(diff.removed, [b]);
+        assert_eq!(diff.added, [reused]);
+        assert!(diff.modified.is_empty());
+    }
+
+    #[test]
+    fn reports_each_property_channel() {
+        let (store, root, a, b) = sample();
+        let mut other = store.clone();
+        other.set_opacity(root, 0.5);
+        other.set_clip(a, Some(ClipShape::Rect(Rect::new(0.0, 0.0, 4.0, 4.0))));
+        other.set_content(a, Some(SurfaceId::from_raw_parts(7, 0)));
+        other.set_flags(
+            b,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
+        other.remove_from_parent(b);
+        let extra = other.create_layer();
+
+        let diff = store.diff(&other);
+        assert_eq!(diff.added, [extra]);
+        assert!(diff.removed.is_empty());
+        let find = |id| diff.modified.iter().find(|(m, _)| *m == id).unwrap().1;
+        assert!(find(root).opacity);
+        assert!(find(a).clip && find(a).content && !find(a).transform);
+        assert!(find(b).flags && find(b).parent);
+    }
+
+    #[test]
+    fn reports_geometry_blend_hit_test_and_tag() {
+        let (store, root, a, b) = sample();
+        let mut other = store.clone();
+        other.set_bounds(a, Size::new(4.0, 4.0));
+        other.set_anchor_point(a, (0.5, 0.5));
+        other.set_blend_mode(b, BlendMode::Multiply);
+        other.set_hit_policy(b, HitPolicy::Disabled);
+        other.set_tag(root, 9);
+
+        let diff = store.diff(&other);
+        let find = |id| diff.modified.iter().find(|(m, _)| *m == id).unwrap().1;
+        assert!(find(a).bounds && find(a).anchor_point && !find(a).blend_mode);
+        assert!(find(b).blend_mode && find(b).hit_test);
+        assert!(find(root).tag);
+    }
+
+    #[test]
+    fn sibling_reorder_is_not_empty() {
+        let (store, _, a, b) = sample();
+        let mut other = store.clone();
+        other.swap_children(a, b);
+
+        let diff = store.diff(&other);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        let find = |id| diff.modified.iter().find(|(m, _)| *m == id).unwrap().1;
+        assert!(find(a).order && !find(a).parent);
+        assert!(find(b).order);
+    }
+}
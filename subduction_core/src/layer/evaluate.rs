@@ -6,13 +6,21 @@
 //! Evaluation follows a drain-recompute pattern for each dirty channel:
 //!
 //! 1. **TRANSFORM** — Drain dirty indices, recompute each layer's
-//!    `world_transform` as `parent_world * local_transform` and
-//!    `effective_hidden` as `parent_effective_hidden || flags.hidden`.
+//!    `world_transform` as `parent_world * anchored_local_transform` and
+//!    `effective_hidden` as `parent_effective_hidden || flags.hidden`. With the
+//!    `rayon` feature, large dirty sets are split into independent subtrees
+//!    that are recomputed in parallel.
 //! 2. **OPACITY** — Drain dirty indices, recompute each layer's
-//!    `effective_opacity` as `parent_effective * local_opacity`.
-//! 3. **CLIP** / **CONTENT** — Drain dirty indices (no recomputation;
-//!    backends read the current values directly from the store).
-//! 4. **TOPOLOGY** — Drain and discard (the traversal order was already
+//!    `effective_opacity` as `parent_effective * local_opacity`, where a
+//!    parent flagged `isolate` contributes 1.0.
+//! 3. **CLIP** — Drain dirty indices, recompute each layer's
+//!    `effective_clip` as the intersection of `parent_effective_clip` with the
+//!    world-space bounding box of its own clip. Layers whose world transform
+//!    changed in step 1 are recomputed there as well.
+//! 4. **CONTENT** / **BOUNDS** / **BLEND** — Drain dirty indices (no
+//!    recomputation; backends read the current values directly from the
+//!    store).
+//! 5. **TOPOLOGY** — Drain and discard (the traversal order was already
 //!    rebuilt at the start of evaluation if needed).
 //!
 //! [`FrameChanges`] uses raw slot indices (`u32`) rather than [`LayerId`]
@@ -25,9 +33,15 @@
 
 use alloc::vec::Vec;
 
+use kurbo::{Point, Rect, Size};
+
+use super::clip::ClipShape;
 use super::id::INVALID;
-use super::store::LayerStore;
+use super::store::{LayerFlags, LayerStore, anchored_transform};
 use crate::dirty;
+#[cfg(feature = "trace-rich")]
+use crate::trace::{DamageRect, LayerChange, LayerField};
+use crate::transform::{Transform2d, Transform3d};
 
 /// The set of changes produced by a single [`LayerStore::evaluate`] call.
 ///
@@ -39,12 +53,19 @@ pub struct FrameChanges {
     pub transforms: Vec<u32>,
     /// Layers whose effective opacity was recomputed.
     pub opacities: Vec<u32>,
-    /// Layers whose clip shape changed.
+    /// Layers whose clip shape or inherited effective clip changed.
+    ///
+    /// A layer whose [effective clip](LayerStore::effective_clip) moved only
+    /// because its world transform changed is not listed here; it is already
+    /// in [`transforms`](Self::transforms), and its effective clip is up to
+    /// date.
     pub clips: Vec<u32>,
     /// Layers whose surface content changed.
     pub content: Vec<u32>,
     /// Layers whose bounds changed.
     pub bounds: Vec<u32>,
+    /// Layers whose blend mode changed.
+    pub blend_modes: Vec<u32>,
     /// Layers that transitioned from visible to effectively hidden.
     pub hidden: Vec<u32>,
     /// Layers that transitioned from effectively hidden to visible.
@@ -55,6 +76,12 @@ pub struct FrameChanges {
     pub removed: Vec<u32>,
     /// Whether the tree topology changed (traversal order was rebuilt).
     pub topology_changed: bool,
+    /// Whether [`LayerStore::mark_all_dirty`] requested a full rebuild.
+    ///
+    /// When set, every live layer is listed in `added` and on every property
+    /// channel. Presenters that kept per-layer native state should discard it
+    /// before applying the additions.
+    pub full_rebuild: bool,
 }
 
 impl FrameChanges {
@@ -66,11 +93,13 @@ impl FrameChanges {
             && self.clips.is_empty()
             && self.content.is_empty()
             && self.bounds.is_empty()
+            && self.blend_modes.is_empty()
             && self.hidden.is_empty()
             && self.unhidden.is_empty()
             && self.added.is_empty()
             && self.removed.is_empty()
             && !self.topology_changed
+            && !self.full_rebuild
     }
 
     /// Clears all change lists.
@@ -80,11 +109,74 @@ impl FrameChanges {
         self.clips.clear();
         self.content.clear();
         self.bounds.clear();
+        self.blend_modes.clear();
         self.hidden.clear();
         self.unhidden.clear();
         self.added.clear();
         self.removed.clear();
         self.topology_changed = false;
+        self.full_rebuild = false;
+    }
+
+    /// Returns a change set that rebuilds every live layer of `store` from
+    /// scratch.
+    ///
+    /// Every layer in [`traversal_order`](LayerStore::traversal_order) is
+    /// listed in `added` and on every property channel, in back-to-front
+    /// order, with `topology_changed` and `full_rebuild` set. This is the
+    /// shape [`mark_all_dirty`](LayerStore::mark_all_dirty) produces, built
+    /// without touching the store's dirty state; see
+    /// [`Presenter::apply_full`](crate::backend::Presenter::apply_full).
+    ///
+    /// `store` must have been evaluated since its last mutation.
+    #[must_use]
+    pub fn full(store: &LayerStore) -> Self {
+        let order = store.traversal_order().to_vec();
+        Self {
+            transforms: order.clone(),
+            opacities: order.clone(),
+            clips: order.clone(),
+            content: order.clone(),
+            bounds: order.clone(),
+            blend_modes: order.clone(),
+            hidden: Vec::new(),
+            unhidden: Vec::new(),
+            added: order,
+            removed: Vec::new(),
+            topology_changed: true,
+            full_rebuild: true,
+        }
+    }
+
+    /// Converts this change set into per-layer trace records.
+    ///
+    /// Clears `out`, then emits one [`LayerChange`] per reported slot with the
+    /// matching [`LayerField`]. Hidden/unhidden transitions are reported as
+    /// [`LayerField::Flags`] and added/removed layers as
+    /// [`LayerField::Topology`]. Pass the result to
+    /// [`Tracer::layer_changes`](crate::trace::Tracer::layer_changes).
+    #[cfg(feature = "trace-rich")]
+    pub fn layer_changes_into(&self, out: &mut Vec<LayerChange>) {
+        out.clear();
+        let groups: [(&[u32], LayerField); 10] = [
+            (self.transforms.as_slice(), LayerField::Transform),
+            (self.opacities.as_slice(), LayerField::Opacity),
+            (self.clips.as_slice(), LayerField::Clip),
+            (self.content.as_slice(), LayerField::Content),
+            (self.bounds.as_slice(), LayerField::Bounds),
+            (self.blend_modes.as_slice(), LayerField::BlendMode),
+            (self.hidden.as_slice(), LayerField::Flags),
+            (self.unhidden.as_slice(), LayerField::Flags),
+            (self.added.as_slice(), LayerField::Topology),
+            (self.removed.as_slice(), LayerField::Topology),
+        ];
+        for (indices, field) in groups {
+            out.extend(
+                indices
+                    .iter()
+                    .map(|&layer_index| LayerChange { layer_index, field }),
+            );
+        }
     }
 }
 
@@ -93,8 +185,12 @@ impl LayerStore {
     /// the set of changes.
     ///
     /// This rebuilds the traversal order if topology changed, then drains each
-    /// dirty channel and recomputes world transforms and effective opacities
-    /// in parent-before-child order.
+    /// dirty channel and recomputes world transforms, effective opacities, and
+    /// effective clips in parent-before-child order.
+    ///
+    /// When nothing changed since the last evaluation (see
+    /// [`has_pending_changes`](Self::has_pending_changes)), this returns empty
+    /// changes without touching the dirty tracker, so idle frames are cheap.
     pub fn evaluate(&mut self) -> FrameChanges {
         let mut changes = FrameChanges::default();
         self.evaluate_into(&mut changes);
@@ -105,6 +201,9 @@ impl LayerStore {
     /// to avoid allocation.
     pub fn evaluate_into(&mut self, changes: &mut FrameChanges) {
         changes.clear();
+        if !self.has_pending_changes() {
+            return;
+        }
 
         // Rebuild traversal order if needed.
         if self.traversal_dirty {
@@ -121,30 +220,23 @@ impl LayerStore {
             .deterministic()
             .run()
             .collect();
-        for &idx in &dirty_transforms {
-            let parent_idx = self.parent[idx as usize];
-            let parent_world = if parent_idx != INVALID {
-                self.world_transform[parent_idx as usize]
-            } else {
-                crate::transform::Transform3d::IDENTITY
-            };
-            self.world_transform[idx as usize] = parent_world * self.local_transform[idx as usize];
-
-            // Compute effective hidden: parent_effective_hidden || self.flags.hidden
-            let parent_hidden = if parent_idx != INVALID {
-                self.effective_hidden[parent_idx as usize]
-            } else {
-                false
-            };
-            let new_hidden = parent_hidden || self.flags[idx as usize].hidden;
-            let old_hidden = self.effective_hidden[idx as usize];
-            if new_hidden != old_hidden {
-                if new_hidden {
-                    changes.hidden.push(idx);
-                } else {
-                    changes.unhidden.push(idx);
-                }
-                self.effective_hidden[idx as usize] = new_hidden;
+        let dirty_transforms = self.in_traversal_order(dirty_transforms);
+        #[cfg(feature = "rayon")]
+        let parallel_states = (dirty_transforms.len() >= self.parallel_min_dirty).then(|| {
+            self.transform_inputs()
+                .states_parallel(&dirty_transforms, self.len as usize)
+        });
+        #[cfg(not(feature = "rayon"))]
+        let parallel_states: Option<Vec<TransformState>> = None;
+        if let Some(states) = parallel_states {
+            for (&idx, state) in dirty_transforms.iter().zip(states) {
+                self.apply_transform_state(idx, state, changes);
+            }
+        } else {
+            for &idx in &dirty_transforms {
+                let inputs = self.transform_inputs();
+                let state = inputs.transform_state(idx, inputs.stored_parent_state(idx));
+                self.apply_transform_state(idx, state, changes);
             }
         }
         changes.transforms = dirty_transforms;
@@ -157,24 +249,34 @@ impl LayerStore {
             .deterministic()
             .run()
             .collect();
+        let dirty_opacities = self.in_traversal_order(dirty_opacities);
         for &idx in &dirty_opacities {
-            let parent_opacity = if self.parent[idx as usize] != INVALID {
-                self.effective_opacity[self.parent[idx as usize] as usize]
-            } else {
-                1.0
-            };
+            let parent_idx = self.parent[idx as usize];
+            let parent_opacity =
+                if parent_idx != INVALID && !self.flags[parent_idx as usize].isolate {
+                    self.effective_opacity[parent_idx as usize]
+                } else {
+                    // Roots, and children of an opacity group, start fresh.
+                    1.0
+                };
             self.effective_opacity[idx as usize] =
                 parent_opacity * self.local_opacity[idx as usize];
         }
         changes.opacities = dirty_opacities;
 
-        // Drain CLIP channel — no recomputation, just collect.
-        changes.clips = self
+        // Drain CLIP channel.
+        let dirty_clips: Vec<u32> = self
             .dirty
             .drain(dirty::CLIP)
+            .affected()
             .deterministic()
             .run()
             .collect();
+        let dirty_clips = self.in_traversal_order(dirty_clips);
+        for &idx in &dirty_clips {
+            self.recompute_effective_clip(idx);
+        }
+        changes.clips = dirty_clips;
 
         // Drain CONTENT channel.
         changes.content = self
@@ -192,6 +294,14 @@ impl LayerStore {
             .run()
             .collect();
 
+        // Drain BLEND channel — no recomputation, just collect.
+        changes.blend_modes = self
+            .dirty
+            .drain(dirty::BLEND)
+            .deterministic()
+            .run()
+            .collect();
+
         // Drain TOPOLOGY channel (just consume, changes are structural).
         let _: Vec<u32> = self
             .dirty
@@ -203,6 +313,90 @@ impl LayerStore {
         // Move lifecycle lists.
         core::mem::swap(&mut self.pending_added, &mut changes.added);
         core::mem::swap(&mut self.pending_removed, &mut changes.removed);
+        changes.full_rebuild = core::mem::take(&mut self.pending_full_rebuild);
+        self.dirty_marked = false;
+
+        // Refresh cached world bounds for layers whose geometry or visibility
+        // may have changed. Removed slots are processed first so a slot that
+        // was destroyed and reused keeps the old layer's bounds as previous.
+        self.evaluate_epoch += 1;
+        for &idx in &changes.removed {
+            self.update_world_bounds(idx, None);
+        }
+        for list in [
+            &changes.transforms,
+            &changes.bounds,
+            &changes.hidden,
+            &changes.unhidden,
+            &changes.added,
+        ] {
+            for &idx in list {
+                let current = self.current_world_bounds(idx);
+                self.update_world_bounds(idx, current);
+            }
+        }
+    }
+
+    /// Computes damage rectangles for the changes from the latest evaluation.
+    ///
+    /// Clears `out`, then emits one [`DamageRect`] per reported change. For
+    /// geometry changes (transform, bounds, visibility, added, removed) the
+    /// rectangle is the union of the layer's previous and current world
+    /// bounds; for other changes it is the current world bounds. Layers
+    /// without bounds produce no damage. Rectangles may overlap and are
+    /// expanded outward to whole pixels.
+    ///
+    /// `changes` must be the result of the most recent
+    /// [`evaluate`](Self::evaluate) call on this store.
+    #[cfg(feature = "trace-rich")]
+    pub fn damage_rects_into(&self, changes: &FrameChanges, out: &mut Vec<DamageRect>) {
+        out.clear();
+        let geometry = [
+            changes.transforms.as_slice(),
+            changes.bounds.as_slice(),
+            changes.hidden.as_slice(),
+            changes.unhidden.as_slice(),
+            changes.added.as_slice(),
+            changes.removed.as_slice(),
+        ];
+        for &idx in geometry.into_iter().flatten() {
+            let i = idx as usize;
+            let damage = match (self.prev_world_bounds[i], self.world_bounds[i]) {
+                (Some(prev), Some(current)) => Some(prev.union(current)),
+                (rect, None) | (None, rect) => rect,
+            };
+            out.extend(damage.map(damage_rect));
+        }
+        let appearance = [
+            changes.opacities.as_slice(),
+            changes.clips.as_slice(),
+            changes.content.as_slice(),
+            changes.blend_modes.as_slice(),
+        ];
+        for &idx in appearance.into_iter().flatten() {
+            out.extend(self.world_bounds[idx as usize].map(damage_rect));
+        }
+    }
+
+    /// Returns the world-space bounding box of a live layer's bounds.
+    fn current_world_bounds(&self, idx: u32) -> Option<Rect> {
+        let i = idx as usize;
+        let bounds = self.bounds[i];
+        if self.effective_hidden[i] || bounds.is_zero_area() {
+            return None;
+        }
+        world_rect_bounds(bounds.to_rect(), &self.world_transform[i])
+    }
+
+    /// Records `current` as the world bounds of `idx`, shifting the cached
+    /// value into `prev_world_bounds` once per evaluation.
+    fn update_world_bounds(&mut self, idx: u32, current: Option<Rect>) {
+        let i = idx as usize;
+        if self.world_bounds_epoch[i] != self.evaluate_epoch {
+            self.prev_world_bounds[i] = self.world_bounds[i];
+            self.world_bounds_epoch[i] = self.evaluate_epoch;
+        }
+        self.world_bounds[i] = current;
     }
 
     /// Returns the current traversal order (depth-first pre-order).
@@ -217,6 +411,59 @@ impl LayerStore {
         &self.traversal_order
     }
 
+    /// Recomputes `effective_clip` for `idx` from its parent's effective clip.
+    ///
+    /// The parent must already be up to date.
+    fn recompute_effective_clip(&mut self, idx: u32) {
+        let parent_idx = self.parent[idx as usize];
+        let parent_clip = if parent_idx != INVALID {
+            self.effective_clip[parent_idx as usize]
+        } else {
+            None
+        };
+        self.effective_clip[idx as usize] = effective_clip(
+            self.clip[idx as usize],
+            parent_clip,
+            &self.world_transform[idx as usize],
+        );
+    }
+
+    /// Borrows the arrays the transform channel reads.
+    fn transform_inputs(&self) -> TransformInputs<'_> {
+        TransformInputs {
+            parent: &self.parent,
+            local_transform: &self.local_transform,
+            anchor_point: &self.anchor_point,
+            bounds: &self.bounds,
+            flags: &self.flags,
+            clip: &self.clip,
+            world_transform: &self.world_transform,
+            effective_hidden: &self.effective_hidden,
+            effective_clip: &self.effective_clip,
+            two_d_only: self.two_d_only,
+        }
+    }
+
+    /// Stores a computed state, recording visibility transitions.
+    fn apply_transform_state(
+        &mut self,
+        idx: u32,
+        state: TransformState,
+        changes: &mut FrameChanges,
+    ) {
+        let i = idx as usize;
+        self.world_transform[i] = state.world;
+        if state.hidden != self.effective_hidden[i] {
+            if state.hidden {
+                changes.hidden.push(idx);
+            } else {
+                changes.unhidden.push(idx);
+            }
+            self.effective_hidden[i] = state.hidden;
+        }
+        self.effective_clip[i] = state.clip;
+    }
+
     /// Rebuilds the depth-first pre-order traversal of all live layers.
     fn rebuild_traversal_order(&mut self) {
         self.traversal_order.clear();
@@ -226,6 +473,26 @@ impl LayerStore {
                 self.dfs_collect(idx);
             }
         }
+        self.traversal_pos.clear();
+        self.traversal_pos.resize(self.len as usize, INVALID);
+        for (pos, &idx) in (0_u32..).zip(&self.traversal_order) {
+            self.traversal_pos[idx as usize] = pos;
+        }
+    }
+
+    /// Orders drained indices parent-before-child.
+    ///
+    /// Inherited properties are recomputed from the parent's stored value, so
+    /// a dirty parent must be processed before its dirty children. The dirty
+    /// tracker already drains in dependency order; this sorts by traversal
+    /// position only when that order is not already a pre-order sequence, so
+    /// correctness does not rest on the tracker's ordering.
+    fn in_traversal_order(&self, mut indices: Vec<u32>) -> Vec<u32> {
+        let pos = |idx: &u32| self.traversal_pos[*idx as usize];
+        if !indices.is_sorted_by_key(pos) {
+            indices.sort_unstable_by_key(pos);
+        }
+        indices
     }
 
     /// Depth-first pre-order collection starting from `idx`.
@@ -239,11 +506,222 @@ impl LayerStore {
     }
 }
 
+/// Returns the world-space axis-aligned bounding box of a layer-local clip.
+///
+/// Returns `None` if any corner cannot be projected (degenerate perspective),
+/// in which case the clip does not constrain the effective clip.
+fn world_clip_bounds(clip: &ClipShape, world: &Transform3d) -> Option<Rect> {
+    world_rect_bounds(clip.bounding_box(), world)
+}
+
+/// Returns the world-space axis-aligned bounding box of a layer-local rect.
+///
+/// Returns `None` if any corner cannot be projected (degenerate perspective).
+fn world_rect_bounds(local: Rect, world: &Transform3d) -> Option<Rect> {
+    let corners = [
+        Point::new(local.x0, local.y0),
+        Point::new(local.x1, local.y0),
+        Point::new(local.x1, local.y1),
+        Point::new(local.x0, local.y1),
+    ];
+    let mut bounds: Option<Rect> = None;
+    for corner in corners {
+        let p = world.transform_point(corner)?;
+        bounds = Some(match bounds {
+            Some(b) => b.union_pt(p),
+            None => Rect::from_points(p, p),
+        });
+    }
+    bounds
+}
+
+/// Expands a world-space rectangle outward to whole pixels.
+#[cfg(feature = "trace-rich")]
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "float-to-int casts saturate; damage is clamped to the representable range"
+)]
+fn damage_rect(rect: Rect) -> DamageRect {
+    let rect = rect.expand();
+    DamageRect {
+        x: rect.x0 as i32,
+        y: rect.y0 as i32,
+        width: rect.width() as u32,
+        height: rect.height() as u32,
+    }
+}
+
+/// Transform-channel outputs for one layer.
+#[derive(Clone, Copy)]
+struct TransformState {
+    world: Transform3d,
+    hidden: bool,
+    clip: Option<Rect>,
+}
+
+impl TransformState {
+    /// What a root layer inherits.
+    const ROOT_PARENT: Self = Self {
+        world: Transform3d::IDENTITY,
+        hidden: false,
+        clip: None,
+    };
+}
+
+/// Borrowed inputs of the transform channel.
+///
+/// Holds only plain-data slices so it can be shared across threads.
+#[derive(Clone, Copy)]
+struct TransformInputs<'a> {
+    parent: &'a [u32],
+    local_transform: &'a [Transform3d],
+    anchor_point: &'a [(f32, f32)],
+    bounds: &'a [Size],
+    flags: &'a [LayerFlags],
+    clip: &'a [Option<ClipShape>],
+    world_transform: &'a [Transform3d],
+    effective_hidden: &'a [bool],
+    effective_clip: &'a [Option<Rect>],
+    two_d_only: bool,
+}
+
+impl TransformInputs<'_> {
+    /// Reads the parent's already-evaluated state for `idx`.
+    fn stored_parent_state(&self, idx: u32) -> TransformState {
+        let parent_idx = self.parent[idx as usize];
+        if parent_idx == INVALID {
+            return TransformState::ROOT_PARENT;
+        }
+        let p = parent_idx as usize;
+        TransformState {
+            world: self.world_transform[p],
+            hidden: self.effective_hidden[p],
+            clip: self.effective_clip[p],
+        }
+    }
+
+    /// Computes the transform-channel outputs for `idx` given its parent's.
+    fn transform_state(&self, idx: u32, parent: TransformState) -> TransformState {
+        let i = idx as usize;
+        let local = anchored_transform(
+            self.local_transform[i],
+            self.anchor_point[i],
+            self.bounds[i],
+        );
+        let world = if self.two_d_only {
+            compose_2d(parent.world, local)
+        } else {
+            parent.world * local
+        };
+        TransformState {
+            world,
+            hidden: parent.hidden || self.flags[i].hidden,
+            clip: effective_clip(self.clip[i], parent.clip, &world),
+        }
+    }
+
+    /// Computes states for `dirty` (in parent-before-child order) with
+    /// independent subtrees evaluated in parallel.
+    ///
+    /// A dirty layer whose parent is not dirty roots a group; every other
+    /// dirty layer joins its parent's group. Each layer reads only its
+    /// parent's state, which is either in the same group or unchanged this
+    /// pass, so groups never observe each other and the results are
+    /// bit-identical to the serial walk.
+    #[cfg(feature = "rayon")]
+    fn states_parallel(&self, dirty: &[u32], slots: usize) -> Vec<TransformState> {
+        use alloc::vec;
+        use rayon::prelude::*;
+
+        const NO_GROUP: u32 = u32::MAX;
+
+        // Per slot: owning group and position within it.
+        let mut group_of = vec![NO_GROUP; slots];
+        let mut local_pos = vec![0_u32; slots];
+        let mut groups: Vec<Vec<u32>> = Vec::new();
+        for &idx in dirty {
+            let parent_idx = self.parent[idx as usize];
+            let parent_group = if parent_idx == INVALID {
+                NO_GROUP
+            } else {
+                group_of[parent_idx as usize]
+            };
+            let group = if parent_group == NO_GROUP {
+                groups.push(Vec::new());
+                u32::try_from(groups.len() - 1).expect("group count fits the slot space")
+            } else {
+                parent_group
+            };
+            let members = &mut groups[group as usize];
+            group_of[idx as usize] = group;
+            local_pos[idx as usize] =
+                u32::try_from(members.len()).expect("group size fits the slot space");
+            members.push(idx);
+        }
+
+        let computed: Vec<Vec<TransformState>> = groups
+            .par_iter()
+            .enumerate()
+            .map(|(group, members)| {
+                let mut states: Vec<TransformState> = Vec::with_capacity(members.len());
+                for &idx in members {
+                    let parent_idx = self.parent[idx as usize];
+                    let parent = if parent_idx != INVALID
+                        && group_of[parent_idx as usize] as usize == group
+                    {
+                        states[local_pos[parent_idx as usize] as usize]
+                    } else {
+                        self.stored_parent_state(idx)
+                    };
+                    states.push(self.transform_state(idx, parent));
+                }
+                states
+            })
+            .collect();
+
+        dirty
+            .iter()
+            .map(|&idx| {
+                let i = idx as usize;
+                computed[group_of[i] as usize][local_pos[i] as usize]
+            })
+            .collect()
+    }
+}
+
+/// Intersects `parent_clip` with the world-space bounds of `own` under
+/// `world`.
+fn effective_clip(
+    own: Option<ClipShape>,
+    parent_clip: Option<Rect>,
+    world: &Transform3d,
+) -> Option<Rect> {
+    let own_clip = own.and_then(|clip| world_clip_bounds(&clip, world));
+    match (parent_clip, own_clip) {
+        (Some(parent), Some(own)) => Some(parent.intersect(own)),
+        (clip, None) | (None, clip) => clip,
+    }
+}
+
+/// Composes `parent * local` through [`Transform2d`] when both are planar.
+fn compose_2d(parent: Transform3d, local: Transform3d) -> Transform3d {
+    match (
+        Transform2d::from_transform3d(&parent),
+        Transform2d::from_transform3d(&local),
+    ) {
+        (Some(parent), Some(local)) => (parent * local).to_transform3d(),
+        _ => parent * local,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::transform::Transform3d;
+
+    use alloc::vec;
 
     use super::*;
+    use crate::layer::LayerId;
 
     #[test]
     fn evaluate_computes_world_transforms() {
@@ -265,6 +743,166 @@ mod tests {
         assert_eq!(store.world_transform(child), expected);
     }
 
+    #[test]
+    fn deep_chain_dirtied_in_reverse_evaluates_in_one_pass() {
+        const DEPTH: usize = 256;
+        let mut store = LayerStore::new();
+        // Allocate leaf-first so slot order runs child-before-parent.
+        let mut chain: Vec<LayerId> = (0..DEPTH).map(|_| store.create_layer()).collect();
+        chain.reverse();
+        for pair in chain.windows(2) {
+            store.add_child(pair[0], pair[1]);
+        }
+        let _ = store.evaluate();
+
+        for &id in chain.iter().rev() {
+            store.set_transform(id, Transform3d::from_translation(1.0, 0.0, 0.0));
+            store.set_opacity(id, 0.99);
+            store.set_clip(
+                id,
+                Some(ClipShape::Rect(Rect::new(-1.0, 0.0, 1000.0, 10.0))),
+            );
+        }
+        let changes = store.evaluate();
+        assert_eq!(changes.transforms.len(), DEPTH);
+
+        let mut opacity = 1.0_f32;
+        let mut clip_x0 = f64::NEG_INFINITY;
+        for (depth, &id) in chain.iter().enumerate() {
+            let x = (depth + 1) as f64;
+            opacity *= 0.99;
+            clip_x0 = clip_x0.max(x - 1.0);
+            assert_eq!(
+                store.world_transform(id),
+                Transform3d::from_translation(x, 0.0, 0.0)
+            );
+            assert!((store.effective_opacity(id) - opacity).abs() < 1e-5);
+            assert_eq!(store.effective_clip(id).map(|c| c.x0), Some(clip_x0));
+        }
+    }
+
+    #[test]
+    fn two_d_only_matches_full_evaluation() {
+        // Mirrors web-lotta-layers: groups orbiting a center, each with many
+        // rotated, scaled children, plus one layer with a Z translation.
+        fn build(two_d_only: bool) -> (LayerStore, Vec<LayerId>) {
+            let mut store = LayerStore::new();
+            store.set_2d_only(two_d_only);
+            let root = store.create_layer();
+            store.set_transform(root, Transform3d::from_translation(640.0, 360.0, 0.0));
+            let mut ids = vec![root];
+            for g in 0..10_u32 {
+                let group = store.create_layer();
+                store.add_child(root, group);
+                let angle = f64::from(g) * 0.628;
+                store.set_transform(
+                    group,
+                    Transform3d::from_rotation_z(angle)
+                        * Transform3d::from_translation(200.0, 0.0, 0.0),
+                );
+                ids.push(group);
+                for i in 0..600_u32 {
+                    let child = store.create_layer();
+                    store.add_child(group, child);
+                    let t = f64::from(i);
+                    store.set_transform(
+                        child,
+                        Transform3d::from_translation(t * 0.3, -t * 0.2, 0.0)
+                            * Transform3d::from_rotation_z(t * 0.01)
+                            * Transform3d::from_scale(1.0 + t * 0.001, 0.9, 1.0),
+                    );
+                    ids.push(child);
+                }
+            }
+            let lifted = store.create_layer();
+            store.add_child(ids[1], lifted);
+            store.set_transform(lifted, Transform3d::from_translation(1.0, 2.0, 3.0));
+            ids.push(lifted);
+            let _ = store.evaluate();
+            (store, ids)
+        }
+
+        let (full, ids) = build(false);
+        let (planar, _) = build(true);
+        assert!(planar.is_2d_only());
+        for &id in &ids {
+            assert_eq!(planar.world_transform(id), full.world_transform(id));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_transforms_match_serial() {
+        // Small LCG so the tree shape is reproducible without a rand dependency.
+        fn next(state: &mut u64) -> u64 {
+            *state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            *state >> 33
+        }
+
+        fn build(seed: u64, parallel: bool) -> (LayerStore, Vec<LayerId>) {
+            let mut rng = seed;
+            let mut store = LayerStore::new();
+            store.set_parallel_threshold(if parallel { 0 } else { usize::MAX });
+            let mut ids: Vec<LayerId> = Vec::new();
+            for i in 0..2000_u32 {
+                let id = store.create_layer();
+                if !ids.is_empty() && !next(&mut rng).is_multiple_of(8) {
+                    let pick = usize::try_from(next(&mut rng)).unwrap() % ids.len();
+                    let parent = ids[pick];
+                    store.add_child(parent, id);
+                }
+                let t = f64::from(i);
+                store.set_transform(
+                    id,
+                    Transform3d::from_translation(t % 37.0, -(t % 11.0), 0.0)
+                        * Transform3d::from_rotation_z((next(&mut rng) % 628) as f64 / 100.0),
+                );
+                store.set_opacity(id, 0.5 + (next(&mut rng) % 50) as f32 / 100.0);
+                if next(&mut rng).is_multiple_of(5) {
+                    store.set_clip(id, Some(ClipShape::Rect(Rect::new(0.0, 0.0, 50.0, 40.0))));
+                }
+                if next(&mut rng).is_multiple_of(17) {
+                    store.set_flags(
+                        id,
+                        LayerFlags {
+                            hidden: true,
+                            ..LayerFlags::default()
+                        },
+                    );
+                }
+                ids.push(id);
+            }
+            let _ = store.evaluate();
+
+            // A partial update: independent subtrees dirty at once.
+            for &id in ids.iter().step_by(97) {
+                store.set_transform(id, Transform3d::from_translation(3.0, 4.0, 0.0));
+            }
+            (store, ids)
+        }
+
+        for seed in [1, 7, 42] {
+            let (mut serial, ids) = build(seed, false);
+            let (mut parallel, _) = build(seed, true);
+            let serial_changes = serial.evaluate();
+            let parallel_changes = parallel.evaluate();
+            assert_eq!(serial_changes.transforms, parallel_changes.transforms);
+            assert_eq!(serial_changes.hidden, parallel_changes.hidden);
+            assert_eq!(serial_changes.unhidden, parallel_changes.unhidden);
+            for &id in &ids {
+                assert_eq!(serial.world_transform(id), parallel.world_transform(id));
+                assert_eq!(
+                    serial.effective_opacity(id).to_bits(),
+                    parallel.effective_opacity(id).to_bits()
+                );
+                assert_eq!(serial.effective_hidden(id), parallel.effective_hidden(id));
+                assert_eq!(serial.effective_clip(id), parallel.effective_clip(id));
+            }
+        }
+    }
+
     #[test]
     fn evaluate_computes_effective_opacity() {
         let mut store = LayerStore::new();
@@ -282,6 +920,85 @@ mod tests {
         assert!((store.effective_opacity(child) - 0.4).abs() < eps);
     }
 
+    /// Builds a group at opacity 0.5 with two children at 0.8 and 0.6.
+    fn opacity_group(isolate: bool) -> (LayerStore, LayerId, [LayerId; 2]) {
+        let mut store = LayerStore::new();
+        let group = store.create_layer();
+        let children = [store.create_layer(), store.create_layer()];
+        store.set_opacity(group, 0.5);
+        store.set_opacity(children[0], 0.8);
+        store.set_opacity(children[1], 0.6);
+        for child in children {
+            store.add_child(group, child);
+        }
+        store.set_flags(
+            group,
+            LayerFlags {
+                isolate,
+                ..LayerFlags::default()
+            },
+        );
+        let _ = store.evaluate();
+        (store, group, children)
+    }
+
+    #[test]
+    fn isolated_group_does_not_multiply_into_children() {
+        let eps = 1e-6;
+
+        let (store, group, [a, b]) = opacity_group(false);
+        assert!((store.effective_opacity(group) - 0.5).abs() < eps);
+        assert!((store.effective_opacity(a) - 0.4).abs() < eps);
+        assert!((store.effective_opacity(b) - 0.3).abs() < eps);
+
+        let (store, group, [a, b]) = opacity_group(true);
+        assert!(
+            (store.effective_opacity(group) - 0.5).abs() < eps,
+            "the group carries the accumulated opacity"
+        );
+        assert!((store.effective_opacity(a) - 0.8).abs() < eps);
+        assert!((store.effective_opacity(b) - 0.6).abs() < eps);
+    }
+
+    #[test]
+    fn isolated_group_accumulates_ancestor_opacity() {
+        let (mut store, group, [a, _]) = opacity_group(true);
+        let outer = store.create_layer();
+        store.set_opacity(outer, 0.5);
+        store.add_child(outer, group);
+        let _ = store.evaluate();
+
+        let eps = 1e-6;
+        assert!((store.effective_opacity(group) - 0.25).abs() < eps);
+        assert!((store.effective_opacity(a) - 0.8).abs() < eps);
+    }
+
+    #[test]
+    fn ungrouped_opacity_multiplies_isolated_ancestors() {
+        let (mut store, group, [a, b]) = opacity_group(true);
+        let outer = store.create_layer();
+        store.set_opacity(outer, 0.5);
+        store.add_child(outer, group);
+        let _ = store.evaluate();
+
+        let eps = 1e-6;
+        assert!((store.ungrouped_opacity(outer) - 0.5).abs() < eps);
+        assert!((store.ungrouped_opacity(group) - 0.25).abs() < eps);
+        assert!((store.ungrouped_opacity(a) - 0.2).abs() < eps);
+        assert!((store.ungrouped_opacity(b) - 0.15).abs() < eps);
+    }
+
+    #[test]
+    fn toggling_isolation_recomputes_children() {
+        let (mut store, group, [a, b]) = opacity_group(true);
+
+        store.set_flags(group, LayerFlags::default());
+        let changes = store.evaluate();
+        assert!(changes.opacities.contains(&a.idx));
+        assert!(changes.opacities.contains(&b.idx));
+        assert!((store.effective_opacity(a) - 0.4).abs() < 1e-6);
+    }
+
     #[test]
     fn no_change_evaluate_returns_empty() {
         let mut store = LayerStore::new();
@@ -297,11 +1014,33 @@ mod tests {
         assert!(changes.clips.is_empty());
         assert!(changes.content.is_empty());
         assert!(changes.bounds.is_empty());
+        assert!(changes.blend_modes.is_empty());
         assert!(changes.added.is_empty());
         assert!(changes.removed.is_empty());
         assert!(!changes.topology_changed);
     }
 
+    #[test]
+    fn idle_evaluate_skips_all_work() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(root, child);
+        let mut changes = store.evaluate();
+        assert!(!changes.is_empty());
+
+        // Nothing changed: the buffer is cleared and no evaluation pass runs.
+        let epoch = store.evaluate_epoch;
+        store.evaluate_into(&mut changes);
+        assert!(changes.is_empty());
+        assert_eq!(store.evaluate_epoch, epoch);
+
+        store.set_opacity(child, 0.5);
+        store.evaluate_into(&mut changes);
+        assert_eq!(changes.opacities, [child.index()]);
+        assert_eq!(store.evaluate_epoch, epoch + 1);
+    }
+
     #[test]
     fn traversal_order_is_depth_first() {
         let mut store = LayerStore::new();
@@ -329,16 +1068,228 @@ mod tests {
         let id = store.create_layer();
         let _ = store.evaluate();
 
-        store.set_clip(
-            id,
-            Some(ClipShape::Rect(kurbo::Rect::new(0.0, 0.0, 50.0, 50.0))),
-        );
+        store.set_clip(id, Some(ClipShape::Rect(Rect::new(0.0, 0.0, 50.0, 50.0))));
         store.set_content(id, Some(SurfaceId::from_raw_parts(1, 0)));
         let changes = store.evaluate();
         assert!(changes.clips.contains(&id.idx));
         assert!(changes.content.contains(&id.idx));
     }
 
+    #[test]
+    fn parent_clip_reduces_child_effective_clip() {
+        use crate::layer::ClipShape;
+        use kurbo::Rect;
+
+        let mut store = LayerStore::new();
+        let parent = store.create_layer();
+        let child = store.create_layer();
+        let grandchild = store.create_layer();
+        store.add_child(parent, child);
+        store.add_child(child, grandchild);
+
+        store.set_clip(
+            parent,
+            Some(ClipShape::Rect(Rect::new(0.0, 0.0, 100.0, 100.0))),
+        );
+        store.set_transform(child, Transform3d::from_translation(50.0, 50.0, 0.0));
+        store.set_clip(
+            child,
+            Some(ClipShape::Rect(Rect::new(0.0, 0.0, 100.0, 100.0))),
+        );
+        let _ = store.evaluate();
+
+        assert_eq!(
+            store.effective_clip(parent),
+            Some(Rect::new(0.0, 0.0, 100.0, 100.0))
+        );
+        assert_eq!(
+            store.effective_clip(child),
+            Some(Rect::new(50.0, 50.0, 100.0, 100.0)),
+            "child clip is intersected with the parent clip in world space"
+        );
+        assert_eq!(
+            store.effective_clip(grandchild),
+            store.effective_clip(child),
+            "an unclipped layer inherits its parent's effective clip"
+        );
+    }
+
+    #[test]
+    fn parent_clip_change_propagates_to_descendants() {
+        use crate::layer::ClipShape;
+        use kurbo::Rect;
+
+        let mut store = LayerStore::new();
+        let parent = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(parent, child);
+        let _ = store.evaluate();
+        assert_eq!(store.effective_clip(child), None);
+
+        store.set_clip(
+            parent,
+            Some(ClipShape::Rect(Rect::new(0.0, 0.0, 40.0, 30.0))),
+        );
+        let changes = store.evaluate();
+        assert!(
+            changes.clips.contains(&child.idx),
+            "descendants of a re-clipped layer should be reported"
+        );
+        assert_eq!(
+            store.effective_clip_at(child.idx),
+            Some(Rect::new(0.0, 0.0, 40.0, 30.0))
+        );
+
+        // Moving the parent moves the inherited clip too.
+        store.set_transform(parent, Transform3d::from_translation(10.0, 0.0, 0.0));
+        let _ = store.evaluate();
+        assert_eq!(
+            store.effective_clip(child),
+            Some(Rect::new(10.0, 0.0, 50.0, 30.0))
+        );
+    }
+
+    #[test]
+    fn rotated_parent_clip_uses_world_aabb() {
+        use crate::layer::ClipShape;
+        use kurbo::Rect;
+
+        let mut store = LayerStore::new();
+        let parent = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(parent, child);
+
+        // A 100×100 clip rotated 45° about the origin spans x ∈ [-70.7, 70.7]
+        // and y ∈ [0, 141.4]; the effective clip is its bounding box.
+        store.set_transform(
+            parent,
+            Transform3d::from_rotation_z(core::f64::consts::FRAC_PI_4),
+        );
+        store.set_clip(
+            parent,
+            Some(ClipShape::Rect(Rect::new(0.0, 0.0, 100.0, 100.0))),
+        );
+        let _ = store.evaluate();
+
+        let clip = store.effective_clip(child).unwrap();
+        let half_diag = 100.0 * core::f64::consts::FRAC_1_SQRT_2;
+        let eps = 1e-9;
+        assert!((clip.x0 + half_diag).abs() < eps, "x0 = {}", clip.x0);
+        assert!((clip.x1 - half_diag).abs() < eps, "x1 = {}", clip.x1);
+        assert!(clip.y0.abs() < eps, "y0 = {}", clip.y0);
+        assert!((clip.y1 - 2.0 * half_diag).abs() < eps, "y1 = {}", clip.y1);
+    }
+
+    #[cfg(feature = "trace-rich")]
+    #[test]
+    fn layer_changes_match_frame_changes() {
+        use crate::layer::ClipShape;
+        use crate::trace::{LayerChange, LayerField};
+
+        let mut store = LayerStore::new();
+        let parent = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(parent, child);
+        let _ = store.evaluate();
+
+        store.set_transform(parent, Transform3d::from_translation(1.0, 0.0, 0.0));
+        store.set_opacity(child, 0.5);
+        store.set_clip(
+            child,
+            Some(ClipShape::Rect(Rect::new(0.0, 0.0, 10.0, 10.0))),
+        );
+        let changes = store.evaluate();
+
+        let mut out = Vec::new();
+        changes.layer_changes_into(&mut out);
+
+        let count = |field: LayerField| out.iter().filter(|c| c.field == field).count();
+        assert_eq!(count(LayerField::Transform), changes.transforms.len());
+        assert_eq!(count(LayerField::Opacity), changes.opacities.len());
+        assert_eq!(count(LayerField::Clip), changes.clips.len());
+        assert_eq!(count(LayerField::Content), 0);
+        assert_eq!(
+            out.len(),
+            changes.transforms.len() + changes.opacities.len() + changes.clips.len()
+        );
+        assert!(out.iter().any(|c| matches!(
+            c,
+            LayerChange {
+                layer_index,
+                field: LayerField::Opacity,
+            } if *layer_index == child.idx
+        )));
+
+        // The buffer is cleared on reuse.
+        let empty = store.evaluate();
+        empty.layer_changes_into(&mut out);
+        assert!(out.is_empty(), "an empty frame should produce no records");
+    }
+
+    #[cfg(feature = "trace-rich")]
+    #[test]
+    fn moving_layer_damages_old_and_new_positions() {
+        use kurbo::Size;
+
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_bounds(id, Size::new(10.0, 10.0));
+        let _ = store.evaluate();
+
+        store.set_transform(id, Transform3d::from_translation(50.0, 20.0, 0.0));
+        let changes = store.evaluate();
+        let mut damage = Vec::new();
+        store.damage_rects_into(&changes, &mut damage);
+
+        assert_eq!(damage.len(), 1, "one moved layer yields one rect");
+        let rect = damage[0];
+        assert_eq!((rect.x, rect.y), (0, 0), "covers the old position");
+        assert_eq!(
+            (rect.width, rect.height),
+            (60, 30),
+            "covers the new position"
+        );
+    }
+
+    #[cfg(feature = "trace-rich")]
+    #[test]
+    fn unchanged_frame_produces_no_damage() {
+        use kurbo::Size;
+
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_bounds(id, Size::new(10.0, 10.0));
+        let _ = store.evaluate();
+
+        let changes = store.evaluate();
+        let mut damage = Vec::new();
+        store.damage_rects_into(&changes, &mut damage);
+        assert!(damage.is_empty(), "nothing changed, got {damage:?}");
+    }
+
+    #[cfg(feature = "trace-rich")]
+    #[test]
+    fn removed_layer_damages_its_last_position() {
+        use kurbo::Size;
+
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_bounds(id, Size::new(8.0, 4.0));
+        store.set_transform(id, Transform3d::from_translation(2.0, 3.0, 0.0));
+        let _ = store.evaluate();
+
+        store.destroy_layer(id);
+        let changes = store.evaluate();
+        let mut damage = Vec::new();
+        store.damage_rects_into(&changes, &mut damage);
+        assert_eq!(damage.len(), 1, "removal should damage one rect");
+        assert_eq!(
+            (damage[0].x, damage[0].y, damage[0].width, damage[0].height),
+            (2, 3, 8, 4)
+        );
+        assert_eq!(store.world_bounds_at(id.idx), None);
+    }
+
     #[test]
     fn evaluate_multiple_roots() {
         let mut store = LayerStore::new();
@@ -508,7 +1459,13 @@ mod tests {
         let root = store.create_layer();
         let _ = store.evaluate();
 
-        store.set_flags(root, LayerFlags { hidden: true });
+        store.set_flags(
+            root,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
 
         assert!(store.effective_hidden(root));
@@ -526,7 +1483,13 @@ mod tests {
         store.add_child(parent, child);
         let _ = store.evaluate();
 
-        store.set_flags(parent, LayerFlags { hidden: true });
+        store.set_flags(
+            parent,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
 
         assert!(store.effective_hidden(parent));
@@ -544,12 +1507,24 @@ mod tests {
         let _ = store.evaluate();
 
         // Hide
-        store.set_flags(root, LayerFlags { hidden: true });
+        store.set_flags(
+            root,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         let _ = store.evaluate();
         assert!(store.effective_hidden(root));
 
         // Unhide
-        store.set_flags(root, LayerFlags { hidden: false });
+        store.set_flags(
+            root,
+            LayerFlags {
+                hidden: false,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
 
         assert!(!store.effective_hidden(root));
@@ -570,7 +1545,13 @@ mod tests {
         let child_xf = Transform3d::from_translation(0.0, 5.0, 0.0);
         store.set_transform(parent, parent_xf);
         store.set_transform(child, child_xf);
-        store.set_flags(parent, LayerFlags { hidden: true });
+        store.set_flags(
+            parent,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
 
         let _ = store.evaluate();
 
@@ -587,7 +1568,13 @@ mod tests {
 
         let mut store = LayerStore::new();
         let root = store.create_layer();
-        store.set_flags(root, LayerFlags { hidden: true });
+        store.set_flags(
+            root,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         let _ = store.evaluate();
 
         // Mutate transform while hidden.
@@ -597,7 +1584,13 @@ mod tests {
         assert_eq!(store.world_transform(root), xf);
 
         // Unhide — transform should reflect the mutation.
-        store.set_flags(root, LayerFlags { hidden: false });
+        store.set_flags(
+            root,
+            LayerFlags {
+                hidden: false,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
 
         assert!(!store.effective_hidden(root));
@@ -203,7 +203,13 @@ mod tests {
         let id = store.create_layer();
         store.set_bounds(id, Size::new(100.0, 100.0));
         store.set_content(id, Some(surface_id(1)));
-        store.set_flags(id, LayerFlags { hidden: true });
+        store.set_flags(
+            id,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         store.evaluate();
 
         assert!(store.hit_test(Point::new(50.0, 50.0)).is_empty());
@@ -72,6 +72,7 @@ impl fmt::Debug for LayerId {
 /// for the current frame; callers that move content should detach it from the
 /// old layer and attach it to the new one before evaluation.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SurfaceId {
     idx: u32,
     generation: u32,
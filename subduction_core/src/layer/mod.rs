@@ -18,10 +18,18 @@
 //!   [`opacity`](LayerStore::set_opacity), [`clip`](LayerStore::set_clip),
 //!   [`content`](LayerStore::set_content), [`bounds`](LayerStore::set_bounds),
 //!   [`hit region`](LayerStore::set_hit_region),
-//!   [`hit policy`](LayerStore::set_hit_policy), and [`flags`](LayerStore::set_flags).
+//!   [`hit policy`](LayerStore::set_hit_policy),
+//!   [`blend mode`](LayerStore::set_blend_mode),
+//!   [`anchor point`](LayerStore::set_anchor_point), and [`flags`](LayerStore::set_flags),
+//!   plus an application [`tag`](LayerStore::set_tag) and an optional unique
+//!   [`name`](LayerStore::set_name), which the store never interprets.
 //! - **Computed properties** produced by [`evaluate`](LayerStore::evaluate):
-//!   `world_transform` (product of ancestor local transforms) and
-//!   `effective_opacity` (product of ancestor local opacities).
+//!   `world_transform` (product of ancestor local transforms, each pivoted
+//!   about its layer's anchor point),
+//!   `effective_opacity` (product of ancestor local opacities, restarting
+//!   below each [isolated](LayerFlags::isolate) opacity group), and
+//!   [`effective_clip`](LayerStore::effective_clip) (world-space intersection
+//!   of ancestor clips).
 //!
 //! Layers are stored in struct-of-arrays layout with index-based handles
 //! for cache-friendly traversal.
@@ -41,20 +49,31 @@
 //! Property mutations automatically mark the corresponding dirty channel
 //! (see [`dirty`](crate::dirty)). The channels map to property categories:
 //!
-//! - **TRANSFORM** / **OPACITY** — propagate to all descendants, since
-//!   world transforms and effective opacities are inherited.
-//! - **CLIP** / **CONTENT** / **BOUNDS** — local-only; only the modified layer is marked.
+//! - **TRANSFORM** / **OPACITY** / **CLIP** — propagate to all descendants,
+//!   since world transforms, effective opacities, and effective clips are
+//!   inherited.
+//! - **CONTENT** / **BOUNDS** / **BLEND** — local-only; only the modified
+//!   layer is marked.
 //! - **TOPOLOGY** — structural changes (add/remove child, create/destroy
 //!   layer) that trigger a traversal-order rebuild.
 
+mod blend;
 mod clip;
+mod cull;
+mod diff;
 mod evaluate;
 mod hit_test;
 mod id;
+mod name;
+#[cfg(feature = "serde")]
+mod scene;
 mod store;
+mod surface;
 mod traverse;
 
+pub use blend::BlendMode;
 pub use clip::ClipShape;
+pub use diff::{LayerDelta, StoreDiff};
 pub use evaluate::FrameChanges;
 pub use hit_test::HitEntry;
 pub use id::{INVALID, LayerId, SurfaceId, SurfaceIds};
@@ -0,0 +1,119 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Optional human-readable layer names.
+//!
+//! Debug tooling, traces, and scene files can refer to layers by stable names
+//! such as `"hud.timecode"` instead of slot indices. Names are unique within a
+//! store: [`LayerStore::set_name`] moves a name that is already in use to the
+//! new layer (last write wins), and destroying a layer releases its name.
+//! Most layers are unnamed, so names live in a pair of small maps rather than
+//! a per-slot array.
+
+use alloc::string::String;
+
+use super::id::LayerId;
+use super::store::LayerStore;
+
+impl LayerStore {
+    /// Names a layer, replacing any name it had.
+    ///
+    /// If another layer already holds `name`, that layer becomes unnamed: the
+    /// most recent call wins. Names do not affect rendering and mark no dirty
+    /// channel.
+    pub fn set_name(&mut self, id: LayerId, name: &str) {
+        self.validate(id);
+        self.clear_name_slot(id.index());
+        if let Some(previous) = self.name_index.insert(String::from(name), id.index()) {
+            self.names.remove(&previous);
+        }
+        self.names.insert(id.index(), String::from(name));
+    }
+
+    /// Removes a layer's name, if it has one.
+    pub fn clear_name(&mut self, id: LayerId) {
+        self.validate(id);
+        self.clear_name_slot(id.index());
+    }
+
+    /// Returns a layer's name, if it has one.
+    #[must_use]
+    pub fn name(&self, id: LayerId) -> Option<&str> {
+        self.validate(id);
+        self.names.get(&id.index()).map(String::as_str)
+    }
+
+    /// Returns the layer currently named `name`.
+    #[must_use]
+    pub fn find_by_name(&self, name: &str) -> Option<LayerId> {
+        self.name_index.get(name).map(|&idx| self.id_at(idx))
+    }
+
+    /// Releases the name held by slot `idx`, if any.
+    pub(crate) fn clear_name_slot(&mut self, idx: u32) {
+        if let Some(name) = self.names.remove(&idx) {
+            self.name_index.remove(&name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_find() {
+        let mut store = LayerStore::new();
+        let sweep = store.create_layer();
+        let flash = store.create_layer();
+        store.set_name(sweep, "sweep");
+        store.set_name(flash, "flash");
+
+        assert_eq!(store.find_by_name("sweep"), Some(sweep));
+        assert_eq!(store.find_by_name("flash"), Some(flash));
+        assert_eq!(store.find_by_name("hand"), None);
+        assert_eq!(store.name(sweep), Some("sweep"));
+
+        store.set_name(sweep, "hud.sweep");
+        assert_eq!(
+            store.find_by_name("sweep"),
+            None,
+            "rename releases old name"
+        );
+        assert_eq!(store.find_by_name("hud.sweep"), Some(sweep));
+
+        store.clear_name(flash);
+        assert_eq!(store.name(flash), None);
+        assert_eq!(store.find_by_name("flash"), None);
+    }
+
+    #[test]
+    fn destroy_clears_name() {
+        let mut store = LayerStore::new();
+        let tooltip = store.create_layer();
+        store.set_name(tooltip, "tooltip");
+        store.destroy_layer(tooltip);
+        assert_eq!(store.find_by_name("tooltip"), None);
+
+        let reused = store.create_layer();
+        assert_eq!(reused.index(), tooltip.index());
+        assert_eq!(store.name(reused), None);
+    }
+
+    #[test]
+    fn duplicate_name_moves_to_last_writer() {
+        let mut store = LayerStore::new();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        store.set_name(a, "hand");
+        store.set_name(b, "hand");
+
+        assert_eq!(store.find_by_name("hand"), Some(b));
+        assert_eq!(store.name(a), None);
+        assert_eq!(store.name(b), Some("hand"));
+
+        // Renaming a layer to its own name is a no-op.
+        store.set_name(b, "hand");
+        assert_eq!(store.find_by_name("hand"), Some(b));
+    }
+}
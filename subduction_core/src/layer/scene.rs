@@ -0,0 +1,214 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Serde support for [`LayerStore`] scenes (behind the `serde` feature).
+//!
+//! A store serializes as a flat sequence of layers in depth-first pre-order.
+//! Each entry carries its parent as an index into that sequence, so a parent
+//! always precedes its children and siblings keep their back-to-front order.
+//! Only caller-set state is written: topology, local properties, flags,
+//! content, tags, and names. Computed properties are rebuilt by the first
+//! [`evaluate`](LayerStore::evaluate) after loading.
+//!
+//! Layer handles are remapped on load: the `n`-th serialized layer occupies
+//! slot `n` of the new store with generation zero, regardless of the slot and
+//! generation it had before. [`SurfaceId`]s are written verbatim, since they
+//! belong to the host.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use kurbo::Size;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::transform::Transform3d;
+
+use super::blend::BlendMode;
+use super::clip::ClipShape;
+use super::id::{INVALID, SurfaceId};
+use super::store::{HitPolicy, HitRegion, LayerFlags, LayerStore};
+
+/// One serialized layer.
+#[derive(Serialize, Deserialize)]
+struct SceneLayer {
+    parent: Option<u32>,
+    transform: Transform3d,
+    opacity: f32,
+    clip: Option<ClipShape>,
+    content: Option<SurfaceId>,
+    flags: LayerFlags,
+    bounds: Size,
+    hit_region: Option<HitRegion>,
+    hit_policy: HitPolicy,
+    blend_mode: BlendMode,
+    anchor_point: (f32, f32),
+    #[serde(default)]
+    tag: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl LayerStore {
+    /// Returns live layers in depth-first pre-order, roots in slot order.
+    fn scene_order(&self) -> Vec<u32> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut stack = Vec::new();
+        for root in 0..self.len {
+            if self.parent[root as usize] != INVALID || self.free_list.contains(&root) {
+                continue;
+            }
+            stack.push(root);
+            while let Some(idx) = stack.pop() {
+                order.push(idx);
+                let first = stack.len();
+                let mut child = self.first_child[idx as usize];
+                while child != INVALID {
+                    stack.push(child);
+                    child = self.next_sibling[child as usize];
+                }
+                stack[first..].reverse();
+            }
+        }
+        order
+    }
+}
+
+impl Serialize for LayerStore {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let order = self.scene_order();
+        let mut position = alloc::vec![INVALID; self.len as usize];
+        for (n, &idx) in (0_u32..).zip(&order) {
+            position[idx as usize] = n;
+        }
+        let layers: Vec<SceneLayer> = order
+            .iter()
+            .map(|&idx| {
+                let i = idx as usize;
+                SceneLayer {
+                    parent: (self.parent[i] != INVALID).then(|| position[self.parent[i] as usize]),
+                    transform: self.local_transform[i],
+                    opacity: self.local_opacity[i],
+                    clip: self.clip[i],
+                    content: self.content[i],
+                    flags: self.flags[i],
+                    bounds: self.bounds[i],
+                    hit_region: self.hit_region[i],
+                    hit_policy: self.hit_policy[i],
+                    blend_mode: self.blend_mode[i],
+                    anchor_point: self.anchor_point[i],
+                    tag: self.tag[i],
+                    name: self.names.get(&idx).cloned(),
+                }
+            })
+            .collect();
+        layers.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LayerStore {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let layers = Vec::<SceneLayer>::deserialize(deserializer)?;
+        let mut store = Self::with_capacity(layers.len());
+        let mut ids = Vec::with_capacity(layers.len());
+        for (n, layer) in layers.into_iter().enumerate() {
+            let id = store.create_layer();
+            if let Some(parent) = layer.parent {
+                let parent = ids.get(parent as usize).copied().ok_or_else(|| {
+                    D::Error::custom(alloc::format!(
+                        "layer {n} refers to parent {parent}, which does not precede it"
+                    ))
+                })?;
+                store.add_child(parent, id);
+            }
+            store.set_transform(id, layer.transform);
+            store.set_opacity(id, layer.opacity);
+            store.set_clip(id, layer.clip);
+            store.set_content(id, layer.content);
+            store.set_flags(id, layer.flags);
+            store.set_bounds(id, layer.bounds);
+            store.set_hit_region(id, layer.hit_region);
+            store.set_hit_policy(id, layer.hit_policy);
+            store.set_blend_mode(id, layer.blend_mode);
+            store.set_anchor_point(id, layer.anchor_point);
+            store.set_tag(id, layer.tag);
+            if let Some(name) = &layer.name {
+                store.set_name(id, name);
+            }
+            ids.push(id);
+        }
+        Ok(store)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::layer::LayerId;
+
+    fn build() -> (LayerStore, Vec<LayerId>) {
+        let mut store = LayerStore::new();
+        let scratch = store.create_layer();
+        let root = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        let c = store.create_layer();
+        store.destroy_layer(scratch);
+        store.add_child(root, a);
+        store.add_child(root, b);
+        store.add_child(a, c);
+        store.set_transform(root, Transform3d::from_translation(3.0, 4.0, 0.0));
+        store.set_transform(a, Transform3d::from_scale(2.0, 2.0, 1.0));
+        store.set_transform(c, Transform3d::from_rotation_z(0.5));
+        store.set_opacity(b, 0.25);
+        store.set_bounds(c, Size::new(10.0, 20.0));
+        store.set_anchor_point(c, (0.5, 0.5));
+        store.set_flags(
+            b,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
+        store.set_content(c, Some(SurfaceId::from_raw_parts(9, 2)));
+        store.set_tag(b, 7);
+        store.set_name(c, "hud.timecode");
+        (store, vec![root, a, b, c])
+    }
+
+    #[test]
+    fn round_trip_reproduces_world_transforms() {
+        let (mut store, ids) = build();
+        let _ = store.evaluate();
+
+        let json = serde_json::to_string(&store).unwrap();
+        let mut loaded: LayerStore = serde_json::from_str(&json).unwrap();
+        let _ = loaded.evaluate();
+
+        assert_eq!(loaded.len(), store.len());
+        // Pre-order: root, a, c, b.
+        let remapped = [0, 1, 3, 2].map(|n| loaded.id_at(n));
+        for (&old, new) in ids.iter().zip(remapped) {
+            assert_eq!(store.world_transform(old), loaded.world_transform(new));
+            assert_eq!(store.effective_opacity(old), loaded.effective_opacity(new));
+            assert_eq!(store.effective_hidden(old), loaded.effective_hidden(new));
+            assert_eq!(store.content(old), loaded.content(new));
+            assert_eq!(store.tag(old), loaded.tag(new));
+            assert_eq!(store.name(old), loaded.name(new));
+        }
+        assert_eq!(
+            loaded.children(remapped[0]).collect::<Vec<_>>(),
+            [remapped[1], remapped[2]]
+        );
+    }
+
+    #[test]
+    fn rejects_forward_parent_reference() {
+        let (store, _) = build();
+        let json = serde_json::to_string(&store).unwrap();
+        let bad = json.replacen("\"parent\":null", "\"parent\":3", 1);
+        assert!(serde_json::from_str::<LayerStore>(&bad).is_err());
+    }
+}
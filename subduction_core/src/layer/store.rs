@@ -3,13 +3,16 @@
 
 //! Struct-of-arrays layer storage with allocation, topology, and property management.
 
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
 use alloc::vec::Vec;
 
-use invalidation::{CycleHandling, EagerPolicy, InvalidationTracker};
+use invalidation::{Channel, CycleHandling, EagerPolicy, InvalidationTracker};
 use kurbo::{Point, Rect, RoundedRect, Size};
 
 use crate::transform::Transform3d;
 
+use super::blend::BlendMode;
 use super::clip::ClipShape;
 use super::id::{INVALID, LayerId, SurfaceId};
 use super::traverse::Children;
@@ -20,10 +23,31 @@ use crate::dirty;
 /// Setting [`hidden`](Self::hidden) suppresses all visual contribution of the
 /// layer and its entire subtree. Properties can still be mutated while hidden;
 /// unhiding restores state immediately without re-evaluation.
+///
+/// Setting [`input_transparent`](Self::input_transparent) lets pointer input
+/// pass through the layer's native element to whatever lies beneath it.
+/// Presenters translate it to their platform's mechanism, such as
+/// `pointer-events: none` on the web. It applies to this layer only and does
+/// not affect [`LayerStore::hit_test`], which [`HitPolicy`] controls.
+///
+/// Setting [`isolate`](Self::isolate) makes the layer an opacity group: its
+/// [effective opacity](LayerStore::effective_opacity) still accumulates its
+/// ancestors', but its descendants start again from 1.0 instead of
+/// multiplying it in. Backends that can composite the subtree into one
+/// offscreen group apply the group's opacity once, so overlapping children
+/// no longer show through each other. Backends that apply opacity per layer
+/// use [`LayerStore::ungrouped_opacity`] instead and keep the ungrouped look.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LayerFlags {
     /// Whether the layer (and its subtree) is hidden.
     pub hidden: bool,
+    /// Whether the layer's native element ignores pointer input.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub input_transparent: bool,
+    /// Whether the layer's subtree is composited as one opacity group.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isolate: bool,
 }
 
 /// Controls whether a layer participates in coarse hit testing.
@@ -33,6 +57,7 @@ pub struct LayerFlags {
 /// point within its hit rect or bounds, and pass its own and ancestor clips.
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HitPolicy {
     /// Hit test the layer only when it has attached content.
     ///
@@ -57,6 +82,7 @@ pub enum HitPolicy {
 /// test against the layer's full bounds.
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HitRegion {
     /// An axis-aligned rectangle in layer-local coordinates.
     Rect(Rect),
@@ -92,7 +118,12 @@ impl HitRegion {
 /// Layers are addressed by [`LayerId`] handles. Internally, each layer occupies
 /// a slot in parallel arrays. Destroyed layers are recycled via a free list,
 /// and generation counters prevent stale handle access.
-#[derive(Debug)]
+///
+/// Cloning produces an independent deep copy, including pending dirty state,
+/// lifecycle lists, and the free list. Every [`LayerId`] valid against the
+/// original is equally valid against the clone, so a clone can be used to
+/// try speculative edits and discarded (or kept) afterwards.
+#[derive(Clone, Debug)]
 pub struct LayerStore {
     // -- Topology --
     pub(crate) parent: Vec<u32>,
@@ -109,12 +140,31 @@ pub struct LayerStore {
     pub(crate) bounds: Vec<Size>,
     pub(crate) hit_region: Vec<Option<HitRegion>>,
     pub(crate) hit_policy: Vec<HitPolicy>,
+    pub(crate) blend_mode: Vec<BlendMode>,
+    pub(crate) anchor_point: Vec<(f32, f32)>,
+    pub(crate) tag: Vec<u64>,
 
     // -- Computed properties (written by evaluate) --
     pub(crate) world_transform: Vec<Transform3d>,
     pub(crate) effective_opacity: Vec<f32>,
+    pub(crate) effective_clip: Vec<Option<Rect>>,
     pub(crate) effective_hidden: Vec<bool>,
 
+    // -- World bounds cache (written by evaluate) --
+    // Slots keep their cached bounds across destroy/reuse so that damage for
+    // a removed layer survives until the next evaluate.
+    pub(crate) world_bounds: Vec<Option<Rect>>,
+    pub(crate) prev_world_bounds: Vec<Option<Rect>>,
+    pub(crate) world_bounds_epoch: Vec<u64>,
+    pub(crate) evaluate_epoch: u64,
+
+    // -- Names (sparse; see `name.rs`) --
+    pub(crate) names: BTreeMap<u32, String>,
+    pub(crate) name_index: BTreeMap<String, u32>,
+
+    // -- Surface-sized slots (sparse; see `surface.rs`) --
+    pub(crate) surface_sized: BTreeSet<u32>,
+
     // -- Allocation --
     pub(crate) generation: Vec<u32>,
     pub(crate) free_list: Vec<u32>,
@@ -122,14 +172,40 @@ pub struct LayerStore {
 
     // -- Dirty tracking --
     pub(crate) dirty: InvalidationTracker<u32>,
+    /// Whether any channel was marked since the last evaluation.
+    pub(crate) dirty_marked: bool,
 
     // -- Traversal cache --
     pub(crate) traversal_order: Vec<u32>,
+    /// Position of each slot in `traversal_order` (`INVALID` if absent).
+    pub(crate) traversal_pos: Vec<u32>,
     pub(crate) traversal_dirty: bool,
 
+    // -- Evaluation options --
+    pub(crate) two_d_only: bool,
+    #[cfg(feature = "rayon")]
+    pub(crate) parallel_min_dirty: usize,
+
     // -- Lifecycle tracking --
     pub(crate) pending_added: Vec<u32>,
     pub(crate) pending_removed: Vec<u32>,
+    pub(crate) pending_full_rebuild: bool,
+}
+
+/// Folds an anchor point (as a fraction of `bounds`) into `local`.
+pub(super) fn anchored_transform(
+    local: Transform3d,
+    (ax, ay): (f32, f32),
+    bounds: Size,
+) -> Transform3d {
+    if (ax, ay) == (0.0, 0.0) {
+        return local;
+    }
+    let px = f64::from(ax) * bounds.width;
+    let py = f64::from(ay) * bounds.height;
+    Transform3d::from_translation(px, py, 0.0)
+        * local
+        * Transform3d::from_translation(-px, -py, 0.0)
 }
 
 impl Default for LayerStore {
@@ -155,20 +231,86 @@ impl LayerStore {
             bounds: Vec::new(),
             hit_region: Vec::new(),
             hit_policy: Vec::new(),
+            blend_mode: Vec::new(),
+            anchor_point: Vec::new(),
+            tag: Vec::new(),
             world_transform: Vec::new(),
             effective_opacity: Vec::new(),
+            effective_clip: Vec::new(),
             effective_hidden: Vec::new(),
+            world_bounds: Vec::new(),
+            prev_world_bounds: Vec::new(),
+            world_bounds_epoch: Vec::new(),
+            evaluate_epoch: 0,
+            names: BTreeMap::new(),
+            name_index: BTreeMap::new(),
+            surface_sized: BTreeSet::new(),
             generation: Vec::new(),
             free_list: Vec::new(),
             len: 0,
             dirty: InvalidationTracker::with_cycle_handling(CycleHandling::Error),
+            dirty_marked: false,
             traversal_order: Vec::new(),
+            traversal_pos: Vec::new(),
             traversal_dirty: true,
+            two_d_only: false,
+            #[cfg(feature = "rayon")]
+            parallel_min_dirty: Self::DEFAULT_PARALLEL_THRESHOLD,
             pending_added: Vec::new(),
             pending_removed: Vec::new(),
+            pending_full_rebuild: false,
         }
     }
 
+    /// Creates an empty layer store with room for `capacity` layers.
+    ///
+    /// The first `capacity` calls to [`create_layer`](Self::create_layer) do
+    /// not reallocate any per-layer array.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut store = Self::new();
+        store.reserve(capacity);
+        store
+    }
+
+    /// Reserves room for at least `additional` more layer slots beyond those
+    /// already allocated.
+    ///
+    /// Freed slots are reused before new ones are allocated, so this is an
+    /// upper bound on what the next `additional` creations need.
+    pub fn reserve(&mut self, additional: usize) {
+        self.parent.reserve(additional);
+        self.first_child.reserve(additional);
+        self.next_sibling.reserve(additional);
+        self.prev_sibling.reserve(additional);
+        self.local_transform.reserve(additional);
+        self.local_opacity.reserve(additional);
+        self.clip.reserve(additional);
+        self.content.reserve(additional);
+        self.flags.reserve(additional);
+        self.bounds.reserve(additional);
+        self.hit_region.reserve(additional);
+        self.hit_policy.reserve(additional);
+        self.blend_mode.reserve(additional);
+        self.anchor_point.reserve(additional);
+        self.tag.reserve(additional);
+        self.world_transform.reserve(additional);
+        self.effective_opacity.reserve(additional);
+        self.effective_clip.reserve(additional);
+        self.effective_hidden.reserve(additional);
+        self.world_bounds.reserve(additional);
+        self.prev_world_bounds.reserve(additional);
+        self.world_bounds_epoch.reserve(additional);
+        self.generation.reserve(additional);
+
+        // These are not kept at slot length, so size them against the slot
+        // count rather than their own length.
+        let slots = self.len as usize + additional;
+        reserve_total(&mut self.traversal_order, slots);
+        reserve_total(&mut self.traversal_pos, slots);
+        reserve_total(&mut self.pending_added, slots);
+    }
+
     /// Returns the number of live layers in the store.
     ///
     /// Destroyed layers are not counted, even though their slots may remain
@@ -184,9 +326,73 @@ impl LayerStore {
         self.len() == 0
     }
 
+    /// Enables or disables the 2-D evaluation fast path.
+    ///
+    /// When enabled, [`evaluate`](Self::evaluate) composes world transforms
+    /// with [`Transform2d`](crate::transform::Transform2d) whenever both the
+    /// parent's world transform and the layer's local transform lie in the XY
+    /// plane, falling back to the 4×4 product otherwise. Results are identical
+    /// either way; scenes that only translate, rotate around Z, and scale
+    /// evaluate faster. Off by default.
+    pub fn set_2d_only(&mut self, enabled: bool) {
+        self.two_d_only = enabled;
+    }
+
+    /// Returns whether the 2-D evaluation fast path is enabled.
+    #[must_use]
+    pub fn is_2d_only(&self) -> bool {
+        self.two_d_only
+    }
+
+    /// Default minimum number of transform-dirty layers before
+    /// [`evaluate`](Self::evaluate) recomputes them in parallel.
+    #[cfg(feature = "rayon")]
+    pub const DEFAULT_PARALLEL_THRESHOLD: usize = 4096;
+
+    /// Sets the minimum number of transform-dirty layers before
+    /// [`evaluate`](Self::evaluate) splits them into independent subtrees and
+    /// recomputes those in parallel.
+    ///
+    /// Results are bit-identical to the serial path; below the threshold the
+    /// grouping overhead outweighs the parallel speedup. Pass `usize::MAX` to
+    /// always evaluate serially.
+    #[cfg(feature = "rayon")]
+    pub fn set_parallel_threshold(&mut self, min_dirty: usize) {
+        self.parallel_min_dirty = min_dirty;
+    }
+
+    /// Marks `idx` dirty in a local-only or structural channel.
+    fn mark_dirty(&mut self, idx: u32, channel: Channel) {
+        self.dirty.mark(idx, channel);
+        self.dirty_marked = true;
+    }
+
+    /// Marks `idx` and its descendants dirty in a propagating channel.
+    fn mark_dirty_eager(&mut self, idx: u32, channel: Channel) {
+        self.dirty.mark_with(idx, channel, &EagerPolicy);
+        self.dirty_marked = true;
+    }
+
+    /// Returns whether anything changed since the last
+    /// [`evaluate`](Self::evaluate), without draining any dirty state.
+    ///
+    /// When this returns `false`, the next `evaluate` would report empty
+    /// [`FrameChanges`](super::FrameChanges), so a host can skip the frame.
+    /// It may return `true` for changes that turn out to be no-ops, such as
+    /// setting a property to its current value.
+    #[must_use]
+    pub fn has_pending_changes(&self) -> bool {
+        self.dirty_marked
+            || self.traversal_dirty
+            || self.pending_full_rebuild
+            || !self.pending_added.is_empty()
+            || !self.pending_removed.is_empty()
+    }
+
     fn mark_inherited_dirty(&mut self, idx: u32) {
-        self.dirty.mark_with(idx, dirty::TRANSFORM, &EagerPolicy);
-        self.dirty.mark_with(idx, dirty::OPACITY, &EagerPolicy);
+        self.mark_dirty_eager(idx, dirty::TRANSFORM);
+        self.mark_dirty_eager(idx, dirty::OPACITY);
+        self.mark_dirty_eager(idx, dirty::CLIP);
     }
 
     // -- Allocation API --
@@ -198,49 +404,18 @@ impl LayerStore {
     pub fn create_layer(&mut self) -> LayerId {
         let idx = if let Some(idx) = self.free_list.pop() {
             // Reuse a freed slot.
-            self.generation[idx as usize] += 1;
-            self.parent[idx as usize] = INVALID;
-            self.first_child[idx as usize] = INVALID;
-            self.next_sibling[idx as usize] = INVALID;
-            self.prev_sibling[idx as usize] = INVALID;
-            self.local_transform[idx as usize] = Transform3d::IDENTITY;
-            self.local_opacity[idx as usize] = 1.0;
-            self.clip[idx as usize] = None;
-            self.content[idx as usize] = None;
-            self.flags[idx as usize] = LayerFlags::default();
-            self.bounds[idx as usize] = Size::ZERO;
-            self.hit_region[idx as usize] = None;
-            self.hit_policy[idx as usize] = HitPolicy::default();
-            self.world_transform[idx as usize] = Transform3d::IDENTITY;
-            self.effective_opacity[idx as usize] = 1.0;
-            self.effective_hidden[idx as usize] = false;
+            self.reset_slot(idx);
             idx
         } else {
             // Allocate a new slot.
             let idx = self.len;
-            self.len += 1;
-            self.parent.push(INVALID);
-            self.first_child.push(INVALID);
-            self.next_sibling.push(INVALID);
-            self.prev_sibling.push(INVALID);
-            self.local_transform.push(Transform3d::IDENTITY);
-            self.local_opacity.push(1.0);
-            self.clip.push(None);
-            self.content.push(None);
-            self.flags.push(LayerFlags::default());
-            self.bounds.push(Size::ZERO);
-            self.hit_region.push(None);
-            self.hit_policy.push(HitPolicy::default());
-            self.world_transform.push(Transform3d::IDENTITY);
-            self.effective_opacity.push(1.0);
-            self.effective_hidden.push(false);
-            self.generation.push(0);
+            self.grow_slots(1);
             idx
         };
 
         self.traversal_dirty = true;
         self.pending_added.push(idx);
-        self.dirty.mark(idx, dirty::TOPOLOGY);
+        self.mark_dirty(idx, dirty::TOPOLOGY);
 
         LayerId {
             idx,
@@ -248,6 +423,105 @@ impl LayerStore {
         }
     }
 
+    /// Creates `n` new layers and returns their handles.
+    ///
+    /// Equivalent to calling [`create_layer`](Self::create_layer) `n` times:
+    /// freed slots are reused first, and the remaining layers occupy a
+    /// contiguous run of new slots. Storage is reserved once for the whole
+    /// batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the store would exceed `u32::MAX - 1` slots.
+    pub fn create_layers(&mut self, n: usize) -> Vec<LayerId> {
+        let reused = n.min(self.free_list.len());
+        let fresh = u32::try_from(n - reused)
+            .ok()
+            .filter(|&fresh| self.len.checked_add(fresh).is_some_and(|len| len < INVALID))
+            .expect("too many layers");
+        self.reserve(n - reused);
+
+        let first_new = self.free_list.len() - reused;
+        let mut slots: Vec<u32> = self.free_list.drain(first_new..).rev().collect();
+        for &idx in &slots {
+            self.reset_slot(idx);
+        }
+        let start = self.len;
+        self.grow_slots(fresh);
+        slots.extend(start..start + fresh);
+
+        if !slots.is_empty() {
+            self.traversal_dirty = true;
+        }
+        self.pending_added.extend_from_slice(&slots);
+        for &idx in &slots {
+            self.mark_dirty(idx, dirty::TOPOLOGY);
+        }
+
+        slots
+            .into_iter()
+            .map(|idx| LayerId {
+                idx,
+                generation: self.generation[idx as usize],
+            })
+            .collect()
+    }
+
+    /// Resets a freed slot to the state of a newly created layer, bumping its
+    /// generation.
+    fn reset_slot(&mut self, idx: u32) {
+        let i = idx as usize;
+        self.generation[i] += 1;
+        self.parent[i] = INVALID;
+        self.first_child[i] = INVALID;
+        self.next_sibling[i] = INVALID;
+        self.prev_sibling[i] = INVALID;
+        self.local_transform[i] = Transform3d::IDENTITY;
+        self.local_opacity[i] = 1.0;
+        self.clip[i] = None;
+        self.content[i] = None;
+        self.flags[i] = LayerFlags::default();
+        self.bounds[i] = Size::ZERO;
+        self.hit_region[i] = None;
+        self.hit_policy[i] = HitPolicy::default();
+        self.blend_mode[i] = BlendMode::default();
+        self.anchor_point[i] = (0.0, 0.0);
+        self.tag[i] = 0;
+        self.world_transform[i] = Transform3d::IDENTITY;
+        self.effective_opacity[i] = 1.0;
+        self.effective_clip[i] = None;
+        self.effective_hidden[i] = false;
+    }
+
+    /// Appends `n` new slots in the state of a newly created layer.
+    fn grow_slots(&mut self, n: u32) {
+        self.len += n;
+        let len = self.len as usize;
+        self.parent.resize(len, INVALID);
+        self.first_child.resize(len, INVALID);
+        self.next_sibling.resize(len, INVALID);
+        self.prev_sibling.resize(len, INVALID);
+        self.local_transform.resize(len, Transform3d::IDENTITY);
+        self.local_opacity.resize(len, 1.0);
+        self.clip.resize(len, None);
+        self.content.resize(len, None);
+        self.flags.resize(len, LayerFlags::default());
+        self.bounds.resize(len, Size::ZERO);
+        self.hit_region.resize(len, None);
+        self.hit_policy.resize(len, HitPolicy::default());
+        self.blend_mode.resize(len, BlendMode::default());
+        self.anchor_point.resize(len, (0.0, 0.0));
+        self.tag.resize(len, 0);
+        self.world_transform.resize(len, Transform3d::IDENTITY);
+        self.effective_opacity.resize(len, 1.0);
+        self.effective_clip.resize(len, None);
+        self.effective_hidden.resize(len, false);
+        self.world_bounds.resize(len, None);
+        self.prev_world_bounds.resize(len, None);
+        self.world_bounds_epoch.resize(len, 0);
+        self.generation.resize(len, 0);
+    }
+
     /// Destroys a layer, freeing its slot for reuse.
     ///
     /// # Panics
@@ -269,6 +543,8 @@ impl LayerStore {
 
         // Remove dirty tracking dependencies.
         self.dirty.remove_key(idx);
+        self.clear_name_slot(idx);
+        self.surface_sized.remove(&idx);
 
         // Bump generation so old handles immediately fail validation.
         self.generation[idx as usize] += 1;
@@ -276,7 +552,39 @@ impl LayerStore {
         self.free_list.push(idx);
         self.traversal_dirty = true;
         self.pending_removed.push(idx);
-        self.dirty.mark(idx, dirty::TOPOLOGY);
+        self.mark_dirty(idx, dirty::TOPOLOGY);
+    }
+
+    /// Forces the next [`evaluate`](Self::evaluate) to report every live
+    /// layer as changed.
+    ///
+    /// Use this after a presenter loses its native state (e.g. GPU device
+    /// loss, or attaching a new presenter to a fresh container). The next
+    /// evaluation sets [`FrameChanges::full_rebuild`], lists every live layer
+    /// in [`added`], rebuilds the traversal order, and
+    /// reports every live layer on the transform, opacity, clip, content,
+    /// bounds, and blend-mode channels.
+    ///
+    /// [`FrameChanges::full_rebuild`]: super::FrameChanges::full_rebuild
+    /// [`added`]: super::FrameChanges::added
+    pub fn mark_all_dirty(&mut self) {
+        let mut free = alloc::vec![false; self.len as usize];
+        for &idx in &self.free_list {
+            free[idx as usize] = true;
+        }
+        self.pending_added.clear();
+        for idx in 0..self.len {
+            if free[idx as usize] {
+                continue;
+            }
+            self.mark_inherited_dirty(idx);
+            self.mark_dirty(idx, dirty::CONTENT);
+            self.mark_dirty(idx, dirty::BOUNDS);
+            self.mark_dirty(idx, dirty::BLEND);
+            self.pending_added.push(idx);
+        }
+        self.traversal_dirty = true;
+        self.pending_full_rebuild = true;
     }
 
     /// Returns whether the given handle refers to a live layer.
@@ -323,13 +631,15 @@ impl LayerStore {
             self.prev_sibling[c as usize] = last;
         }
 
-        // Add dirty dependency edges: child depends on parent for TRANSFORM and OPACITY.
+        // Add dirty dependency edges: child depends on parent for TRANSFORM,
+        // OPACITY, and CLIP.
         let _ = self.dirty.add_dependency(c, p, dirty::TRANSFORM);
         let _ = self.dirty.add_dependency(c, p, dirty::OPACITY);
+        let _ = self.dirty.add_dependency(c, p, dirty::CLIP);
 
         self.traversal_dirty = true;
         self.mark_inherited_dirty(c);
-        self.dirty.mark(p, dirty::TOPOLOGY);
+        self.mark_dirty(p, dirty::TOPOLOGY);
     }
 
     /// Moves `layer` directly before `sibling` in their parent's child list.
@@ -390,6 +700,45 @@ impl LayerStore {
         self.mark_topology_reordered(parent);
     }
 
+    /// Swaps the positions of two siblings in their parent's child list.
+    ///
+    /// Only sibling links change; both layers keep their parent, so no
+    /// inherited state is invalidated. Swapping a layer with itself is a
+    /// no-op.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either handle is stale, either layer has no parent, or the
+    /// layers do not share the same parent.
+    pub fn swap_children(&mut self, a: LayerId, b: LayerId) {
+        self.validate(a);
+        self.validate(b);
+        if a == b {
+            return;
+        }
+
+        let (a, b) = (a.idx, b.idx);
+        let parent = self.shared_reorder_parent(a, b);
+        if self.next_sibling[a as usize] == b {
+            self.unlink_from_parent(b);
+            self.insert_unlinked_before(b, a, parent);
+        } else if self.next_sibling[b as usize] == a {
+            self.unlink_from_parent(a);
+            self.insert_unlinked_before(a, b, parent);
+        } else {
+            let a_next = self.next_sibling[a as usize];
+            self.unlink_from_parent(a);
+            self.insert_unlinked_before(a, b, parent);
+            self.unlink_from_parent(b);
+            if a_next == INVALID {
+                self.append_unlinked_child(parent, b);
+            } else {
+                self.insert_unlinked_before(b, a_next, parent);
+            }
+        }
+        self.mark_topology_reordered(parent);
+    }
+
     /// Moves `layer` to the front of its parent's child list.
     ///
     /// Sibling order is back-to-front, so the front-most child is the last
@@ -450,10 +799,11 @@ impl LayerStore {
         // Remove dirty dependency edges.
         self.dirty.remove_dependency(c, p, dirty::TRANSFORM);
         self.dirty.remove_dependency(c, p, dirty::OPACITY);
+        self.dirty.remove_dependency(c, p, dirty::CLIP);
 
         self.traversal_dirty = true;
         self.mark_inherited_dirty(c);
-        self.dirty.mark(p, dirty::TOPOLOGY);
+        self.mark_dirty(p, dirty::TOPOLOGY);
     }
 
     /// Destroys `root` and all descendants in postorder.
@@ -482,7 +832,10 @@ impl LayerStore {
 
     /// Moves `child` to be a child of `new_parent`.
     ///
-    /// If `child` already has a parent, it is removed first.
+    /// If `child` already has a parent, it is removed first. Reparenting to the
+    /// current parent is a pure reorder: like
+    /// [`move_to_front`](Self::move_to_front), it leaves inherited transform
+    /// and opacity state untouched.
     ///
     /// # Panics
     ///
@@ -491,6 +844,11 @@ impl LayerStore {
         self.validate(child);
         self.validate(new_parent);
 
+        if self.parent[child.idx as usize] == new_parent.idx {
+            self.move_to_front(child);
+            return;
+        }
+
         if self.parent[child.idx as usize] != INVALID {
             let old_p = self.parent[child.idx as usize];
             self.unlink_from_parent(child.idx);
@@ -498,7 +856,8 @@ impl LayerStore {
                 .remove_dependency(child.idx, old_p, dirty::TRANSFORM);
             self.dirty
                 .remove_dependency(child.idx, old_p, dirty::OPACITY);
-            self.dirty.mark(old_p, dirty::TOPOLOGY);
+            self.dirty.remove_dependency(child.idx, old_p, dirty::CLIP);
+            self.mark_dirty(old_p, dirty::TOPOLOGY);
         }
 
         // Now add as child of new parent (inline the logic to avoid double-validate).
@@ -521,10 +880,11 @@ impl LayerStore {
 
         let _ = self.dirty.add_dependency(c, p, dirty::TRANSFORM);
         let _ = self.dirty.add_dependency(c, p, dirty::OPACITY);
+        let _ = self.dirty.add_dependency(c, p, dirty::CLIP);
 
         self.traversal_dirty = true;
         self.mark_inherited_dirty(c);
-        self.dirty.mark(p, dirty::TOPOLOGY);
+        self.mark_dirty(p, dirty::TOPOLOGY);
     }
 
     /// Inserts `child` before `sibling` in the sibling list.
@@ -561,10 +921,11 @@ impl LayerStore {
 
         let _ = self.dirty.add_dependency(c, p, dirty::TRANSFORM);
         let _ = self.dirty.add_dependency(c, p, dirty::OPACITY);
+        let _ = self.dirty.add_dependency(c, p, dirty::CLIP);
 
         self.traversal_dirty = true;
         self.mark_inherited_dirty(c);
-        self.dirty.mark(p, dirty::TOPOLOGY);
+        self.mark_dirty(p, dirty::TOPOLOGY);
     }
 
     /// Returns the parent of a layer, if any.
@@ -589,6 +950,34 @@ impl LayerStore {
         Children::new(self, self.first_child[id.idx as usize])
     }
 
+    /// Returns the position of a layer among its parent's children, or `None`
+    /// if it has no parent.
+    ///
+    /// Position 0 is the back-most child, matching [`children`](Self::children)
+    /// order. This walks the preceding siblings, so it is O(siblings).
+    #[must_use]
+    pub fn child_index(&self, child: LayerId) -> Option<usize> {
+        self.validate(child);
+        if self.parent[child.idx as usize] == INVALID {
+            return None;
+        }
+        let mut index = 0;
+        let mut prev = self.prev_sibling[child.idx as usize];
+        while prev != INVALID {
+            index += 1;
+            prev = self.prev_sibling[prev as usize];
+        }
+        Some(index)
+    }
+
+    /// Returns the number of direct children of a layer.
+    ///
+    /// This walks the child list, so it is O(children).
+    #[must_use]
+    pub fn child_count(&self, parent: LayerId) -> usize {
+        self.children(parent).count()
+    }
+
     /// Returns the raw slot indices of root layers (those with no parent).
     ///
     /// Roots are layers whose parent is [`INVALID`] and that are not in the
@@ -616,6 +1005,13 @@ impl LayerStore {
         self.local_transform[id.idx as usize]
     }
 
+    /// Returns the anchor point of a layer, normalized to its bounds.
+    #[must_use]
+    pub fn anchor_point(&self, id: LayerId) -> (f32, f32) {
+        self.validate(id);
+        self.anchor_point[id.idx as usize]
+    }
+
     /// Returns the local opacity of a layer.
     #[must_use]
     pub fn local_opacity(&self, id: LayerId) -> f32 {
@@ -651,6 +1047,22 @@ impl LayerStore {
         self.bounds[id.idx as usize]
     }
 
+    /// Returns the blend mode of a layer.
+    #[must_use]
+    pub fn blend_mode(&self, id: LayerId) -> BlendMode {
+        self.validate(id);
+        self.blend_mode[id.idx as usize]
+    }
+
+    /// Returns the application tag of a layer (0 unless set).
+    ///
+    /// See [`set_tag`](Self::set_tag).
+    #[must_use]
+    pub fn tag(&self, id: LayerId) -> u64 {
+        self.validate(id);
+        self.tag[id.idx as usize]
+    }
+
     /// Returns the optional hit-test region of a layer.
     ///
     /// When `Some`, [`hit_test`](Self::hit_test) checks containment against
@@ -680,6 +1092,10 @@ impl LayerStore {
 
     /// Returns the computed effective opacity of a layer.
     ///
+    /// This is the product of local opacities up to, but not including, the
+    /// nearest [isolated](LayerFlags::isolate) ancestor. An isolated layer's
+    /// own value is the opacity applied once to its flattened group.
+    ///
     /// Only valid after [`evaluate`](Self::evaluate) has been called.
     #[must_use]
     pub fn effective_opacity(&self, id: LayerId) -> f32 {
@@ -687,6 +1103,38 @@ impl LayerStore {
         self.effective_opacity[id.idx as usize]
     }
 
+    /// Returns the opacity of a layer drawn on its own, outside any
+    /// [isolated](LayerFlags::isolate) opacity group.
+    ///
+    /// This is the [effective opacity](Self::effective_opacity) multiplied by
+    /// that of every isolated ancestor. Presenters that cannot composite a
+    /// group offscreen apply this per layer instead; overlapping children of
+    /// a group then show through each other, as they would without
+    /// isolation.
+    ///
+    /// Only valid after [`evaluate`](Self::evaluate) has been called.
+    #[must_use]
+    pub fn ungrouped_opacity(&self, id: LayerId) -> f32 {
+        self.validate(id);
+        self.ungrouped_opacity_at(id.idx)
+    }
+
+    /// Returns the computed effective clip of a layer in world space.
+    ///
+    /// This is the intersection of the world-space bounding boxes of the
+    /// layer's own clip and every ancestor clip, or `None` if neither the
+    /// layer nor any ancestor clips. Each clip is approximated by the
+    /// axis-aligned bounding box of its transformed shape, so rotated or
+    /// rounded clips yield a conservative (larger) rectangle; use the
+    /// per-layer [`clip`](Self::clip) shapes when exact geometry matters.
+    ///
+    /// Only valid after [`evaluate`](Self::evaluate) has been called.
+    #[must_use]
+    pub fn effective_clip(&self, id: LayerId) -> Option<Rect> {
+        self.validate(id);
+        self.effective_clip[id.idx as usize]
+    }
+
     /// Returns whether the layer is effectively hidden (including by an
     /// ancestor's hidden flag).
     ///
@@ -697,6 +1145,117 @@ impl LayerStore {
         self.effective_hidden[id.idx as usize]
     }
 
+    // -- Fallible getters (return `None` for stale handles) --
+    //
+    // Tools such as editors and debuggers may hold handles that outlive their
+    // layers. These mirror the getters above but check `is_alive` instead of
+    // panicking. Where the plain getter already returns an `Option`, the outer
+    // `Option` reports staleness.
+
+    /// Returns the parent of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`parent`](Self::parent).
+    #[must_use]
+    pub fn try_parent(&self, id: LayerId) -> Option<Option<LayerId>> {
+        self.is_alive(id).then(|| self.parent(id))
+    }
+
+    /// Returns the children of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`children`](Self::children).
+    #[must_use]
+    pub fn try_children(&self, id: LayerId) -> Option<Children<'_>> {
+        self.is_alive(id).then(|| self.children(id))
+    }
+
+    /// Returns the local transform of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`local_transform`](Self::local_transform).
+    #[must_use]
+    pub fn try_local_transform(&self, id: LayerId) -> Option<Transform3d> {
+        self.is_alive(id).then(|| self.local_transform(id))
+    }
+
+    /// Returns the local opacity of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`local_opacity`](Self::local_opacity).
+    #[must_use]
+    pub fn try_local_opacity(&self, id: LayerId) -> Option<f32> {
+        self.is_alive(id).then(|| self.local_opacity(id))
+    }
+
+    /// Returns the clip shape of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`clip`](Self::clip).
+    #[must_use]
+    pub fn try_clip(&self, id: LayerId) -> Option<Option<ClipShape>> {
+        self.is_alive(id).then(|| self.clip(id))
+    }
+
+    /// Returns the surface content of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`content`](Self::content).
+    #[must_use]
+    pub fn try_content(&self, id: LayerId) -> Option<Option<SurfaceId>> {
+        self.is_alive(id).then(|| self.content(id))
+    }
+
+    /// Returns the flags of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`flags`](Self::flags).
+    #[must_use]
+    pub fn try_flags(&self, id: LayerId) -> Option<LayerFlags> {
+        self.is_alive(id).then(|| self.flags(id))
+    }
+
+    /// Returns the bounds of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`bounds`](Self::bounds).
+    #[must_use]
+    pub fn try_bounds(&self, id: LayerId) -> Option<Size> {
+        self.is_alive(id).then(|| self.bounds(id))
+    }
+
+    /// Returns the blend mode of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`blend_mode`](Self::blend_mode).
+    #[must_use]
+    pub fn try_blend_mode(&self, id: LayerId) -> Option<BlendMode> {
+        self.is_alive(id).then(|| self.blend_mode(id))
+    }
+
+    /// Returns the computed world transform of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`world_transform`](Self::world_transform).
+    #[must_use]
+    pub fn try_world_transform(&self, id: LayerId) -> Option<Transform3d> {
+        self.is_alive(id).then(|| self.world_transform(id))
+    }
+
+    /// Returns the computed effective opacity of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`effective_opacity`](Self::effective_opacity).
+    #[must_use]
+    pub fn try_effective_opacity(&self, id: LayerId) -> Option<f32> {
+        self.is_alive(id).then(|| self.effective_opacity(id))
+    }
+
+    /// Returns the computed effective clip of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`effective_clip`](Self::effective_clip).
+    #[must_use]
+    pub fn try_effective_clip(&self, id: LayerId) -> Option<Option<Rect>> {
+        self.is_alive(id).then(|| self.effective_clip(id))
+    }
+
+    /// Returns the effective hidden state of a layer, or `None` if `id` is stale.
+    ///
+    /// See [`effective_hidden`](Self::effective_hidden).
+    #[must_use]
+    pub fn try_effective_hidden(&self, id: LayerId) -> Option<bool> {
+        self.is_alive(id).then(|| self.effective_hidden(id))
+    }
+
     // -- Mutation API (auto-marks dirty) --
 
     /// Sets the local transform of a layer.
@@ -705,7 +1264,51 @@ impl LayerStore {
     pub fn set_transform(&mut self, id: LayerId, transform: Transform3d) {
         self.validate(id);
         self.local_transform[id.idx as usize] = transform;
-        self.dirty.mark_with(id.idx, dirty::TRANSFORM, &EagerPolicy);
+        self.mark_dirty_eager(id.idx, dirty::TRANSFORM);
+    }
+
+    /// Replaces only the translation of a layer's local transform, keeping
+    /// its rotation and scale.
+    ///
+    /// Marks the TRANSFORM channel dirty with eager propagation to descendants.
+    pub fn set_local_translation(&mut self, id: LayerId, x: f64, y: f64, z: f64) {
+        self.validate(id);
+        let mut transform = self.local_transform[id.idx as usize];
+        transform.cols[3][0] = x;
+        transform.cols[3][1] = y;
+        transform.cols[3][2] = z;
+        self.set_transform(id, transform);
+    }
+
+    /// Replaces only the per-axis scale of a layer's local transform, keeping
+    /// its rotation and translation.
+    ///
+    /// The linear part is treated as a rotation times a scale: each basis
+    /// column keeps its direction and is resized to the new scale. Shear is
+    /// not preserved, so a sheared transform changes shape. A collapsed
+    /// (zero-length) axis is restored along its unrotated direction.
+    ///
+    /// Marks the TRANSFORM channel dirty with eager propagation to descendants.
+    pub fn set_local_scale(&mut self, id: LayerId, x: f64, y: f64, z: f64) {
+        self.validate(id);
+        let mut transform = self.local_transform[id.idx as usize];
+        for (axis, scale) in [x, y, z].into_iter().enumerate() {
+            let col = &mut transform.cols[axis];
+            let len_sq = col[0] * col[0] + col[1] * col[1] + col[2] * col[2];
+            #[cfg(feature = "std")]
+            let len = len_sq.sqrt();
+            #[cfg(not(feature = "std"))]
+            let len = <f64 as kurbo::common::FloatFuncs>::sqrt(len_sq);
+            if len > 0.0 {
+                for v in &mut col[..3] {
+                    *v *= scale / len;
+                }
+            } else {
+                col[..3].fill(0.0);
+                col[axis] = scale;
+            }
+        }
+        self.set_transform(id, transform);
     }
 
     /// Sets the local opacity of a layer.
@@ -714,14 +1317,17 @@ impl LayerStore {
     pub fn set_opacity(&mut self, id: LayerId, opacity: f32) {
         self.validate(id);
         self.local_opacity[id.idx as usize] = opacity;
-        self.dirty.mark_with(id.idx, dirty::OPACITY, &EagerPolicy);
+        self.mark_dirty_eager(id.idx, dirty::OPACITY);
     }
 
     /// Sets the clip shape of a layer.
+    ///
+    /// Marks the CLIP channel dirty with eager propagation to descendants,
+    /// since their [`effective_clip`](Self::effective_clip) depends on it.
     pub fn set_clip(&mut self, id: LayerId, clip: Option<ClipShape>) {
         self.validate(id);
         self.clip[id.idx as usize] = clip;
-        self.dirty.mark(id.idx, dirty::CLIP);
+        self.mark_dirty_eager(id.idx, dirty::CLIP);
     }
 
     /// Sets the surface content token attached to a layer.
@@ -732,22 +1338,73 @@ impl LayerStore {
     pub fn set_content(&mut self, id: LayerId, content: Option<SurfaceId>) {
         self.validate(id);
         self.content[id.idx as usize] = content;
-        self.dirty.mark(id.idx, dirty::CONTENT);
+        self.mark_dirty(id.idx, dirty::CONTENT);
     }
 
     /// Sets the flags of a layer.
     pub fn set_flags(&mut self, id: LayerId, flags: LayerFlags) {
         self.validate(id);
+        let isolate_changed = self.flags[id.idx as usize].isolate != flags.isolate;
         self.flags[id.idx as usize] = flags;
         // Flags can affect both transform computation (hidden) and topology.
-        self.dirty.mark_with(id.idx, dirty::TRANSFORM, &EagerPolicy);
+        self.mark_dirty_eager(id.idx, dirty::TRANSFORM);
+        // Isolation changes what descendants inherit as their parent opacity.
+        if isolate_changed {
+            self.mark_dirty_eager(id.idx, dirty::OPACITY);
+        }
     }
 
     /// Sets the bounds (width × height) of a layer.
+    ///
+    /// If the layer has a non-origin [anchor point](Self::set_anchor_point),
+    /// this also marks the TRANSFORM channel, since the pivot moves with the
+    /// bounds. Explicit bounds are never replaced by
+    /// [`size_from_surfaces`](Self::size_from_surfaces).
     pub fn set_bounds(&mut self, id: LayerId, bounds: Size) {
         self.validate(id);
+        self.surface_sized.remove(&id.idx);
         self.bounds[id.idx as usize] = bounds;
-        self.dirty.mark(id.idx, dirty::BOUNDS);
+        self.mark_dirty(id.idx, dirty::BOUNDS);
+        if self.anchor_point[id.idx as usize] != (0.0, 0.0) {
+            self.mark_dirty_eager(id.idx, dirty::TRANSFORM);
+        }
+    }
+
+    /// Sets the anchor point that the local transform pivots about.
+    ///
+    /// The anchor is normalized to the layer's [bounds](Self::set_bounds):
+    /// `(0.0, 0.0)` is the top-left corner (the default) and `(0.5, 0.5)` is
+    /// the center. During [`evaluate`](Self::evaluate) the local transform `L`
+    /// is applied as `T(anchor) * L * T(-anchor)`, so rotation and scale keep
+    /// the anchor fixed instead of the layer origin.
+    ///
+    /// Marks the TRANSFORM channel dirty with eager propagation to descendants.
+    pub fn set_anchor_point(&mut self, id: LayerId, anchor: (f32, f32)) {
+        self.validate(id);
+        self.anchor_point[id.idx as usize] = anchor;
+        self.mark_dirty_eager(id.idx, dirty::TRANSFORM);
+    }
+
+    /// Sets the blend mode used to composite a layer over the content behind
+    /// it.
+    pub fn set_blend_mode(&mut self, id: LayerId, blend_mode: BlendMode) {
+        self.validate(id);
+        self.blend_mode[id.idx as usize] = blend_mode;
+        self.mark_dirty(id.idx, dirty::BLEND);
+    }
+
+    /// Attaches an application-defined tag to a layer, such as an entity id
+    /// or a key into host-side tables.
+    ///
+    /// Tags default to 0 and are reset to 0 when a destroyed layer's slot is
+    /// reused, so a stale tag never leaks to a new layer. The store never
+    /// interprets tags. Presenters can read them through
+    /// [`tag_at`](Self::tag_at) to correlate slots with native objects.
+    ///
+    /// No dirty channel is marked — tags do not affect rendering.
+    pub fn set_tag(&mut self, id: LayerId, tag: u64) {
+        self.validate(id);
+        self.tag[id.idx as usize] = tag;
     }
 
     /// Sets an optional hit-test region for a layer (in local coordinates).
@@ -809,6 +1466,31 @@ impl LayerStore {
         self.local_transform[idx as usize]
     }
 
+    /// Returns the local transform at raw slot `idx` with the layer's anchor
+    /// point folded in.
+    ///
+    /// This is the per-layer factor [`evaluate`](Self::evaluate) multiplies
+    /// onto the parent's world transform. Presenters that build native
+    /// hierarchies from local transforms should use this instead of
+    /// [`local_transform_at`](Self::local_transform_at).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.len`.
+    #[must_use]
+    pub fn anchored_transform_at(&self, idx: u32) -> Transform3d {
+        assert!(
+            idx < self.len,
+            "slot index {idx} out of range (len {})",
+            self.len
+        );
+        anchored_transform(
+            self.local_transform[idx as usize],
+            self.anchor_point[idx as usize],
+            self.bounds[idx as usize],
+        )
+    }
+
     /// Returns the local (non-inherited) opacity at raw slot `idx`.
     ///
     /// # Panics
@@ -839,6 +1521,25 @@ impl LayerStore {
         self.effective_opacity[idx as usize]
     }
 
+    /// Returns the [ungrouped opacity](Self::ungrouped_opacity) at raw slot
+    /// `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.len`.
+    #[must_use]
+    pub fn ungrouped_opacity_at(&self, idx: u32) -> f32 {
+        let mut opacity = self.effective_opacity_at(idx);
+        let mut parent = self.parent[idx as usize];
+        while parent != INVALID {
+            if self.flags[parent as usize].isolate {
+                opacity *= self.effective_opacity[parent as usize];
+            }
+            parent = self.parent[parent as usize];
+        }
+        opacity
+    }
+
     /// Returns whether the layer at raw slot `idx` is effectively hidden.
     ///
     /// # Panics
@@ -869,6 +1570,43 @@ impl LayerStore {
         self.clip[idx as usize]
     }
 
+    /// Returns the computed effective clip at raw slot `idx`.
+    ///
+    /// See [`effective_clip`](Self::effective_clip).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.len`.
+    #[must_use]
+    pub fn effective_clip_at(&self, idx: u32) -> Option<Rect> {
+        assert!(
+            idx < self.len,
+            "slot index {idx} out of range (len {})",
+            self.len
+        );
+        self.effective_clip[idx as usize]
+    }
+
+    /// Returns the world-space bounding box of the layer at raw slot `idx`.
+    ///
+    /// This is the axis-aligned bounding box of the layer's
+    /// [bounds](Self::set_bounds) under its world transform, as of the last
+    /// [`evaluate`](Self::evaluate). It is `None` for layers that have empty
+    /// bounds, are effectively hidden, or were removed. Clips are not applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.len`.
+    #[must_use]
+    pub fn world_bounds_at(&self, idx: u32) -> Option<Rect> {
+        assert!(
+            idx < self.len,
+            "slot index {idx} out of range (len {})",
+            self.len
+        );
+        self.world_bounds[idx as usize]
+    }
+
     /// Returns the surface content at raw slot `idx`.
     ///
     /// # Panics
@@ -914,42 +1652,94 @@ impl LayerStore {
         self.bounds[idx as usize]
     }
 
-    /// Returns the hit-test region at raw slot `idx`.
+    /// Returns the blend mode at raw slot `idx`.
     ///
     /// # Panics
     ///
     /// Panics if `idx >= self.len`.
     #[must_use]
-    pub fn hit_region_at(&self, idx: u32) -> Option<HitRegion> {
+    pub fn blend_mode_at(&self, idx: u32) -> BlendMode {
         assert!(
             idx < self.len,
             "slot index {idx} out of range (len {})",
             self.len
         );
-        self.hit_region[idx as usize]
+        self.blend_mode[idx as usize]
     }
 
-    /// Returns the raw parent slot index at raw slot `idx`, or `None` if
-    /// the layer is a root (has no parent).
+    /// Returns the application tag at raw slot `idx`.
     ///
     /// # Panics
     ///
     /// Panics if `idx >= self.len`.
     #[must_use]
-    pub fn parent_at(&self, idx: u32) -> Option<u32> {
+    pub fn tag_at(&self, idx: u32) -> u64 {
         assert!(
             idx < self.len,
             "slot index {idx} out of range (len {})",
             self.len
         );
-        let p = self.parent[idx as usize];
-        if p == INVALID { None } else { Some(p) }
+        self.tag[idx as usize]
     }
 
-    // -- Internal helpers --
-
+    /// Returns the hit-test region at raw slot `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.len`.
+    #[must_use]
+    pub fn hit_region_at(&self, idx: u32) -> Option<HitRegion> {
+        assert!(
+            idx < self.len,
+            "slot index {idx} out of range (len {})",
+            self.len
+        );
+        self.hit_region[idx as usize]
+    }
+
+    /// Returns the raw parent slot index at raw slot `idx`, or `None` if
+    /// the layer is a root (has no parent).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.len`.
+    #[must_use]
+    pub fn parent_at(&self, idx: u32) -> Option<u32> {
+        assert!(
+            idx < self.len,
+            "slot index {idx} out of range (len {})",
+            self.len
+        );
+        let p = self.parent[idx as usize];
+        if p == INVALID { None } else { Some(p) }
+    }
+
+    /// Returns the handle for the layer at raw slot `idx`.
+    ///
+    /// The slot is assumed to be live, e.g. taken from
+    /// [`traversal_order`](Self::traversal_order); a freed slot yields a handle
+    /// that fails validation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.len`.
+    #[must_use]
+    pub fn id_at(&self, idx: u32) -> LayerId {
+        assert!(
+            idx < self.len,
+            "slot index {idx} out of range (len {})",
+            self.len
+        );
+        LayerId {
+            idx,
+            generation: self.generation[idx as usize],
+        }
+    }
+
+    // -- Internal helpers --
+
     /// Panics if the handle is stale.
-    fn validate(&self, id: LayerId) {
+    pub(super) fn validate(&self, id: LayerId) {
         assert!(
             id.idx < self.len && self.generation[id.idx as usize] == id.generation,
             "stale LayerId: {id:?} (current gen: {})",
@@ -976,7 +1766,7 @@ impl LayerStore {
 
     fn mark_topology_reordered(&mut self, parent: u32) {
         self.traversal_dirty = true;
-        self.dirty.mark(parent, dirty::TOPOLOGY);
+        self.mark_dirty(parent, dirty::TOPOLOGY);
     }
 
     fn prepend_unlinked_child(&mut self, parent: u32, child: u32) {
@@ -1065,6 +1855,11 @@ impl LayerStore {
     }
 }
 
+/// Reserves capacity for at least `total` elements in `v`.
+fn reserve_total<T>(v: &mut Vec<T>, total: usize) {
+    v.reserve(total.saturating_sub(v.len()));
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::vec;
@@ -1084,6 +1879,68 @@ mod tests {
         assert!(!store.is_alive(id));
     }
 
+    #[test]
+    fn create_layers_reuses_free_slots_then_allocates() {
+        let mut store = LayerStore::new();
+        let existing = store.create_layers(4);
+        store.destroy_layer(existing[1]);
+        store.destroy_layer(existing[3]);
+        let _ = store.evaluate();
+
+        let ids = store.create_layers(5);
+        assert_eq!(ids.len(), 5);
+        assert_eq!(store.len(), 7);
+        for (i, &id) in ids.iter().enumerate() {
+            assert!(store.is_alive(id));
+            assert!(ids[..i].iter().all(|&other| other != id));
+        }
+        // Freed slots first, in `create_layer` order, then a contiguous run.
+        let slots: Vec<u32> = ids.iter().map(|id| id.index()).collect();
+        assert_eq!(slots, [3, 1, 4, 5, 6]);
+        assert!(!store.is_alive(existing[1]));
+
+        let changes = store.evaluate();
+        assert_eq!(changes.added, slots);
+        assert!(store.create_layers(0).is_empty());
+    }
+
+    #[test]
+    fn create_layers_matches_repeated_create_layer() {
+        let mut batch = LayerStore::new();
+        let mut single = LayerStore::new();
+        for store in [&mut batch, &mut single] {
+            let ids = store.create_layers(3);
+            store.destroy_layer(ids[0]);
+        }
+        let batched = batch.create_layers(3);
+        let singles: Vec<LayerId> = (0..3).map(|_| single.create_layer()).collect();
+        assert_eq!(batched, singles);
+        assert_eq!(batch.evaluate().added, single.evaluate().added);
+    }
+
+    #[test]
+    fn has_pending_changes_tracks_evaluate() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        assert!(store.has_pending_changes());
+        let _ = store.evaluate();
+        assert!(!store.has_pending_changes());
+
+        store.set_opacity(id, 0.5);
+        assert!(store.has_pending_changes());
+        // Peeking does not consume the change.
+        assert!(store.has_pending_changes());
+        let changes = store.evaluate();
+        assert_eq!(changes.opacities, [id.index()]);
+        assert!(!store.has_pending_changes());
+        assert!(store.evaluate().is_empty());
+
+        store.destroy_layer(id);
+        assert!(store.has_pending_changes());
+        let _ = store.evaluate();
+        assert!(!store.has_pending_changes());
+    }
+
     #[test]
     fn len_counts_live_layers() {
         let mut store = LayerStore::new();
@@ -1109,6 +1966,156 @@ mod tests {
         assert!(store.is_empty());
     }
 
+    #[test]
+    fn with_capacity_avoids_reallocation() {
+        fn capacities(store: &LayerStore) -> [usize; 25] {
+            [
+                store.parent.capacity(),
+                store.first_child.capacity(),
+                store.next_sibling.capacity(),
+                store.prev_sibling.capacity(),
+                store.local_transform.capacity(),
+                store.local_opacity.capacity(),
+                store.clip.capacity(),
+                store.content.capacity(),
+                store.flags.capacity(),
+                store.bounds.capacity(),
+                store.hit_region.capacity(),
+                store.hit_policy.capacity(),
+                store.blend_mode.capacity(),
+                store.anchor_point.capacity(),
+                store.tag.capacity(),
+                store.world_transform.capacity(),
+                store.effective_opacity.capacity(),
+                store.effective_clip.capacity(),
+                store.effective_hidden.capacity(),
+                store.world_bounds.capacity(),
+                store.prev_world_bounds.capacity(),
+                store.world_bounds_epoch.capacity(),
+                store.generation.capacity(),
+                store.traversal_order.capacity(),
+                store.pending_added.capacity(),
+            ]
+        }
+
+        let mut store = LayerStore::with_capacity(1000);
+        let before = capacities(&store);
+        assert!(before.iter().all(|&c| c >= 1000));
+        for _ in 0..1000 {
+            let _ = store.create_layer();
+        }
+        assert_eq!(capacities(&store), before);
+
+        store.reserve(10);
+        assert!(capacities(&store).iter().all(|&c| c >= 1010));
+    }
+
+    #[test]
+    fn tags_round_trip_and_reset_on_reuse() {
+        let mut store = LayerStore::new();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        let _ = store.evaluate();
+        assert_eq!(store.tag(a), 0);
+
+        store.set_tag(a, 42);
+        store.set_tag(b, u64::MAX);
+        assert_eq!(store.tag(a), 42);
+        assert_eq!(store.tag_at(b.index()), u64::MAX);
+        assert!(!store.has_pending_changes(), "tags are not rendered state");
+
+        store.destroy_layer(a);
+        let reused = store.create_layer();
+        assert_eq!(reused.index(), a.index());
+        assert_eq!(store.tag(reused), 0);
+
+        store.destroy_layer(b);
+        let bulk = store.create_layers(2);
+        assert!(bulk.iter().any(|id| id.index() == b.index()));
+        assert!(bulk.iter().all(|&id| store.tag(id) == 0));
+    }
+
+    #[test]
+    fn try_getters_return_none_for_stale_handles() {
+        let mut store = LayerStore::new();
+        let parent = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(parent, child);
+        store.set_opacity(child, 0.5);
+        let _ = store.evaluate();
+
+        assert_eq!(store.try_parent(child), Some(Some(parent)));
+        assert_eq!(store.try_effective_opacity(child), Some(0.5));
+        assert_eq!(store.try_children(parent).map(Iterator::count), Some(1));
+
+        store.remove_from_parent(child);
+        store.destroy_layer(child);
+        assert_eq!(store.try_parent(child), None);
+        assert_eq!(store.try_world_transform(child), None);
+        assert_eq!(store.try_effective_opacity(child), None);
+        assert_eq!(store.try_effective_clip(child), None);
+        assert!(store.try_children(child).is_none());
+        assert_eq!(store.try_parent(parent), Some(None));
+    }
+
+    #[test]
+    #[should_panic(expected = "stale LayerId")]
+    fn plain_getter_still_panics_on_stale_handle() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.destroy_layer(id);
+        let _ = store.world_transform(id);
+    }
+
+    #[test]
+    fn mark_all_dirty_reports_every_layer() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        let gone = store.create_layer();
+        store.add_child(root, a);
+        store.add_child(a, b);
+        store.destroy_layer(gone);
+        let _ = store.evaluate();
+        assert!(store.evaluate().is_empty());
+
+        store.mark_all_dirty();
+        let changes = store.evaluate();
+        let mut live = vec![root.idx, a.idx, b.idx];
+        live.sort_unstable();
+        for list in [
+            &changes.added,
+            &changes.transforms,
+            &changes.opacities,
+            &changes.clips,
+            &changes.content,
+            &changes.bounds,
+            &changes.blend_modes,
+        ] {
+            let mut list = list.clone();
+            list.sort_unstable();
+            assert_eq!(list, live);
+        }
+        assert!(changes.removed.is_empty());
+        assert!(changes.topology_changed);
+        assert!(changes.full_rebuild);
+
+        let changes = store.evaluate();
+        assert!(!changes.full_rebuild);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn id_at_round_trips_live_handles() {
+        let mut store = LayerStore::new();
+        let first = store.create_layer();
+        store.destroy_layer(first);
+        let reused = store.create_layer();
+        assert_eq!(store.id_at(reused.index()), reused);
+        assert!(!store.is_alive(first));
+    }
+
     #[test]
     fn generation_prevents_stale_access() {
         let mut store = LayerStore::new();
@@ -1141,6 +2148,79 @@ mod tests {
         assert_eq!(kids[1], child2);
     }
 
+    #[test]
+    fn child_index_and_count_follow_sibling_order() {
+        let mut store = LayerStore::new();
+        let parent = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        let c = store.create_layer();
+        assert_eq!(store.child_count(parent), 0);
+        assert_eq!(store.child_index(a), None);
+
+        store.add_child(parent, a);
+        store.add_child(parent, b);
+        store.add_child(parent, c);
+        assert_eq!(store.child_count(parent), 3);
+        assert_eq!(store.child_index(a), Some(0));
+        assert_eq!(store.child_index(b), Some(1));
+        assert_eq!(store.child_index(c), Some(2));
+        assert_eq!(store.child_count(a), 0);
+
+        store.move_to_front(a);
+        assert_eq!(store.child_index(a), Some(2));
+        assert_eq!(store.child_index(b), Some(0));
+    }
+
+    #[test]
+    fn swap_children_relinks_siblings() {
+        let mut store = LayerStore::new();
+        let parent = store.create_layer();
+        let [a, b, c, d] = [(); 4].map(|()| store.create_layer());
+        for id in [a, b, c, d] {
+            store.add_child(parent, id);
+        }
+        let _ = store.evaluate();
+        let order = |store: &LayerStore| -> Vec<u32> { store.traversal_order().to_vec() };
+        let slots = |ids: [LayerId; 4]| -> Vec<u32> {
+            core::iter::once(parent.index())
+                .chain(ids.map(|id| id.index()))
+                .collect()
+        };
+
+        // First and last.
+        store.swap_children(a, d);
+        let _ = store.evaluate();
+        assert_eq!(order(&store), slots([d, b, c, a]));
+
+        // Adjacent, in either argument order.
+        store.swap_children(b, c);
+        let _ = store.evaluate();
+        assert_eq!(order(&store), slots([d, c, b, a]));
+        store.swap_children(a, b);
+        let _ = store.evaluate();
+        assert_eq!(order(&store), slots([d, c, a, b]));
+        assert_eq!(child_order(&store, parent), vec![d, c, a, b]);
+
+        // Same layer.
+        store.swap_children(c, c);
+        assert!(!store.has_pending_changes());
+        assert_eq!(order(&store), slots([d, c, a, b]));
+    }
+
+    #[test]
+    #[should_panic(expected = "layers must share a parent")]
+    fn swap_children_requires_shared_parent() {
+        let mut store = LayerStore::new();
+        let p1 = store.create_layer();
+        let p2 = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        store.add_child(p1, a);
+        store.add_child(p2, b);
+        store.swap_children(a, b);
+    }
+
     #[test]
     fn remove_from_parent_works() {
         let mut store = LayerStore::new();
@@ -1221,6 +2301,68 @@ mod tests {
         assert!(changes.opacities.is_empty());
     }
 
+    /// Builds `parent` with children `[a, b, c]`, each carrying a child of its
+    /// own, with transforms and opacities evaluated.
+    fn reorder_fixture() -> (LayerStore, LayerId, [LayerId; 3]) {
+        let mut store = LayerStore::new();
+        let parent = store.create_layer();
+        store.set_opacity(parent, 0.5);
+        let kids = [(); 3].map(|()| store.create_layer());
+        for (i, &kid) in kids.iter().enumerate() {
+            store.add_child(parent, kid);
+            store.set_transform(kid, Transform3d::from_translation(i as f64, 0.0, 0.0));
+            let grandchild = store.create_layer();
+            store.add_child(kid, grandchild);
+        }
+        let _ = store.evaluate();
+        (store, parent, kids)
+    }
+
+    fn assert_pure_reorder(store: &mut LayerStore) {
+        let changes = store.evaluate();
+        assert!(changes.topology_changed);
+        assert!(changes.transforms.is_empty(), "{:?}", changes.transforms);
+        assert!(changes.opacities.is_empty(), "{:?}", changes.opacities);
+        assert!(changes.clips.is_empty(), "{:?}", changes.clips);
+    }
+
+    #[test]
+    fn sibling_reorders_only_change_topology() {
+        let (mut store, parent, [a, b, c]) = reorder_fixture();
+
+        store.move_to_front(a);
+        assert_pure_reorder(&mut store);
+        assert_eq!(child_order(&store, parent), vec![b, c, a]);
+
+        store.move_to_back(a);
+        assert_pure_reorder(&mut store);
+
+        // Raising one step.
+        store.move_after(a, b);
+        assert_pure_reorder(&mut store);
+        assert_eq!(child_order(&store, parent), vec![b, a, c]);
+
+        store.move_before(c, b);
+        assert_pure_reorder(&mut store);
+
+        store.swap_children(b, c);
+        assert_pure_reorder(&mut store);
+        assert_eq!(child_order(&store, parent), vec![b, c, a]);
+    }
+
+    #[test]
+    fn reparent_to_same_parent_is_a_pure_reorder() {
+        let (mut store, parent, [a, b, c]) = reorder_fixture();
+
+        store.reparent(a, parent);
+        assert_pure_reorder(&mut store);
+        assert_eq!(child_order(&store, parent), vec![b, c, a]);
+
+        // Already at the front: nothing to do.
+        store.reparent(a, parent);
+        assert!(!store.has_pending_changes());
+    }
+
     #[test]
     fn redundant_reorder_does_not_mark_topology() {
         let mut store = LayerStore::new();
@@ -1367,6 +2509,56 @@ mod tests {
         let _ = store.parent(id);
     }
 
+    fn assert_transform_near(actual: Transform3d, expected: Transform3d) {
+        for (a, e) in actual
+            .cols
+            .iter()
+            .flatten()
+            .zip(expected.cols.iter().flatten())
+        {
+            assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn set_local_translation_keeps_rotation() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        let rotation = Transform3d::from_rotation_z(0.5) * Transform3d::from_scale(2.0, 3.0, 1.0);
+        store.set_transform(id, Transform3d::from_translation(1.0, 2.0, 0.0) * rotation);
+        let _ = store.evaluate();
+
+        store.set_local_translation(id, 10.0, 20.0, 5.0);
+        assert_transform_near(
+            store.local_transform(id),
+            Transform3d::from_translation(10.0, 20.0, 5.0) * rotation,
+        );
+        let changes = store.evaluate();
+        assert!(changes.transforms.contains(&id.idx));
+    }
+
+    #[test]
+    fn set_local_scale_keeps_rotation_and_translation() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        let base = Transform3d::from_translation(4.0, 5.0, 0.0) * Transform3d::from_rotation_z(1.2);
+        store.set_transform(id, base * Transform3d::from_scale(2.0, 2.0, 1.0));
+        let _ = store.evaluate();
+
+        store.set_local_scale(id, 0.5, 3.0, 1.0);
+        assert_transform_near(
+            store.local_transform(id),
+            base * Transform3d::from_scale(0.5, 3.0, 1.0),
+        );
+        let changes = store.evaluate();
+        assert!(changes.transforms.contains(&id.idx));
+
+        // A collapsed axis comes back along its unrotated direction.
+        store.set_local_scale(id, 0.0, 3.0, 1.0);
+        store.set_local_scale(id, 2.0, 3.0, 1.0);
+        assert_eq!(store.local_transform(id).cols[0], [2.0, 0.0, 0.0, 0.0]);
+    }
+
     #[test]
     fn set_transform_marks_dirty() {
         let mut store = LayerStore::new();
@@ -1409,6 +2601,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_clip_round_trips_each_variant() {
+        use crate::layer::ClipShape;
+        use kurbo::Ellipse;
+
+        let rect = Rect::new(0.0, 0.0, 100.0, 60.0);
+        let shapes = [
+            ClipShape::Rect(rect),
+            ClipShape::RoundedRect(RoundedRect::from_rect(rect, 12.0)),
+            ClipShape::Ellipse(Ellipse::from_rect(rect)),
+        ];
+
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        let _ = store.evaluate();
+
+        for shape in shapes {
+            store.set_clip(id, Some(shape));
+            assert_eq!(store.clip(id), Some(shape));
+            assert_eq!(store.clip_at(id.idx), Some(shape));
+
+            let changes = store.evaluate();
+            assert!(
+                changes.clips.contains(&id.idx),
+                "clip channel should report {shape:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn blend_mode_defaults_and_round_trips() {
+        use crate::layer::BlendMode;
+
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        assert_eq!(store.blend_mode(id), BlendMode::SourceOver);
+        let _ = store.evaluate();
+
+        store.set_blend_mode(id, BlendMode::Multiply);
+        assert_eq!(store.blend_mode(id), BlendMode::Multiply);
+        assert_eq!(store.blend_mode_at(id.idx), BlendMode::Multiply);
+        let changes = store.evaluate();
+        assert!(
+            changes.blend_modes.contains(&id.idx),
+            "blend channel should contain the layer"
+        );
+
+        // Reused slots start from the default again.
+        store.destroy_layer(id);
+        let reused = store.create_layer();
+        assert_eq!(reused.idx, id.idx, "slot should be reused");
+        assert_eq!(store.blend_mode(reused), BlendMode::SourceOver);
+    }
+
+    #[test]
+    fn center_anchor_rotation_keeps_center_fixed() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_bounds(id, Size::new(100.0, 40.0));
+        store.set_anchor_point(id, (0.5, 0.5));
+        store.set_transform(
+            id,
+            Transform3d::from_rotation_z(core::f64::consts::FRAC_PI_2),
+        );
+        let _ = store.evaluate();
+
+        let world = store.world_transform(id);
+        let center = world.transform_point(Point::new(50.0, 20.0)).unwrap();
+        assert!(
+            (center.x - 50.0).abs() < 1e-9 && (center.y - 20.0).abs() < 1e-9,
+            "content center should stay fixed, got {center:?}"
+        );
+        let corner = world.transform_point(Point::new(0.0, 0.0)).unwrap();
+        assert!(
+            (corner.x - 70.0).abs() < 1e-9 && (corner.y + 30.0).abs() < 1e-9,
+            "origin should rotate about the center, got {corner:?}"
+        );
+    }
+
+    #[test]
+    fn anchor_tracks_bounds_changes() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        store.set_anchor_point(id, (0.5, 0.5));
+        store.set_transform(id, Transform3d::from_scale(2.0, 2.0, 1.0));
+        store.set_bounds(id, Size::new(10.0, 10.0));
+        let _ = store.evaluate();
+
+        store.set_bounds(id, Size::new(20.0, 20.0));
+        let changes = store.evaluate();
+        assert!(
+            changes.transforms.contains(&id.idx),
+            "bounds change should re-evaluate an anchored transform"
+        );
+        let center = store
+            .world_transform(id)
+            .transform_point(Point::new(10.0, 10.0))
+            .unwrap();
+        assert!(
+            (center.x - 10.0).abs() < 1e-9 && (center.y - 10.0).abs() < 1e-9,
+            "scale should pivot about the new center, got {center:?}"
+        );
+        assert_eq!(store.anchor_point(id), (0.5, 0.5));
+    }
+
     #[test]
     fn set_content_marks_dirty() {
         use crate::layer::SurfaceId;
@@ -1457,7 +2754,13 @@ mod tests {
         let id = store.create_layer();
         let _ = store.evaluate();
 
-        store.set_flags(id, LayerFlags { hidden: true });
+        store.set_flags(
+            id,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
         let changes = store.evaluate();
         assert!(
             changes.transforms.contains(&id.idx),
@@ -1465,6 +2768,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_input_transparent_marks_dirty() {
+        let mut store = LayerStore::new();
+        let id = store.create_layer();
+        let _ = store.evaluate();
+        assert!(!store.flags(id).input_transparent, "defaults to false");
+
+        store.set_flags(
+            id,
+            LayerFlags {
+                input_transparent: true,
+                ..LayerFlags::default()
+            },
+        );
+        assert!(store.has_pending_changes());
+        let changes = store.evaluate();
+        assert!(changes.transforms.contains(&id.idx));
+        assert!(store.flags_at(id.idx).input_transparent);
+        assert!(
+            changes.hidden.is_empty(),
+            "input transparency does not hide the layer"
+        );
+    }
+
     #[test]
     fn bounds_default_is_zero() {
         let mut store = LayerStore::new();
@@ -1559,4 +2886,78 @@ mod tests {
         store.set_opacity(id, 0.42);
         assert!((store.local_opacity_at(id.idx) - 0.42).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn clone_is_independent() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(root, child);
+        store.set_transform(child, Transform3d::from_translation(5.0, 0.0, 0.0));
+        let _ = store.evaluate();
+
+        let mut copy = store.clone();
+        copy.set_transform(root, Transform3d::from_translation(10.0, 0.0, 0.0));
+        copy.set_opacity(child, 0.5);
+        let extra = copy.create_layer();
+        copy.add_child(root, extra);
+        let _ = copy.evaluate();
+
+        assert_eq!(
+            copy.world_transform(child),
+            Transform3d::from_translation(15.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            store.world_transform(child),
+            Transform3d::from_translation(5.0, 0.0, 0.0)
+        );
+        assert!((store.effective_opacity(child) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(store.len(), 2);
+        assert_eq!(copy.len(), 3);
+        assert!(!store.is_alive(extra));
+        assert!(store.evaluate().is_empty());
+    }
+
+    #[test]
+    fn clone_preserves_pending_dirty_state() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        store.add_child(root, a);
+        store.add_child(a, b);
+        let _ = store.evaluate();
+
+        store.set_transform(a, Transform3d::from_translation(1.0, 2.0, 0.0));
+        store.set_opacity(root, 0.25);
+        let gone = store.create_layer();
+        store.destroy_layer(gone);
+        let added = store.create_layer();
+
+        let mut copy = store.clone();
+        let original = store.evaluate();
+        let cloned = copy.evaluate();
+
+        for (lhs, rhs) in [
+            (&original.transforms, &cloned.transforms),
+            (&original.opacities, &cloned.opacities),
+            (&original.clips, &cloned.clips),
+            (&original.content, &cloned.content),
+            (&original.added, &cloned.added),
+            (&original.removed, &cloned.removed),
+        ] {
+            let (mut lhs, mut rhs) = (lhs.clone(), rhs.clone());
+            lhs.sort_unstable();
+            rhs.sort_unstable();
+            assert_eq!(lhs, rhs);
+        }
+        assert_eq!(original.topology_changed, cloned.topology_changed);
+        for id in [root, a, b, added] {
+            assert_eq!(store.world_transform(id), copy.world_transform(id));
+            assert_eq!(store.effective_opacity(id), copy.effective_opacity(id));
+        }
+
+        // Generations and free list match, so the next allocation agrees.
+        assert_eq!(store.create_layer(), copy.create_layer());
+    }
 }
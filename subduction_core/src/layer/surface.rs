@@ -0,0 +1,154 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Sizing content layers from their surfaces.
+//!
+//! A layer showing a decoded image or video frame usually wants exactly the
+//! surface's pixel size. [`LayerStore::size_from_surfaces`] asks a
+//! [`SurfaceResolver`] for those sizes so hosts do not mirror them by hand.
+
+use kurbo::Size;
+
+use crate::backend::SurfaceResolver;
+
+use super::store::LayerStore;
+
+impl LayerStore {
+    /// Sizes content layers from their surfaces.
+    ///
+    /// Every live layer with [content](Self::set_content) and zero-area
+    /// [bounds](Self::set_bounds) takes the pixel size `resolver` reports for
+    /// its surface, as if set with [`set_bounds`](Self::set_bounds). Layers
+    /// sized this way keep following their surface: when a later call
+    /// resolves a different size (a video changing resolution), the bounds
+    /// are updated again. Bounds set explicitly with `set_bounds` are never
+    /// replaced, and surfaces the resolver does not know are skipped. Returns
+    /// the number of layers resized.
+    ///
+    /// Call this before [`evaluate`](Self::evaluate) so world bounds, culling,
+    /// and hit testing see the resolved sizes.
+    pub fn size_from_surfaces<R: SurfaceResolver + ?Sized>(&mut self, resolver: &R) -> usize {
+        let mut live = alloc::vec![true; self.len as usize];
+        for &idx in &self.free_list {
+            live[idx as usize] = false;
+        }
+        let mut resized = 0;
+        for idx in 0..self.len {
+            let i = idx as usize;
+            let Some(surface) = self.content[i] else {
+                continue;
+            };
+            if !live[i] {
+                continue;
+            }
+            if !self.bounds[i].is_zero_area() && !self.surface_sized.contains(&idx) {
+                continue;
+            }
+            let Some((width, height)) = resolver.size(surface) else {
+                continue;
+            };
+            let size = Size::new(f64::from(width), f64::from(height));
+            if size.is_zero_area() || size == self.bounds[i] {
+                continue;
+            }
+            let id = self.id_at(idx);
+            self.set_bounds(id, size);
+            self.surface_sized.insert(idx);
+            resized += 1;
+        }
+        resized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use kurbo::Rect;
+
+    use super::*;
+    use crate::layer::{SurfaceId, SurfaceIds};
+    use crate::transform::Transform3d;
+
+    struct MockResolver(Vec<(SurfaceId, (u32, u32))>);
+
+    impl SurfaceResolver for MockResolver {
+        type Handle = ();
+
+        fn size(&self, surface: SurfaceId) -> Option<(u32, u32)> {
+            self.0
+                .iter()
+                .find(|(id, _)| *id == surface)
+                .map(|(_, size)| *size)
+        }
+    }
+
+    #[test]
+    fn resolved_sizes_feed_world_bounds() {
+        let mut surfaces = SurfaceIds::default();
+        let video = surfaces.create();
+        let unknown = surfaces.create();
+        let resolver = MockResolver(vec![(video, (640, 360))]);
+
+        let mut store = LayerStore::new();
+        let player = store.create_layer();
+        let sized = store.create_layer();
+        let missing = store.create_layer();
+        store.set_content(player, Some(video));
+        store.set_transform(player, Transform3d::from_translation(10.0, 20.0, 0.0));
+        store.set_content(sized, Some(video));
+        store.set_bounds(sized, Size::new(320.0, 180.0));
+        store.set_content(missing, Some(unknown));
+
+        assert_eq!(store.size_from_surfaces(&resolver), 1);
+        let changes = store.evaluate();
+        assert!(changes.bounds.contains(&player.index()));
+
+        assert_eq!(store.bounds(player), Size::new(640.0, 360.0));
+        assert_eq!(
+            store.world_bounds_at(player.index()),
+            Some(Rect::new(10.0, 20.0, 650.0, 380.0))
+        );
+        assert_eq!(store.bounds(sized), Size::new(320.0, 180.0));
+        assert_eq!(store.world_bounds_at(missing.index()), None);
+
+        // Already sized layers are left alone on later calls.
+        assert_eq!(store.size_from_surfaces(&resolver), 0);
+    }
+
+    #[test]
+    fn resolved_bounds_follow_surface_resizes() {
+        let mut surfaces = SurfaceIds::default();
+        let video = surfaces.create();
+
+        let mut store = LayerStore::new();
+        let player = store.create_layer();
+        let pinned = store.create_layer();
+        store.set_content(player, Some(video));
+        store.set_content(pinned, Some(video));
+        store.set_bounds(pinned, Size::new(320.0, 180.0));
+
+        assert_eq!(
+            store.size_from_surfaces(&MockResolver(vec![(video, (640, 360))])),
+            1
+        );
+        let _ = store.evaluate();
+
+        // The stream switches resolution.
+        let resolver = MockResolver(vec![(video, (1280, 720))]);
+        assert_eq!(store.size_from_surfaces(&resolver), 1);
+        let changes = store.evaluate();
+        assert_eq!(changes.bounds, [player.index()]);
+        assert_eq!(store.bounds(player), Size::new(1280.0, 720.0));
+        assert_eq!(store.bounds(pinned), Size::new(320.0, 180.0));
+
+        // Explicit bounds take the layer out of surface sizing.
+        store.set_bounds(player, Size::new(100.0, 100.0));
+        assert_eq!(
+            store.size_from_surfaces(&MockResolver(vec![(video, (1920, 1080))])),
+            0
+        );
+        assert_eq!(store.bounds(player), Size::new(100.0, 100.0));
+    }
+}
@@ -38,11 +38,12 @@
 //!
 //! **[`layer`]** — Struct-of-arrays layer tree with generational handles.
 //! Properties (transform, opacity, clip, content) are set by the caller;
-//! world transforms and effective opacities are computed by evaluation.
+//! world transforms, effective opacities, and effective clips are computed by
+//! evaluation.
 //!
 //! **[`dirty`]** — Multi-channel dirty tracking via `invalidation`.
-//! Property mutations automatically mark the appropriate channel. TRANSFORM
-//! and OPACITY propagate to descendants; CLIP and CONTENT are local-only;
+//! Property mutations automatically mark the appropriate channel. TRANSFORM,
+//! OPACITY, and CLIP propagate to descendants; CONTENT is local-only;
 //! TOPOLOGY triggers a traversal rebuild.
 //!
 //! **[`timing`]** — Compatibility re-export of `frameclock::timing`.
@@ -50,9 +51,12 @@
 //! **[`scheduler`]** — Compatibility re-export of `frameclock::scheduler`.
 //!
 //! **[`backend`]** — The [`Presenter`](backend::Presenter) trait that
-//! platform backends implement to apply frame changes to native trees.
+//! platform backends implement to apply frame changes to native trees, and
+//! the [`SurfaceResolver`](backend::SurfaceResolver) trait hosts implement to
+//! report surface sizes and native handles.
 //!
-//! **[`transform`]** — 3D affine transform type for layer positioning.
+//! **[`transform`]** — 3D affine transform type for layer positioning, with a
+//! 2D fast path.
 //!
 //! **[`output`]** — Layer-root presentation policy such as the backdrop style,
 //! plus a compatibility re-export of `frameclock::OutputId`.
@@ -61,6 +65,9 @@
 //! phase summaries, and rich layer/damage events. Timing diagnostics are
 //! compatibility re-exports of the `frameclock` event types.
 //!
+//! **`testing`** (with the `test-util` feature) — A scripted tick source and
+//! a recording presenter for headless frame-loop tests.
+//!
 //! # Crate features
 //!
 //! - `std` (disabled by default): Enables `std` support in dependencies and
@@ -69,6 +76,12 @@
 //!   per call site).
 //! - `trace-rich` (disabled by default, implies `trace`): Gates per-layer
 //!   change and damage-rect events.
+//! - `rayon` (disabled by default, implies `std`): Recomputes large sets of
+//!   dirty world transforms in parallel across independent subtrees.
+//! - `serde` (disabled by default): Implements `Serialize` and `Deserialize`
+//!   for [`LayerStore`](layer::LayerStore) scenes and the value types they
+//!   contain.
+//! - `test-util` (disabled by default): Enables the `testing` module.
 
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -79,6 +92,8 @@ pub mod backend;
 pub mod dirty;
 pub mod layer;
 pub mod output;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
 pub mod trace;
 pub mod transform;
 
@@ -6,8 +6,14 @@
 //! This module defines backend-neutral layer-root semantics such as the
 //! backdrop color. These settings belong to the root container the scene is
 //! presented into, not to any particular layer in the scene tree.
+//!
+//! [`OutputRouter`] routes frame ticks to per-output host state when one
+//! frame loop drives several displays.
+
+use alloc::vec::Vec;
 
 use color::{AlphaColor, Srgb};
+use frameclock::FrameTick;
 
 pub use frameclock::OutputId;
 
@@ -39,10 +45,156 @@ pub enum Backdrop {
     Color(Color),
 }
 
+/// Per-output state keyed by [`OutputId`].
+///
+/// Backends that drive several displays from one frame loop keep one `S`
+/// (presenter, scheduler, layer store, ...) per output. [`route`](Self::route)
+/// looks up the state for a tick's [`output`](FrameTick::output), creating it
+/// on first use, and hands it to a closure together with the tick.
+///
+/// Outputs are stored in first-seen order and looked up linearly; hosts rarely
+/// have more than a handful of displays.
+#[derive(Clone, Debug)]
+pub struct OutputRouter<S> {
+    entries: Vec<(OutputId, S)>,
+}
+
+impl<S> Default for OutputRouter<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> OutputRouter<S> {
+    /// Creates a router with no outputs.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Calls `f` with the state for `tick.output` and the tick itself.
+    ///
+    /// If the output has not been seen before, its state is first created
+    /// with `init`.
+    pub fn route<R>(
+        &mut self,
+        tick: &FrameTick,
+        init: impl FnOnce(OutputId) -> S,
+        f: impl FnOnce(&mut S, &FrameTick) -> R,
+    ) -> R {
+        f(self.state_mut(tick.output, init), tick)
+    }
+
+    /// Returns the state for `output`, creating it with `init` if needed.
+    pub fn state_mut(&mut self, output: OutputId, init: impl FnOnce(OutputId) -> S) -> &mut S {
+        let idx = match self.position(output) {
+            Some(idx) => idx,
+            None => {
+                self.entries.push((output, init(output)));
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[idx].1
+    }
+
+    /// Returns the state for `output`, if it exists.
+    #[must_use]
+    pub fn get(&self, output: OutputId) -> Option<&S> {
+        self.position(output).map(|idx| &self.entries[idx].1)
+    }
+
+    /// Returns the state for `output` mutably, if it exists.
+    #[must_use]
+    pub fn get_mut(&mut self, output: OutputId) -> Option<&mut S> {
+        self.position(output).map(|idx| &mut self.entries[idx].1)
+    }
+
+    /// Removes and returns the state for `output`, e.g. when a display is
+    /// disconnected.
+    pub fn remove(&mut self, output: OutputId) -> Option<S> {
+        self.position(output).map(|idx| self.entries.remove(idx).1)
+    }
+
+    /// Returns the number of outputs with state.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no output has state.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over outputs and their state in first-seen order.
+    pub fn iter(&self) -> impl Iterator<Item = (OutputId, &S)> {
+        self.entries.iter().map(|(output, state)| (*output, state))
+    }
+
+    fn position(&self, output: OutputId) -> Option<usize> {
+        self.entries.iter().position(|(id, _)| *id == output)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use frameclock::HostTime;
+
     use super::*;
 
+    fn tick(output: u32, frame_index: u64) -> FrameTick {
+        FrameTick::builder()
+            .now(HostTime(frame_index * 1_000))
+            .frame_index(frame_index)
+            .output(OutputId(output))
+            .build()
+    }
+
+    #[test]
+    fn outputs_get_independent_state() {
+        let mut router = OutputRouter::new();
+        for (output, frame_index) in [(0, 0), (1, 0), (0, 1), (0, 2), (1, 1)] {
+            router.route(
+                &tick(output, frame_index),
+                |_| Vec::new(),
+                |frames: &mut Vec<u64>, tick| frames.push(tick.frame_index),
+            );
+        }
+
+        assert_eq!(router.len(), 2);
+        assert_eq!(router.get(OutputId(0)).unwrap(), &[0, 1, 2]);
+        assert_eq!(router.get(OutputId(1)).unwrap(), &[0, 1]);
+    }
+
+    #[test]
+    fn new_output_initializes_lazily() {
+        let mut router = OutputRouter::new();
+        let mut inits = Vec::new();
+        let mut init = |output: OutputId| {
+            inits.push(output);
+            output.0 * 10
+        };
+
+        assert!(router.is_empty());
+        assert!(router.get(OutputId(3)).is_none());
+        let seen = router.route(&tick(3, 0), &mut init, |state, _| *state);
+        assert_eq!(seen, 30);
+        router.route(&tick(3, 1), &mut init, |state, _| *state += 1);
+        router.route(&tick(7, 0), &mut init, |_, _| ());
+
+        assert_eq!(inits, [OutputId(3), OutputId(7)]);
+        assert_eq!(router.get(OutputId(3)), Some(&31));
+        assert_eq!(
+            router.iter().collect::<Vec<_>>(),
+            [(OutputId(3), &31), (OutputId(7), &70)]
+        );
+        assert_eq!(router.remove(OutputId(3)), Some(31));
+        assert_eq!(router.len(), 1);
+    }
+
     #[test]
     fn backdrop_none_is_distinct_from_color() {
         assert_ne!(
@@ -0,0 +1,343 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Headless test doubles for driving a frame loop without a platform backend.
+//!
+//! [`FakeTickSource`] emits scripted [`FrameTick`]s on a fixed cadence, and
+//! [`CapturePresenter`] records every [`FrameChanges`] it is asked to apply.
+//! Together they let scheduler, store, and presenter integration be tested
+//! end to end:
+//!
+//! ```rust,ignore
+//! use subduction_core::backend::Presenter;
+//! use subduction_core::layer::LayerStore;
+//! use subduction_core::testing::{CapturePresenter, FakeTickSource};
+//! use subduction_core::time::{Duration, HostTime};
+//!
+//! let mut ticks = FakeTickSource::new(HostTime(0), Duration(16));
+//! let mut store = LayerStore::new();
+//! let layer = store.create_layer();
+//! let mut presenter = CapturePresenter::new();
+//!
+//! for tick in ticks.by_ref().take(3) {
+//!     store.set_opacity(layer, 1.0 / (tick.frame_index + 2) as f32);
+//!     let changes = store.evaluate();
+//!     presenter.present_frame(&tick, &store, &changes);
+//! }
+//!
+//! assert_eq!(presenter.frames_presented(), 3);
+//! assert_eq!(presenter.applies()[2].changes.opacities, [layer.index()]);
+//! ```
+//!
+//! This module is available with the `test-util` feature.
+
+use alloc::vec::Vec;
+
+use frameclock::timing::{DisplayTiming, FrameOpportunity, PresentHints, PresentationTiming};
+use frameclock::{Duration, FrameTick, HostTime, OutputId};
+
+use crate::backend::Presenter;
+use crate::layer::{FrameChanges, LayerStore};
+
+/// Emits scripted [`FrameTick`]s at a fixed refresh interval.
+///
+/// Each tick is one refresh interval after the previous one and carries the
+/// next frame index. By default ticks are
+/// [`Predictive`](PresentationTiming::Predictive), with a predicted present
+/// two intervals after the tick (one interval to build, one to scan out) and
+/// the previous tick's prediction reported as its actual present time.
+///
+/// The source is also an unbounded [`Iterator`] over ticks, so a test can
+/// drive a fixed number of frames with `.take(n)`.
+#[derive(Clone, Debug)]
+pub struct FakeTickSource {
+    /// Time of the next tick.
+    now: HostTime,
+    interval: Duration,
+    frame_index: u64,
+    output: OutputId,
+    timing: PresentationTiming,
+    /// Predicted present time relative to the tick, or `None` for ticks
+    /// without a prediction.
+    prediction_lead: Option<Duration>,
+    /// Predicted present of the previous tick.
+    last_present: Option<HostTime>,
+}
+
+impl FakeTickSource {
+    /// Creates a source whose first tick is at `start`, with one tick every
+    /// `interval`.
+    #[must_use]
+    pub fn new(start: HostTime, interval: Duration) -> Self {
+        Self {
+            now: start,
+            interval,
+            frame_index: 0,
+            output: OutputId(0),
+            timing: PresentationTiming::Predictive,
+            prediction_lead: Some(interval.saturating_mul(2)),
+            last_present: None,
+        }
+    }
+
+    /// Returns this source reporting the given timing confidence in the
+    /// hints of [`next_opportunity`](Self::next_opportunity).
+    ///
+    /// [`PacingOnly`](PresentationTiming::PacingOnly) also drops the
+    /// predicted present time from every tick.
+    #[must_use]
+    pub fn with_presentation_timing(mut self, timing: PresentationTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Returns this source predicting presentation `lead` after each tick,
+    /// or with `None`, emitting ticks without a predicted present time.
+    #[must_use]
+    pub fn with_prediction_lead(mut self, lead: Option<Duration>) -> Self {
+        self.prediction_lead = lead;
+        self
+    }
+
+    /// Returns this source emitting ticks for `output`.
+    #[must_use]
+    pub fn with_output(mut self, output: OutputId) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Returns the time of the next tick.
+    #[must_use]
+    pub fn now(&self) -> HostTime {
+        self.now
+    }
+
+    /// Returns the refresh interval.
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Advances time by `frames` refresh intervals without emitting ticks,
+    /// as when the host misses display callbacks.
+    ///
+    /// The frame index is not advanced, and the next tick reports no previous
+    /// present time.
+    pub fn skip_frames(&mut self, frames: u64) {
+        self.now = self.now + self.interval.saturating_mul(frames);
+        self.last_present = None;
+    }
+
+    /// Emits the next tick and advances time by one refresh interval.
+    pub fn next_tick(&mut self) -> FrameTick {
+        let predicted_present = self
+            .prediction_lead
+            .filter(|_| self.timing.has_target_present())
+            .map(|lead| self.now + lead);
+        let tick = FrameTick::builder()
+            .now(self.now)
+            .predicted_present(predicted_present)
+            .refresh_interval(Some(self.interval.ticks()))
+            .frame_index(self.frame_index)
+            .output(self.output)
+            .prev_actual_present(self.last_present)
+            .build();
+        self.now = self.now + self.interval;
+        self.frame_index += 1;
+        self.last_present = predicted_present;
+        tick
+    }
+
+    /// Emits the next tick packaged as a [`FrameOpportunity`] for a
+    /// scheduler or frame driver.
+    ///
+    /// The hints carry the configured timing confidence, the tick's predicted
+    /// present as the desired present, and a commit deadline one refresh
+    /// interval after the tick.
+    pub fn next_opportunity(&mut self) -> FrameOpportunity {
+        let tick = self.next_tick();
+        let hints = PresentHints::new(
+            self.timing,
+            tick.predicted_present,
+            tick.now + self.interval,
+        );
+        FrameOpportunity::new(tick, hints, DisplayTiming::fixed(self.interval))
+    }
+}
+
+impl Iterator for FakeTickSource {
+    type Item = FrameTick;
+
+    fn next(&mut self) -> Option<FrameTick> {
+        Some(self.next_tick())
+    }
+}
+
+/// One [`apply`](Presenter::apply) call recorded by a [`CapturePresenter`].
+#[derive(Clone, Debug)]
+pub struct CapturedApply {
+    /// Frame index of the enclosing [`begin_frame`](Presenter::begin_frame),
+    /// or `None` for an `apply` outside a frame bracket.
+    pub frame_index: Option<u64>,
+    /// The changes that were applied.
+    pub changes: FrameChanges,
+}
+
+/// A [`Presenter`] that presents nothing and records every `apply` call.
+#[derive(Clone, Debug, Default)]
+pub struct CapturePresenter {
+    applies: Vec<CapturedApply>,
+    open_frame: Option<u64>,
+    frames_presented: u64,
+}
+
+impl CapturePresenter {
+    /// Creates a presenter with nothing recorded.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns every recorded `apply` call, oldest first.
+    #[must_use]
+    pub fn applies(&self) -> &[CapturedApply] {
+        &self.applies
+    }
+
+    /// Returns the most recent `apply` call, if any.
+    #[must_use]
+    pub fn last(&self) -> Option<&CapturedApply> {
+        self.applies.last()
+    }
+
+    /// Removes and returns every recorded `apply` call.
+    pub fn take_applies(&mut self) -> Vec<CapturedApply> {
+        core::mem::take(&mut self.applies)
+    }
+
+    /// Returns the number of frames closed by
+    /// [`end_frame`](Presenter::end_frame).
+    #[must_use]
+    pub fn frames_presented(&self) -> u64 {
+        self.frames_presented
+    }
+}
+
+impl Presenter for CapturePresenter {
+    fn apply(&mut self, _store: &LayerStore, changes: &FrameChanges) {
+        self.applies.push(CapturedApply {
+            frame_index: self.open_frame,
+            changes: changes.clone(),
+        });
+    }
+
+    fn begin_frame(&mut self, tick: &FrameTick) {
+        self.open_frame = Some(tick.frame_index);
+    }
+
+    fn end_frame(&mut self) {
+        if self.open_frame.take().is_some() {
+            self.frames_presented += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::Transform3d;
+
+    #[test]
+    fn ticks_follow_the_script() {
+        let mut ticks = FakeTickSource::new(HostTime(100), Duration(10));
+        let first = ticks.next_tick();
+        let second = ticks.next_tick();
+        assert_eq!((first.now, first.frame_index), (HostTime(100), 0));
+        assert_eq!((second.now, second.frame_index), (HostTime(110), 1));
+        assert_eq!(first.predicted_present, Some(HostTime(120)));
+        assert_eq!(first.prev_actual_present, None);
+        assert_eq!(second.prev_actual_present, first.predicted_present);
+        assert_eq!(second.refresh_interval, Some(10));
+
+        ticks.skip_frames(2);
+        let late = ticks.next_tick();
+        assert_eq!((late.now, late.frame_index), (HostTime(140), 2));
+        assert_eq!(late.prev_actual_present, None);
+    }
+
+    #[test]
+    fn opportunities_carry_configured_confidence() {
+        let mut ticks = FakeTickSource::new(HostTime(0), Duration(10))
+            .with_presentation_timing(PresentationTiming::Estimated)
+            .with_prediction_lead(Some(Duration(15)));
+        let opportunity = ticks.next_opportunity();
+        let hints = opportunity.hints;
+        assert_eq!(hints.presentation_timing(), PresentationTiming::Estimated);
+        assert_eq!(hints.desired_present(), Some(HostTime(15)));
+        assert_eq!(hints.latest_commit(), HostTime(10));
+
+        let mut pacing = FakeTickSource::new(HostTime(0), Duration(10))
+            .with_presentation_timing(PresentationTiming::PacingOnly);
+        let opportunity = pacing.next_opportunity();
+        assert_eq!(opportunity.tick.predicted_present, None);
+        assert_eq!(opportunity.hints.desired_present(), None);
+    }
+
+    #[test]
+    fn ten_scripted_frames_reach_the_presenter() {
+        let mut store = LayerStore::new();
+        let root = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(root, child);
+        let mut presenter = CapturePresenter::new();
+        let changes = store.evaluate();
+        presenter.apply(&store, &changes);
+
+        let mut extra = None;
+        for tick in FakeTickSource::new(HostTime(0), Duration(16)).take(10) {
+            let i = tick.frame_index;
+            store.set_transform(
+                root,
+                Transform3d::from_translation((i + 1) as f64, 0.0, 0.0),
+            );
+            match i {
+                4 => store.set_opacity(child, 0.5),
+                6 => {
+                    let layer = store.create_layer();
+                    store.add_child(root, layer);
+                    extra = Some(layer);
+                }
+                8 => store.destroy_layer(extra.unwrap()),
+                _ => {}
+            }
+            let changes = store.evaluate();
+            presenter.present_frame(&tick, &store, &changes);
+        }
+
+        assert_eq!(presenter.frames_presented(), 10);
+        let applies = presenter.applies();
+        assert_eq!(applies.len(), 11);
+        assert_eq!(applies[0].frame_index, None);
+        assert_eq!(applies[0].changes.added, [root.index(), child.index()]);
+
+        let extra = extra.unwrap().index();
+        for (i, apply) in (0..).zip(&applies[1..]) {
+            let changes = &apply.changes;
+            assert_eq!(apply.frame_index, Some(i));
+            assert!(changes.transforms.contains(&root.index()), "frame {i}");
+            assert!(changes.transforms.contains(&child.index()), "frame {i}");
+            // A newly attached layer inherits its parent's opacity.
+            let opacities: &[u32] = match i {
+                4 => &[child.index()],
+                6 => &[extra],
+                _ => &[],
+            };
+            assert_eq!(changes.opacities, opacities, "frame {i}");
+            let added: &[u32] = if i == 6 { &[extra] } else { &[] };
+            assert_eq!(changes.added, added, "frame {i}");
+            let removed: &[u32] = if i == 8 { &[extra] } else { &[] };
+            assert_eq!(changes.removed, removed, "frame {i}");
+            assert_eq!(changes.topology_changed, i == 6 || i == 8, "frame {i}");
+        }
+    }
+}
@@ -24,6 +24,7 @@
 //! - `trace-rich` (implies `trace`) — gates `LayerChange` and `DamageRect`
 //!   events plus the corresponding `TraceSink` methods.
 
+use frameclock::diagnostics::DiagnosticsSink;
 use frameclock::timing::PresentationTiming;
 use frameclock::{HostTime, OutputId};
 
@@ -43,10 +44,14 @@ pub enum PhaseKind {
     Plan,
     /// Layer tree evaluation (dirty propagation, world transforms).
     Evaluate,
+    /// Acquiring the next drawable (e.g. a swapchain texture).
+    Acquire,
     /// Backend rendering / compositing.
     Render,
     /// Submitting the frame to the display pipeline.
     Submit,
+    /// Handing the drawable to the display (e.g. swapchain present).
+    Present,
 }
 
 /// Which property of a layer changed.
@@ -61,6 +66,10 @@ pub enum LayerField {
     Clip,
     /// Content (surface, texture, etc.).
     Content,
+    /// Layer bounds.
+    Bounds,
+    /// Blend mode.
+    BlendMode,
     /// Layer flags.
     Flags,
     /// Topology (parent/child relationships).
@@ -116,10 +125,14 @@ pub struct FrameSummary {
     pub plan_ticks: u64,
     /// Evaluate phase duration in ticks (0 if not measured).
     pub eval_ticks: u64,
+    /// Acquire phase duration in ticks (0 if not measured).
+    pub acquire_ticks: u64,
     /// Render phase duration in ticks (0 if not measured).
     pub render_ticks: u64,
     /// Submit phase duration in ticks (0 if not measured).
     pub submit_ticks: u64,
+    /// Present phase duration in ticks (0 if not measured).
+    pub present_ticks: u64,
     /// Whether the deadline was missed.
     pub missed_deadline: bool,
 }
@@ -225,6 +238,378 @@ pub struct NoopSink;
 
 impl TraceSink for NoopSink {}
 
+// ---------------------------------------------------------------------------
+// TeeSink
+// ---------------------------------------------------------------------------
+
+/// A [`TraceSink`] that forwards every event to two inner sinks.
+///
+/// Events reach `first` before `second`. Nest tees to fan out to more sinks,
+/// e.g. `TeeSink::new(a, TeeSink::new(b, c))`. The inner sinks can be owned
+/// values or `&mut` references.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TeeSink<A, B> {
+    /// The sink that receives each event first.
+    pub first: A,
+    /// The sink that receives each event second.
+    pub second: B,
+}
+
+impl<A, B> TeeSink<A, B> {
+    /// Creates a tee forwarding to `first`, then `second`.
+    #[inline]
+    #[must_use]
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Consumes the tee and returns the inner sinks.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: TraceSink, B: TraceSink> TraceSink for TeeSink<A, B> {
+    fn on_frame_tick(&mut self, e: &FrameTickEvent) {
+        self.first.on_frame_tick(e);
+        self.second.on_frame_tick(e);
+    }
+
+    fn on_frame_plan(&mut self, e: &FramePlanEvent) {
+        self.first.on_frame_plan(e);
+        self.second.on_frame_plan(e);
+    }
+
+    fn on_phase_begin(&mut self, e: &PhaseBeginEvent) {
+        self.first.on_phase_begin(e);
+        self.second.on_phase_begin(e);
+    }
+
+    fn on_phase_end(&mut self, e: &PhaseEndEvent) {
+        self.first.on_phase_end(e);
+        self.second.on_phase_end(e);
+    }
+
+    fn on_submit(&mut self, e: &SubmitEvent) {
+        self.first.on_submit(e);
+        self.second.on_submit(e);
+    }
+
+    fn on_present_feedback(&mut self, e: &PresentFeedbackEvent) {
+        self.first.on_present_feedback(e);
+        self.second.on_present_feedback(e);
+    }
+
+    fn on_scheduler_state(&mut self, e: &SchedulerStateEvent) {
+        self.first.on_scheduler_state(e);
+        self.second.on_scheduler_state(e);
+    }
+
+    fn on_frame_timing_summary(&mut self, s: &FrameTimingSummary) {
+        self.first.on_frame_timing_summary(s);
+        self.second.on_frame_timing_summary(s);
+    }
+
+    fn on_frame_summary(&mut self, s: &FrameSummary) {
+        self.first.on_frame_summary(s);
+        self.second.on_frame_summary(s);
+    }
+
+    #[cfg(feature = "trace-rich")]
+    fn on_layer_changes(&mut self, frame_index: u64, changes: &[LayerChange]) {
+        self.first.on_layer_changes(frame_index, changes);
+        self.second.on_layer_changes(frame_index, changes);
+    }
+
+    #[cfg(feature = "trace-rich")]
+    fn on_damage_rects(&mut self, frame_index: u64, rects: &[DamageRect]) {
+        self.first.on_damage_rects(frame_index, rects);
+        self.second.on_damage_rects(frame_index, rects);
+    }
+}
+
+impl<S: TraceSink + ?Sized> TraceSink for &mut S {
+    fn on_frame_tick(&mut self, e: &FrameTickEvent) {
+        (**self).on_frame_tick(e);
+    }
+
+    fn on_frame_plan(&mut self, e: &FramePlanEvent) {
+        (**self).on_frame_plan(e);
+    }
+
+    fn on_phase_begin(&mut self, e: &PhaseBeginEvent) {
+        (**self).on_phase_begin(e);
+    }
+
+    fn on_phase_end(&mut self, e: &PhaseEndEvent) {
+        (**self).on_phase_end(e);
+    }
+
+    fn on_submit(&mut self, e: &SubmitEvent) {
+        (**self).on_submit(e);
+    }
+
+    fn on_present_feedback(&mut self, e: &PresentFeedbackEvent) {
+        (**self).on_present_feedback(e);
+    }
+
+    fn on_scheduler_state(&mut self, e: &SchedulerStateEvent) {
+        (**self).on_scheduler_state(e);
+    }
+
+    fn on_frame_timing_summary(&mut self, s: &FrameTimingSummary) {
+        (**self).on_frame_timing_summary(s);
+    }
+
+    fn on_frame_summary(&mut self, s: &FrameSummary) {
+        (**self).on_frame_summary(s);
+    }
+
+    #[cfg(feature = "trace-rich")]
+    fn on_layer_changes(&mut self, frame_index: u64, changes: &[LayerChange]) {
+        (**self).on_layer_changes(frame_index, changes);
+    }
+
+    #[cfg(feature = "trace-rich")]
+    fn on_damage_rects(&mut self, frame_index: u64, rects: &[DamageRect]) {
+        (**self).on_damage_rects(frame_index, rects);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FilterSink
+// ---------------------------------------------------------------------------
+
+/// A borrowed view of a single trace event, passed to [`FilterSink`]
+/// predicates.
+#[derive(Clone, Copy, Debug)]
+pub enum TraceEvent<'a> {
+    /// See [`TraceSink::on_frame_tick`].
+    FrameTick(&'a FrameTickEvent),
+    /// See [`TraceSink::on_frame_plan`].
+    FramePlan(&'a FramePlanEvent),
+    /// See [`TraceSink::on_phase_begin`].
+    PhaseBegin(&'a PhaseBeginEvent),
+    /// See [`TraceSink::on_phase_end`].
+    PhaseEnd(&'a PhaseEndEvent),
+    /// See [`TraceSink::on_submit`].
+    Submit(&'a SubmitEvent),
+    /// See [`TraceSink::on_present_feedback`].
+    PresentFeedback(&'a PresentFeedbackEvent),
+    /// See [`TraceSink::on_scheduler_state`].
+    SchedulerState(&'a SchedulerStateEvent),
+    /// See [`TraceSink::on_frame_timing_summary`].
+    FrameTimingSummary(&'a FrameTimingSummary),
+    /// See [`TraceSink::on_frame_summary`].
+    FrameSummary(&'a FrameSummary),
+    /// See [`TraceSink::on_layer_changes`].
+    #[cfg(feature = "trace-rich")]
+    LayerChanges {
+        /// Frame counter.
+        frame_index: u64,
+        /// Changes recorded for the frame.
+        changes: &'a [LayerChange],
+    },
+    /// See [`TraceSink::on_damage_rects`].
+    #[cfg(feature = "trace-rich")]
+    DamageRects {
+        /// Frame counter.
+        frame_index: u64,
+        /// Damage recorded for the frame.
+        rects: &'a [DamageRect],
+    },
+}
+
+/// Predicate type used by the preset [`FilterSink`] constructors.
+pub type TraceEventFilter = fn(&TraceEvent<'_>) -> bool;
+
+/// A [`TraceSink`] that forwards only the events accepted by a predicate.
+///
+/// The predicate sees a borrowed [`TraceEvent`] before the inner sink does, so
+/// a rejected event costs one predicate call and nothing else; the inner sink
+/// never formats or records it.
+///
+/// ```
+/// use subduction_core::trace::{FilterSink, NoopSink, PhaseKind, TraceEvent};
+///
+/// // Only forward render and submit phase markers.
+/// let sink = FilterSink::new(NoopSink, |e: &TraceEvent<'_>| match e {
+///     TraceEvent::PhaseBegin(p) => matches!(p.phase, PhaseKind::Render | PhaseKind::Submit),
+///     TraceEvent::PhaseEnd(p) => matches!(p.phase, PhaseKind::Render | PhaseKind::Submit),
+///     _ => false,
+/// });
+/// # let _ = sink;
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FilterSink<S, F> {
+    /// The sink that receives accepted events.
+    pub inner: S,
+    /// Returns `true` for events that should reach `inner`.
+    pub predicate: F,
+}
+
+impl<S, F> FilterSink<S, F>
+where
+    F: FnMut(&TraceEvent<'_>) -> bool,
+{
+    /// Creates a filter forwarding events accepted by `predicate` to `inner`.
+    #[inline]
+    #[must_use]
+    pub const fn new(inner: S, predicate: F) -> Self {
+        Self { inner, predicate }
+    }
+
+    /// Consumes the filter and returns the inner sink.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> FilterSink<S, TraceEventFilter> {
+    /// Creates a filter that forwards only [`FrameSummary`] events.
+    #[inline]
+    #[must_use]
+    pub fn frame_summaries_only(inner: S) -> Self {
+        Self::new(inner, |e| matches!(e, TraceEvent::FrameSummary(_)))
+    }
+
+    /// Creates a filter that forwards only [`FrameSummary`] events for frames
+    /// that missed their deadline.
+    #[inline]
+    #[must_use]
+    pub fn misses_only(inner: S) -> Self {
+        Self::new(
+            inner,
+            |e| matches!(e, TraceEvent::FrameSummary(s) if s.missed_deadline),
+        )
+    }
+}
+
+impl<S, F> TraceSink for FilterSink<S, F>
+where
+    S: TraceSink,
+    F: FnMut(&TraceEvent<'_>) -> bool,
+{
+    fn on_frame_tick(&mut self, e: &FrameTickEvent) {
+        if (self.predicate)(&TraceEvent::FrameTick(e)) {
+            self.inner.on_frame_tick(e);
+        }
+    }
+
+    fn on_frame_plan(&mut self, e: &FramePlanEvent) {
+        if (self.predicate)(&TraceEvent::FramePlan(e)) {
+            self.inner.on_frame_plan(e);
+        }
+    }
+
+    fn on_phase_begin(&mut self, e: &PhaseBeginEvent) {
+        if (self.predicate)(&TraceEvent::PhaseBegin(e)) {
+            self.inner.on_phase_begin(e);
+        }
+    }
+
+    fn on_phase_end(&mut self, e: &PhaseEndEvent) {
+        if (self.predicate)(&TraceEvent::PhaseEnd(e)) {
+            self.inner.on_phase_end(e);
+        }
+    }
+
+    fn on_submit(&mut self, e: &SubmitEvent) {
+        if (self.predicate)(&TraceEvent::Submit(e)) {
+            self.inner.on_submit(e);
+        }
+    }
+
+    fn on_present_feedback(&mut self, e: &PresentFeedbackEvent) {
+        if (self.predicate)(&TraceEvent::PresentFeedback(e)) {
+            self.inner.on_present_feedback(e);
+        }
+    }
+
+    fn on_scheduler_state(&mut self, e: &SchedulerStateEvent) {
+        if (self.predicate)(&TraceEvent::SchedulerState(e)) {
+            self.inner.on_scheduler_state(e);
+        }
+    }
+
+    fn on_frame_timing_summary(&mut self, s: &FrameTimingSummary) {
+        if (self.predicate)(&TraceEvent::FrameTimingSummary(s)) {
+            self.inner.on_frame_timing_summary(s);
+        }
+    }
+
+    fn on_frame_summary(&mut self, s: &FrameSummary) {
+        if (self.predicate)(&TraceEvent::FrameSummary(s)) {
+            self.inner.on_frame_summary(s);
+        }
+    }
+
+    #[cfg(feature = "trace-rich")]
+    fn on_layer_changes(&mut self, frame_index: u64, changes: &[LayerChange]) {
+        if (self.predicate)(&TraceEvent::LayerChanges {
+            frame_index,
+            changes,
+        }) {
+            self.inner.on_layer_changes(frame_index, changes);
+        }
+    }
+
+    #[cfg(feature = "trace-rich")]
+    fn on_damage_rects(&mut self, frame_index: u64, rects: &[DamageRect]) {
+        if (self.predicate)(&TraceEvent::DamageRects { frame_index, rects }) {
+            self.inner.on_damage_rects(frame_index, rects);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TraceDiagnostics
+// ---------------------------------------------------------------------------
+
+/// Adapts a [`TraceSink`] into a frameclock [`DiagnosticsSink`].
+///
+/// This lets frameclock APIs that report to a `DiagnosticsSink`, such as
+/// `Scheduler::plan_traced` and `Scheduler::observe_traced`, feed a trace
+/// sink directly:
+///
+/// ```rust,ignore
+/// let plan = scheduler.plan_traced(opportunity, demand, &mut TraceDiagnostics(&mut recorder));
+/// ```
+///
+/// Frame-drop events have no `TraceSink` counterpart and are discarded.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TraceDiagnostics<S>(pub S);
+
+impl<S: TraceSink> DiagnosticsSink for TraceDiagnostics<S> {
+    fn on_frame_tick(&mut self, event: &FrameTickEvent) {
+        self.0.on_frame_tick(event);
+    }
+
+    fn on_frame_plan(&mut self, event: &FramePlanEvent) {
+        self.0.on_frame_plan(event);
+    }
+
+    fn on_submit(&mut self, event: &SubmitEvent) {
+        self.0.on_submit(event);
+    }
+
+    fn on_present_feedback(&mut self, event: &PresentFeedbackEvent) {
+        self.0.on_present_feedback(event);
+    }
+
+    fn on_scheduler_state(&mut self, event: &SchedulerStateEvent) {
+        self.0.on_scheduler_state(event);
+    }
+
+    fn on_frame_timing_summary(&mut self, event: &FrameTimingSummary) {
+        self.0.on_frame_timing_summary(event);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tracer wrapper
 // ---------------------------------------------------------------------------
@@ -426,8 +811,8 @@ impl<'a> Tracer<'a> {
 pub struct FrameSummaryBuilder {
     tick: FrameTickEvent,
     plan: FramePlanEvent,
-    phase_starts: [Option<HostTime>; 4],
-    phase_ends: [Option<HostTime>; 4],
+    phase_starts: [Option<HostTime>; PHASE_COUNT],
+    phase_ends: [Option<HostTime>; PHASE_COUNT],
     missed_deadline: bool,
 }
 
@@ -438,8 +823,8 @@ impl FrameSummaryBuilder {
         Self {
             tick: *tick,
             plan: *plan,
-            phase_starts: [None; 4],
-            phase_ends: [None; 4],
+            phase_starts: [None; PHASE_COUNT],
+            phase_ends: [None; PHASE_COUNT],
             missed_deadline: false,
         }
     }
@@ -471,30 +856,46 @@ impl FrameSummaryBuilder {
             sample_time: self.plan.sample_time,
             deadline: self.plan.commit_deadline,
             pipeline_depth: self.plan.pipeline_depth,
-            plan_ticks: self.phase_duration(PhaseKind::Plan),
-            eval_ticks: self.phase_duration(PhaseKind::Evaluate),
-            render_ticks: self.phase_duration(PhaseKind::Render),
-            submit_ticks: self.phase_duration(PhaseKind::Submit),
+            plan_ticks: self.phase_ticks(PhaseKind::Plan),
+            eval_ticks: self.phase_ticks(PhaseKind::Evaluate),
+            acquire_ticks: self.phase_ticks(PhaseKind::Acquire),
+            render_ticks: self.phase_ticks(PhaseKind::Render),
+            submit_ticks: self.phase_ticks(PhaseKind::Submit),
+            present_ticks: self.phase_ticks(PhaseKind::Present),
             missed_deadline: self.missed_deadline,
         }
     }
 
-    fn phase_duration(&self, phase: PhaseKind) -> u64 {
+    /// Returns the duration of `phase` in ticks so far, or `None` if its
+    /// start or end has not been recorded.
+    ///
+    /// This reads the same value [`finish`](Self::finish) would report, so a
+    /// live display can show phase timing mid-frame. An end recorded before
+    /// its start yields zero.
+    #[must_use]
+    pub fn phase_duration(&self, phase: PhaseKind) -> Option<u64> {
         let idx = phase_index(phase);
-        match (self.phase_starts[idx], self.phase_ends[idx]) {
-            (Some(start), Some(end)) => end.saturating_duration_since(start).ticks(),
-            _ => 0,
-        }
+        let (start, end) = (self.phase_starts[idx]?, self.phase_ends[idx]?);
+        Some(end.saturating_duration_since(start).ticks())
+    }
+
+    fn phase_ticks(&self, phase: PhaseKind) -> u64 {
+        self.phase_duration(phase).unwrap_or(0)
     }
 }
 
+/// Number of [`PhaseKind`] variants.
+const PHASE_COUNT: usize = 6;
+
 /// Maps a [`PhaseKind`] to an array index.
 const fn phase_index(phase: PhaseKind) -> usize {
     match phase {
         PhaseKind::Plan => 0,
         PhaseKind::Evaluate => 1,
-        PhaseKind::Render => 2,
-        PhaseKind::Submit => 3,
+        PhaseKind::Acquire => 2,
+        PhaseKind::Render => 3,
+        PhaseKind::Submit => 4,
+        PhaseKind::Present => 5,
     }
 }
 
@@ -588,8 +989,10 @@ mod tests {
             pipeline_depth: 1,
             plan_ticks: 0,
             eval_ticks: 0,
+            acquire_ticks: 0,
             render_ticks: 0,
             submit_ticks: 0,
+            present_ticks: 0,
             missed_deadline: false,
         });
     }
@@ -659,6 +1062,181 @@ mod tests {
         assert_eq!(summary.eval_ticks, 0);
         assert_eq!(summary.render_ticks, 0);
         assert_eq!(summary.submit_ticks, 0);
+        assert_eq!(summary.acquire_ticks, 0);
+        assert_eq!(summary.present_ticks, 0);
+    }
+
+    #[test]
+    fn summary_builder_measures_acquire_and_present() {
+        let mut builder = FrameSummaryBuilder::new(&sample_tick(), &sample_plan());
+        builder.phase_begin(PhaseKind::Acquire, HostTime(1_000_500));
+        builder.phase_end(PhaseKind::Acquire, HostTime(1_000_700));
+        builder.phase_begin(PhaseKind::Render, HostTime(1_000_700));
+        builder.phase_end(PhaseKind::Render, HostTime(1_002_000));
+        builder.phase_begin(PhaseKind::Present, HostTime(1_002_050));
+        builder.phase_end(PhaseKind::Present, HostTime(1_002_350));
+
+        let summary = builder.finish();
+        assert_eq!(summary.acquire_ticks, 200);
+        assert_eq!(summary.render_ticks, 1300);
+        assert_eq!(summary.present_ticks, 300);
+        assert_eq!(summary.submit_ticks, 0);
+    }
+
+    #[derive(Default)]
+    struct CountingSink {
+        events: u32,
+    }
+
+    impl TraceSink for CountingSink {
+        fn on_frame_tick(&mut self, _: &FrameTickEvent) {
+            self.events += 1;
+        }
+
+        fn on_frame_plan(&mut self, _: &FramePlanEvent) {
+            self.events += 1;
+        }
+
+        fn on_phase_begin(&mut self, _: &PhaseBeginEvent) {
+            self.events += 1;
+        }
+
+        fn on_phase_end(&mut self, _: &PhaseEndEvent) {
+            self.events += 1;
+        }
+
+        fn on_frame_summary(&mut self, _: &FrameSummary) {
+            self.events += 1;
+        }
+    }
+
+    #[test]
+    fn phase_duration_before_finish() {
+        let mut builder = FrameSummaryBuilder::new(&sample_tick(), &sample_plan());
+        builder.phase_begin(PhaseKind::Render, HostTime(1_000));
+        builder.phase_end(PhaseKind::Render, HostTime(1_750));
+        builder.phase_begin(PhaseKind::Submit, HostTime(2_000));
+        builder.phase_end(PhaseKind::Present, HostTime(2_100));
+        builder.phase_begin(PhaseKind::Acquire, HostTime(900));
+        builder.phase_end(PhaseKind::Acquire, HostTime(900));
+
+        assert_eq!(builder.phase_duration(PhaseKind::Render), Some(750));
+        // Only one end of these phases was recorded.
+        assert_eq!(builder.phase_duration(PhaseKind::Submit), None);
+        assert_eq!(builder.phase_duration(PhaseKind::Present), None);
+        assert_eq!(builder.phase_duration(PhaseKind::Plan), None);
+        assert_eq!(builder.phase_duration(PhaseKind::Acquire), Some(0));
+
+        let summary = builder.finish();
+        assert_eq!(summary.render_ticks, 750);
+        assert_eq!(summary.submit_ticks, 0);
+        assert_eq!(summary.acquire_ticks, 0);
+    }
+
+    #[test]
+    fn trace_diagnostics_forwards_scheduler_events() {
+        use frameclock::scheduler::{Scheduler, SchedulerConfig};
+        use frameclock::timing::{DisplayTiming, FrameOpportunity, PresentHints};
+
+        let tick = FrameTick::builder()
+            .now(HostTime(1_000))
+            .predicted_present(Some(HostTime(2_000)))
+            .build();
+        let opportunity = FrameOpportunity::new(
+            tick,
+            PresentHints::predictive(HostTime(2_000), HostTime(1_800)),
+            DisplayTiming::from_tick(&tick, Duration(16_666_667)),
+        );
+        let mut scheduler = Scheduler::new(SchedulerConfig::predictive());
+        let mut sink = CountingSink::default();
+
+        let _ = scheduler.plan_traced(
+            opportunity,
+            FrameDemand::ANIMATION,
+            &mut TraceDiagnostics(&mut sink),
+        );
+        // One tick event and one plan event.
+        assert_eq!(sink.events, 2);
+    }
+
+    #[test]
+    fn tee_sink_forwards_to_both_sinks() {
+        let mut a = CountingSink::default();
+        let mut b = CountingSink::default();
+        {
+            let mut tee = TeeSink::new(&mut a, &mut b);
+            tee.on_frame_tick(&sample_tick());
+            tee.on_frame_plan(&sample_plan());
+            let phase = PhaseBeginEvent {
+                frame_index: 42,
+                phase: PhaseKind::Plan,
+                timestamp: HostTime(1_000_000),
+            };
+            tee.on_phase_begin(&phase);
+            tee.on_phase_end(&PhaseEndEvent {
+                frame_index: 42,
+                phase: PhaseKind::Plan,
+                timestamp: HostTime(1_000_100),
+            });
+            tee.on_frame_summary(
+                &FrameSummaryBuilder::new(&sample_tick(), &sample_plan()).finish(),
+            );
+        }
+        assert_eq!(a.events, 5, "first sink sees every event");
+        assert_eq!(b.events, 5, "second sink sees every event");
+    }
+
+    #[test]
+    fn nested_tee_sink_fans_out() {
+        let mut tee = TeeSink::new(
+            CountingSink::default(),
+            TeeSink::new(CountingSink::default(), CountingSink::default()),
+        );
+        tee.on_frame_tick(&sample_tick());
+        let (a, rest) = tee.into_inner();
+        let (b, c) = rest.into_inner();
+        assert_eq!((a.events, b.events, c.events), (1, 1, 1));
+    }
+
+    fn summary_with_miss(frame_index: u64, missed: bool) -> FrameSummary {
+        let mut builder = FrameSummaryBuilder::new(&sample_tick(), &sample_plan());
+        builder.set_missed_deadline(missed);
+        FrameSummary {
+            frame_index,
+            ..builder.finish()
+        }
+    }
+
+    #[test]
+    fn misses_only_filter_drops_on_time_summaries() {
+        let mut filter = FilterSink::misses_only(CountingSink::default());
+        filter.on_frame_summary(&summary_with_miss(1, false));
+        filter.on_frame_summary(&summary_with_miss(2, true));
+        filter.on_frame_summary(&summary_with_miss(3, false));
+        filter.on_frame_summary(&summary_with_miss(4, true));
+        filter.on_frame_tick(&sample_tick());
+        assert_eq!(
+            filter.into_inner().events,
+            2,
+            "only the two missed summaries pass"
+        );
+    }
+
+    #[test]
+    fn filter_predicate_selects_phases() {
+        let mut filter = FilterSink::new(
+            CountingSink::default(),
+            |e: &TraceEvent<'_>| matches!(e, TraceEvent::PhaseBegin(p) if p.phase == PhaseKind::Render),
+        );
+        for phase in [PhaseKind::Plan, PhaseKind::Render, PhaseKind::Submit] {
+            filter.on_phase_begin(&PhaseBeginEvent {
+                frame_index: 42,
+                phase,
+                timestamp: HostTime(1_000_000),
+            });
+        }
+        filter.on_frame_summary(&summary_with_miss(42, true));
+        assert_eq!(filter.inner.events, 1, "only the render phase passes");
     }
 
     #[cfg(feature = "trace")]
@@ -1,11 +1,13 @@
 // Copyright 2026 the Subduction Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-//! Minimal column-major 4×4 transform.
+//! Minimal column-major 4×4 transform, plus a 2×3 affine fast path.
 //!
-//! This type covers the subset of 3-D affine transforms that `subduction_core`
-//! actually needs (identity, multiply, column access, field mutation) without
-//! pulling in a full linear-algebra crate.
+//! [`Transform3d`] covers the subset of 3-D affine transforms that
+//! `subduction_core` actually needs (identity, multiply, column access, field
+//! mutation) without pulling in a full linear-algebra crate. [`Transform2d`]
+//! is the XY-plane subset, for scenes that only translate, rotate around Z,
+//! and scale.
 
 use core::ops::Mul;
 
@@ -14,6 +16,7 @@ use core::ops::Mul;
 /// Each inner array is one *column* of the matrix, matching the memory layout
 /// used by GPU APIs and Core Animation's `CATransform3D`.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform3d {
     /// Four columns, each a 4-element array `[x, y, z, w]`.
     pub cols: [[f64; 4]; 4],
@@ -309,6 +312,130 @@ impl Mul<[f64; 4]> for Transform3d {
     }
 }
 
+/// A 2×3 affine transform in the XY plane.
+///
+/// Coefficients are `[a, b, c, d, e, f]` in the same order as
+/// [`kurbo::Affine`], mapping `(x, y)` to `(a·x + c·y + e, b·x + d·y + f)`.
+/// Composition does 12 multiplies instead of the 64 of [`Transform3d`], and for
+/// transforms that stay in the XY plane the result matches the 4×4 product
+/// exactly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform2d {
+    /// Affine coefficients `[a, b, c, d, e, f]`.
+    pub coeffs: [f64; 6],
+}
+
+impl Transform2d {
+    /// The identity transform.
+    pub const IDENTITY: Self = Self {
+        coeffs: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+    };
+
+    /// Creates a pure translation transform.
+    #[inline]
+    #[must_use]
+    pub const fn from_translation(x: f64, y: f64) -> Self {
+        Self {
+            coeffs: [1.0, 0.0, 0.0, 1.0, x, y],
+        }
+    }
+
+    /// Creates a non-uniform scale transform.
+    #[inline]
+    #[must_use]
+    pub const fn from_scale(sx: f64, sy: f64) -> Self {
+        Self {
+            coeffs: [sx, 0.0, 0.0, sy, 0.0, 0.0],
+        }
+    }
+
+    /// Creates a rotation around the Z axis (radians).
+    #[inline]
+    #[must_use]
+    pub fn from_rotation_z(radians: f64) -> Self {
+        let c = Transform3d::from_rotation_z(radians).cols;
+        Self {
+            coeffs: [c[0][0], c[0][1], c[1][0], c[1][1], 0.0, 0.0],
+        }
+    }
+
+    /// Embeds this transform in a [`Transform3d`].
+    #[inline]
+    #[must_use]
+    pub const fn to_transform3d(self) -> Transform3d {
+        let [a, b, c, d, e, f] = self.coeffs;
+        Transform3d::from_cols(
+            [a, b, 0.0, 0.0],
+            [c, d, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [e, f, 0.0, 1.0],
+        )
+    }
+
+    /// Extracts the XY-plane part of `t`.
+    ///
+    /// Returns `None` if `t` touches the Z axis or has a perspective
+    /// component, i.e. it is not the embedding of a 2×3 affine.
+    #[inline]
+    #[must_use]
+    pub fn from_transform3d(t: &Transform3d) -> Option<Self> {
+        let c = &t.cols;
+        let planar = c[0][2] == 0.0
+            && c[0][3] == 0.0
+            && c[1][2] == 0.0
+            && c[1][3] == 0.0
+            && c[2] == [0.0, 0.0, 1.0, 0.0]
+            && c[3][2] == 0.0
+            && c[3][3] == 1.0;
+        planar.then_some(Self {
+            coeffs: [c[0][0], c[0][1], c[1][0], c[1][1], c[3][0], c[3][1]],
+        })
+    }
+}
+
+impl Default for Transform2d {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<kurbo::Affine> for Transform2d {
+    #[inline]
+    fn from(affine: kurbo::Affine) -> Self {
+        Self {
+            coeffs: affine.as_coeffs(),
+        }
+    }
+}
+
+impl From<Transform2d> for Transform3d {
+    #[inline]
+    fn from(t: Transform2d) -> Self {
+        t.to_transform3d()
+    }
+}
+
+impl Mul for Transform2d {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let [a0, b0, c0, d0, e0, f0] = self.coeffs;
+        let [a1, b1, c1, d1, e1, f1] = rhs.coeffs;
+        Self {
+            coeffs: [
+                a0 * a1 + c0 * b1,
+                b0 * a1 + d0 * b1,
+                a0 * c1 + c0 * d1,
+                b0 * c1 + d0 * d1,
+                a0 * e1 + c0 * f1 + e0,
+                b0 * e1 + d0 * f1 + f0,
+            ],
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -555,4 +682,56 @@ mod tests {
         assert!((recovered.x - original.x).abs() < eps);
         assert!((recovered.y - original.y).abs() < eps);
     }
+
+    #[test]
+    fn transform2d_matches_transform3d() {
+        let pairs = [
+            (
+                Transform2d::from_translation(3.0, -4.0),
+                Transform3d::from_translation(3.0, -4.0, 0.0),
+            ),
+            (
+                Transform2d::from_scale(2.0, 0.5),
+                Transform3d::from_scale(2.0, 0.5, 1.0),
+            ),
+            (
+                Transform2d::from_rotation_z(0.7),
+                Transform3d::from_rotation_z(0.7),
+            ),
+        ];
+        for (t2, t3) in pairs {
+            assert_eq!(t2.to_transform3d(), t3);
+            assert_eq!(Transform2d::from_transform3d(&t3), Some(t2));
+        }
+        for (a2, a3) in pairs {
+            for (b2, b3) in pairs {
+                assert_eq!((a2 * b2).to_transform3d(), a3 * b3);
+            }
+        }
+    }
+
+    #[test]
+    fn transform2d_rejects_non_planar() {
+        assert_eq!(
+            Transform2d::from_transform3d(&Transform3d::from_translation(0.0, 0.0, 1.0)),
+            None
+        );
+        assert_eq!(
+            Transform2d::from_transform3d(&Transform3d::from_scale(1.0, 1.0, 2.0)),
+            None
+        );
+        assert_eq!(
+            Transform2d::from_transform3d(&Transform3d::IDENTITY),
+            Some(Transform2d::IDENTITY)
+        );
+    }
+
+    #[test]
+    fn transform2d_from_affine_matches_kurbo() {
+        let affine = kurbo::Affine::new([2.0, 3.0, 5.0, 7.0, 11.0, 13.0]);
+        assert_eq!(
+            Transform3d::from(Transform2d::from(affine)),
+            Transform3d::from(affine)
+        );
+    }
 }
@@ -8,6 +8,7 @@
 //!
 //! [spec]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
 
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Write};
 
 use serde_json::{Value, json};
@@ -22,148 +23,98 @@ use crate::recorder::{RecordedEvent, decode};
 /// loading into `chrome://tracing` or [Perfetto](https://ui.perfetto.dev/).
 ///
 /// Timestamps are converted to microseconds using the provided [`Timebase`].
+///
+/// Counter tracks graph scheduler state over time: `pipeline_depth` from each
+/// `FramePlan`, and a running `missed_deadlines` count from `PresentFeedback`.
+/// A miss is graphed at the frame's actual present time, or at its
+/// `FrameTick` time when the feedback has none.
+///
+/// Each `Submit` starts a flow event (`ph: "s"`) keyed by frame index, which
+/// the matching `PresentFeedback` finishes (`ph: "f"`), so viewers draw an
+/// arrow spanning the frame's submit-to-present latency. Viewers attach flow
+/// events to slices rather than instants, so `Submit` and `PresentFeedback`
+/// are written as zero-length complete slices (`ph: "X"`) that the flow
+/// binds to. Feedback without an actual present time leaves its flow
+/// unfinished.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::InvalidData`] if `bytes` is not a recording this
+/// version can decode (see [`decode`]), or any error from `writer`.
 pub fn export(bytes: &[u8], timebase: Timebase, writer: &mut dyn Write) -> io::Result<()> {
+    let records = decode(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
     let mut events: Vec<Value> = Vec::new();
+    // Frames whose submit started a flow that no feedback has finished yet.
+    let mut open_flows: HashSet<u64> = HashSet::new();
+    let mut missed_deadlines: u64 = 0;
+    // Tick time of each frame awaiting feedback, in microseconds.
+    let mut frame_now: HashMap<u64, f64> = HashMap::new();
+    let mut last_counter_ts = 0.0;
 
-    for recorded in decode(bytes) {
+    for recorded in records {
+        events.push(trace_event(&recorded, timebase));
         match recorded {
             RecordedEvent::FrameTick(e) => {
-                events.push(json!({
-                    "ph": "i",
-                    "name": "FrameTick",
-                    "cat": "Scheduler",
-                    "ts": ticks_to_us(e.now.ticks(), timebase),
-                    "pid": e.output.0,
-                    "tid": 0,
-                    "s": "g",
-                    "args": {
-                        "frame_index": e.frame_index,
-                    }
-                }));
+                frame_now.insert(e.frame_index, ticks_to_us(e.now.ticks(), timebase));
             }
             RecordedEvent::FramePlan(e) => {
                 events.push(json!({
-                    "ph": "i",
-                    "name": "FramePlan",
+                    "ph": "C",
+                    "name": "pipeline_depth",
                     "cat": "Scheduler",
                     "ts": ticks_to_us(e.commit_deadline.ticks(), timebase),
                     "pid": e.output.0,
-                    "tid": 0,
-                    "s": "g",
                     "args": {
-                        "frame_index": e.frame_index,
-                        "presentation_timing": format!("{:?}", e.presentation_timing),
-                        "pipeline_depth": e.pipeline_depth,
-                        "safety_margin_ticks": e.safety_margin_ticks,
-                    }
-                }));
-            }
-            RecordedEvent::PhaseBegin(e) => {
-                events.push(json!({
-                    "ph": "B",
-                    "name": format!("{:?}", e.phase),
-                    "cat": "Frame",
-                    "ts": ticks_to_us(e.timestamp.ticks(), timebase),
-                    "pid": 0,
-                    "tid": 0,
-                    "args": {
-                        "frame_index": e.frame_index,
-                    }
-                }));
-            }
-            RecordedEvent::PhaseEnd(e) => {
-                events.push(json!({
-                    "ph": "E",
-                    "name": format!("{:?}", e.phase),
-                    "cat": "Frame",
-                    "ts": ticks_to_us(e.timestamp.ticks(), timebase),
-                    "pid": 0,
-                    "tid": 0,
-                    "args": {
-                        "frame_index": e.frame_index,
+                        "depth": e.pipeline_depth,
                     }
                 }));
             }
             RecordedEvent::Submit(e) => {
                 events.push(json!({
-                    "ph": "i",
-                    "name": "Submit",
-                    "cat": "Frame",
+                    "ph": "s",
+                    "name": "Present",
+                    "cat": "Flow",
+                    "id": e.frame_index,
                     "ts": ticks_to_us(e.submitted_at.ticks(), timebase),
                     "pid": 0,
                     "tid": 0,
-                    "s": "t",
-                    "args": {
-                        "frame_index": e.frame_index,
-                    }
                 }));
+                open_flows.insert(e.frame_index);
             }
             RecordedEvent::PresentFeedback(e) => {
+                missed_deadlines += u64::from(e.missed_deadline == Some(true));
+                let now = frame_now.remove(&e.frame_index);
+                last_counter_ts = e
+                    .actual_present
+                    .map(|t| ticks_to_us(t.ticks(), timebase))
+                    .or(now)
+                    .unwrap_or(last_counter_ts);
                 events.push(json!({
-                    "ph": "i",
-                    "name": "PresentFeedback",
+                    "ph": "C",
+                    "name": "missed_deadlines",
                     "cat": "Frame",
-                    "ts": e.actual_present.map_or(0.0, |t| ticks_to_us(t.ticks(), timebase)),
-                    "pid": 0,
-                    "tid": 0,
-                    "s": "t",
-                    "args": {
-                        "frame_index": e.frame_index,
-                        "missed": e.missed_deadline,
-                        "pacing_overrun": e.pacing_overrun,
-                    }
-                }));
-            }
-            RecordedEvent::FrameSummary(s) => {
-                events.push(json!({
-                    "ph": "i",
-                    "name": "FrameSummary",
-                    "cat": "Summary",
-                    "ts": ticks_to_us(s.now.ticks(), timebase),
-                    "pid": s.output.0,
-                    "tid": 0,
-                    "s": "g",
-                    "args": {
-                        "frame_index": s.frame_index,
-                        "pipeline_depth": s.pipeline_depth,
-                        "plan_us": ticks_to_us(s.plan_ticks, timebase),
-                        "eval_us": ticks_to_us(s.eval_ticks, timebase),
-                        "render_us": ticks_to_us(s.render_ticks, timebase),
-                        "submit_us": ticks_to_us(s.submit_ticks, timebase),
-                        "missed_deadline": s.missed_deadline,
-                    }
-                }));
-            }
-            RecordedEvent::LayerChangesCount { frame_index, count } => {
-                events.push(json!({
-                    "ph": "i",
-                    "name": "LayerChanges",
-                    "cat": "Rich",
-                    "ts": 0,
-                    "pid": 0,
-                    "tid": 0,
-                    "s": "p",
-                    "args": {
-                        "frame_index": frame_index,
-                        "count": count,
-                    }
-                }));
-            }
-            RecordedEvent::DamageRectsCount { frame_index, count } => {
-                events.push(json!({
-                    "ph": "i",
-                    "name": "DamageRects",
-                    "cat": "Rich",
-                    "ts": 0,
+                    "ts": last_counter_ts,
                     "pid": 0,
-                    "tid": 0,
-                    "s": "p",
                     "args": {
-                        "frame_index": frame_index,
-                        "count": count,
+                        "missed": missed_deadlines,
                     }
                 }));
+                if open_flows.remove(&e.frame_index)
+                    && let Some(actual) = e.actual_present
+                {
+                    events.push(json!({
+                        "ph": "f",
+                        "bp": "e",
+                        "name": "Present",
+                        "cat": "Flow",
+                        "id": e.frame_index,
+                        "ts": ticks_to_us(actual.ticks(), timebase),
+                        "pid": 0,
+                        "tid": 0,
+                    }));
+                }
             }
+            _ => {}
         }
     }
 
@@ -171,7 +122,178 @@ pub fn export(bytes: &[u8], timebase: Timebase, writer: &mut dyn Write) -> io::R
     Ok(())
 }
 
-fn ticks_to_us(ticks: u64, timebase: Timebase) -> f64 {
+/// Converts one recorded event to its Chrome trace event object.
+///
+/// This is the per-event part of [`export`]; counters and flows, which need
+/// state across events, are added there.
+pub(crate) fn trace_event(recorded: &RecordedEvent, timebase: Timebase) -> Value {
+    match recorded {
+        RecordedEvent::FrameTick(e) => json!({
+            "ph": "i",
+            "name": "FrameTick",
+            "cat": "Scheduler",
+            "ts": ticks_to_us(e.now.ticks(), timebase),
+            "pid": e.output.0,
+            "tid": 0,
+            "s": "g",
+            "args": {
+                "frame_index": e.frame_index,
+            }
+        }),
+        RecordedEvent::FramePlan(e) => json!({
+            "ph": "i",
+            "name": "FramePlan",
+            "cat": "Scheduler",
+            "ts": ticks_to_us(e.commit_deadline.ticks(), timebase),
+            "pid": e.output.0,
+            "tid": 0,
+            "s": "g",
+            "args": {
+                "frame_index": e.frame_index,
+                "presentation_timing": format!("{:?}", e.presentation_timing),
+                "pipeline_depth": e.pipeline_depth,
+                "safety_margin_ticks": e.safety_margin_ticks,
+            }
+        }),
+        RecordedEvent::PhaseBegin(e) => json!({
+            "ph": "B",
+            "name": format!("{:?}", e.phase),
+            "cat": "Frame",
+            "ts": ticks_to_us(e.timestamp.ticks(), timebase),
+            "pid": 0,
+            "tid": 0,
+            "args": {
+                "frame_index": e.frame_index,
+            }
+        }),
+        RecordedEvent::PhaseEnd(e) => json!({
+            "ph": "E",
+            "name": format!("{:?}", e.phase),
+            "cat": "Frame",
+            "ts": ticks_to_us(e.timestamp.ticks(), timebase),
+            "pid": 0,
+            "tid": 0,
+            "args": {
+                "frame_index": e.frame_index,
+            }
+        }),
+        RecordedEvent::Submit(e) => json!({
+            "ph": "X",
+            "name": "Submit",
+            "cat": "Frame",
+            "ts": ticks_to_us(e.submitted_at.ticks(), timebase),
+            "dur": 0,
+            "pid": 0,
+            "tid": 0,
+            "args": {
+                "frame_index": e.frame_index,
+            }
+        }),
+        RecordedEvent::PresentFeedback(e) => json!({
+            "ph": "X",
+            "name": "PresentFeedback",
+            "cat": "Frame",
+            "ts": e.actual_present.map_or(0.0, |t| ticks_to_us(t.ticks(), timebase)),
+            "dur": 0,
+            "pid": 0,
+            "tid": 0,
+            "args": {
+                "frame_index": e.frame_index,
+                "missed": e.missed_deadline,
+                "pacing_overrun": e.pacing_overrun,
+            }
+        }),
+        RecordedEvent::FrameSummary(s) => json!({
+            "ph": "i",
+            "name": "FrameSummary",
+            "cat": "Summary",
+            "ts": ticks_to_us(s.now.ticks(), timebase),
+            "pid": s.output.0,
+            "tid": 0,
+            "s": "g",
+            "args": {
+                "frame_index": s.frame_index,
+                "pipeline_depth": s.pipeline_depth,
+                "plan_us": ticks_to_us(s.plan_ticks, timebase),
+                "eval_us": ticks_to_us(s.eval_ticks, timebase),
+                "acquire_us": ticks_to_us(s.acquire_ticks, timebase),
+                "render_us": ticks_to_us(s.render_ticks, timebase),
+                "submit_us": ticks_to_us(s.submit_ticks, timebase),
+                "present_us": ticks_to_us(s.present_ticks, timebase),
+                "missed_deadline": s.missed_deadline,
+            }
+        }),
+        RecordedEvent::LayerChangesCount { frame_index, count } => json!({
+            "ph": "i",
+            "name": "LayerChanges",
+            "cat": "Rich",
+            "ts": 0,
+            "pid": 0,
+            "tid": 0,
+            "s": "p",
+            "args": {
+                "frame_index": frame_index,
+                "count": count,
+            }
+        }),
+        RecordedEvent::DamageRectsCount { frame_index, count } => json!({
+            "ph": "i",
+            "name": "DamageRects",
+            "cat": "Rich",
+            "ts": 0,
+            "pid": 0,
+            "tid": 0,
+            "s": "p",
+            "args": {
+                "frame_index": frame_index,
+                "count": count,
+            }
+        }),
+        RecordedEvent::LayerChanges {
+            frame_index,
+            changes,
+        } => json!({
+            "ph": "i",
+            "name": "LayerChanges",
+            "cat": "Rich",
+            "ts": 0,
+            "pid": 0,
+            "tid": 0,
+            "s": "p",
+            "args": {
+                "frame_index": frame_index,
+                "count": changes.len(),
+                "changes": changes
+                    .iter()
+                    .map(|c| json!({
+                        "layer_index": c.layer_index,
+                        "field": format!("{:?}", c.field),
+                    }))
+                    .collect::<Vec<_>>(),
+            }
+        }),
+        RecordedEvent::DamageRects { frame_index, rects } => json!({
+            "ph": "i",
+            "name": "DamageRects",
+            "cat": "Rich",
+            "ts": 0,
+            "pid": 0,
+            "tid": 0,
+            "s": "p",
+            "args": {
+                "frame_index": frame_index,
+                "count": rects.len(),
+                "rects": rects
+                    .iter()
+                    .map(|r| json!([r.x, r.y, r.width, r.height]))
+                    .collect::<Vec<_>>(),
+            }
+        }),
+    }
+}
+
+/// Converts ticks in `timebase` to (fractional) microseconds.
+pub(crate) fn ticks_to_us(ticks: u64, timebase: Timebase) -> f64 {
     timebase.ticks_to_nanos(ticks) as f64 / 1000.0
 }
 
@@ -179,10 +301,11 @@ fn ticks_to_us(ticks: u64, timebase: Timebase) -> f64 {
 mod tests {
     use super::*;
     use crate::recorder::RecorderSink;
-    use frameclock::HostTime;
-    use frameclock::OutputId;
+    use frameclock::timing::PresentationTiming;
+    use frameclock::{Duration, FrameDemand, HostTime, OutputId};
     use subduction_core::trace::{
-        FrameTickEvent, PhaseBeginEvent, PhaseEndEvent, PhaseKind, TraceSink,
+        FramePlanEvent, FrameTickEvent, PhaseBeginEvent, PhaseEndEvent, PhaseKind,
+        PresentFeedbackEvent, SubmitEvent, TraceSink,
     };
 
     #[test]
@@ -227,6 +350,183 @@ mod tests {
         assert_eq!(parsed[2]["name"], "Plan");
     }
 
+    #[test]
+    fn export_acquire_and_present_slices() {
+        let mut rec = RecorderSink::new();
+        let phases = [
+            (PhaseKind::Acquire, 1_000_000, 1_000_200),
+            (PhaseKind::Render, 1_000_200, 1_001_500),
+            (PhaseKind::Present, 1_001_600, 1_001_900),
+        ];
+        for (phase, begin, end) in phases {
+            rec.on_phase_begin(&PhaseBeginEvent {
+                frame_index: 0,
+                phase,
+                timestamp: HostTime(begin),
+            });
+            rec.on_phase_end(&PhaseEndEvent {
+                frame_index: 0,
+                phase,
+                timestamp: HostTime(end),
+            });
+        }
+
+        let mut out = Vec::new();
+        export(rec.as_bytes(), Timebase::NANOS, &mut out).unwrap();
+        let parsed: Vec<Value> = serde_json::from_slice(&out).unwrap();
+        let slices: Vec<_> = parsed
+            .iter()
+            .map(|e| (e["ph"].as_str().unwrap(), e["name"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            slices,
+            [
+                ("B", "Acquire"),
+                ("E", "Acquire"),
+                ("B", "Render"),
+                ("E", "Render"),
+                ("B", "Present"),
+                ("E", "Present"),
+            ]
+        );
+        assert_eq!(parsed[4]["ts"], 1001.6);
+    }
+
+    #[test]
+    fn export_links_submit_to_present_with_flows() {
+        let mut rec = RecorderSink::new();
+        for frame_index in 0..3_u64 {
+            let submitted = 1_000_000 + frame_index * 16_000_000;
+            rec.on_submit(&SubmitEvent {
+                frame_index,
+                submitted_at: HostTime(submitted),
+                expected_present: Some(HostTime(submitted + 8_000_000)),
+            });
+        }
+        // Feedback arrives late and out of order, as with deferred resolution.
+        for frame_index in [1, 0, 2_u64] {
+            rec.on_present_feedback(&PresentFeedbackEvent {
+                frame_index,
+                actual_present: Some(HostTime(9_000_000 + frame_index * 16_000_000)),
+                missed_deadline: Some(false),
+                pacing_overrun: None,
+            });
+        }
+
+        let mut out = Vec::new();
+        export(rec.as_bytes(), Timebase::NANOS, &mut out).unwrap();
+        let parsed: Vec<Value> = serde_json::from_slice(&out).unwrap();
+        let flow = |ph: &str| -> Vec<(u64, f64)> {
+            let mut flows: Vec<_> = parsed
+                .iter()
+                .filter(|e| e["ph"] == ph)
+                .map(|e| (e["id"].as_u64().unwrap(), e["ts"].as_f64().unwrap()))
+                .collect();
+            flows.sort_by_key(|&(id, _)| id);
+            flows
+        };
+        let starts = flow("s");
+        let finishes = flow("f");
+
+        // Each flow endpoint shares its timestamp with a slice on its thread.
+        for e in parsed.iter().filter(|e| e["ph"] == "s" || e["ph"] == "f") {
+            assert!(
+                parsed.iter().any(|slice| slice["ph"] == "X"
+                    && slice["tid"] == e["tid"]
+                    && slice["ts"] == e["ts"]),
+                "flow event {e} has no slice to bind to"
+            );
+        }
+
+        assert_eq!(starts.len(), 3);
+        assert_eq!(finishes.len(), 3);
+        for ((start_id, start_ts), (finish_id, finish_ts)) in starts.into_iter().zip(finishes) {
+            assert_eq!(start_id, finish_id);
+            assert!(finish_ts > start_ts, "flow {start_id} runs forward in time");
+        }
+    }
+
+    #[test]
+    fn export_counters_track_depth_and_misses() {
+        let mut rec = RecorderSink::new();
+        let depths = [1_u8, 2, 3, 2];
+        let misses = [false, true, true, false];
+        for (frame_index, (depth, missed)) in (0_u64..).zip(depths.into_iter().zip(misses)) {
+            rec.on_frame_plan(&FramePlanEvent {
+                frame_index,
+                output: OutputId(0),
+                demand: FrameDemand::ANIMATION,
+                frame_interval: Duration(16_666_667),
+                frame_start: HostTime(frame_index * 16_666_667),
+                sample_time: HostTime(frame_index * 16_666_667),
+                target_present: None,
+                presentation_timing: PresentationTiming::Predictive,
+                commit_deadline: HostTime(frame_index * 16_666_667),
+                pipeline_depth: depth,
+                safety_margin_ticks: 0,
+            });
+            rec.on_present_feedback(&PresentFeedbackEvent {
+                frame_index,
+                actual_present: Some(HostTime(frame_index * 16_666_667 + 8_000_000)),
+                missed_deadline: Some(missed),
+                pacing_overrun: None,
+            });
+        }
+
+        let mut out = Vec::new();
+        export(rec.as_bytes(), Timebase::NANOS, &mut out).unwrap();
+        let parsed: Vec<Value> = serde_json::from_slice(&out).unwrap();
+        let counter = |name: &str, arg: &str| -> Vec<u64> {
+            parsed
+                .iter()
+                .filter(|e| e["ph"] == "C" && e["name"] == name)
+                .map(|e| e["args"][arg].as_u64().unwrap())
+                .collect()
+        };
+
+        assert_eq!(counter("pipeline_depth", "depth"), [1, 2, 3, 2]);
+        assert_eq!(counter("missed_deadlines", "missed"), [0, 1, 2, 2]);
+    }
+
+    #[test]
+    fn missed_deadline_without_present_uses_frame_time() {
+        let mut rec = RecorderSink::new();
+        rec.on_frame_tick(&FrameTickEvent {
+            frame_index: 7,
+            output: OutputId(0),
+            now: HostTime(5_000_000),
+            predicted_present: None,
+            refresh_interval: Some(16_666_667),
+        });
+        rec.on_present_feedback(&PresentFeedbackEvent {
+            frame_index: 7,
+            actual_present: None,
+            missed_deadline: Some(true),
+            pacing_overrun: None,
+        });
+
+        let mut out = Vec::new();
+        export(rec.as_bytes(), Timebase::NANOS, &mut out).unwrap();
+        let parsed: Vec<Value> = serde_json::from_slice(&out).unwrap();
+        let counter = parsed
+            .iter()
+            .find(|e| e["ph"] == "C" && e["name"] == "missed_deadlines")
+            .unwrap();
+        assert_eq!(counter["ts"], 5000.0);
+        assert_eq!(counter["args"]["missed"], 1);
+    }
+
+    #[test]
+    fn export_rejects_foreign_bytes() {
+        let mut out = Vec::new();
+        let err = export(b"not a recording", Timebase::NANOS, &mut out).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(
+            out.is_empty(),
+            "nothing is written for a rejected recording"
+        );
+    }
+
     #[test]
     fn export_empty_recording() {
         let mut out = Vec::new();
@@ -0,0 +1,204 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Event tallies for tests.
+//!
+//! [`CountingSink`] implements [`TraceSink`] by incrementing one counter per
+//! event type and discarding the payload, so a test can assert how often each
+//! hook fired ("exactly one frame plan, 60 phase begins") without hand-rolling
+//! a sink.
+
+use subduction_core::trace::{
+    DamageRect, FramePlanEvent, FrameSummary, FrameTickEvent, FrameTimingSummary, LayerChange,
+    PhaseBeginEvent, PhaseEndEvent, PresentFeedbackEvent, SchedulerStateEvent, SubmitEvent,
+    TraceSink,
+};
+
+/// A [`TraceSink`] that counts events by type.
+///
+/// Each field is the number of calls to the matching `on_*` method. Rich
+/// events count calls, not the layer changes or rectangles they carry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CountingSink {
+    /// Calls to [`on_frame_tick`](TraceSink::on_frame_tick).
+    pub frame_tick: u64,
+    /// Calls to [`on_frame_plan`](TraceSink::on_frame_plan).
+    pub frame_plan: u64,
+    /// Calls to [`on_phase_begin`](TraceSink::on_phase_begin).
+    pub phase_begin: u64,
+    /// Calls to [`on_phase_end`](TraceSink::on_phase_end).
+    pub phase_end: u64,
+    /// Calls to [`on_submit`](TraceSink::on_submit).
+    pub submit: u64,
+    /// Calls to [`on_present_feedback`](TraceSink::on_present_feedback).
+    pub present_feedback: u64,
+    /// Calls to [`on_scheduler_state`](TraceSink::on_scheduler_state).
+    pub scheduler_state: u64,
+    /// Calls to [`on_frame_timing_summary`](TraceSink::on_frame_timing_summary).
+    pub frame_timing_summary: u64,
+    /// Calls to [`on_frame_summary`](TraceSink::on_frame_summary).
+    pub frame_summary: u64,
+    /// Calls to [`on_layer_changes`](TraceSink::on_layer_changes).
+    pub layer_changes: u64,
+    /// Calls to [`on_damage_rects`](TraceSink::on_damage_rects).
+    pub damage_rects: u64,
+}
+
+impl CountingSink {
+    /// Creates a sink with every count at zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of events received, across all types.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.frame_tick
+            + self.frame_plan
+            + self.phase_begin
+            + self.phase_end
+            + self.submit
+            + self.present_feedback
+            + self.scheduler_state
+            + self.frame_timing_summary
+            + self.frame_summary
+            + self.layer_changes
+            + self.damage_rects
+    }
+
+    /// Resets every count to zero.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl TraceSink for CountingSink {
+    fn on_frame_tick(&mut self, _: &FrameTickEvent) {
+        self.frame_tick += 1;
+    }
+
+    fn on_frame_plan(&mut self, _: &FramePlanEvent) {
+        self.frame_plan += 1;
+    }
+
+    fn on_phase_begin(&mut self, _: &PhaseBeginEvent) {
+        self.phase_begin += 1;
+    }
+
+    fn on_phase_end(&mut self, _: &PhaseEndEvent) {
+        self.phase_end += 1;
+    }
+
+    fn on_submit(&mut self, _: &SubmitEvent) {
+        self.submit += 1;
+    }
+
+    fn on_present_feedback(&mut self, _: &PresentFeedbackEvent) {
+        self.present_feedback += 1;
+    }
+
+    fn on_scheduler_state(&mut self, _: &SchedulerStateEvent) {
+        self.scheduler_state += 1;
+    }
+
+    fn on_frame_timing_summary(&mut self, _: &FrameTimingSummary) {
+        self.frame_timing_summary += 1;
+    }
+
+    fn on_frame_summary(&mut self, _: &FrameSummary) {
+        self.frame_summary += 1;
+    }
+
+    fn on_layer_changes(&mut self, _: u64, _: &[LayerChange]) {
+        self.layer_changes += 1;
+    }
+
+    fn on_damage_rects(&mut self, _: u64, _: &[DamageRect]) {
+        self.damage_rects += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frameclock::timing::PresentationTiming;
+    use frameclock::{Duration, FrameDemand, HostTime, OutputId};
+    use subduction_core::trace::{LayerField, PhaseKind};
+
+    #[test]
+    fn tallies_known_sequence() {
+        let mut sink = CountingSink::new();
+        sink.on_frame_tick(&FrameTickEvent {
+            frame_index: 0,
+            output: OutputId(0),
+            now: HostTime(0),
+            predicted_present: None,
+            refresh_interval: None,
+        });
+        sink.on_frame_plan(&FramePlanEvent {
+            frame_index: 0,
+            output: OutputId(0),
+            demand: FrameDemand::ANIMATION,
+            frame_interval: Duration(16_667),
+            frame_start: HostTime(0),
+            sample_time: HostTime(16_667),
+            target_present: Some(HostTime(16_667)),
+            presentation_timing: PresentationTiming::Predictive,
+            commit_deadline: HostTime(12_000),
+            pipeline_depth: 1,
+            safety_margin_ticks: 0,
+        });
+        for i in 0..60 {
+            let phase = PhaseBeginEvent {
+                frame_index: 0,
+                phase: PhaseKind::Evaluate,
+                timestamp: HostTime(i),
+            };
+            sink.on_phase_begin(&phase);
+        }
+        sink.on_phase_end(&PhaseEndEvent {
+            frame_index: 0,
+            phase: PhaseKind::Evaluate,
+            timestamp: HostTime(60),
+        });
+        sink.on_submit(&SubmitEvent {
+            frame_index: 0,
+            submitted_at: HostTime(100),
+            expected_present: None,
+        });
+        let change = LayerChange {
+            layer_index: 0,
+            field: LayerField::Opacity,
+        };
+        sink.on_layer_changes(0, &[change, change]);
+
+        assert_eq!(
+            sink,
+            CountingSink {
+                frame_tick: 1,
+                frame_plan: 1,
+                phase_begin: 60,
+                phase_end: 1,
+                submit: 1,
+                layer_changes: 1,
+                ..CountingSink::default()
+            }
+        );
+        assert_eq!(sink.total(), 65);
+
+        sink.reset();
+        assert_eq!(sink.total(), 0);
+    }
+
+    #[test]
+    fn counts_through_mutable_reference() {
+        fn drive(mut sink: impl TraceSink) {
+            sink.on_damage_rects(0, &[]);
+        }
+        let mut sink = CountingSink::new();
+        drive(&mut sink);
+        drive(&mut sink);
+        assert_eq!(sink.damage_rects, 2);
+    }
+}
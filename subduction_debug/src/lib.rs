@@ -9,10 +9,18 @@
 //!
 //! - [`pretty::PrettyPrintSink`] — human-readable one-line-per-event output.
 //! - [`recorder::RecorderSink`] — compact binary recording with
-//!   [`recorder::decode`] for playback.
+//!   [`recorder::decode`] for playback and [`recorder::summarize`] for
+//!   aggregate frame statistics.
 //! - [`chrome::export`] — writes Chrome Trace Event Format JSON
 //!   from recorded recorder bytes.
+//! - [`ndjson::export`] — streams recorded events as newline-delimited JSON.
+//! - [`report::export_html`] — renders a self-contained HTML page charting
+//!   recorded frame summaries.
+//! - [`counting::CountingSink`] — per-type event tallies for tests.
 
 pub mod chrome;
+pub mod counting;
+pub mod ndjson;
 pub mod pretty;
 pub mod recorder;
+pub mod report;
@@ -0,0 +1,101 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Newline-delimited JSON exporter.
+//!
+//! [`export`] reads recorded bytes from a [`RecorderSink`](super::recorder::RecorderSink)
+//! and writes one JSON object per event, one per line, straight to the
+//! writer. Unlike [`chrome::export`](super::chrome::export) nothing is
+//! buffered, and the output suits line-based tools such as `jq` and `grep`.
+
+use std::io::{self, Write};
+
+use frameclock::time::Timebase;
+
+use crate::chrome::trace_event;
+use crate::recorder::decode;
+
+/// Exports recorded events as newline-delimited JSON.
+///
+/// Each line is the same trace event object [`chrome::export`] produces for
+/// that event, with timestamps converted to microseconds using the provided
+/// [`Timebase`]. Derived counter and flow events are not emitted.
+///
+/// [`chrome::export`]: super::chrome::export
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::InvalidData`] if `bytes` is not a recording this
+/// version can decode (see [`decode`]), or any error from `writer`.
+pub fn export(bytes: &[u8], timebase: Timebase, writer: &mut dyn Write) -> io::Result<()> {
+    let records = decode(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    for recorded in records {
+        serde_json::to_writer(&mut *writer, &trace_event(&recorded, timebase))?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recorder::RecorderSink;
+    use frameclock::{HostTime, OutputId};
+    use serde_json::Value;
+    use subduction_core::trace::{
+        FrameTickEvent, PhaseBeginEvent, PhaseEndEvent, PhaseKind, SubmitEvent, TraceSink,
+    };
+
+    #[test]
+    fn export_writes_one_json_object_per_line() {
+        let mut rec = RecorderSink::new();
+        for frame_index in 0..5_u64 {
+            let now = 1_000_000 + frame_index * 16_666_667;
+            rec.on_frame_tick(&FrameTickEvent {
+                frame_index,
+                output: OutputId(0),
+                now: HostTime(now),
+                predicted_present: None,
+                refresh_interval: Some(16_666_667),
+            });
+            rec.on_phase_begin(&PhaseBeginEvent {
+                frame_index,
+                phase: PhaseKind::Render,
+                timestamp: HostTime(now + 100_000),
+            });
+            rec.on_phase_end(&PhaseEndEvent {
+                frame_index,
+                phase: PhaseKind::Render,
+                timestamp: HostTime(now + 2_000_000),
+            });
+            rec.on_submit(&SubmitEvent {
+                frame_index,
+                submitted_at: HostTime(now + 2_100_000),
+                expected_present: None,
+            });
+        }
+
+        let mut out = Vec::new();
+        export(rec.as_bytes(), Timebase::NANOS, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.ends_with('\n'));
+
+        let lines: Vec<Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 20);
+        assert!(lines.iter().all(Value::is_object));
+        assert_eq!(lines[0]["name"], "FrameTick");
+        assert_eq!(lines[1]["ph"], "B");
+        assert_eq!(lines[3]["name"], "Submit");
+        assert_eq!(lines[19]["args"]["frame_index"], 4);
+    }
+
+    #[test]
+    fn export_empty_recording_writes_nothing() {
+        let mut out = Vec::new();
+        export(&[], Timebase::NANOS, &mut out).unwrap();
+        assert!(out.is_empty());
+    }
+}
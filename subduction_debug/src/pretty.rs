@@ -4,27 +4,70 @@
 //! Human-readable trace output.
 //!
 //! [`PrettyPrintSink`] implements [`TraceSink`] and writes one line per event
-//! to a [`Write`] destination (default: stderr). Timestamps
-//! are converted to microseconds using a [`Timebase`].
+//! to a [`Write`] destination (default: stderr). Timestamps and durations
+//! are printed in milliseconds using a [`Timebase`], optionally followed by
+//! the raw tick count (see [`PrettyPrintSink::with_raw_ticks`]).
+//!
+//! [`ColorMode`] controls ANSI styling (missed deadlines in red, on-time
+//! summaries dimmed) and [`Verbosity`] controls which events are printed.
 
-use std::io::Write;
+use std::fmt;
+use std::io::{IsTerminal, Write};
 
-use frameclock::time::Timebase;
+use frameclock::HostTime;
+use frameclock::time::{HostTimeMillis, Timebase};
 use subduction_core::trace::{
     DamageRect, FramePlanEvent, FrameSummary, FrameTickEvent, LayerChange, PhaseBeginEvent,
     PhaseEndEvent, PhaseKind, PresentFeedbackEvent, SubmitEvent, TraceSink,
 };
 
+const RED: &str = "\x1b[31m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// When [`PrettyPrintSink`] styles its output with ANSI escapes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Color when writing to stderr and stderr is a terminal, unless the
+    /// `NO_COLOR` environment variable is set. Other writers get no color.
+    #[default]
+    Auto,
+    /// Always emit ANSI escapes.
+    Always,
+    /// Never emit ANSI escapes.
+    Never,
+}
+
+/// Which events [`PrettyPrintSink`] prints.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only frame summaries and present feedback.
+    Summaries,
+    /// Everything except phase begin/end markers.
+    Frames,
+    /// Every event.
+    #[default]
+    All,
+}
+
 /// Writes human-readable trace lines to a [`Write`] destination.
 pub struct PrettyPrintSink<W: Write = Box<dyn Write>> {
     writer: W,
     timebase: Timebase,
+    color: ColorMode,
+    verbosity: Verbosity,
+    raw_ticks: bool,
+    /// Whether the writer is known to be a color-capable terminal.
+    terminal: bool,
 }
 
-impl<W: Write> std::fmt::Debug for PrettyPrintSink<W> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<W: Write> fmt::Debug for PrettyPrintSink<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PrettyPrintSink")
             .field("timebase", &self.timebase)
+            .field("color", &self.color)
+            .field("verbosity", &self.verbosity)
+            .field("raw_ticks", &self.raw_ticks)
             .finish_non_exhaustive()
     }
 }
@@ -33,16 +76,18 @@ impl PrettyPrintSink {
     /// Creates a sink that writes to stderr.
     #[must_use]
     pub fn stderr(timebase: Timebase) -> Self {
+        let stderr = std::io::stderr();
+        let terminal = stderr.is_terminal() && std::env::var_os("NO_COLOR").is_none();
         Self {
-            writer: Box::new(std::io::stderr()),
-            timebase,
+            terminal,
+            ..Self::new(Box::new(stderr), timebase)
         }
     }
 
     /// Creates a sink that writes to a boxed writer.
     #[must_use]
     pub fn new(writer: Box<dyn Write>, timebase: Timebase) -> Self {
-        Self { writer, timebase }
+        Self::with_writer(writer, timebase)
     }
 }
 
@@ -50,15 +95,77 @@ impl<W: Write> PrettyPrintSink<W> {
     /// Creates a sink that writes to the given destination.
     #[must_use]
     pub fn with_writer(writer: W, timebase: Timebase) -> Self {
-        Self { writer, timebase }
+        Self {
+            writer,
+            timebase,
+            color: ColorMode::Auto,
+            verbosity: Verbosity::All,
+            raw_ticks: false,
+            terminal: false,
+        }
+    }
+
+    /// Sets when output is styled with ANSI escapes.
+    #[must_use]
+    pub fn with_color(mut self, color: ColorMode) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets which events are printed.
+    #[must_use]
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Sets whether times are followed by their raw tick counts, as in
+    /// `16.666ms (16666667t)`.
+    #[must_use]
+    pub fn with_raw_ticks(mut self, raw_ticks: bool) -> Self {
+        self.raw_ticks = raw_ticks;
+        self
+    }
+
+    fn use_color(&self) -> bool {
+        match self.color {
+            ColorMode::Auto => self.terminal,
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
     }
 
-    fn ticks_to_us(&self, ticks: u64) -> f64 {
-        self.timebase.ticks_to_nanos(ticks) as f64 / 1000.0
+    /// Returns the escape that starts and ends a line styled for `missed`.
+    fn line_style(&self, missed: bool) -> (&'static str, &'static str) {
+        match (self.use_color(), missed) {
+            (false, _) => ("", ""),
+            (true, true) => (RED, RESET),
+            (true, false) => (DIM, RESET),
+        }
     }
 
-    fn host_us(&self, t: frameclock::HostTime) -> f64 {
-        self.ticks_to_us(t.ticks())
+    /// Formats a tick count (a timestamp or a duration) in milliseconds.
+    fn ms(&self, ticks: u64) -> Millis {
+        Millis {
+            millis: HostTime(ticks).fmt_with(self.timebase),
+            ticks: self.raw_ticks.then_some(ticks),
+        }
+    }
+}
+
+/// Milliseconds with an optional raw tick suffix.
+struct Millis {
+    millis: HostTimeMillis,
+    ticks: Option<u64>,
+}
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.millis)?;
+        if let Some(ticks) = self.ticks {
+            write!(f, " ({ticks}t)")?;
+        }
+        Ok(())
     }
 }
 
@@ -66,63 +173,80 @@ fn phase_name(phase: PhaseKind) -> &'static str {
     match phase {
         PhaseKind::Plan => "plan",
         PhaseKind::Evaluate => "eval",
+        PhaseKind::Acquire => "acquire",
         PhaseKind::Render => "render",
         PhaseKind::Submit => "submit",
+        PhaseKind::Present => "present",
     }
 }
 
 impl<W: Write> TraceSink for PrettyPrintSink<W> {
     fn on_frame_tick(&mut self, e: &FrameTickEvent) {
+        if self.verbosity < Verbosity::Frames {
+            return;
+        }
         let _ = writeln!(
             self.writer,
-            "[tick] frame={} output={} now={:.1}µs",
+            "[tick] frame={} output={} now={}",
             e.frame_index,
             e.output.0,
-            self.host_us(e.now),
+            self.ms(e.now.ticks()),
         );
     }
 
     fn on_frame_plan(&mut self, e: &FramePlanEvent) {
+        if self.verbosity < Verbosity::Frames {
+            return;
+        }
         let _ = writeln!(
             self.writer,
-            "[plan] frame={} demand={:?} timing={:?} interval={:.1}µs start={:.1}µs deadline={:.1}µs depth={} margin={}t",
+            "[plan] frame={} demand={:?} timing={:?} interval={} start={} deadline={} depth={} margin={}",
             e.frame_index,
             e.demand,
             e.presentation_timing,
-            self.ticks_to_us(e.frame_interval.ticks()),
-            self.host_us(e.frame_start),
-            self.host_us(e.commit_deadline),
+            self.ms(e.frame_interval.ticks()),
+            self.ms(e.frame_start.ticks()),
+            self.ms(e.commit_deadline.ticks()),
             e.pipeline_depth,
-            e.safety_margin_ticks,
+            self.ms(e.safety_margin_ticks),
         );
     }
 
     fn on_phase_begin(&mut self, e: &PhaseBeginEvent) {
+        if self.verbosity < Verbosity::All {
+            return;
+        }
         let _ = writeln!(
             self.writer,
-            "[phase:begin] frame={} {} at {:.1}µs",
+            "[phase:begin] frame={} {} at {}",
             e.frame_index,
             phase_name(e.phase),
-            self.host_us(e.timestamp),
+            self.ms(e.timestamp.ticks()),
         );
     }
 
     fn on_phase_end(&mut self, e: &PhaseEndEvent) {
+        if self.verbosity < Verbosity::All {
+            return;
+        }
         let _ = writeln!(
             self.writer,
-            "[phase:end] frame={} {} at {:.1}µs",
+            "[phase:end] frame={} {} at {}",
             e.frame_index,
             phase_name(e.phase),
-            self.host_us(e.timestamp),
+            self.ms(e.timestamp.ticks()),
         );
     }
 
     fn on_submit(&mut self, e: &SubmitEvent) {
+        if self.verbosity < Verbosity::Frames {
+            return;
+        }
         let _ = writeln!(
             self.writer,
-            "[submit] frame={} at {:.1}µs",
+            "[submit] frame={} at {}",
             e.frame_index,
-            self.host_us(e.submitted_at),
+            self.ms(e.submitted_at.ticks()),
         );
     }
 
@@ -137,29 +261,39 @@ impl<W: Write> TraceSink for PrettyPrintSink<W> {
             Some(false) => "ok",
             None => "?",
         };
+        let (start, end) = match e.missed_deadline {
+            Some(true) => self.line_style(true),
+            _ => ("", ""),
+        };
         let _ = writeln!(
             self.writer,
-            "[feedback] frame={} missed={missed} pacing={overrun}",
+            "{start}[feedback] frame={} missed={missed} pacing={overrun}{end}",
             e.frame_index,
         );
     }
 
     fn on_frame_summary(&mut self, s: &FrameSummary) {
         let missed = if s.missed_deadline { "MISSED" } else { "ok" };
+        let (start, end) = self.line_style(s.missed_deadline);
         let _ = writeln!(
             self.writer,
-            "[summary] frame={} depth={} plan={:.1}µs eval={:.1}µs \
-             render={:.1}µs submit={:.1}µs deadline={missed}",
+            "{start}[summary] frame={} depth={} plan={} eval={} acquire={} \
+             render={} submit={} present={} deadline={missed}{end}",
             s.frame_index,
             s.pipeline_depth,
-            self.ticks_to_us(s.plan_ticks),
-            self.ticks_to_us(s.eval_ticks),
-            self.ticks_to_us(s.render_ticks),
-            self.ticks_to_us(s.submit_ticks),
+            self.ms(s.plan_ticks),
+            self.ms(s.eval_ticks),
+            self.ms(s.acquire_ticks),
+            self.ms(s.render_ticks),
+            self.ms(s.submit_ticks),
+            self.ms(s.present_ticks),
         );
     }
 
     fn on_layer_changes(&mut self, frame_index: u64, changes: &[LayerChange]) {
+        if self.verbosity < Verbosity::Frames {
+            return;
+        }
         let _ = writeln!(
             self.writer,
             "[layers] frame={frame_index} changes={}",
@@ -168,6 +302,9 @@ impl<W: Write> TraceSink for PrettyPrintSink<W> {
     }
 
     fn on_damage_rects(&mut self, frame_index: u64, rects: &[DamageRect]) {
+        if self.verbosity < Verbosity::Frames {
+            return;
+        }
         let _ = writeln!(
             self.writer,
             "[damage] frame={frame_index} rects={}",
@@ -179,7 +316,6 @@ impl<W: Write> TraceSink for PrettyPrintSink<W> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use frameclock::HostTime;
     use frameclock::OutputId;
 
     #[test]
@@ -196,4 +332,145 @@ mod tests {
         assert!(output.contains("[tick]"), "got: {output}");
         assert!(output.contains("frame=1"), "got: {output}");
     }
+
+    fn render_tick(sink: PrettyPrintSink<Vec<u8>>, now: HostTime) -> String {
+        let mut sink = sink;
+        sink.on_frame_tick(&FrameTickEvent {
+            frame_index: 1,
+            output: OutputId(0),
+            now,
+            predicted_present: None,
+            refresh_interval: None,
+        });
+        String::from_utf8(sink.writer).unwrap()
+    }
+
+    #[test]
+    fn times_render_in_milliseconds_for_the_timebase() {
+        let nanos = PrettyPrintSink::with_writer(Vec::new(), Timebase::NANOS);
+        assert_eq!(
+            render_tick(nanos, HostTime(16_666_667)),
+            "[tick] frame=1 output=0 now=16.666ms\n"
+        );
+        let micros = PrettyPrintSink::with_writer(Vec::new(), Timebase::MICROS);
+        assert_eq!(
+            render_tick(micros, HostTime(16_666)),
+            "[tick] frame=1 output=0 now=16.666ms\n"
+        );
+        let raw = PrettyPrintSink::with_writer(Vec::new(), Timebase::MICROS).with_raw_ticks(true);
+        assert_eq!(
+            render_tick(raw, HostTime(16_666)),
+            "[tick] frame=1 output=0 now=16.666ms (16666t)\n"
+        );
+    }
+
+    #[test]
+    fn summary_durations_render_in_milliseconds() {
+        let mut sink = PrettyPrintSink::with_writer(Vec::<u8>::new(), Timebase::MICROS)
+            .with_color(ColorMode::Never);
+        sink.on_frame_summary(&summary(false));
+        let output = String::from_utf8(sink.writer).unwrap();
+        assert!(
+            output.contains("plan=0.100ms eval=0.200ms"),
+            "got: {output}"
+        );
+        assert!(output.contains("render=1.000ms"), "got: {output}");
+    }
+
+    fn summary(missed: bool) -> FrameSummary {
+        FrameSummary {
+            frame_index: 3,
+            output: OutputId(0),
+            presentation_timing: frameclock::timing::PresentationTiming::Predictive,
+            now: HostTime(1_000_000),
+            target_present: None,
+            sample_time: HostTime(1_000_000),
+            deadline: HostTime(1_010_000),
+            pipeline_depth: 2,
+            plan_ticks: 100,
+            eval_ticks: 200,
+            acquire_ticks: 0,
+            render_ticks: 1_000,
+            submit_ticks: 50,
+            present_ticks: 0,
+            missed_deadline: missed,
+        }
+    }
+
+    fn phase_begin() -> PhaseBeginEvent {
+        PhaseBeginEvent {
+            frame_index: 3,
+            phase: PhaseKind::Render,
+            timestamp: HostTime(1_000_100),
+        }
+    }
+
+    #[test]
+    fn no_ansi_codes_when_color_is_off() {
+        let mut sink = PrettyPrintSink::with_writer(Vec::<u8>::new(), Timebase::NANOS)
+            .with_color(ColorMode::Never);
+        sink.on_phase_begin(&phase_begin());
+        sink.on_frame_summary(&summary(false));
+        sink.on_frame_summary(&summary(true));
+        let output = String::from_utf8(sink.writer).unwrap();
+        assert!(!output.contains('\x1b'), "got: {output:?}");
+        assert!(output.contains("deadline=MISSED"), "got: {output}");
+    }
+
+    #[test]
+    fn auto_color_is_off_for_plain_writers() {
+        let mut sink = PrettyPrintSink::with_writer(Vec::<u8>::new(), Timebase::NANOS);
+        sink.on_frame_summary(&summary(true));
+        let output = String::from_utf8(sink.writer).unwrap();
+        assert!(!output.contains('\x1b'), "got: {output:?}");
+    }
+
+    #[test]
+    fn missed_summary_is_red_when_color_is_on() {
+        let mut sink = PrettyPrintSink::with_writer(Vec::<u8>::new(), Timebase::NANOS)
+            .with_color(ColorMode::Always);
+        sink.on_frame_summary(&summary(true));
+        sink.on_frame_summary(&summary(false));
+        let output = String::from_utf8(sink.writer).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert!(
+            lines[0].starts_with("\x1b[31m[summary]") && lines[0].ends_with("\x1b[0m"),
+            "got: {:?}",
+            lines[0]
+        );
+        assert!(lines[1].starts_with("\x1b[2m"), "got: {:?}", lines[1]);
+    }
+
+    #[test]
+    fn summaries_verbosity_drops_phase_noise() {
+        let mut sink = PrettyPrintSink::with_writer(Vec::<u8>::new(), Timebase::NANOS)
+            .with_verbosity(Verbosity::Summaries);
+        sink.on_frame_tick(&FrameTickEvent {
+            frame_index: 3,
+            output: OutputId(0),
+            now: HostTime(1_000_000),
+            predicted_present: None,
+            refresh_interval: None,
+        });
+        sink.on_phase_begin(&phase_begin());
+        sink.on_frame_summary(&summary(false));
+        let output = String::from_utf8(sink.writer).unwrap();
+        assert_eq!(output.lines().count(), 1, "got: {output}");
+        assert!(output.starts_with("[summary]"), "got: {output}");
+    }
+
+    #[test]
+    fn frames_verbosity_keeps_submits_but_drops_phases() {
+        let mut sink = PrettyPrintSink::with_writer(Vec::<u8>::new(), Timebase::NANOS)
+            .with_verbosity(Verbosity::Frames);
+        sink.on_phase_begin(&phase_begin());
+        sink.on_submit(&SubmitEvent {
+            frame_index: 3,
+            submitted_at: HostTime(1_002_000),
+            expected_present: None,
+        });
+        let output = String::from_utf8(sink.writer).unwrap();
+        assert!(!output.contains("[phase"), "got: {output}");
+        assert!(output.contains("[submit]"), "got: {output}");
+    }
 }
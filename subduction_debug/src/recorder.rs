@@ -5,18 +5,59 @@
 //!
 //! [`RecorderSink`] implements [`TraceSink`] and encodes events into a
 //! `Vec<u8>` as fixed-size little-endian records. [`decode`] reads them back
-//! as an iterator of [`RecordedEvent`].
+//! as an iterator of [`RecordedEvent`], and [`try_decode`] also reports
+//! corrupt records. For long captures,
+//! [`RecorderSink::to_writer`] streams the same bytes to any [`Write`]
+//! through a buffer instead of holding them in memory.
 //!
 //! Rich events ([`on_layer_changes`](TraceSink::on_layer_changes),
-//! [`on_damage_rects`](TraceSink::on_damage_rects)) store only the count.
+//! [`on_damage_rects`](TraceSink::on_damage_rects)) store only the count by
+//! default. [`RecorderSink::with_rich_records`] opts in to recording every
+//! [`LayerChange`] and [`DamageRect`] instead.
+//!
+//! # Format
+//!
+//! A non-empty recording starts with a header: the four bytes of [`MAGIC`]
+//! followed by a one-byte [`FORMAT_VERSION`]. The header is written before the
+//! first event, so a recorder that saw no events produces an empty buffer.
+//! Each record after the header starts with a one-byte tag.
+//!
+//! Version 0 is the original headerless stream of records; read it with
+//! [`decode_v0`]. Version 2 added the rich layer-change and damage-rect
+//! records; [`decode`] still reads version 1 recordings, which never contain
+//! them. The version is bumped whenever a record is added or its layout
+//! changes.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufWriter, Write};
 
 use frameclock::timing::PresentationTiming;
 use frameclock::{Duration, FrameDemand, HostTime, OutputId};
 use subduction_core::trace::{
-    DamageRect, FramePlanEvent, FrameSummary, FrameTickEvent, LayerChange, PhaseBeginEvent,
-    PhaseEndEvent, PhaseKind, PresentFeedbackEvent, SubmitEvent, TraceSink,
+    DamageRect, FramePlanEvent, FrameSummary, FrameTickEvent, LayerChange, LayerField,
+    PhaseBeginEvent, PhaseEndEvent, PhaseKind, PresentFeedbackEvent, SubmitEvent, TraceSink,
 };
 
+// ---------------------------------------------------------------------------
+// Header
+// ---------------------------------------------------------------------------
+
+/// Magic bytes at the start of every non-empty recording.
+pub const MAGIC: [u8; 4] = *b"SDTR";
+
+/// Version of the record layout written by [`RecorderSink`].
+pub const FORMAT_VERSION: u8 = 2;
+
+/// Oldest header version [`decode`] reads.
+const MIN_FORMAT_VERSION: u8 = 1;
+
+/// Length in bytes of the magic + version header.
+const HEADER_LEN: usize = MAGIC.len() + 1;
+
+/// The header written at the start of every non-empty recording.
+const HEADER: [u8; HEADER_LEN] = [MAGIC[0], MAGIC[1], MAGIC[2], MAGIC[3], FORMAT_VERSION];
+
 // ---------------------------------------------------------------------------
 // Event type discriminants
 // ---------------------------------------------------------------------------
@@ -30,48 +71,244 @@ const TAG_PRESENT_FEEDBACK: u8 = 6;
 const TAG_FRAME_SUMMARY: u8 = 7;
 const TAG_LAYER_CHANGES_COUNT: u8 = 8;
 const TAG_DAMAGE_RECTS_COUNT: u8 = 9;
+// Frame summary with acquire and present phase durations.
+const TAG_FRAME_SUMMARY_V2: u8 = 10;
+// Every layer change of a frame, written in rich mode.
+const TAG_LAYER_CHANGES: u8 = 11;
+// Every damage rect of a frame, written in rich mode.
+const TAG_DAMAGE_RECTS: u8 = 12;
 
 // ---------------------------------------------------------------------------
 // RecorderSink
 // ---------------------------------------------------------------------------
 
 /// A [`TraceSink`] that encodes events into a compact binary buffer.
+///
+/// By default the recording is kept in memory (see [`as_bytes`] and
+/// [`into_bytes`]). [`with_capacity_bytes`] bounds the in-memory recording,
+/// keeping only the most recent records. A recorder created with
+/// [`to_writer`] streams records to a buffered writer instead. `TraceSink`
+/// methods cannot fail, so the first write error is held and every later
+/// event is dropped; [`flush`] and [`into_writer`] report it.
+///
+/// [`as_bytes`]: RecorderSink::as_bytes
+/// [`into_bytes`]: RecorderSink::into_bytes
+/// [`with_capacity_bytes`]: RecorderSink::with_capacity_bytes
+/// [`to_writer`]: RecorderSink::to_writer
+/// [`flush`]: RecorderSink::flush
+/// [`into_writer`]: RecorderSink::into_writer
 #[derive(Debug, Default)]
-pub struct RecorderSink {
-    buf: Vec<u8>,
+pub struct RecorderSink<W = Vec<u8>> {
+    out: W,
+    /// The record being encoded; emitted whole by `end_record`.
+    record: Vec<u8>,
+    /// Bounded storage used instead of `out` in ring-buffer mode.
+    ring: Option<RecordRing>,
+    started: bool,
+    /// Whether rich events are recorded in full rather than as counts.
+    rich: bool,
+    error: Option<io::Error>,
 }
 
 impl RecorderSink {
-    /// Creates an empty recorder.
+    /// Creates an empty in-memory recorder.
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Creates an unbounded in-memory recorder with room for `bytes` bytes
+    /// before it reallocates.
+    ///
+    /// Use this to pre-size long captures. Unlike
+    /// [`with_capacity_bytes`](Self::with_capacity_bytes), it does not limit
+    /// how large the recording may grow.
+    #[must_use]
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            out: Vec::with_capacity(bytes),
+            ..Self::default()
+        }
+    }
+
+    /// Creates an in-memory recorder that keeps at most `max` bytes, header
+    /// included.
+    ///
+    /// When appending a record would exceed `max`, the oldest whole records
+    /// are evicted first, so the recording always decodes cleanly and holds
+    /// the most recent events. A record that cannot fit even on its own is
+    /// dropped.
+    #[must_use]
+    pub fn with_capacity_bytes(max: usize) -> Self {
+        Self {
+            ring: Some(RecordRing::new(max)),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the number of records evicted or dropped to stay within the
+    /// capacity set by [`with_capacity_bytes`](Self::with_capacity_bytes).
+    ///
+    /// Always 0 for an unbounded recorder.
+    #[must_use]
+    pub fn dropped_records(&self) -> u64 {
+        self.ring.as_ref().map_or(0, |ring| ring.dropped)
+    }
+
+    /// Returns the length of the recording in bytes, header included.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Returns whether nothing has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+
+    /// Discards the recording so the recorder can be reused, keeping its
+    /// allocated storage.
+    ///
+    /// The next event starts a new recording with its own header, and
+    /// [`dropped_records`](Self::dropped_records) restarts from 0.
+    pub fn clear(&mut self) {
+        self.out.clear();
+        self.started = false;
+        if let Some(ring) = &mut self.ring {
+            ring.clear();
+        }
+    }
+
     /// Returns a view of the recorded bytes.
     #[must_use]
     pub fn as_bytes(&self) -> &[u8] {
-        &self.buf
+        match &self.ring {
+            Some(ring) => ring.as_bytes(),
+            None => &self.out,
+        }
     }
 
     /// Consumes the recorder and returns the recorded bytes.
     #[must_use]
     pub fn into_bytes(self) -> Vec<u8> {
-        self.buf
+        match self.ring {
+            Some(ring) => ring.into_bytes(),
+            None => self.out,
+        }
+    }
+}
+
+impl<W: Write> RecorderSink<BufWriter<W>> {
+    /// Creates a recorder that streams records to `writer` through a
+    /// [`BufWriter`].
+    ///
+    /// The bytes written are identical to what an in-memory recorder would
+    /// hold, so [`decode`] reads them unchanged.
+    #[must_use]
+    pub fn to_writer(writer: W) -> Self {
+        Self {
+            out: BufWriter::new(writer),
+            record: Vec::new(),
+            ring: None,
+            started: false,
+            rich: false,
+            error: None,
+        }
+    }
+
+    /// Flushes buffered records and returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error hit while recording, or the error from the
+    /// final flush.
+    pub fn into_writer(mut self) -> io::Result<W> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.out
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)
+    }
+}
+
+impl<W: Write> RecorderSink<W> {
+    /// Sets whether layer changes and damage rects are recorded in full.
+    ///
+    /// By default only their counts are kept: a fixed 13-byte record per
+    /// frame, decoded as [`RecordedEvent::LayerChangesCount`] and
+    /// [`RecordedEvent::DamageRectsCount`]. Rich records add 5 bytes per
+    /// [`LayerChange`] and 16 bytes per [`DamageRect`], so a frame that
+    /// touches thousands of layers costs tens of kilobytes; they decode as
+    /// [`RecordedEvent::LayerChanges`] and [`RecordedEvent::DamageRects`].
+    #[must_use]
+    pub fn with_rich_records(mut self, rich: bool) -> Self {
+        self.rich = rich;
+        self
+    }
+
+    /// Flushes records buffered so far to the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error hit while recording (clearing it), or the error
+    /// from flushing the output.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.out.flush()
     }
 
     // -- encoding helpers --------------------------------------------------
 
+    fn begin_record(&mut self, tag: u8) {
+        self.record.clear();
+        self.write_u8(tag);
+    }
+
+    /// Emits the encoded record, preceded by the header if it is the first.
+    fn end_record(&mut self) {
+        if let Some(ring) = &mut self.ring {
+            ring.push(&self.record);
+            return;
+        }
+        if self.error.is_some() {
+            return;
+        }
+        let header: &[u8] = if self.started { &[] } else { &HEADER };
+        self.started = true;
+        if let Err(e) = self
+            .out
+            .write_all(header)
+            .and_then(|()| self.out.write_all(&self.record))
+        {
+            self.error = Some(e);
+        }
+    }
+
     fn write_u8(&mut self, v: u8) {
-        self.buf.push(v);
+        self.record.push(v);
     }
 
     fn write_u32(&mut self, v: u32) {
-        self.buf.extend_from_slice(&v.to_le_bytes());
+        self.record.extend_from_slice(&v.to_le_bytes());
     }
 
     fn write_u64(&mut self, v: u64) {
-        self.buf.extend_from_slice(&v.to_le_bytes());
+        self.record.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, v: i32) {
+        self.record.extend_from_slice(&v.to_le_bytes());
+    }
+
+    /// Writes `len` capped at `u32::MAX` and returns the capped value.
+    fn write_count(&mut self, len: usize) -> usize {
+        let count = u32::try_from(len).unwrap_or(u32::MAX);
+        self.write_u32(count);
+        count as usize
     }
 
     fn write_option_u64(&mut self, v: Option<u64>) {
@@ -103,28 +340,44 @@ impl RecorderSink {
         });
     }
 
+    fn write_layer_field(&mut self, field: LayerField) {
+        self.write_u8(match field {
+            LayerField::Transform => 0,
+            LayerField::Opacity => 1,
+            LayerField::Clip => 2,
+            LayerField::Content => 3,
+            LayerField::Bounds => 4,
+            LayerField::BlendMode => 5,
+            LayerField::Flags => 6,
+            LayerField::Topology => 7,
+        });
+    }
+
     fn write_phase(&mut self, p: PhaseKind) {
         self.write_u8(match p {
             PhaseKind::Plan => 0,
             PhaseKind::Evaluate => 1,
             PhaseKind::Render => 2,
             PhaseKind::Submit => 3,
+            PhaseKind::Acquire => 4,
+            PhaseKind::Present => 5,
         });
     }
 }
 
-impl TraceSink for RecorderSink {
+impl<W: Write> TraceSink for RecorderSink<W> {
     fn on_frame_tick(&mut self, e: &FrameTickEvent) {
-        self.write_u8(TAG_FRAME_TICK);
+        self.begin_record(TAG_FRAME_TICK);
         self.write_u64(e.frame_index);
         self.write_u32(e.output.0);
         self.write_u64(e.now.ticks());
         self.write_option_u64(e.predicted_present.map(|t| t.ticks()));
         self.write_option_u64(e.refresh_interval);
+        self.end_record();
     }
 
     fn on_frame_plan(&mut self, e: &FramePlanEvent) {
-        self.write_u8(TAG_FRAME_PLAN);
+        self.begin_record(TAG_FRAME_PLAN);
         self.write_u64(e.frame_index);
         self.write_u32(e.output.0);
         self.write_u8(e.demand.bits());
@@ -136,39 +389,44 @@ impl TraceSink for RecorderSink {
         self.write_u64(e.commit_deadline.ticks());
         self.write_u8(e.pipeline_depth);
         self.write_u64(e.safety_margin_ticks);
+        self.end_record();
     }
 
     fn on_phase_begin(&mut self, e: &PhaseBeginEvent) {
-        self.write_u8(TAG_PHASE_BEGIN);
+        self.begin_record(TAG_PHASE_BEGIN);
         self.write_u64(e.frame_index);
         self.write_phase(e.phase);
         self.write_u64(e.timestamp.ticks());
+        self.end_record();
     }
 
     fn on_phase_end(&mut self, e: &PhaseEndEvent) {
-        self.write_u8(TAG_PHASE_END);
+        self.begin_record(TAG_PHASE_END);
         self.write_u64(e.frame_index);
         self.write_phase(e.phase);
         self.write_u64(e.timestamp.ticks());
+        self.end_record();
     }
 
     fn on_submit(&mut self, e: &SubmitEvent) {
-        self.write_u8(TAG_SUBMIT);
+        self.begin_record(TAG_SUBMIT);
         self.write_u64(e.frame_index);
         self.write_u64(e.submitted_at.ticks());
         self.write_option_u64(e.expected_present.map(|t| t.ticks()));
+        self.end_record();
     }
 
     fn on_present_feedback(&mut self, e: &PresentFeedbackEvent) {
-        self.write_u8(TAG_PRESENT_FEEDBACK);
+        self.begin_record(TAG_PRESENT_FEEDBACK);
         self.write_u64(e.frame_index);
         self.write_option_u64(e.actual_present.map(|t| t.ticks()));
         self.write_option_bool(e.missed_deadline);
         self.write_option_bool(e.pacing_overrun);
+        self.end_record();
     }
 
     fn on_frame_summary(&mut self, s: &FrameSummary) {
-        self.write_u8(TAG_FRAME_SUMMARY);
+        self.begin_record(TAG_FRAME_SUMMARY_V2);
         self.write_u64(s.frame_index);
         self.write_u32(s.output.0);
         self.write_presentation_timing(s.presentation_timing);
@@ -182,26 +440,129 @@ impl TraceSink for RecorderSink {
         self.write_u64(s.render_ticks);
         self.write_u64(s.submit_ticks);
         self.write_u8(u8::from(s.missed_deadline));
+        self.write_u64(s.acquire_ticks);
+        self.write_u64(s.present_ticks);
+        self.end_record();
     }
 
     fn on_layer_changes(&mut self, frame_index: u64, changes: &[LayerChange]) {
-        self.write_u8(TAG_LAYER_CHANGES_COUNT);
+        if !self.rich {
+            self.begin_record(TAG_LAYER_CHANGES_COUNT);
+            self.write_u64(frame_index);
+            self.write_count(changes.len());
+            self.end_record();
+            return;
+        }
+        self.begin_record(TAG_LAYER_CHANGES);
         self.write_u64(frame_index);
-        #[expect(
-            clippy::cast_possible_truncation,
-            reason = "layer change count capped at u32::MAX for recording"
-        )]
-        self.write_u32(changes.len().min(u32::MAX as usize) as u32);
+        let count = self.write_count(changes.len());
+        for change in &changes[..count] {
+            self.write_u32(change.layer_index);
+            self.write_layer_field(change.field);
+        }
+        self.end_record();
     }
 
     fn on_damage_rects(&mut self, frame_index: u64, rects: &[DamageRect]) {
-        self.write_u8(TAG_DAMAGE_RECTS_COUNT);
+        if !self.rich {
+            self.begin_record(TAG_DAMAGE_RECTS_COUNT);
+            self.write_u64(frame_index);
+            self.write_count(rects.len());
+            self.end_record();
+            return;
+        }
+        self.begin_record(TAG_DAMAGE_RECTS);
         self.write_u64(frame_index);
-        #[expect(
-            clippy::cast_possible_truncation,
-            reason = "damage rect count capped at u32::MAX for recording"
-        )]
-        self.write_u32(rects.len().min(u32::MAX as usize) as u32);
+        let count = self.write_count(rects.len());
+        for rect in &rects[..count] {
+            self.write_i32(rect.x);
+            self.write_i32(rect.y);
+            self.write_u32(rect.width);
+            self.write_u32(rect.height);
+        }
+        self.end_record();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RecordRing
+// ---------------------------------------------------------------------------
+
+/// Bounded record storage for [`RecorderSink::with_capacity_bytes`].
+///
+/// The live recording (header, then records) is kept contiguous at the end of
+/// `buf`. Evicting records moves the header forward over the evicted bytes
+/// rather than shifting the buffer; the dead prefix is compacted once it grows
+/// past the capacity, so appends stay amortized O(record length).
+#[derive(Debug)]
+struct RecordRing {
+    buf: Vec<u8>,
+    /// Offset of the header in `buf`.
+    head: usize,
+    /// Lengths of the live records, oldest first.
+    record_lens: VecDeque<usize>,
+    max_bytes: usize,
+    dropped: u64,
+}
+
+impl RecordRing {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            head: 0,
+            record_lens: VecDeque::new(),
+            max_bytes,
+            dropped: 0,
+        }
+    }
+
+    fn push(&mut self, record: &[u8]) {
+        if HEADER_LEN + record.len() > self.max_bytes {
+            self.dropped += 1;
+            return;
+        }
+        if self.buf.is_empty() {
+            self.buf.extend_from_slice(&HEADER);
+        }
+
+        let mut live = self.buf.len() - self.head;
+        let mut evicted = 0;
+        while live + record.len() > self.max_bytes {
+            let len = self
+                .record_lens
+                .pop_front()
+                .expect("a lone record always fits after the size check");
+            evicted += len;
+            live -= len;
+            self.dropped += 1;
+        }
+        if evicted > 0 {
+            self.head += evicted;
+            self.buf[self.head..self.head + HEADER_LEN].copy_from_slice(&HEADER);
+        }
+        if self.head > self.max_bytes {
+            self.buf.drain(..self.head);
+            self.head = 0;
+        }
+
+        self.buf.extend_from_slice(record);
+        self.record_lens.push_back(record.len());
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[self.head..]
+    }
+
+    fn clear(&mut self) {
+        self.buf.clear();
+        self.head = 0;
+        self.record_lens.clear();
+        self.dropped = 0;
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.buf.drain(..self.head);
+        self.buf
     }
 }
 
@@ -240,25 +601,225 @@ pub enum RecordedEvent {
         /// Number of damage rects.
         count: u32,
     },
+    /// Every layer change of a frame, from a recorder with
+    /// [`with_rich_records`](RecorderSink::with_rich_records) enabled.
+    LayerChanges {
+        /// Frame counter.
+        frame_index: u64,
+        /// The recorded changes, in order.
+        changes: Vec<LayerChange>,
+    },
+    /// Every damage rect of a frame, from a recorder with
+    /// [`with_rich_records`](RecorderSink::with_rich_records) enabled.
+    DamageRects {
+        /// Frame counter.
+        frame_index: u64,
+        /// The recorded rects, in order.
+        rects: Vec<DamageRect>,
+    },
 }
 
+impl RecordedEvent {
+    /// Returns the host time this event describes, if it carries one.
+    ///
+    /// Plans report their frame start and summaries their tick time. Layer
+    /// changes and damage rects carry no time, nor does feedback without an
+    /// actual present time.
+    #[must_use]
+    pub fn timestamp(&self) -> Option<HostTime> {
+        match self {
+            Self::FrameTick(e) => Some(e.now),
+            Self::FramePlan(e) => Some(e.frame_start),
+            Self::PhaseBegin(e) => Some(e.timestamp),
+            Self::PhaseEnd(e) => Some(e.timestamp),
+            Self::Submit(e) => Some(e.submitted_at),
+            Self::PresentFeedback(e) => e.actual_present,
+            Self::FrameSummary(s) => Some(s.now),
+            Self::LayerChangesCount { .. }
+            | Self::DamageRectsCount { .. }
+            | Self::LayerChanges { .. }
+            | Self::DamageRects { .. } => None,
+        }
+    }
+}
+
+/// Error returned by [`decode`] when a buffer has no valid header, or by
+/// [`try_decode`] iteration when a record is corrupt.
+///
+/// Offsets are byte positions in the buffer passed to the decoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer is shorter than the header.
+    Truncated,
+    /// The buffer does not start with [`MAGIC`].
+    BadMagic,
+    /// The header names a version this decoder does not read.
+    UnsupportedVersion(u8),
+    /// A record starts with a tag this decoder does not know.
+    UnknownTag {
+        /// The unrecognized tag byte.
+        tag: u8,
+        /// Offset of the tag byte.
+        offset: usize,
+    },
+    /// The buffer ends partway through a record, or the record holds a value
+    /// no writer produces.
+    TruncatedRecord {
+        /// Offset of the incomplete record's tag byte.
+        offset: usize,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => f.write_str("recording is shorter than its header"),
+            Self::BadMagic => f.write_str("recording does not start with the expected magic"),
+            Self::UnsupportedVersion(v) => write!(
+                f,
+                "unsupported recording version {v} \
+                 (expected {MIN_FORMAT_VERSION} to {FORMAT_VERSION})"
+            ),
+            Self::UnknownTag { tag, offset } => {
+                write!(f, "unknown record tag {tag} at offset {offset}")
+            }
+            Self::TruncatedRecord { offset } => {
+                write!(f, "truncated or malformed record at offset {offset}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
 /// Decodes a byte slice produced by [`RecorderSink`] into an iterator of
 /// [`RecordedEvent`].
-pub fn decode(bytes: &[u8]) -> DecodeIter<'_> {
+///
+/// An empty slice decodes to no events. Otherwise the header is validated
+/// first; iteration stops at the first truncated record or unknown tag. Use
+/// [`try_decode`] to tell such corruption apart from a clean end.
+///
+/// # Errors
+///
+/// Returns a [`DecodeError`] if the buffer is too short for the header, has
+/// the wrong magic, or was written with a version other than 1 through
+/// [`FORMAT_VERSION`].
+pub fn decode(bytes: &[u8]) -> Result<DecodeIter<'_>, DecodeError> {
+    if bytes.is_empty() {
+        return Ok(decode_v0(bytes));
+    }
+    let Some((header, records)) = bytes.split_at_checked(HEADER_LEN) else {
+        return Err(DecodeError::Truncated);
+    };
+    if header[..MAGIC.len()] != MAGIC {
+        return Err(DecodeError::BadMagic);
+    }
+    // Later versions only add records, so one decoder reads them all.
+    match header[MAGIC.len()] {
+        MIN_FORMAT_VERSION..=FORMAT_VERSION => Ok(DecodeIter {
+            data: records,
+            pos: 0,
+            base: HEADER_LEN,
+        }),
+        v => Err(DecodeError::UnsupportedVersion(v)),
+    }
+}
+
+/// Decodes a byte slice produced by [`RecorderSink`], reporting corrupt
+/// records instead of stopping silently.
+///
+/// The iterator yields `Ok` for each record and ends (`None`) at a clean end
+/// of the buffer. An unknown tag or a record cut short yields one `Err` with
+/// its offset, after which iteration ends.
+///
+/// # Errors
+///
+/// Returns a [`DecodeError`] if the header is invalid, as [`decode`] does.
+pub fn try_decode(bytes: &[u8]) -> Result<TryDecodeIter<'_>, DecodeError> {
+    decode(bytes).map(TryDecodeIter)
+}
+
+/// Decodes a headerless (version 0) recording into an iterator of
+/// [`RecordedEvent`].
+pub fn decode_v0(bytes: &[u8]) -> DecodeIter<'_> {
     DecodeIter {
         data: bytes,
         pos: 0,
+        base: 0,
     }
 }
 
 /// Iterator over decoded events.
+///
+/// Iteration ends at the first corrupt record; see [`try_decode`] to observe
+/// it.
 #[derive(Debug)]
 pub struct DecodeIter<'a> {
     data: &'a [u8],
     pos: usize,
+    /// Offset of `data` within the buffer passed to the decoder.
+    base: usize,
+}
+
+/// Iterator over decoded events that reports corrupt records.
+///
+/// Created by [`try_decode`].
+#[derive(Debug)]
+pub struct TryDecodeIter<'a>(DecodeIter<'a>);
+
+impl Iterator for TryDecodeIter<'_> {
+    type Item = Result<RecordedEvent, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_checked()
+    }
+}
+
+impl<'a> DecodeIter<'a> {
+    /// Decodes the next event along with the raw bytes of its record.
+    fn next_raw(&mut self) -> Option<(RecordedEvent, &'a [u8])> {
+        let start = self.pos;
+        let event = self.next()?;
+        Some((event, &self.data[start..self.pos]))
+    }
 }
 
 impl DecodeIter<'_> {
+    /// Decodes the next record, or reports why it could not be decoded.
+    ///
+    /// After an error the iterator is exhausted.
+    fn next_checked(&mut self) -> Option<Result<RecordedEvent, DecodeError>> {
+        let start = self.pos;
+        let tag = self.read_u8()?;
+        let event = match tag {
+            TAG_FRAME_TICK => self.decode_frame_tick(),
+            TAG_FRAME_PLAN => self.decode_frame_plan(),
+            TAG_PHASE_BEGIN => self.decode_phase_begin(),
+            TAG_PHASE_END => self.decode_phase_end(),
+            TAG_SUBMIT => self.decode_submit(),
+            TAG_PRESENT_FEEDBACK => self.decode_present_feedback(),
+            TAG_FRAME_SUMMARY => self.decode_frame_summary(1),
+            TAG_FRAME_SUMMARY_V2 => self.decode_frame_summary(2),
+            TAG_LAYER_CHANGES_COUNT => self.decode_layer_changes_count(),
+            TAG_DAMAGE_RECTS_COUNT => self.decode_damage_rects_count(),
+            TAG_LAYER_CHANGES => self.decode_layer_changes(),
+            TAG_DAMAGE_RECTS => self.decode_damage_rects(),
+            _ => {
+                self.pos = self.data.len();
+                return Some(Err(DecodeError::UnknownTag {
+                    tag,
+                    offset: self.base + start,
+                }));
+            }
+        };
+        Some(event.ok_or_else(|| {
+            self.pos = self.data.len();
+            DecodeError::TruncatedRecord {
+                offset: self.base + start,
+            }
+        }))
+    }
+
     fn remaining(&self) -> usize {
         self.data.len() - self.pos
     }
@@ -290,6 +851,17 @@ impl DecodeIter<'_> {
         Some(v)
     }
 
+    fn read_i32(&mut self) -> Option<i32> {
+        self.read_u32().map(|v| i32::from_le_bytes(v.to_le_bytes()))
+    }
+
+    /// Reads a count of entries that are each at least `entry_len` bytes,
+    /// rejecting counts the remaining data cannot hold.
+    fn read_count(&mut self, entry_len: usize) -> Option<usize> {
+        let count = self.read_u32()? as usize;
+        (count <= self.remaining() / entry_len).then_some(count)
+    }
+
     fn read_option_u64(&mut self) -> Option<Option<u64>> {
         let present = self.read_u8()?;
         let val = self.read_u64()?;
@@ -313,12 +885,31 @@ impl DecodeIter<'_> {
         })
     }
 
+    /// Reads a [`LayerField`], rejecting bytes no writer produces.
+    fn read_layer_field(&mut self) -> Option<LayerField> {
+        Some(match self.read_u8()? {
+            0 => LayerField::Transform,
+            1 => LayerField::Opacity,
+            2 => LayerField::Clip,
+            3 => LayerField::Content,
+            4 => LayerField::Bounds,
+            5 => LayerField::BlendMode,
+            6 => LayerField::Flags,
+            7 => LayerField::Topology,
+            _ => return None,
+        })
+    }
+
+    /// Reads a [`PhaseKind`], rejecting bytes no writer produces.
     fn read_phase(&mut self) -> Option<PhaseKind> {
         Some(match self.read_u8()? {
             0 => PhaseKind::Plan,
             1 => PhaseKind::Evaluate,
             2 => PhaseKind::Render,
-            _ => PhaseKind::Submit,
+            3 => PhaseKind::Submit,
+            4 => PhaseKind::Acquire,
+            5 => PhaseKind::Present,
+            _ => return None,
         })
     }
 
@@ -381,8 +972,10 @@ impl DecodeIter<'_> {
         }))
     }
 
-    fn decode_frame_summary(&mut self) -> Option<RecordedEvent> {
-        Some(RecordedEvent::FrameSummary(FrameSummary {
+    /// Decodes a frame summary; version 1 records lack the acquire and
+    /// present durations, which decode as 0.
+    fn decode_frame_summary(&mut self, version: u8) -> Option<RecordedEvent> {
+        let mut summary = FrameSummary {
             frame_index: self.read_u64()?,
             output: OutputId(self.read_u32()?),
             presentation_timing: self.read_presentation_timing()?,
@@ -393,10 +986,17 @@ impl DecodeIter<'_> {
             pipeline_depth: self.read_u8()?,
             plan_ticks: self.read_u64()?,
             eval_ticks: self.read_u64()?,
+            acquire_ticks: 0,
             render_ticks: self.read_u64()?,
             submit_ticks: self.read_u64()?,
+            present_ticks: 0,
             missed_deadline: self.read_u8()? != 0,
-        }))
+        };
+        if version >= 2 {
+            summary.acquire_ticks = self.read_u64()?;
+            summary.present_ticks = self.read_u64()?;
+        }
+        Some(RecordedEvent::FrameSummary(summary))
     }
 
     fn decode_layer_changes_count(&mut self) -> Option<RecordedEvent> {
@@ -410,28 +1010,263 @@ impl DecodeIter<'_> {
         let count = self.read_u32()?;
         Some(RecordedEvent::DamageRectsCount { frame_index, count })
     }
+
+    fn decode_layer_changes(&mut self) -> Option<RecordedEvent> {
+        let frame_index = self.read_u64()?;
+        let count = self.read_count(5)?;
+        let changes = (0..count)
+            .map(|_| {
+                Some(LayerChange {
+                    layer_index: self.read_u32()?,
+                    field: self.read_layer_field()?,
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(RecordedEvent::LayerChanges {
+            frame_index,
+            changes,
+        })
+    }
+
+    fn decode_damage_rects(&mut self) -> Option<RecordedEvent> {
+        let frame_index = self.read_u64()?;
+        let count = self.read_count(16)?;
+        let rects = (0..count)
+            .map(|_| {
+                Some(DamageRect {
+                    x: self.read_i32()?,
+                    y: self.read_i32()?,
+                    width: self.read_u32()?,
+                    height: self.read_u32()?,
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(RecordedEvent::DamageRects { frame_index, rects })
+    }
 }
 
 impl Iterator for DecodeIter<'_> {
     type Item = RecordedEvent;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let tag = self.read_u8()?;
-        match tag {
-            TAG_FRAME_TICK => self.decode_frame_tick(),
-            TAG_FRAME_PLAN => self.decode_frame_plan(),
-            TAG_PHASE_BEGIN => self.decode_phase_begin(),
-            TAG_PHASE_END => self.decode_phase_end(),
-            TAG_SUBMIT => self.decode_submit(),
-            TAG_PRESENT_FEEDBACK => self.decode_present_feedback(),
-            TAG_FRAME_SUMMARY => self.decode_frame_summary(),
-            TAG_LAYER_CHANGES_COUNT => self.decode_layer_changes_count(),
-            TAG_DAMAGE_RECTS_COUNT => self.decode_damage_rects_count(),
-            _ => None, // unknown tag → stop iteration
+        // Corrupt records end iteration; `try_decode` reports them.
+        self.next_checked()?.ok()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Merge
+// ---------------------------------------------------------------------------
+
+/// Merges several recordings into one, interleaving records by timestamp.
+///
+/// Records are ordered by [`RecordedEvent::timestamp`]; a record without one
+/// takes the time of the record before it in its own recording, so layer and
+/// damage counts stay next to their frame. Each recording's own record order
+/// is preserved, and ties go to the recording listed first. Records are copied
+/// byte for byte.
+///
+/// Timestamps are compared directly, so the recordings must share a host
+/// clock and [`Timebase`](frameclock::time::Timebase), as processes on one
+/// machine do. [`OutputId`]s are kept as recorded; give each process distinct
+/// output ids if its frames should stay distinguishable (e.g. as separate
+/// tracks in [`chrome::export`](crate::chrome::export)) after merging.
+///
+/// # Errors
+///
+/// Returns a [`DecodeError`] if any recording has an invalid header.
+pub fn merge(recordings: &[&[u8]]) -> Result<Vec<u8>, DecodeError> {
+    let mut sources = recordings
+        .iter()
+        .map(|bytes| decode(bytes).map(MergeSource::new))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut out = Vec::new();
+
+    // Linear scan for the earliest head: merges involve a handful of inputs.
+    while let Some((_, idx)) = sources
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, source)| Some((source.head?.0, idx)))
+        .min()
+    {
+        let source = &mut sources[idx];
+        if out.is_empty() {
+            out.extend_from_slice(&HEADER);
+        }
+        if let Some((_, raw)) = source.head {
+            out.extend_from_slice(raw);
+        }
+        source.advance();
+    }
+    Ok(out)
+}
+
+/// One input to [`merge`], positioned at its next record.
+struct MergeSource<'a> {
+    records: DecodeIter<'a>,
+    /// Sort key and raw bytes of the next record.
+    head: Option<(u64, &'a [u8])>,
+    last_ticks: u64,
+}
+
+impl<'a> MergeSource<'a> {
+    fn new(records: DecodeIter<'a>) -> Self {
+        let mut source = Self {
+            records,
+            head: None,
+            last_ticks: 0,
+        };
+        source.advance();
+        source
+    }
+
+    fn advance(&mut self) {
+        self.head = self.records.next_raw().map(|(event, raw)| {
+            if let Some(t) = event.timestamp() {
+                self.last_ticks = t.ticks();
+            }
+            (self.last_ticks, raw)
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Statistics
+// ---------------------------------------------------------------------------
+
+/// Nearest-rank percentiles of a distribution, in ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Percentiles {
+    /// Median.
+    pub p50: u64,
+    /// 95th percentile.
+    pub p95: u64,
+    /// 99th percentile.
+    pub p99: u64,
+}
+
+impl Percentiles {
+    /// Computes percentiles from unsorted samples; all zero when empty.
+    fn from_samples(mut samples: Vec<u64>) -> Self {
+        samples.sort_unstable();
+        Self {
+            p50: nearest_rank(&samples, 50),
+            p95: nearest_rank(&samples, 95),
+            p99: nearest_rank(&samples, 99),
         }
     }
 }
 
+/// Returns the `pct`th percentile of `sorted` by the nearest-rank method.
+fn nearest_rank(sorted: &[u64], pct: usize) -> u64 {
+    match (pct * sorted.len()).div_ceil(100) {
+        0 => sorted.first().copied().unwrap_or(0),
+        rank => sorted[rank - 1],
+    }
+}
+
+/// Aggregate statistics over the [`FrameSummary`] records in a recording.
+///
+/// Durations are in ticks of the recording's timebase.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecordingStats {
+    /// Number of frame summaries.
+    pub frame_count: usize,
+    /// Number of frames that missed their deadline.
+    pub missed_count: usize,
+    /// Total build time per frame (sum of all phase durations).
+    pub build: Percentiles,
+    /// Plan phase durations.
+    pub plan: Percentiles,
+    /// Evaluate phase durations.
+    pub eval: Percentiles,
+    /// Acquire phase durations.
+    pub acquire: Percentiles,
+    /// Render phase durations.
+    pub render: Percentiles,
+    /// Submit phase durations.
+    pub submit: Percentiles,
+    /// Present phase durations.
+    pub present: Percentiles,
+    /// Intervals between consecutive frame ticks; the reciprocal gives the
+    /// frame-rate distribution.
+    pub frame_interval: Percentiles,
+}
+
+impl RecordingStats {
+    /// Fraction of frames that missed their deadline, in `0.0..=1.0`.
+    ///
+    /// Returns 0 for a recording with no frames.
+    #[must_use]
+    pub fn miss_rate(&self) -> f64 {
+        if self.frame_count == 0 {
+            return 0.0;
+        }
+        self.missed_count as f64 / self.frame_count as f64
+    }
+}
+
+/// Computes [`RecordingStats`] over the frame summaries in a recording
+/// produced by [`RecorderSink`].
+///
+/// Other records are ignored. This buffers every duration, so it is meant for
+/// offline analysis rather than the frame loop.
+///
+/// # Errors
+///
+/// Returns a [`DecodeError`] if the recording header is invalid (see
+/// [`decode`]).
+pub fn summarize(bytes: &[u8]) -> Result<RecordingStats, DecodeError> {
+    let mut frame_count = 0;
+    let mut missed_count = 0;
+    let mut build = Vec::new();
+    let mut phases: [Vec<u64>; 6] = Default::default();
+    let mut intervals = Vec::new();
+    let mut prev_now = None;
+
+    for event in decode(bytes)? {
+        let RecordedEvent::FrameSummary(s) = event else {
+            continue;
+        };
+        frame_count += 1;
+        missed_count += usize::from(s.missed_deadline);
+        let durations = [
+            s.plan_ticks,
+            s.eval_ticks,
+            s.acquire_ticks,
+            s.render_ticks,
+            s.submit_ticks,
+            s.present_ticks,
+        ];
+        for (samples, ticks) in phases.iter_mut().zip(durations) {
+            samples.push(ticks);
+        }
+        build.push(
+            durations
+                .iter()
+                .fold(0_u64, |sum, &t| sum.saturating_add(t)),
+        );
+        if let Some(prev) = prev_now {
+            intervals.push(s.now.saturating_duration_since(prev).ticks());
+        }
+        prev_now = Some(s.now);
+    }
+
+    let [plan, eval, acquire, render, submit, present] = phases.map(Percentiles::from_samples);
+    Ok(RecordingStats {
+        frame_count,
+        missed_count,
+        build: Percentiles::from_samples(build),
+        plan,
+        eval,
+        acquire,
+        render,
+        submit,
+        present,
+        frame_interval: Percentiles::from_samples(intervals),
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -478,8 +1313,10 @@ mod tests {
             pipeline_depth: 2,
             plan_ticks: 100,
             eval_ticks: 400,
+            acquire_ticks: 120,
             render_ticks: 1500,
             submit_ticks: 50,
+            present_ticks: 80,
             missed_deadline: false,
         }
     }
@@ -490,7 +1327,7 @@ mod tests {
         let orig = sample_tick_event();
         rec.on_frame_tick(&orig);
 
-        let events: Vec<_> = decode(rec.as_bytes()).collect();
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
         assert_eq!(events.len(), 1);
         match &events[0] {
             RecordedEvent::FrameTick(e) => {
@@ -510,7 +1347,7 @@ mod tests {
         let orig = sample_plan_event();
         rec.on_frame_plan(&orig);
 
-        let events: Vec<_> = decode(rec.as_bytes()).collect();
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
         assert_eq!(events.len(), 1);
         match &events[0] {
             RecordedEvent::FramePlan(e) => {
@@ -542,7 +1379,7 @@ mod tests {
         rec.on_phase_begin(&begin);
         rec.on_phase_end(&end);
 
-        let events: Vec<_> = decode(rec.as_bytes()).collect();
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
         assert_eq!(events.len(), 2);
         match &events[0] {
             RecordedEvent::PhaseBegin(e) => {
@@ -562,6 +1399,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn round_trip_acquire_and_present_phases() {
+        let mut rec = RecorderSink::new();
+        for phase in [PhaseKind::Acquire, PhaseKind::Present] {
+            rec.on_phase_begin(&PhaseBeginEvent {
+                frame_index: 5,
+                phase,
+                timestamp: HostTime(2000),
+            });
+            rec.on_phase_end(&PhaseEndEvent {
+                frame_index: 5,
+                phase,
+                timestamp: HostTime(2500),
+            });
+        }
+
+        let phases: Vec<_> = decode(rec.as_bytes())
+            .unwrap()
+            .map(|event| match event {
+                RecordedEvent::PhaseBegin(e) => e.phase,
+                RecordedEvent::PhaseEnd(e) => e.phase,
+                other => panic!("expected a phase event, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                PhaseKind::Acquire,
+                PhaseKind::Acquire,
+                PhaseKind::Present,
+                PhaseKind::Present,
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_legacy_frame_summary_record() {
+        let mut rec = RecorderSink::new();
+        rec.on_frame_summary(&sample_summary());
+        // Rewrite as a headerless legacy record: old tag, no trailing durations.
+        let mut bytes = rec.into_bytes().split_off(HEADER_LEN);
+        bytes[0] = TAG_FRAME_SUMMARY;
+        bytes.truncate(bytes.len() - 16);
+
+        let events: Vec<_> = decode_v0(&bytes).collect();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            RecordedEvent::FrameSummary(s) => {
+                assert_eq!(s.render_ticks, 1500);
+                assert!(!s.missed_deadline);
+                assert_eq!(s.acquire_ticks, 0);
+                assert_eq!(s.present_ticks, 0);
+            }
+            other => panic!("expected FrameSummary, got {other:?}"),
+        }
+    }
+
     #[test]
     fn round_trip_submit() {
         let mut rec = RecorderSink::new();
@@ -572,7 +1466,7 @@ mod tests {
         };
         rec.on_submit(&orig);
 
-        let events: Vec<_> = decode(rec.as_bytes()).collect();
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
         assert_eq!(events.len(), 1);
         match &events[0] {
             RecordedEvent::Submit(e) => {
@@ -595,7 +1489,7 @@ mod tests {
         };
         rec.on_present_feedback(&orig);
 
-        let events: Vec<_> = decode(rec.as_bytes()).collect();
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
         assert_eq!(events.len(), 1);
         match &events[0] {
             RecordedEvent::PresentFeedback(e) => {
@@ -614,7 +1508,7 @@ mod tests {
         let orig = sample_summary();
         rec.on_frame_summary(&orig);
 
-        let events: Vec<_> = decode(rec.as_bytes()).collect();
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
         assert_eq!(events.len(), 1);
         match &events[0] {
             RecordedEvent::FrameSummary(s) => {
@@ -623,6 +1517,8 @@ mod tests {
                 assert_eq!(s.eval_ticks, orig.eval_ticks);
                 assert_eq!(s.render_ticks, orig.render_ticks);
                 assert_eq!(s.submit_ticks, orig.submit_ticks);
+                assert_eq!(s.acquire_ticks, orig.acquire_ticks);
+                assert_eq!(s.present_ticks, orig.present_ticks);
                 assert_eq!(s.missed_deadline, orig.missed_deadline);
                 assert_eq!(s.presentation_timing, orig.presentation_timing);
                 assert_eq!(s.pipeline_depth, orig.pipeline_depth);
@@ -643,7 +1539,7 @@ mod tests {
         });
         rec.on_frame_summary(&sample_summary());
 
-        let events: Vec<_> = decode(rec.as_bytes()).collect();
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
         assert_eq!(events.len(), 4);
         assert!(matches!(events[0], RecordedEvent::FrameTick(_)));
         assert!(matches!(events[1], RecordedEvent::FramePlan(_)));
@@ -651,15 +1547,448 @@ mod tests {
         assert!(matches!(events[3], RecordedEvent::FrameSummary(_)));
     }
 
+    #[test]
+    fn recording_starts_with_header() {
+        let mut rec = RecorderSink::new();
+        assert!(
+            rec.as_bytes().is_empty(),
+            "no header before the first event"
+        );
+        rec.on_frame_tick(&sample_tick_event());
+        rec.on_frame_tick(&sample_tick_event());
+
+        let bytes = rec.as_bytes();
+        assert_eq!(bytes[..4], MAGIC);
+        assert_eq!(bytes[4], FORMAT_VERSION);
+        assert_eq!(decode(bytes).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn wrong_magic_is_rejected() {
+        let mut rec = RecorderSink::new();
+        rec.on_frame_tick(&sample_tick_event());
+        let mut bytes = rec.into_bytes();
+        bytes[0] = b'X';
+
+        assert_eq!(decode(&bytes).err(), Some(DecodeError::BadMagic));
+        assert_eq!(decode(&MAGIC[..2]).err(), Some(DecodeError::Truncated));
+    }
+
+    #[test]
+    fn version_mismatch_is_rejected() {
+        let mut rec = RecorderSink::new();
+        rec.on_frame_tick(&sample_tick_event());
+        let mut bytes = rec.into_bytes();
+        bytes[4] = FORMAT_VERSION + 1;
+
+        assert_eq!(
+            decode(&bytes).err(),
+            Some(DecodeError::UnsupportedVersion(FORMAT_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn version_1_recording_still_decodes() {
+        let mut bytes = two_ticks();
+        bytes[4] = 1;
+
+        let events: Vec<_> = decode(&bytes).unwrap().collect();
+        assert!(matches!(
+            events[..],
+            [RecordedEvent::FrameTick(_), RecordedEvent::FrameTick(_)]
+        ));
+        assert_eq!(
+            decode(&[MAGIC[0], MAGIC[1], MAGIC[2], MAGIC[3], 0]).err(),
+            Some(DecodeError::UnsupportedVersion(0))
+        );
+    }
+
+    #[test]
+    fn headerless_recording_decodes_as_v0() {
+        let mut rec = RecorderSink::new();
+        rec.on_frame_tick(&sample_tick_event());
+        let bytes = &rec.as_bytes()[HEADER_LEN..];
+
+        assert_eq!(decode(bytes).err(), Some(DecodeError::BadMagic));
+        let events: Vec<_> = decode_v0(bytes).collect();
+        assert!(matches!(events[..], [RecordedEvent::FrameTick(_)]));
+    }
+
+    fn two_ticks() -> Vec<u8> {
+        let mut rec = RecorderSink::new();
+        rec.on_frame_tick(&sample_tick_event());
+        rec.on_frame_tick(&sample_tick_event());
+        rec.into_bytes()
+    }
+
+    #[test]
+    fn try_decode_clean_stream_ends_without_error() {
+        let bytes = two_ticks();
+        let results: Vec<_> = try_decode(&bytes).unwrap().collect();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn try_decode_reports_unknown_tag_offset() {
+        let mut bytes = two_ticks();
+        let offset = HEADER_LEN + TICK_RECORD_LEN;
+        bytes[offset] = 0xEE;
+
+        let results: Vec<_> = try_decode(&bytes).unwrap().collect();
+        assert!(matches!(results[0], Ok(RecordedEvent::FrameTick(_))));
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[1].as_ref().err(),
+            Some(&DecodeError::UnknownTag { tag: 0xEE, offset })
+        );
+        // The lenient decoder stops at the same place.
+        assert_eq!(decode(&bytes).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn unknown_phase_is_rejected() {
+        let mut rec = RecorderSink::new();
+        rec.on_phase_begin(&PhaseBeginEvent {
+            frame_index: 5,
+            phase: PhaseKind::Render,
+            timestamp: HostTime(2000),
+        });
+        let mut bytes = rec.into_bytes();
+        // Tag, then the frame index, then the phase byte.
+        bytes[HEADER_LEN + 1 + 8] = 0xEE;
+
+        let results: Vec<_> = try_decode(&bytes).unwrap().collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].as_ref().err(),
+            Some(&DecodeError::TruncatedRecord { offset: HEADER_LEN })
+        );
+    }
+
+    #[test]
+    fn try_decode_reports_truncated_record() {
+        let bytes = two_ticks();
+        let cut = &bytes[..bytes.len() - 3];
+
+        let results: Vec<_> = try_decode(cut).unwrap().collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[1].as_ref().err(),
+            Some(&DecodeError::TruncatedRecord {
+                offset: HEADER_LEN + TICK_RECORD_LEN,
+            })
+        );
+        assert_eq!(decode(cut).unwrap().count(), 1);
+    }
+
+    fn record_sample_frames(sink: &mut impl TraceSink, frames: u64) {
+        for frame_index in 0..frames {
+            sink.on_frame_tick(&FrameTickEvent {
+                frame_index,
+                ..sample_tick_event()
+            });
+            sink.on_frame_plan(&sample_plan_event());
+            sink.on_phase_begin(&PhaseBeginEvent {
+                frame_index,
+                phase: PhaseKind::Render,
+                timestamp: HostTime(2000),
+            });
+            sink.on_frame_summary(&FrameSummary {
+                frame_index,
+                ..sample_summary()
+            });
+        }
+    }
+
+    #[test]
+    fn streaming_matches_in_memory() {
+        let mut in_memory = RecorderSink::new();
+        record_sample_frames(&mut in_memory, 100);
+        let mut streaming = RecorderSink::to_writer(Vec::new());
+        record_sample_frames(&mut streaming, 100);
+        let streamed = streaming.into_writer().unwrap();
+
+        assert_eq!(streamed, in_memory.as_bytes());
+        let events: Vec<_> = decode(&streamed).unwrap().collect();
+        assert_eq!(events.len(), 400);
+        assert!(matches!(
+            events[396],
+            RecordedEvent::FrameTick(FrameTickEvent {
+                frame_index: 99,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn streaming_reports_first_write_error() {
+        struct FailingWriter;
+        impl Write for FailingWriter {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk full"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut rec = RecorderSink::to_writer(FailingWriter);
+        record_sample_frames(&mut rec, 1000);
+        let err = rec.flush().unwrap_err();
+        assert_eq!(err.to_string(), "disk full");
+    }
+
+    /// Encoded size of one tick record from [`sample_tick_event`].
+    const TICK_RECORD_LEN: usize = 1 + 8 + 4 + 8 + 9 + 9;
+
+    fn record_ticks(rec: &mut RecorderSink, frames: core::ops::Range<u64>) {
+        for frame_index in frames {
+            rec.on_frame_tick(&FrameTickEvent {
+                frame_index,
+                ..sample_tick_event()
+            });
+        }
+    }
+
+    fn decoded_tick_indices(bytes: &[u8]) -> Vec<u64> {
+        decode(bytes)
+            .unwrap()
+            .map(|event| match event {
+                RecordedEvent::FrameTick(e) => e.frame_index,
+                other => panic!("expected FrameTick, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn with_capacity_holds_known_events_without_reallocating() {
+        let bytes = HEADER_LEN + 100 * TICK_RECORD_LEN;
+        let mut rec = RecorderSink::with_capacity(bytes);
+        let storage = rec.out.as_ptr();
+        assert_eq!(rec.out.capacity(), bytes);
+        record_ticks(&mut rec, 0..100);
+        assert_eq!(rec.len(), bytes);
+        assert_eq!(rec.out.capacity(), bytes);
+        assert_eq!(rec.out.as_ptr(), storage);
+    }
+
+    #[test]
+    fn clear_empties_and_keeps_capacity() {
+        let mut rec = RecorderSink::new();
+        assert!(rec.is_empty());
+        record_ticks(&mut rec, 0..10);
+        assert!(!rec.is_empty());
+        let capacity = rec.out.capacity();
+
+        rec.clear();
+        assert!(rec.is_empty());
+        assert_eq!(rec.len(), 0);
+        assert_eq!(rec.out.capacity(), capacity);
+
+        // The next recording starts with a fresh header.
+        record_ticks(&mut rec, 10..12);
+        assert_eq!(rec.len(), HEADER_LEN + 2 * TICK_RECORD_LEN);
+        assert_eq!(decoded_tick_indices(rec.as_bytes()), [10, 11]);
+
+        let mut ring = RecorderSink::with_capacity_bytes(HEADER_LEN + TICK_RECORD_LEN);
+        record_ticks(&mut ring, 0..3);
+        ring.clear();
+        assert!(ring.is_empty());
+        assert_eq!(ring.dropped_records(), 0);
+        record_ticks(&mut ring, 5..6);
+        assert_eq!(decoded_tick_indices(ring.as_bytes()), [5]);
+    }
+
+    #[test]
+    fn ring_buffer_respects_capacity() {
+        let max = HEADER_LEN + 3 * TICK_RECORD_LEN + 10;
+        let mut rec = RecorderSink::with_capacity_bytes(max);
+        for n in 0..50 {
+            record_ticks(&mut rec, n..n + 1);
+            assert!(
+                rec.as_bytes().len() <= max,
+                "recording grew to {} bytes",
+                rec.as_bytes().len()
+            );
+        }
+        assert_eq!(rec.as_bytes().len(), HEADER_LEN + 3 * TICK_RECORD_LEN);
+        assert_eq!(rec.dropped_records(), 47);
+    }
+
+    #[test]
+    fn ring_buffer_keeps_whole_recent_records_in_order() {
+        let mut rec = RecorderSink::with_capacity_bytes(HEADER_LEN + 4 * TICK_RECORD_LEN + 1);
+        record_ticks(&mut rec, 0..1000);
+        assert_eq!(decoded_tick_indices(rec.as_bytes()), [996, 997, 998, 999]);
+
+        // A larger record has to evict several ticks to fit.
+        rec.on_frame_summary(&sample_summary());
+        let bytes = rec.into_bytes();
+        let events: Vec<_> = decode(&bytes).unwrap().collect();
+        assert!(
+            matches!(
+                events[..],
+                [
+                    RecordedEvent::FrameTick(FrameTickEvent {
+                        frame_index: 999,
+                        ..
+                    }),
+                    RecordedEvent::FrameSummary(_),
+                ]
+            ),
+            "expected the newest tick and the summary, got {events:?}"
+        );
+    }
+
+    #[test]
+    fn ring_buffer_compaction_preserves_recent_events() {
+        let mut rec = RecorderSink::with_capacity_bytes(HEADER_LEN + 2 * TICK_RECORD_LEN);
+        record_ticks(&mut rec, 0..10_000);
+        assert_eq!(decoded_tick_indices(rec.as_bytes()), [9_998, 9_999]);
+        assert_eq!(rec.dropped_records(), 9_998);
+    }
+
+    #[test]
+    fn ring_buffer_drops_records_larger_than_capacity() {
+        let mut rec = RecorderSink::with_capacity_bytes(HEADER_LEN + TICK_RECORD_LEN - 1);
+        record_ticks(&mut rec, 0..3);
+        assert!(rec.as_bytes().is_empty());
+        assert_eq!(rec.dropped_records(), 3);
+    }
+
+    fn tick_at(output: u32, now: u64) -> FrameTickEvent {
+        FrameTickEvent {
+            frame_index: now / 10,
+            output: OutputId(output),
+            now: HostTime(now),
+            predicted_present: None,
+            refresh_interval: None,
+        }
+    }
+
+    #[test]
+    fn merge_interleaves_by_timestamp() {
+        let mut a = RecorderSink::new();
+        let mut b = RecorderSink::new();
+        for now in [0, 20, 40] {
+            a.on_frame_tick(&tick_at(0, now));
+            a.on_phase_begin(&PhaseBeginEvent {
+                frame_index: now / 10,
+                phase: PhaseKind::Render,
+                timestamp: HostTime(now + 5),
+            });
+            // Untimed records stay with the frame that produced them.
+            a.on_layer_changes(now / 10, &[]);
+        }
+        for now in [10, 30, 50] {
+            b.on_frame_tick(&tick_at(1, now));
+        }
+
+        let merged = merge(&[a.as_bytes(), b.as_bytes()]).unwrap();
+        let events: Vec<_> = decode(&merged).unwrap().collect();
+        assert_eq!(events.len(), 12);
+        let times: Vec<u64> = events
+            .iter()
+            .filter_map(|e| e.timestamp().map(HostTime::ticks))
+            .collect();
+        assert_eq!(times, [0, 5, 10, 20, 25, 30, 40, 45, 50]);
+        assert!(matches!(
+            events[2],
+            RecordedEvent::LayerChangesCount { frame_index: 0, .. }
+        ));
+        let outputs: Vec<u32> = events
+            .iter()
+            .filter_map(|e| match e {
+                RecordedEvent::FrameTick(t) => Some(t.output.0),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(outputs, [0, 1, 0, 1, 0, 1], "output ids are preserved");
+    }
+
+    #[test]
+    fn merge_handles_empty_and_invalid_inputs() {
+        let mut a = RecorderSink::new();
+        a.on_frame_tick(&tick_at(0, 0));
+
+        assert!(merge(&[]).unwrap().is_empty());
+        assert_eq!(merge(&[&[], a.as_bytes()]).unwrap(), a.as_bytes());
+        assert_eq!(
+            merge(&[a.as_bytes(), b"junk"]).err(),
+            Some(DecodeError::Truncated)
+        );
+    }
+
+    #[test]
+    fn summarize_computes_percentiles_and_miss_rate() {
+        let mut rec = RecorderSink::new();
+        // 100 frames, 16 ticks apart: render takes 1..=100 ticks, plan a
+        // constant 10, and every tenth frame misses.
+        for i in 1..=100_u64 {
+            rec.on_frame_summary(&FrameSummary {
+                frame_index: i,
+                now: HostTime(i * 16),
+                plan_ticks: 10,
+                eval_ticks: 0,
+                acquire_ticks: 0,
+                render_ticks: i,
+                submit_ticks: 0,
+                present_ticks: 0,
+                missed_deadline: i % 10 == 0,
+                ..sample_summary()
+            });
+            // Non-summary records are ignored.
+            rec.on_frame_tick(&sample_tick_event());
+        }
+
+        let stats = summarize(rec.as_bytes()).unwrap();
+        assert_eq!(stats.frame_count, 100);
+        assert_eq!(stats.missed_count, 10);
+        assert!((stats.miss_rate() - 0.1).abs() < 1e-12);
+        assert_eq!(
+            stats.render,
+            Percentiles {
+                p50: 50,
+                p95: 95,
+                p99: 99,
+            }
+        );
+        assert_eq!(
+            stats.plan,
+            Percentiles {
+                p50: 10,
+                p95: 10,
+                p99: 10,
+            }
+        );
+        assert_eq!(stats.build.p95, 105);
+        assert_eq!(stats.submit, Percentiles::default());
+        assert_eq!(
+            stats.frame_interval,
+            Percentiles {
+                p50: 16,
+                p95: 16,
+                p99: 16,
+            }
+        );
+    }
+
+    #[test]
+    fn summarize_empty_recording() {
+        let stats = summarize(&[]).unwrap();
+        assert_eq!(stats, RecordingStats::default());
+        assert_eq!(stats.miss_rate(), 0.0);
+    }
+
     #[test]
     fn empty_buffer_decodes_to_nothing() {
-        let events: Vec<_> = decode(&[]).collect();
+        let events: Vec<_> = decode(&[]).unwrap().collect();
         assert!(events.is_empty());
     }
 
     #[test]
     fn layer_changes_count() {
-        use subduction_core::trace::LayerField;
         let mut rec = RecorderSink::new();
         let changes = vec![
             LayerChange {
@@ -673,7 +2002,7 @@ mod tests {
         ];
         rec.on_layer_changes(42, &changes);
 
-        let events: Vec<_> = decode(rec.as_bytes()).collect();
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
         assert_eq!(events.len(), 1);
         match &events[0] {
             RecordedEvent::LayerChangesCount { frame_index, count } => {
@@ -683,4 +2012,144 @@ mod tests {
             other => panic!("expected LayerChangesCount, got {other:?}"),
         }
     }
+
+    const ALL_FIELDS: [LayerField; 8] = [
+        LayerField::Transform,
+        LayerField::Opacity,
+        LayerField::Clip,
+        LayerField::Content,
+        LayerField::Bounds,
+        LayerField::BlendMode,
+        LayerField::Flags,
+        LayerField::Topology,
+    ];
+
+    #[test]
+    fn rich_layer_changes_round_trip() {
+        let mut rec = RecorderSink::new().with_rich_records(true);
+        let changes: Vec<_> = ALL_FIELDS
+            .iter()
+            .enumerate()
+            .map(|(i, &field)| LayerChange {
+                layer_index: u32::try_from(i).unwrap() * 1000 + 7,
+                field,
+            })
+            .collect();
+        rec.on_layer_changes(42, &changes);
+        rec.on_layer_changes(43, &[]);
+
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            RecordedEvent::LayerChanges {
+                frame_index,
+                changes: decoded,
+            } => {
+                assert_eq!(*frame_index, 42);
+                assert_eq!(decoded.len(), changes.len());
+                for (d, c) in decoded.iter().zip(&changes) {
+                    assert_eq!(d.layer_index, c.layer_index);
+                    assert_eq!(d.field, c.field);
+                }
+            }
+            other => panic!("expected LayerChanges, got {other:?}"),
+        }
+        assert!(matches!(
+            &events[1],
+            RecordedEvent::LayerChanges { frame_index: 43, changes } if changes.is_empty()
+        ));
+    }
+
+    #[test]
+    fn rich_damage_rects_round_trip() {
+        let mut rec = RecorderSink::new().with_rich_records(true);
+        let rects = [
+            DamageRect {
+                x: -16,
+                y: 32,
+                width: 640,
+                height: 480,
+            },
+            DamageRect {
+                x: i32::MAX,
+                y: i32::MIN,
+                width: u32::MAX,
+                height: 0,
+            },
+        ];
+        rec.on_damage_rects(9, &rects);
+
+        let events: Vec<_> = decode(rec.as_bytes()).unwrap().collect();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            RecordedEvent::DamageRects {
+                frame_index,
+                rects: decoded,
+            } => {
+                assert_eq!(*frame_index, 9);
+                assert_eq!(decoded.len(), rects.len());
+                for (d, r) in decoded.iter().zip(&rects) {
+                    assert_eq!((d.x, d.y, d.width, d.height), (r.x, r.y, r.width, r.height));
+                }
+            }
+            other => panic!("expected DamageRects, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rich_records_cost_per_entry() {
+        let changes = [LayerChange {
+            layer_index: 1,
+            field: LayerField::Clip,
+        }; 10];
+        let mut counts = RecorderSink::new();
+        counts.on_layer_changes(0, &changes);
+        let mut rich = RecorderSink::new().with_rich_records(true);
+        rich.on_layer_changes(0, &changes);
+        assert_eq!(rich.len() - counts.len(), changes.len() * 5);
+    }
+
+    #[test]
+    fn rich_record_with_overlong_count_is_truncated() {
+        let mut rec = RecorderSink::new().with_rich_records(true);
+        rec.on_layer_changes(
+            1,
+            &[LayerChange {
+                layer_index: 3,
+                field: LayerField::Bounds,
+            }],
+        );
+        let mut bytes = rec.into_bytes();
+        // Claim more entries than the record holds.
+        bytes[HEADER_LEN + 9] = 2;
+        let results: Vec<_> = try_decode(&bytes).unwrap().collect();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            results[0].as_ref().err(),
+            Some(DecodeError::TruncatedRecord { offset }) if *offset == HEADER_LEN
+        ));
+    }
+
+    #[test]
+    fn rich_record_with_unknown_field_is_rejected() {
+        let mut rec = RecorderSink::new().with_rich_records(true);
+        rec.on_layer_changes(
+            1,
+            &[LayerChange {
+                layer_index: 3,
+                field: LayerField::Topology,
+            }],
+        );
+        let mut bytes = rec.into_bytes();
+        // The field byte follows the tag, frame index, count, and layer index.
+        let field = HEADER_LEN + 17;
+        assert_eq!(bytes[field], 7);
+        bytes[field] = 8;
+        let results: Vec<_> = try_decode(&bytes).unwrap().collect();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            results[0].as_ref().err(),
+            Some(DecodeError::TruncatedRecord { offset }) if *offset == HEADER_LEN
+        ));
+    }
 }
@@ -0,0 +1,355 @@
+// Copyright 2026 the Subduction Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Self-contained HTML report.
+//!
+//! [`export_html`] reads recorded bytes from a [`RecorderSink`](super::recorder::RecorderSink)
+//! and writes a single HTML page charting its
+//! [`FrameSummary`] records with inline SVG: a frame-rate graph, a
+//! deadline-miss timeline, and a per-frame phase breakdown. The page
+//! references no scripts, stylesheets, or images, so it can be attached to a
+//! bug report and opened in any browser.
+
+use std::io::{self, Write};
+
+use frameclock::time::Timebase;
+use subduction_core::trace::FrameSummary;
+
+use crate::recorder::{Percentiles, RecordedEvent, RecordingStats, decode, summarize};
+
+/// Width of every chart's plot area, in SVG user units.
+const CHART_WIDTH: f64 = 800.0;
+/// Height of the frame-rate and phase charts, in SVG user units.
+const CHART_HEIGHT: f64 = 200.0;
+/// Height of the miss timeline, in SVG user units.
+const TIMELINE_HEIGHT: f64 = 24.0;
+
+/// Phase names and fill colors, in [`FrameSummary`] field order.
+const PHASES: [(&str, &str); 6] = [
+    ("plan", "#8dd3c7"),
+    ("evaluate", "#bebada"),
+    ("acquire", "#80b1d3"),
+    ("render", "#fdb462"),
+    ("submit", "#b3de69"),
+    ("present", "#fccde5"),
+];
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2em;color:#222}\
+svg{background:#fafafa;border:1px solid #ddd;display:block;margin-bottom:1.5em}\
+table{border-collapse:collapse}td,th{padding:2px 10px;text-align:right}\
+.swatch{display:inline-block;width:10px;height:10px;margin:0 4px 0 12px}";
+
+/// Exports the frame summaries of a recording as a self-contained HTML page.
+///
+/// Frame rate is derived from the spacing of consecutive summaries' tick
+/// times; the first frame reuses the interval to the second. Durations are
+/// converted to milliseconds using the provided [`Timebase`]. Events other
+/// than [`FrameSummary`] are ignored, and a recording without summaries
+/// produces a page that says so.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::InvalidData`] if `bytes` is not a recording this
+/// version can decode (see [`decode`]), or any error from `writer`.
+pub fn export_html(bytes: &[u8], timebase: Timebase, writer: &mut dyn Write) -> io::Result<()> {
+    let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+    let summaries: Vec<FrameSummary> = decode(bytes)
+        .map_err(invalid)?
+        .filter_map(|event| match event {
+            RecordedEvent::FrameSummary(s) => Some(s),
+            _ => None,
+        })
+        .collect();
+    let stats = summarize(bytes).map_err(invalid)?;
+    let ms = |ticks: u64| timebase.ticks_to_nanos(ticks) as f64 / 1_000_000.0;
+
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang=\"en\">")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>Subduction frame report</title>")?;
+    writeln!(writer, "<style>{STYLE}</style>")?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<h1>Frame report</h1>")?;
+    writeln!(
+        writer,
+        "<p>Frames: {}. Missed deadlines: {} ({:.1}%).</p>",
+        stats.frame_count,
+        stats.missed_count,
+        stats.miss_rate() * 100.0
+    )?;
+
+    if summaries.is_empty() {
+        writeln!(writer, "<p>The recording contains no frame summaries.</p>")?;
+    } else {
+        let intervals = frame_intervals_ms(&summaries, &ms);
+        write_fps_chart(writer, &summaries, &intervals)?;
+        write_miss_timeline(writer, &summaries)?;
+        write_phase_chart(writer, &summaries, &ms)?;
+        write_stats_table(writer, &stats, &ms)?;
+    }
+
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+    Ok(())
+}
+
+/// Returns each summary's frame interval in milliseconds, taken from the
+/// previous summary's tick time (the next one's, for the first frame).
+///
+/// A lone summary has an interval of 0.
+fn frame_intervals_ms(summaries: &[FrameSummary], ms: &dyn Fn(u64) -> f64) -> Vec<f64> {
+    let between =
+        |a: &FrameSummary, b: &FrameSummary| ms(b.now.saturating_duration_since(a.now).ticks());
+    let mut intervals: Vec<f64> = summaries
+        .windows(2)
+        .map(|pair| between(&pair[0], &pair[1]))
+        .collect();
+    intervals.insert(0, intervals.first().copied().unwrap_or(0.0));
+    intervals
+}
+
+/// Returns the x coordinate of the center of frame `i` of `n`.
+fn frame_x(i: usize, n: usize) -> f64 {
+    (i as f64 + 0.5) * CHART_WIDTH / n as f64
+}
+
+fn write_fps_chart(
+    writer: &mut dyn Write,
+    summaries: &[FrameSummary],
+    intervals: &[f64],
+) -> io::Result<()> {
+    let fps: Vec<f64> = intervals
+        .iter()
+        .map(|&interval| {
+            if interval > 0.0 {
+                1000.0 / interval
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let max = fps.iter().copied().fold(0.0_f64, f64::max).max(1.0) * 1.1;
+    let y = |rate: f64| CHART_HEIGHT - rate / max * CHART_HEIGHT;
+    let n = summaries.len();
+
+    writeln!(writer, "<h2>Frame rate</h2>")?;
+    writeln!(
+        writer,
+        "<svg width=\"{CHART_WIDTH}\" height=\"{CHART_HEIGHT}\" viewBox=\"0 0 {CHART_WIDTH} {CHART_HEIGHT}\">"
+    )?;
+    write!(
+        writer,
+        "<polyline fill=\"none\" stroke=\"#3366cc\" stroke-width=\"1.5\" points=\""
+    )?;
+    for (i, &rate) in fps.iter().enumerate() {
+        write!(writer, "{:.1},{:.1} ", frame_x(i, n), y(rate))?;
+    }
+    writeln!(writer, "\"/>")?;
+    for ((i, s), &rate) in summaries.iter().enumerate().zip(&fps) {
+        writeln!(
+            writer,
+            "<circle class=\"fps-point\" cx=\"{:.1}\" cy=\"{:.1}\" r=\"2\" fill=\"#3366cc\"><title>frame {}: {rate:.1} fps</title></circle>",
+            frame_x(i, n),
+            y(rate),
+            s.frame_index
+        )?;
+    }
+    writeln!(
+        writer,
+        "<text x=\"4\" y=\"14\" font-size=\"12\">{max:.0} fps</text>"
+    )?;
+    writeln!(writer, "</svg>")
+}
+
+fn write_miss_timeline(writer: &mut dyn Write, summaries: &[FrameSummary]) -> io::Result<()> {
+    let width = CHART_WIDTH / summaries.len() as f64;
+
+    writeln!(writer, "<h2>Deadline misses</h2>")?;
+    writeln!(
+        writer,
+        "<svg width=\"{CHART_WIDTH}\" height=\"{TIMELINE_HEIGHT}\" viewBox=\"0 0 {CHART_WIDTH} {TIMELINE_HEIGHT}\">"
+    )?;
+    for (i, s) in summaries.iter().enumerate() {
+        let (class, fill) = if s.missed_deadline {
+            ("miss", "#d62728")
+        } else {
+            ("hit", "#c7e9c0")
+        };
+        writeln!(
+            writer,
+            "<rect class=\"{class}\" x=\"{:.2}\" y=\"0\" width=\"{width:.2}\" height=\"{TIMELINE_HEIGHT}\" fill=\"{fill}\"><title>frame {}</title></rect>",
+            i as f64 * width,
+            s.frame_index
+        )?;
+    }
+    writeln!(writer, "</svg>")
+}
+
+/// Returns a summary's phase durations in [`PHASES`] order.
+fn phase_ticks(s: &FrameSummary) -> [u64; 6] {
+    [
+        s.plan_ticks,
+        s.eval_ticks,
+        s.acquire_ticks,
+        s.render_ticks,
+        s.submit_ticks,
+        s.present_ticks,
+    ]
+}
+
+fn write_phase_chart(
+    writer: &mut dyn Write,
+    summaries: &[FrameSummary],
+    ms: &dyn Fn(u64) -> f64,
+) -> io::Result<()> {
+    let totals: Vec<f64> = summaries
+        .iter()
+        .map(|s| phase_ticks(s).into_iter().map(ms).sum())
+        .collect();
+    let max = totals.iter().copied().fold(0.0_f64, f64::max).max(0.001) * 1.1;
+    let scale = CHART_HEIGHT / max;
+    let width = CHART_WIDTH / summaries.len() as f64;
+
+    writeln!(writer, "<h2>Phase breakdown</h2>")?;
+    write!(writer, "<p>")?;
+    for (name, color) in PHASES {
+        write!(
+            writer,
+            "<span class=\"swatch\" style=\"background:{color}\"></span>{name}"
+        )?;
+    }
+    writeln!(writer, "</p>")?;
+    writeln!(
+        writer,
+        "<svg width=\"{CHART_WIDTH}\" height=\"{CHART_HEIGHT}\" viewBox=\"0 0 {CHART_WIDTH} {CHART_HEIGHT}\">"
+    )?;
+    for (i, s) in summaries.iter().enumerate() {
+        let mut top = CHART_HEIGHT;
+        for ((name, color), ticks) in PHASES.iter().zip(phase_ticks(s)) {
+            let height = ms(ticks) * scale;
+            if height <= 0.0 {
+                continue;
+            }
+            top -= height;
+            writeln!(
+                writer,
+                "<rect x=\"{:.2}\" y=\"{top:.2}\" width=\"{width:.2}\" height=\"{height:.2}\" fill=\"{color}\"><title>frame {} {name}: {:.3} ms</title></rect>",
+                i as f64 * width,
+                s.frame_index,
+                ms(ticks)
+            )?;
+        }
+    }
+    writeln!(
+        writer,
+        "<text x=\"4\" y=\"14\" font-size=\"12\">{max:.2} ms</text>"
+    )?;
+    writeln!(writer, "</svg>")
+}
+
+fn write_stats_table(
+    writer: &mut dyn Write,
+    stats: &RecordingStats,
+    ms: &dyn Fn(u64) -> f64,
+) -> io::Result<()> {
+    let rows: [(&str, Percentiles); 8] = [
+        ("build", stats.build),
+        ("plan", stats.plan),
+        ("evaluate", stats.eval),
+        ("acquire", stats.acquire),
+        ("render", stats.render),
+        ("submit", stats.submit),
+        ("present", stats.present),
+        ("frame interval", stats.frame_interval),
+    ];
+
+    writeln!(writer, "<h2>Percentiles (ms)</h2>")?;
+    writeln!(writer, "<table>")?;
+    writeln!(
+        writer,
+        "<tr><th></th><th>p50</th><th>p95</th><th>p99</th></tr>"
+    )?;
+    for (name, p) in rows {
+        writeln!(
+            writer,
+            "<tr><th>{name}</th><td>{:.3}</td><td>{:.3}</td><td>{:.3}</td></tr>",
+            ms(p.p50),
+            ms(p.p95),
+            ms(p.p99)
+        )?;
+    }
+    writeln!(writer, "</table>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recorder::RecorderSink;
+    use frameclock::timing::PresentationTiming;
+    use frameclock::{HostTime, OutputId};
+    use subduction_core::trace::TraceSink;
+
+    fn summary(frame_index: u64, missed_deadline: bool) -> FrameSummary {
+        let now = 1_000_000 + frame_index * 16_666_667;
+        FrameSummary {
+            frame_index,
+            output: OutputId(0),
+            presentation_timing: PresentationTiming::Predictive,
+            now: HostTime(now),
+            target_present: Some(HostTime(now + 33_333_333)),
+            sample_time: HostTime(now + 33_333_333),
+            deadline: HostTime(now + 12_000_000),
+            pipeline_depth: 1,
+            plan_ticks: 100_000,
+            eval_ticks: 500_000,
+            acquire_ticks: 200_000,
+            render_ticks: 4_000_000,
+            submit_ticks: 300_000,
+            present_ticks: 100_000,
+            missed_deadline,
+        }
+    }
+
+    fn render(bytes: &[u8]) -> String {
+        let mut out = Vec::new();
+        export_html(bytes, Timebase::NANOS, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn export_html_plots_every_summary() {
+        let mut rec = RecorderSink::new();
+        for frame_index in 0..6 {
+            rec.on_frame_summary(&summary(frame_index, frame_index == 3));
+        }
+
+        let html = render(rec.as_bytes());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(html.contains("<p>Frames: 6. Missed deadlines: 1 (16.7%).</p>"));
+        assert_eq!(html.matches("class=\"fps-point\"").count(), 6);
+        assert_eq!(html.matches("class=\"miss\"").count(), 1);
+        assert_eq!(html.matches("class=\"hit\"").count(), 5);
+        // 60 Hz spacing.
+        assert!(html.contains("frame 2: 60.0 fps"));
+        // Self-contained: nothing is fetched when the page loads.
+        assert!(!html.contains("src="));
+        assert!(!html.contains("href="));
+    }
+
+    #[test]
+    fn export_html_without_summaries() {
+        let html = render(&[]);
+        assert!(html.contains("<p>Frames: 0. Missed deadlines: 0 (0.0%).</p>"));
+        assert!(html.contains("no frame summaries"));
+        assert!(!html.contains("<svg"));
+    }
+
+    #[test]
+    fn export_html_rejects_foreign_bytes() {
+        let err = export_html(b"not a recording", Timebase::NANOS, &mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -3,6 +3,7 @@
 
 //! Spatial damage tracking for partial re-rendering.
 
+use alloc::vec;
 use alloc::vec::Vec;
 
 /// A region of the output that needs re-rendering.
@@ -23,23 +24,198 @@ pub enum DamageRegion {
 }
 
 impl DamageRegion {
+    /// Maximum number of rectangles kept before they collapse into their
+    /// bounding box.
+    pub const MAX_RECTS: usize = 16;
+
+    /// Two rectangles are coalesced into their bounding box when its area is
+    /// at most this factor times the sum of their areas.
+    ///
+    /// Overlapping or edge-adjacent rectangles with matching spans always
+    /// coalesce; nearby ones coalesce when the extra area redrawn is small.
+    pub const COALESCE_FACTOR: f32 = 1.25;
+
     /// Returns `true` if no region needs redrawing.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        matches!(self, Self::None)
+        match self {
+            Self::Full => false,
+            Self::Rects(rects) => rects.is_empty(),
+            Self::None => true,
+        }
+    }
+
+    /// Adds a rectangle (`[x, y, width, height]`) to the damaged area.
+    ///
+    /// Rectangles with non-positive width or height are ignored. The new
+    /// rectangle is coalesced with existing ones per
+    /// [`COALESCE_FACTOR`](Self::COALESCE_FACTOR), and if more than
+    /// [`MAX_RECTS`](Self::MAX_RECTS) remain they collapse into one bounding
+    /// box.
+    pub fn union(&mut self, rect: [f32; 4]) {
+        if rect[2] <= 0.0 || rect[3] <= 0.0 {
+            return;
+        }
+        match self {
+            Self::Full => {}
+            Self::None => *self = Self::Rects(vec![rect]),
+            Self::Rects(rects) => {
+                let mut rect = rect;
+                // Absorbing one rect can make the grown rect coalesce with
+                // another, so keep going until nothing merges.
+                while let Some(i) = rects.iter().position(|r| should_coalesce(*r, rect)) {
+                    rect = bounding_box(rects.swap_remove(i), rect);
+                }
+                rects.push(rect);
+                if rects.len() > Self::MAX_RECTS {
+                    let all = rects.iter().copied().reduce(bounding_box).unwrap_or(rect);
+                    rects.clear();
+                    rects.push(all);
+                }
+            }
+        }
     }
 
     /// Merges another damage region into this one.
+    ///
+    /// Rectangles from `other` are added with [`union`](Self::union), so they
+    /// coalesce the same way.
     pub fn merge(&mut self, other: &Self) {
         match (&*self, other) {
             (Self::Full, _) | (_, Self::Full) => *self = Self::Full,
-            (Self::None, _) => *self = other.clone(),
             (_, Self::None) => {}
-            (Self::Rects(a), Self::Rects(b)) => {
-                let mut merged = a.clone();
-                merged.extend_from_slice(b);
-                *self = Self::Rects(merged);
+            (_, Self::Rects(b)) => {
+                for rect in b {
+                    self.union(*rect);
+                }
             }
         }
     }
+
+    /// Restricts the damaged area to `viewport` (`[x, y, width, height]`).
+    ///
+    /// `Full` becomes the viewport itself. Rectangles are intersected with the
+    /// viewport; if none remain the region becomes `None`.
+    pub fn clip_to(&mut self, viewport: [f32; 4]) {
+        match self {
+            Self::None => {}
+            Self::Full => {
+                *self = if viewport[2] > 0.0 && viewport[3] > 0.0 {
+                    Self::Rects(vec![viewport])
+                } else {
+                    Self::None
+                };
+            }
+            Self::Rects(rects) => {
+                rects.retain_mut(|r| match intersect(*r, viewport) {
+                    Some(clipped) => {
+                        *r = clipped;
+                        true
+                    }
+                    None => false,
+                });
+                if rects.is_empty() {
+                    *self = Self::None;
+                }
+            }
+        }
+    }
+}
+
+fn area(r: [f32; 4]) -> f32 {
+    r[2] * r[3]
+}
+
+fn bounding_box(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    let x0 = a[0].min(b[0]);
+    let y0 = a[1].min(b[1]);
+    let x1 = (a[0] + a[2]).max(b[0] + b[2]);
+    let y1 = (a[1] + a[3]).max(b[1] + b[3]);
+    [x0, y0, x1 - x0, y1 - y0]
+}
+
+fn should_coalesce(a: [f32; 4], b: [f32; 4]) -> bool {
+    area(bounding_box(a, b)) <= (area(a) + area(b)) * DamageRegion::COALESCE_FACTOR
+}
+
+fn intersect(a: [f32; 4], b: [f32; 4]) -> Option<[f32; 4]> {
+    let x0 = a[0].max(b[0]);
+    let y0 = a[1].max(b[1]);
+    let x1 = (a[0] + a[2]).min(b[0] + b[2]);
+    let y1 = (a[1] + a[3]).min(b[1] + b[3]);
+    (x1 > x0 && y1 > y0).then_some([x0, y0, x1 - x0, y1 - y0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rects(region: &DamageRegion) -> &[[f32; 4]] {
+        match region {
+            DamageRegion::Rects(rects) => rects,
+            other => panic!("expected rects, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn union_keeps_disjoint_rects() {
+        let mut damage = DamageRegion::None;
+        damage.union([0.0, 0.0, 10.0, 10.0]);
+        damage.union([100.0, 100.0, 10.0, 10.0]);
+        assert_eq!(rects(&damage).len(), 2);
+    }
+
+    #[test]
+    fn union_coalesces_overlapping_and_adjacent_rects() {
+        let mut damage = DamageRegion::None;
+        damage.union([0.0, 0.0, 10.0, 10.0]);
+        damage.union([5.0, 0.0, 10.0, 10.0]);
+        assert_eq!(rects(&damage), [[0.0, 0.0, 15.0, 10.0]]);
+
+        // Edge-adjacent with matching span.
+        damage.union([15.0, 0.0, 5.0, 10.0]);
+        assert_eq!(rects(&damage), [[0.0, 0.0, 20.0, 10.0]]);
+    }
+
+    #[test]
+    fn union_collapses_past_max_rects() {
+        let mut damage = DamageRegion::None;
+        for i in 0..=DamageRegion::MAX_RECTS {
+            damage.union([i as f32 * 100.0, 0.0, 1.0, 1.0]);
+        }
+        let expected_width = DamageRegion::MAX_RECTS as f32 * 100.0 + 1.0;
+        assert_eq!(rects(&damage), [[0.0, 0.0, expected_width, 1.0]]);
+    }
+
+    #[test]
+    fn merge_unions_rects_and_respects_full() {
+        let mut damage = DamageRegion::Rects(vec![[0.0, 0.0, 10.0, 10.0]]);
+        damage.merge(&DamageRegion::Rects(vec![[0.0, 10.0, 10.0, 10.0]]));
+        assert_eq!(rects(&damage), [[0.0, 0.0, 10.0, 20.0]]);
+        damage.merge(&DamageRegion::None);
+        assert_eq!(rects(&damage).len(), 1);
+        damage.merge(&DamageRegion::Full);
+        assert!(matches!(damage, DamageRegion::Full));
+    }
+
+    #[test]
+    fn clip_to_trims_rects_partly_outside() {
+        let mut damage = DamageRegion::Rects(vec![[-5.0, 90.0, 20.0, 20.0]]);
+        damage.clip_to([0.0, 0.0, 100.0, 100.0]);
+        assert_eq!(rects(&damage), [[0.0, 90.0, 15.0, 10.0]]);
+
+        let mut full = DamageRegion::Full;
+        full.clip_to([0.0, 0.0, 100.0, 100.0]);
+        assert_eq!(rects(&full), [[0.0, 0.0, 100.0, 100.0]]);
+    }
+
+    #[test]
+    fn clip_to_outside_viewport_is_empty() {
+        let mut damage = DamageRegion::None;
+        damage.union([200.0, 200.0, 10.0, 10.0]);
+        assert!(!damage.is_empty());
+        damage.clip_to([0.0, 0.0, 100.0, 100.0]);
+        assert!(damage.is_empty());
+        assert!(matches!(damage, DamageRegion::None));
+    }
 }
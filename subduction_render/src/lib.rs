@@ -9,8 +9,10 @@
 //!
 //! - [`RenderItem`] — a single draw command in the render plan
 //! - [`RenderPlan`] — an ordered list of draw commands for one frame
+//! - [`RenderPlanDiff`] — item-level changes between consecutive plans
 //! - [`DamageRegion`] — spatial damage tracking for partial re-rendering
 //! - [`ResourceKey`] — opaque handle for backend-managed resources
+//! - [`ResourceRegistry`] — slot-based key allocator and payload cache
 
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -22,5 +24,6 @@ mod plan;
 mod resource;
 
 pub use damage::DamageRegion;
-pub use plan::{BlendMode, RenderItem, RenderPlan};
-pub use resource::ResourceKey;
+pub use plan::{ItemChanges, RenderGroup, RenderItem, RenderPlan, RenderPlanDiff};
+pub use resource::{ResourceKey, ResourceRegistry};
+pub use subduction_core::layer::BlendMode;
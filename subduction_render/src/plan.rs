@@ -3,22 +3,14 @@
 
 //! Render plan: an ordered sequence of draw items for one frame.
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
+use core::ops::Range;
 
-use subduction_core::layer::{ClipShape, LayerId, SurfaceId};
+use kurbo::Rect;
+use subduction_core::layer::{BlendMode, ClipShape, LayerId, LayerStore, SurfaceId};
 use subduction_core::output::OutputId;
-
-/// Blend mode for compositing a render item.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
-pub enum BlendMode {
-    /// Standard source-over alpha compositing.
-    #[default]
-    SourceOver,
-    /// Multiply blend.
-    Multiply,
-    /// Screen blend.
-    Screen,
-}
+use subduction_core::transform::{Transform2d, Transform3d};
 
 /// A single draw command in the render plan.
 ///
@@ -32,14 +24,109 @@ pub struct RenderItem {
     pub surface: Option<SurfaceId>,
     /// World-space transform (column-major 4x4).
     pub world_transform: [f32; 16],
-    /// Effective opacity (0.0–1.0, accumulated from ancestors).
+    /// Effective opacity (0.0–1.0), accumulated from ancestors up to the
+    /// innermost enclosing [`RenderGroup`].
     pub effective_opacity: f32,
     /// Clip shape in local coordinates, if any.
     pub clip: Option<ClipShape>,
+    /// World-space clip rectangle accumulated from this layer's own clip
+    /// and every ancestor clip, if any.
+    ///
+    /// See [`LayerStore::effective_clip`] for how shapes are approximated.
+    pub effective_clip: Option<Rect>,
     /// Blend mode.
     pub blend_mode: BlendMode,
 }
 
+/// An [isolated](subduction_core::layer::LayerFlags::isolate) opacity group
+/// in a [`RenderPlan`].
+///
+/// The group's items are drawn into one offscreen target, which is then
+/// composited with the group's opacity, so overlapping items do not show
+/// through each other. Groups nest: the range of an outer group contains the
+/// ranges of the groups inside it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderGroup {
+    /// The isolated layer the group originates from.
+    pub layer_id: LayerId,
+    /// Opacity applied once to the flattened group, accumulated from
+    /// ancestors up to the enclosing group.
+    pub opacity: f32,
+    /// Range of [`RenderPlan::items`] drawn inside the group.
+    pub items: Range<usize>,
+}
+
+/// Which fields of a [`RenderItem`] differ between two plans.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItemChanges {
+    /// `world_transform` changed.
+    pub transform: bool,
+    /// `effective_opacity` changed.
+    pub opacity: bool,
+    /// `clip` or `effective_clip` changed.
+    pub clip: bool,
+    /// `surface` changed.
+    pub content: bool,
+    /// `blend_mode` changed.
+    pub blend_mode: bool,
+}
+
+impl ItemChanges {
+    /// Compares two items for the same layer.
+    #[must_use]
+    pub fn between(prev: &RenderItem, next: &RenderItem) -> Self {
+        Self {
+            transform: prev.world_transform != next.world_transform,
+            opacity: prev.effective_opacity != next.effective_opacity,
+            clip: prev.clip != next.clip || prev.effective_clip != next.effective_clip,
+            content: prev.surface != next.surface,
+            blend_mode: prev.blend_mode != next.blend_mode,
+        }
+    }
+
+    /// Returns whether no field changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Item-level differences between two [`RenderPlan`]s.
+///
+/// Items are keyed by their layer's slot index, like [`FrameChanges`], but a
+/// slot reused by a different layer generation counts as a removal plus an
+/// addition.
+///
+/// [`FrameChanges`]: subduction_core::layer::FrameChanges
+#[derive(Clone, Debug, Default)]
+pub struct RenderPlanDiff {
+    /// Slots with an item in the new plan but not the previous one, in new
+    /// plan order.
+    pub added: Vec<u32>,
+    /// Slots with an item in the previous plan but not the new one, in
+    /// previous plan order.
+    pub removed: Vec<u32>,
+    /// Slots present in both plans whose items differ, in new plan order.
+    pub modified: Vec<(u32, ItemChanges)>,
+    /// Whether items present in both plans appear in a different relative
+    /// order.
+    pub order_changed: bool,
+    /// Whether the plans' [`groups`](RenderPlan::groups) differ.
+    pub groups_changed: bool,
+}
+
+impl RenderPlanDiff {
+    /// Returns whether the two plans draw the same items identically.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && !self.order_changed
+            && !self.groups_changed
+    }
+}
+
 /// An ordered list of draw commands for a single frame on a single output.
 ///
 /// Backends translate this into native compositor operations or GPU draw
@@ -50,6 +137,9 @@ pub struct RenderPlan {
     pub output: OutputId,
     /// Draw items in back-to-front order.
     pub items: Vec<RenderItem>,
+    /// Opacity groups over [`items`](Self::items), outer groups before the
+    /// groups nested inside them.
+    pub groups: Vec<RenderGroup>,
 }
 
 impl RenderPlan {
@@ -59,11 +149,568 @@ impl RenderPlan {
         Self {
             output,
             items: Vec::new(),
+            groups: Vec::new(),
         }
     }
 
+    /// Builds a plan from an evaluated layer store.
+    ///
+    /// The plan targets the default output; see
+    /// [`rebuild_from_store`](Self::rebuild_from_store) to fill a plan for a
+    /// specific output and reuse its allocation.
+    #[must_use]
+    pub fn from_store(store: &LayerStore) -> Self {
+        let mut plan = Self::default();
+        plan.rebuild_from_store(store);
+        plan
+    }
+
+    /// Replaces the plan's items with the drawable layers of `store`.
+    ///
+    /// Walks [`LayerStore::traversal_order`] and emits one item per layer
+    /// that has content and is not effectively hidden, so items stay in
+    /// back-to-front order and hidden subtrees are absent. Contentless
+    /// grouping layers contribute only through their descendants' world
+    /// transform and opacity.
+    ///
+    /// Each visible isolated layer whose subtree has items opens a
+    /// [`RenderGroup`] over them. The isolated layer's own item is drawn
+    /// inside its group at full opacity, since the group carries it.
+    ///
+    /// `store` must have been evaluated since its last mutation.
+    pub fn rebuild_from_store(&mut self, store: &LayerStore) {
+        self.items.clear();
+        self.groups.clear();
+        // Open groups as (index into `groups`, isolated slot), innermost last.
+        let mut open: Vec<(usize, u32)> = Vec::new();
+        for &idx in store.traversal_order() {
+            if store.effective_hidden_at(idx) {
+                continue;
+            }
+            while let Some(&(group, layer)) = open.last() {
+                if is_ancestor(store, layer, idx) {
+                    break;
+                }
+                open.pop();
+                self.close_group(group);
+            }
+            let isolated = store.flags_at(idx).isolate;
+            if isolated {
+                open.push((self.groups.len(), idx));
+                self.groups.push(RenderGroup {
+                    layer_id: store.id_at(idx),
+                    opacity: store.effective_opacity_at(idx),
+                    items: self.items.len()..self.items.len(),
+                });
+            }
+            let Some(surface) = store.content_at(idx) else {
+                continue;
+            };
+            self.items.push(RenderItem {
+                layer_id: store.id_at(idx),
+                surface: Some(surface),
+                world_transform: transform_to_f32(&store.world_transform_at(idx)),
+                effective_opacity: if isolated {
+                    1.0
+                } else {
+                    store.effective_opacity_at(idx)
+                },
+                clip: store.clip_at(idx),
+                effective_clip: store.effective_clip_at(idx),
+                blend_mode: store.blend_mode_at(idx),
+            });
+        }
+        while let Some((group, _)) = open.pop() {
+            self.close_group(group);
+        }
+    }
+
+    /// Ends `groups[group]` at the current item count, dropping it if it
+    /// has no items.
+    fn close_group(&mut self, group: usize) {
+        let end = self.items.len();
+        self.groups[group].items.end = end;
+        // Groups opened after this one are nested in it, so an empty group
+        // has no non-empty groups after it and is always the last one.
+        if self.groups[group].items.is_empty() {
+            self.groups.truncate(group);
+        }
+    }
+
+    /// Drops items that are fully hidden behind opaque items in front of
+    /// them, returning how many were dropped.
+    ///
+    /// This optional pass walks the items front to back. An item becomes an
+    /// occluder when its effective opacity and that of every enclosing
+    /// [`RenderGroup`] is exactly 1.0, its blend mode is
+    /// [`BlendMode::SourceOver`], it has neither its own clip nor an
+    /// inherited one, and its world transform is a 2D scale and translation,
+    /// so its world AABB is exactly the area it covers. An item is dropped
+    /// when its world AABB lies within the AABB of an occluder already seen.
+    ///
+    /// The test is conservative: it compares whole AABBs only, never unions
+    /// of several occluders, and items without world bounds are always kept.
+    /// It does assume that attached content fills its layer's bounds with
+    /// opaque pixels, so hosts whose surfaces may be translucent should not
+    /// run it.
+    ///
+    /// `store` must be the evaluated store this plan was built from.
+    pub fn cull_occluded(&mut self, store: &LayerStore) -> usize {
+        let mut occluders: Vec<Rect> = Vec::new();
+        let mut keep = alloc::vec![true; self.items.len()];
+        for (i, item) in self.items.iter().enumerate().rev() {
+            let idx = item.layer_id.index();
+            let Some(bounds) = store.world_bounds_at(idx) else {
+                continue;
+            };
+            if occluders.iter().any(|occluder| contains(*occluder, bounds)) {
+                keep[i] = false;
+                continue;
+            }
+            let opaque = item.effective_opacity == 1.0
+                && self
+                    .groups
+                    .iter()
+                    .all(|group| group.opacity == 1.0 || !group.items.contains(&i))
+                && item.blend_mode == BlendMode::SourceOver
+                && item.clip.is_none()
+                && item.effective_clip.is_none()
+                && is_axis_aligned(&store.world_transform_at(idx));
+            if opaque {
+                occluders.push(bounds);
+            }
+        }
+
+        // Remap group ranges onto the kept items.
+        let mut kept_before = Vec::with_capacity(keep.len() + 1);
+        kept_before.push(0);
+        for &k in &keep {
+            kept_before.push(kept_before[kept_before.len() - 1] + usize::from(k));
+        }
+        for group in &mut self.groups {
+            group.items = kept_before[group.items.start]..kept_before[group.items.end];
+        }
+        self.groups.retain(|group| !group.items.is_empty());
+
+        let before = self.items.len();
+        let mut keep = keep.into_iter();
+        self.items.retain(|_| keep.next().unwrap_or(true));
+        before - self.items.len()
+    }
+
     /// Clears the plan for reuse.
     pub fn clear(&mut self) {
         self.items.clear();
+        self.groups.clear();
+    }
+
+    /// Compares this plan against the previous frame's plan.
+    #[must_use]
+    pub fn diff(&self, prev: &Self) -> RenderPlanDiff {
+        let prev_by_slot: BTreeMap<u32, usize> = prev
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (item.layer_id.index(), i))
+            .collect();
+
+        let mut diff = RenderPlanDiff::default();
+        let mut matched = Vec::with_capacity(prev.items.len());
+        let mut last_prev_pos = None;
+        for item in &self.items {
+            let slot = item.layer_id.index();
+            let prev_pos = prev_by_slot
+                .get(&slot)
+                .copied()
+                .filter(|&i| prev.items[i].layer_id == item.layer_id);
+            let Some(prev_pos) = prev_pos else {
+                diff.added.push(slot);
+                continue;
+            };
+            matched.push(prev_pos);
+            if last_prev_pos.is_some_and(|last| prev_pos < last) {
+                diff.order_changed = true;
+            }
+            last_prev_pos = Some(prev_pos);
+            let changes = ItemChanges::between(&prev.items[prev_pos], item);
+            if !changes.is_empty() {
+                diff.modified.push((slot, changes));
+            }
+        }
+
+        matched.sort_unstable();
+        diff.removed = prev
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| matched.binary_search(i).is_err())
+            .map(|(_, item)| item.layer_id.index())
+            .collect();
+        diff.groups_changed = self.groups != prev.groups;
+        diff
+    }
+}
+
+/// Returns whether slot `ancestor` is a strict ancestor of slot `idx`.
+fn is_ancestor(store: &LayerStore, ancestor: u32, idx: u32) -> bool {
+    let mut parent = store.parent_at(idx);
+    while let Some(p) = parent {
+        if p == ancestor {
+            return true;
+        }
+        parent = store.parent_at(p);
+    }
+    false
+}
+
+/// Returns whether `outer` fully contains `inner`.
+fn contains(outer: Rect, inner: Rect) -> bool {
+    outer.x0 <= inner.x0 && outer.y0 <= inner.y0 && outer.x1 >= inner.x1 && outer.y1 >= inner.y1
+}
+
+/// Returns whether `t` maps axis-aligned rectangles to axis-aligned
+/// rectangles without rotation, skew, or perspective.
+fn is_axis_aligned(t: &Transform3d) -> bool {
+    Transform2d::from_transform3d(t).is_some_and(|t| {
+        let [_, b, c, _, _, _] = t.coeffs;
+        b == 0.0 && c == 0.0
+    })
+}
+
+/// Flattens a `Transform3d` into a column-major `f32` array.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "intentional f64→f32 narrowing for backend consumption"
+)]
+fn transform_to_f32(t: &Transform3d) -> [f32; 16] {
+    let c = t.to_cols_array_2d();
+    let mut out = [0.0; 16];
+    for (i, v) in c.iter().flatten().enumerate() {
+        out[i] = *v as f32;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subduction_core::layer::{LayerFlags, SurfaceIds};
+
+    #[test]
+    fn items_follow_traversal_and_skip_hidden_subtrees() {
+        let mut store = LayerStore::new();
+        let mut surfaces = SurfaceIds::new();
+
+        // root (group)
+        // ├── a (content)
+        // ├── hidden (content, hidden)
+        // │   └── hidden_child (content)
+        // └── group (no content)
+        //     └── b (content)
+        let root = store.create_layer();
+        let a = store.create_layer();
+        let hidden = store.create_layer();
+        let hidden_child = store.create_layer();
+        let group = store.create_layer();
+        let b = store.create_layer();
+        store.add_child(root, a);
+        store.add_child(root, hidden);
+        store.add_child(hidden, hidden_child);
+        store.add_child(root, group);
+        store.add_child(group, b);
+        for id in [a, hidden, hidden_child, b] {
+            store.set_content(id, Some(surfaces.create()));
+        }
+        store.set_flags(
+            hidden,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
+        store.set_opacity(group, 0.5);
+        store.set_blend_mode(b, BlendMode::Multiply);
+        store.set_transform(group, Transform3d::from_translation(10.0, 20.0, 0.0));
+        let _ = store.evaluate();
+
+        let plan = RenderPlan::from_store(&store);
+        let ids: Vec<LayerId> = plan.items.iter().map(|item| item.layer_id).collect();
+        assert_eq!(ids, [a, b]);
+
+        let item = &plan.items[1];
+        assert_eq!(item.surface, store.content(b));
+        assert_eq!(item.effective_opacity, 0.5);
+        assert_eq!(item.blend_mode, BlendMode::Multiply);
+        assert_eq!(&item.world_transform[12..14], &[10.0, 20.0]);
+    }
+
+    #[test]
+    fn diff_reports_only_opacity_change() {
+        let mut store = LayerStore::new();
+        let mut surfaces = SurfaceIds::new();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        store.set_content(a, Some(surfaces.create()));
+        store.set_content(b, Some(surfaces.create()));
+        let _ = store.evaluate();
+        let prev = RenderPlan::from_store(&store);
+
+        store.set_opacity(b, 0.25);
+        let _ = store.evaluate();
+        let next = RenderPlan::from_store(&store);
+
+        let diff = next.diff(&prev);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(!diff.order_changed);
+        assert_eq!(
+            diff.modified,
+            [(
+                b.index(),
+                ItemChanges {
+                    opacity: true,
+                    ..ItemChanges::default()
+                }
+            )]
+        );
+        assert!(next.diff(&next).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_added_and_removed_layers() {
+        let mut store = LayerStore::new();
+        let mut surfaces = SurfaceIds::new();
+        let a = store.create_layer();
+        store.set_content(a, Some(surfaces.create()));
+        let _ = store.evaluate();
+        let prev = RenderPlan::from_store(&store);
+
+        let b = store.create_layer();
+        store.set_content(b, Some(surfaces.create()));
+        let _ = store.evaluate();
+        let next = RenderPlan::from_store(&store);
+
+        let diff = next.diff(&prev);
+        assert_eq!(diff.added, [b.index()]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.modified.is_empty());
+
+        let diff = prev.diff(&next);
+        assert_eq!(diff.removed, [b.index()]);
+        assert!(diff.added.is_empty());
+    }
+
+    #[test]
+    fn rebuild_keeps_output_and_replaces_items() {
+        let mut store = LayerStore::new();
+        let mut surfaces = SurfaceIds::new();
+        let a = store.create_layer();
+        store.set_content(a, Some(surfaces.create()));
+        let _ = store.evaluate();
+
+        let mut plan = RenderPlan::new(OutputId(3));
+        plan.rebuild_from_store(&store);
+        plan.rebuild_from_store(&store);
+        assert_eq!(plan.output, OutputId(3));
+        assert_eq!(plan.items.len(), 1);
+
+        store.set_flags(
+            a,
+            LayerFlags {
+                hidden: true,
+                ..LayerFlags::default()
+            },
+        );
+        let _ = store.evaluate();
+        plan.rebuild_from_store(&store);
+        assert!(plan.items.is_empty());
+    }
+
+    #[test]
+    fn isolated_layer_opens_group_over_its_subtree() {
+        let mut store = LayerStore::new();
+        let mut surfaces = SurfaceIds::new();
+        let outer = store.create_layer();
+        let group = store.create_layer();
+        let a = store.create_layer();
+        let b = store.create_layer();
+        let after = store.create_layer();
+        store.add_child(outer, group);
+        store.add_child(group, a);
+        store.add_child(group, b);
+        store.add_child(outer, after);
+        for id in [group, a, b, after] {
+            store.set_content(id, Some(surfaces.create()));
+        }
+        store.set_opacity(outer, 0.5);
+        store.set_opacity(group, 0.5);
+        store.set_opacity(a, 0.8);
+        store.set_flags(
+            group,
+            LayerFlags {
+                isolate: true,
+                ..LayerFlags::default()
+            },
+        );
+        let _ = store.evaluate();
+
+        let plan = RenderPlan::from_store(&store);
+        let items: Vec<(LayerId, f32)> = plan
+            .items
+            .iter()
+            .map(|item| (item.layer_id, item.effective_opacity))
+            .collect();
+        assert_eq!(items, [(group, 1.0), (a, 0.8), (b, 1.0), (after, 0.5)]);
+        assert_eq!(
+            plan.groups,
+            [RenderGroup {
+                layer_id: group,
+                opacity: 0.25,
+                items: 0..3,
+            }]
+        );
+
+        // A group opacity change leaves every item unchanged.
+        store.set_opacity(group, 1.0);
+        let _ = store.evaluate();
+        let next = RenderPlan::from_store(&store);
+        let diff = next.diff(&plan);
+        assert!(diff.modified.is_empty());
+        assert!(diff.groups_changed);
+    }
+
+    #[test]
+    fn child_carries_parent_clip() {
+        let mut store = LayerStore::new();
+        let mut surfaces = SurfaceIds::new();
+        let parent = store.create_layer();
+        let child = store.create_layer();
+        store.add_child(parent, child);
+        store.set_content(child, Some(surfaces.create()));
+        store.set_transform(parent, Transform3d::from_translation(10.0, 20.0, 0.0));
+        store.set_clip(
+            parent,
+            Some(ClipShape::Rect(Rect::new(0.0, 0.0, 50.0, 40.0))),
+        );
+        let _ = store.evaluate();
+
+        let plan = RenderPlan::from_store(&store);
+        assert_eq!(plan.items.len(), 1);
+        let item = &plan.items[0];
+        assert_eq!(item.layer_id, child);
+        assert_eq!(item.clip, None);
+        assert_eq!(item.effective_clip, Some(Rect::new(10.0, 20.0, 60.0, 60.0)));
+
+        // Dropping the parent clip is reported as a clip change on the child.
+        store.set_clip(parent, None);
+        let _ = store.evaluate();
+        let next = RenderPlan::from_store(&store);
+        assert_eq!(next.items[0].effective_clip, None);
+        let diff = next.diff(&plan);
+        assert_eq!(diff.modified.len(), 1);
+        assert!(diff.modified[0].1.clip);
+    }
+
+    /// Builds `back` then `front` as siblings with opaque content, evaluated.
+    fn stacked(back: Rect, front: Rect) -> (LayerStore, LayerId, LayerId) {
+        let mut store = LayerStore::new();
+        let mut surfaces = SurfaceIds::new();
+        let root = store.create_layer();
+        let mut place = |rect: Rect| {
+            let id = store.create_layer();
+            store.add_child(root, id);
+            store.set_content(id, Some(surfaces.create()));
+            store.set_bounds(id, rect.size());
+            store.set_transform(id, Transform3d::from_translation(rect.x0, rect.y0, 0.0));
+            id
+        };
+        let back = place(back);
+        let front = place(front);
+        let _ = store.evaluate();
+        (store, back, front)
+    }
+
+    #[test]
+    fn large_opaque_layer_culls_covered_layer() {
+        let (store, _, front) = stacked(
+            Rect::new(20.0, 20.0, 40.0, 40.0),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+        );
+        let mut plan = RenderPlan::from_store(&store);
+        assert_eq!(plan.cull_occluded(&store), 1);
+        let ids: Vec<LayerId> = plan.items.iter().map(|item| item.layer_id).collect();
+        assert_eq!(ids, [front]);
+    }
+
+    #[test]
+    fn small_opaque_layer_does_not_cull_larger_one() {
+        let (store, back, front) = stacked(
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Rect::new(20.0, 20.0, 40.0, 40.0),
+        );
+        let mut plan = RenderPlan::from_store(&store);
+        assert_eq!(plan.cull_occluded(&store), 0);
+        let ids: Vec<LayerId> = plan.items.iter().map(|item| item.layer_id).collect();
+        assert_eq!(ids, [back, front]);
+    }
+
+    #[test]
+    fn translucent_or_rotated_layers_do_not_occlude() {
+        let covered = Rect::new(20.0, 20.0, 40.0, 40.0);
+        let cover = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+        let (mut store, _, front) = stacked(covered, cover);
+        store.set_opacity(front, 0.5);
+        let _ = store.evaluate();
+        let mut plan = RenderPlan::from_store(&store);
+        assert_eq!(plan.cull_occluded(&store), 0);
+
+        let (mut store, _, front) = stacked(covered, cover);
+        store.set_blend_mode(front, BlendMode::Multiply);
+        let _ = store.evaluate();
+        let mut plan = RenderPlan::from_store(&store);
+        assert_eq!(plan.cull_occluded(&store), 0);
+
+        let (mut store, _, front) = stacked(covered, cover);
+        store.set_transform(front, Transform3d::from_rotation_z(0.1));
+        let _ = store.evaluate();
+        let mut plan = RenderPlan::from_store(&store);
+        assert_eq!(plan.cull_occluded(&store), 0);
+
+        // Drawn at full opacity inside a translucent group.
+        let (mut store, _, front) = stacked(covered, cover);
+        store.set_opacity(front, 0.5);
+        store.set_flags(
+            front,
+            LayerFlags {
+                isolate: true,
+                ..LayerFlags::default()
+            },
+        );
+        let _ = store.evaluate();
+        let mut plan = RenderPlan::from_store(&store);
+        assert_eq!(plan.items[1].effective_opacity, 1.0);
+        assert_eq!(plan.cull_occluded(&store), 0);
+    }
+
+    #[test]
+    fn culling_remaps_group_ranges() {
+        let (mut store, _, front) = stacked(
+            Rect::new(20.0, 20.0, 40.0, 40.0),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+        );
+        let root = store.parent(front).unwrap();
+        store.set_flags(
+            root,
+            LayerFlags {
+                isolate: true,
+                ..LayerFlags::default()
+            },
+        );
+        let _ = store.evaluate();
+        let mut plan = RenderPlan::from_store(&store);
+        assert_eq!(plan.groups[0].items, 0..2);
+        assert_eq!(plan.cull_occluded(&store), 1);
+        assert_eq!(plan.groups[0].items, 0..1);
     }
 }
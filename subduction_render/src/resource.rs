@@ -3,12 +3,16 @@
 
 //! Opaque resource keys for backend-managed resources.
 
+use alloc::vec::Vec;
 use core::fmt;
 
 /// An opaque handle to a backend-managed resource (texture, buffer, etc.).
 ///
 /// Resource keys are assigned by backends and passed through the render
 /// plan without interpretation by core or render crates.
+///
+/// Keys allocated by a [`ResourceRegistry`] pack a slot index in the low 32
+/// bits and a generation in the high 32 bits.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ResourceKey(pub u64);
 
@@ -17,3 +21,174 @@ impl fmt::Debug for ResourceKey {
         write!(f, "ResourceKey({})", self.0)
     }
 }
+
+/// Slot-based registry mapping [`ResourceKey`]s to backend payloads.
+///
+/// Presenters use this to cache uploaded textures, buffers, or images under a
+/// stable key. Slots are reused through a free list, and each reuse bumps the
+/// slot's generation so a stale key never resolves to a newer payload.
+#[derive(Debug)]
+pub struct ResourceRegistry<T> {
+    slots: Vec<Option<T>>,
+    generation: Vec<u32>,
+    free_list: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Default for ResourceRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ResourceRegistry<T> {
+    /// Creates an empty registry.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            generation: Vec::new(),
+            free_list: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Stores `payload` and returns its key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` slots are allocated over the registry's
+    /// lifetime.
+    #[must_use]
+    pub fn insert(&mut self, payload: T) -> ResourceKey {
+        let idx = if let Some(idx) = self.free_list.pop() {
+            self.slots[idx as usize] = Some(payload);
+            idx
+        } else {
+            let idx = u32::try_from(self.slots.len()).expect("too many resource slots");
+            self.slots.push(Some(payload));
+            self.generation.push(0);
+            idx
+        };
+        self.len += 1;
+        pack(idx, self.generation[idx as usize])
+    }
+
+    /// Removes and returns the payload for `key`.
+    ///
+    /// Returns `None` if `key` is stale or was never allocated here.
+    pub fn remove(&mut self, key: ResourceKey) -> Option<T> {
+        let idx = self.live_index(key)?;
+        let payload = self.slots[idx].take();
+        self.generation[idx] = self.generation[idx].wrapping_add(1);
+        self.free_list.push(unpack(key).0);
+        self.len -= 1;
+        payload
+    }
+
+    /// Returns the payload for `key`, if it is live.
+    #[must_use]
+    pub fn get(&self, key: ResourceKey) -> Option<&T> {
+        let idx = self.live_index(key)?;
+        self.slots[idx].as_ref()
+    }
+
+    /// Returns the payload for `key` mutably, if it is live.
+    #[must_use]
+    pub fn get_mut(&mut self, key: ResourceKey) -> Option<&mut T> {
+        let idx = self.live_index(key)?;
+        self.slots[idx].as_mut()
+    }
+
+    /// Returns whether `key` is live in this registry.
+    #[must_use]
+    pub fn contains(&self, key: ResourceKey) -> bool {
+        self.live_index(key).is_some()
+    }
+
+    /// Returns the number of live payloads.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the registry holds no payloads.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterates over live keys and payloads in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceKey, &T)> {
+        self.slots
+            .iter()
+            .zip(&self.generation)
+            .zip(0_u32..)
+            .filter_map(|((slot, &generation), idx)| {
+                slot.as_ref().map(|p| (pack(idx, generation), p))
+            })
+    }
+
+    /// Returns the slot index for `key` if its generation matches a live slot.
+    fn live_index(&self, key: ResourceKey) -> Option<usize> {
+        let (idx, generation) = unpack(key);
+        let idx = idx as usize;
+        (self.generation.get(idx) == Some(&generation) && self.slots[idx].is_some()).then_some(idx)
+    }
+}
+
+const fn pack(idx: u32, generation: u32) -> ResourceKey {
+    ResourceKey(((generation as u64) << 32) | idx as u64)
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "splitting a packed u64 into its two u32 halves"
+)]
+const fn unpack(key: ResourceKey) -> (u32, u32) {
+    (key.0 as u32, (key.0 >> 32) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_lookup_remove() {
+        let mut registry = ResourceRegistry::new();
+        let a = registry.insert("a");
+        let b = registry.insert("b");
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get(a), Some(&"a"));
+        assert_eq!(registry.get(b), Some(&"b"));
+
+        *registry.get_mut(b).unwrap() = "b2";
+        assert_eq!(registry.remove(b), Some("b2"));
+        assert_eq!(registry.remove(b), None);
+        assert!(!registry.contains(b));
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.iter().collect::<Vec<_>>(), [(a, &"a")]);
+    }
+
+    #[test]
+    fn reused_slot_does_not_alias_stale_key() {
+        let mut registry = ResourceRegistry::new();
+        let old = registry.insert(1);
+        assert_eq!(registry.remove(old), Some(1));
+
+        let new = registry.insert(2);
+        assert_ne!(old, new);
+        assert_eq!(unpack(old).0, unpack(new).0, "slot should be reused");
+        assert_eq!(registry.get(old), None);
+        assert_eq!(registry.get(new), Some(&2));
+        assert_eq!(registry.remove(old), None);
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn foreign_keys_are_rejected() {
+        let registry = ResourceRegistry::<u8>::new();
+        assert_eq!(registry.get(ResourceKey(7)), None);
+        assert!(registry.is_empty());
+    }
+}
@@ -8,6 +8,8 @@
 extern crate alloc;
 
 use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
 use frameclock::timing::PresentationTiming;
 
 /// Runtime pathology toggles for stress tests.
@@ -23,6 +25,63 @@ pub struct PathologyToggles {
     pub vary_refresh: bool,
 }
 
+impl PathologyToggles {
+    /// Query-string keys, in the order [`to_query`](Self::to_query) emits them.
+    pub const KEYS: [&'static str; 4] =
+        ["decode_jitter", "gpu_stall", "timer_jitter", "vary_refresh"];
+
+    /// Encodes the enabled toggles as a query string without the leading `?`,
+    /// e.g. `decode_jitter=1&gpu_stall=1`.
+    ///
+    /// Disabled toggles are omitted, so all-off encodes as an empty string.
+    #[must_use]
+    pub fn to_query(&self) -> String {
+        let mut out = String::new();
+        for (key, on) in Self::KEYS.into_iter().zip(self.flags()) {
+            if on {
+                if !out.is_empty() {
+                    out.push('&');
+                }
+                out.push_str(key);
+                out.push_str("=1");
+            }
+        }
+        out
+    }
+
+    /// Parses toggles from a query string, with or without the leading `?`.
+    ///
+    /// A key enables its toggle when bare or set to `1`, `true` or `on`; any
+    /// other value disables it. Unknown keys are ignored and missing keys are
+    /// off.
+    #[must_use]
+    pub fn from_query(query: &str) -> Self {
+        let mut flags = [false; 4];
+        for pair in query.trim_start_matches('?').split('&') {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, "1"));
+            if let Some(i) = Self::KEYS.iter().position(|k| *k == key) {
+                flags[i] = matches!(value, "1" | "true" | "on");
+            }
+        }
+        let [decode_jitter, gpu_stall, timer_jitter, vary_refresh] = flags;
+        Self {
+            decode_jitter,
+            gpu_stall,
+            timer_jitter,
+            vary_refresh,
+        }
+    }
+
+    const fn flags(&self) -> [bool; 4] {
+        [
+            self.decode_jitter,
+            self.gpu_stall,
+            self.timer_jitter,
+            self.vary_refresh,
+        ]
+    }
+}
+
 /// Per-frame metrics sample fed into [`SyncTracker::observe`].
 #[derive(Clone, Copy, Debug)]
 pub struct SyncSample {
@@ -39,7 +98,9 @@ pub struct SyncSample {
 }
 
 /// Letter grade for synchronization quality.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///
+/// Grades order from best to worst, so `max` picks the worse of two.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SyncGrade {
     /// Tight sync and low miss rate.
     A,
@@ -52,6 +113,22 @@ pub enum SyncGrade {
 }
 
 impl SyncGrade {
+    /// Bands a [`SyncReport::score`] into a letter grade.
+    ///
+    /// Scores above 75 are A, above 50 B, above 25 C, and D otherwise.
+    #[must_use]
+    pub fn from_score(score: f64) -> Self {
+        if score > 75.0 {
+            Self::A
+        } else if score > 50.0 {
+            Self::B
+        } else if score > 25.0 {
+            Self::C
+        } else {
+            Self::D
+        }
+    }
+
     /// Returns a short label for HUD rendering.
     #[must_use]
     pub const fn as_str(self) -> &'static str {
@@ -64,13 +141,232 @@ impl SyncGrade {
     }
 }
 
+/// Grade cutoffs for one presentation timing class.
+///
+/// A frame earns a grade when both its absolute phase error and the windowed
+/// miss rate are strictly below that grade's limits; otherwise it falls
+/// through to the next grade, down to [`SyncGrade::D`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradeLimits {
+    /// Phase error limits for grades A, B and C, in milliseconds.
+    pub phase_ms: [f64; 3],
+    /// Miss-rate limits for grades A, B and C, in misses per 1000 frames.
+    pub miss_per_1000: [f64; 3],
+}
+
+/// Grade cutoffs used by [`SyncTracker`], per presentation timing class.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradeThresholds {
+    /// Limits for [`PresentationTiming::Predictive`].
+    pub predictive: GradeLimits,
+    /// Limits for [`PresentationTiming::Estimated`].
+    pub estimated: GradeLimits,
+    /// Limits for [`PresentationTiming::PacingOnly`].
+    pub pacing_only: GradeLimits,
+}
+
+impl GradeThresholds {
+    /// The default cutoffs, stricter the more precise the timing.
+    pub const DEFAULT: Self = Self {
+        predictive: GradeLimits {
+            phase_ms: [16.0, 32.0, 50.0],
+            miss_per_1000: [1.0, 5.0, 15.0],
+        },
+        estimated: GradeLimits {
+            phase_ms: [24.0, 45.0, 70.0],
+            miss_per_1000: [3.0, 10.0, 25.0],
+        },
+        pacing_only: GradeLimits {
+            phase_ms: [35.0, 65.0, 100.0],
+            miss_per_1000: [10.0, 30.0, 80.0],
+        },
+    };
+
+    /// Returns the limits for `presentation_timing`.
+    #[must_use]
+    pub const fn for_timing(&self, presentation_timing: PresentationTiming) -> &GradeLimits {
+        match presentation_timing {
+            PresentationTiming::Predictive => &self.predictive,
+            PresentationTiming::Estimated => &self.estimated,
+            PresentationTiming::PacingOnly => &self.pacing_only,
+        }
+    }
+
+    /// Scores a frame from 0 to 100 against the limits for
+    /// `presentation_timing`.
+    ///
+    /// Each metric scores 100 at zero, falls linearly by 25 points per grade
+    /// band (so it is exactly 75 at the A limit, 50 at B and 25 at C), and
+    /// reaches 0 one B-to-C band width past the C limit. The score is the
+    /// lower of the two.
+    #[must_use]
+    pub fn score(
+        &self,
+        presentation_timing: PresentationTiming,
+        phase_error_abs_ms: f64,
+        miss_rate_per_1000: f64,
+    ) -> f64 {
+        let limits = self.for_timing(presentation_timing);
+        metric_score(phase_error_abs_ms, limits.phase_ms)
+            .min(metric_score(miss_rate_per_1000, limits.miss_per_1000))
+    }
+
+    /// Grades a frame against the limits for `presentation_timing`.
+    ///
+    /// This is [`SyncGrade::from_score`] applied to [`Self::score`].
+    #[must_use]
+    pub fn grade(
+        &self,
+        presentation_timing: PresentationTiming,
+        phase_error_abs_ms: f64,
+        miss_rate_per_1000: f64,
+    ) -> SyncGrade {
+        SyncGrade::from_score(self.score(
+            presentation_timing,
+            phase_error_abs_ms,
+            miss_rate_per_1000,
+        ))
+    }
+}
+
+/// Combined report returned by [`DualSyncTracker::observe`].
+#[derive(Clone, Copy, Debug)]
+pub struct DualSyncReport {
+    /// Audio-vs-overlay report, or `None` if no audio phase was available
+    /// this frame.
+    pub audio: Option<SyncReport>,
+    /// Video-vs-target report.
+    pub video: SyncReport,
+}
+
+impl DualSyncReport {
+    /// Returns the worse of the audio and video grades.
+    #[must_use]
+    pub fn grade(&self) -> SyncGrade {
+        match self.audio {
+            Some(audio) => audio.grade.max(self.video.grade),
+            None => self.video.grade,
+        }
+    }
+}
+
+/// Pair of [`SyncTracker`]s grading audio and video sync independently.
+///
+/// Both channels see the same frame deltas and misses; only the phase error
+/// differs. This separates "audio clicks are late against the overlay" from
+/// "video frames drift from their target time".
+#[derive(Debug)]
+pub struct DualSyncTracker<const N: usize> {
+    audio: SyncTracker<N>,
+    video: SyncTracker<N>,
+}
+
+impl<const N: usize> Default for DualSyncTracker<N> {
+    fn default() -> Self {
+        Self::new(16.67)
+    }
+}
+
+impl<const N: usize> DualSyncTracker<N> {
+    /// Creates a tracker pair with `seed_delta_ms` prefilled in both rings.
+    #[must_use]
+    pub const fn new(seed_delta_ms: f64) -> Self {
+        Self::with_thresholds(seed_delta_ms, GradeThresholds::DEFAULT)
+    }
+
+    /// Creates a tracker pair that grades both channels against `thresholds`.
+    #[must_use]
+    pub const fn with_thresholds(seed_delta_ms: f64, thresholds: GradeThresholds) -> Self {
+        Self {
+            audio: SyncTracker::with_thresholds(seed_delta_ms, thresholds),
+            video: SyncTracker::with_thresholds(seed_delta_ms, thresholds),
+        }
+    }
+
+    /// Clears both channels.
+    pub fn reset(&mut self) {
+        self.audio.reset();
+        self.video.reset();
+    }
+
+    /// Observes one frame.
+    ///
+    /// `sample.phase_error_ms` is the video-vs-target phase error.
+    /// `audio_phase_error_ms` is the audio-vs-overlay phase error; pass `None`
+    /// (or a non-finite value) when audio is not running, which leaves the
+    /// audio channel untouched.
+    #[must_use]
+    pub fn observe(
+        &mut self,
+        sample: SyncSample,
+        audio_phase_error_ms: Option<f64>,
+    ) -> DualSyncReport {
+        let audio = audio_phase_error_ms
+            .filter(|e| e.is_finite())
+            .map(|phase_error_ms| {
+                self.audio.observe(SyncSample {
+                    phase_error_ms,
+                    ..sample
+                })
+            });
+        DualSyncReport {
+            audio,
+            video: self.video.observe(sample),
+        }
+    }
+
+    /// Returns the audio-vs-overlay tracker.
+    #[must_use]
+    pub const fn audio(&self) -> &SyncTracker<N> {
+        &self.audio
+    }
+
+    /// Returns the video-vs-target tracker.
+    #[must_use]
+    pub const fn video(&self) -> &SyncTracker<N> {
+        &self.video
+    }
+}
+
+/// Maps `value` onto 100..=0 through the `[a, b, c]` grade limits.
+fn metric_score(value: f64, [a, b, c]: [f64; 3]) -> f64 {
+    let zero = c + (c - b);
+    let (lo, hi, top) = if value < a {
+        (0.0, a, 100.0)
+    } else if value < b {
+        (a, b, 75.0)
+    } else if value < c {
+        (b, c, 50.0)
+    } else {
+        (c, zero, 25.0)
+    };
+    let score = if hi > lo {
+        top - 25.0 * (value - lo) / (hi - lo)
+    } else {
+        top - 25.0
+    };
+    score.clamp(0.0, 100.0)
+}
+
+impl Default for GradeThresholds {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// Aggregated report returned by [`SyncTracker::observe`].
 #[derive(Clone, Copy, Debug)]
 pub struct SyncReport {
-    /// Current grade.
+    /// Current grade, a banding of [`score`](Self::score).
     pub grade: SyncGrade,
-    /// Misses per 1000 observed frames.
+    /// Continuous sync quality from 0 (worst) to 100 (perfect), from the
+    /// current phase error and the windowed miss rate.
+    pub score: f64,
+    /// Misses per 1000 observed frames over the tracker's lifetime.
     pub miss_rate_per_1000: f64,
+    /// Misses per 1000 frames over the last `N` frames (or fewer, until `N`
+    /// frames have been observed).
+    pub window_miss_rate_per_1000: f64,
     /// Current frame's signed phase error in milliseconds.
     pub phase_error_ms: f64,
     /// Total frames observed.
@@ -80,10 +376,19 @@ pub struct SyncReport {
 }
 
 /// Rolling sync tracker with fixed-size frame-delta history.
+///
+/// The last `N` frames form the window for the frame-delta history and the
+/// miss rate used for grading, so the grade recovers once early misses slide
+/// out of the window. Lifetime totals are reported alongside.
 #[derive(Debug)]
 pub struct SyncTracker<const N: usize> {
+    thresholds: GradeThresholds,
+    seed_delta_ms: f64,
     deltas_ms: [f64; N],
+    phase_errors_abs_ms: [f64; N],
+    missed: [bool; N],
     cursor: usize,
+    window_misses: usize,
     total_frames: u64,
     missed_frames: u64,
 }
@@ -98,22 +403,52 @@ impl<const N: usize> SyncTracker<N> {
     /// Creates a tracker with `seed_delta_ms` prefilled in the ring buffer.
     #[must_use]
     pub const fn new(seed_delta_ms: f64) -> Self {
+        Self::with_thresholds(seed_delta_ms, GradeThresholds::DEFAULT)
+    }
+
+    /// Creates a tracker that grades frames against `thresholds`.
+    #[must_use]
+    pub const fn with_thresholds(seed_delta_ms: f64, thresholds: GradeThresholds) -> Self {
         Self {
+            thresholds,
+            seed_delta_ms,
             deltas_ms: [seed_delta_ms; N],
+            phase_errors_abs_ms: [0.0; N],
+            missed: [false; N],
             cursor: 0,
+            window_misses: 0,
             total_frames: 0,
             missed_frames: 0,
         }
     }
 
+    /// Clears all counters and history, as if newly created.
+    ///
+    /// Thresholds are kept.
+    pub fn reset(&mut self) {
+        *self = Self::with_thresholds(self.seed_delta_ms, self.thresholds);
+    }
+
+    /// Returns the thresholds used for grading.
+    #[must_use]
+    pub const fn thresholds(&self) -> &GradeThresholds {
+        &self.thresholds
+    }
+
     /// Observes one frame and returns an updated report.
     #[must_use]
     pub fn observe(&mut self, sample: SyncSample) -> SyncReport {
+        let missed = sample.hard_miss || sample.soft_miss;
         self.total_frames = self.total_frames.saturating_add(1);
-        self.deltas_ms[self.cursor % N] = sample.frame_delta_ms;
+        let slot = self.cursor % N;
+        self.deltas_ms[slot] = sample.frame_delta_ms;
+        self.phase_errors_abs_ms[slot] = sample.phase_error_ms.abs();
+        self.window_misses -= usize::from(self.missed[slot]);
+        self.window_misses += usize::from(missed);
+        self.missed[slot] = missed;
         self.cursor = (self.cursor + 1) % N;
 
-        if sample.hard_miss || sample.soft_miss {
+        if missed {
             self.missed_frames = self.missed_frames.saturating_add(1);
         }
 
@@ -122,22 +457,98 @@ impl<const N: usize> SyncTracker<N> {
         } else {
             self.missed_frames as f64 * 1000.0 / self.total_frames as f64
         };
+        let window_frames = self.total_frames.min(N as u64);
+        let window_miss_rate = self.window_misses as f64 * 1000.0 / window_frames as f64;
 
-        let grade = grade_for(
+        let score = self.thresholds.score(
             sample.presentation_timing,
             sample.phase_error_ms.abs(),
-            miss_rate,
+            window_miss_rate,
         );
 
         SyncReport {
-            grade,
+            grade: SyncGrade::from_score(score),
+            score,
             miss_rate_per_1000: miss_rate,
+            window_miss_rate_per_1000: window_miss_rate,
             phase_error_ms: sample.phase_error_ms,
             total_frames: self.total_frames,
             missed_frames: self.missed_frames,
         }
     }
 
+    /// Returns the 95th percentile (nearest-rank) of absolute phase error over
+    /// the last `N` frames, in milliseconds.
+    ///
+    /// Returns `0.0` before any frame has been observed.
+    #[must_use]
+    pub fn phase_error_p95(&self) -> f64 {
+        let len = self.window_len();
+        if len == 0 {
+            return 0.0;
+        }
+        let mut sorted = self.phase_errors_abs_ms;
+        let sorted = &mut sorted[..len];
+        sorted.sort_unstable_by(f64::total_cmp);
+        let rank = (len * 95).div_ceil(100);
+        sorted[rank - 1]
+    }
+
+    /// Returns the population standard deviation of the ring-buffer frame
+    /// deltas, in milliseconds.
+    ///
+    /// Slots not yet overwritten still hold the seed delta.
+    #[must_use]
+    pub fn frame_delta_stddev(&self) -> f64 {
+        if N == 0 {
+            return 0.0;
+        }
+        let n = N as f64;
+        let mean = self.deltas_ms.iter().sum::<f64>() / n;
+        let var = self
+            .deltas_ms
+            .iter()
+            .map(|d| (d - mean) * (d - mean))
+            .sum::<f64>()
+            / n;
+        libm::sqrt(var)
+    }
+
+    /// Bins the ring-buffer frame deltas by the ascending bucket edges in
+    /// `buckets`, in milliseconds.
+    ///
+    /// Returns one count per edge: bucket `i` counts deltas in
+    /// `[buckets[i], buckets[i + 1])`, and the last bucket is open-ended.
+    /// Deltas below the first edge land in the first bucket, so every slot
+    /// of the ring is counted exactly once. Slots not yet overwritten still
+    /// hold the seed delta. Returns an empty vector when `buckets` is empty.
+    #[must_use]
+    pub fn histogram(&self, buckets: &[f64]) -> Vec<u32> {
+        let mut counts = alloc::vec![0_u32; buckets.len()];
+        if buckets.is_empty() {
+            return counts;
+        }
+        for &delta in &self.deltas_ms {
+            let bucket = buckets.partition_point(|&edge| edge <= delta);
+            counts[bucket.saturating_sub(1)] += 1;
+        }
+        counts
+    }
+
+    /// Number of observed frames currently held in the ring.
+    fn window_len(&self) -> usize {
+        if self.total_frames >= N as u64 {
+            N
+        } else {
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "total_frames is below N here"
+            )]
+            let len = self.total_frames as usize;
+            len
+        }
+    }
+
     /// Returns ring-buffer frame deltas oldest→newest.
     #[must_use]
     pub fn frame_deltas(&self) -> [f64; N] {
@@ -173,25 +584,91 @@ impl<const N: usize> SyncTracker<N> {
     }
 }
 
-fn grade_for(
-    presentation_timing: PresentationTiming,
-    phase_error_abs_ms: f64,
-    miss_rate_per_1000: f64,
-) -> SyncGrade {
-    let (a_phase, b_phase, c_phase, a_miss, b_miss, c_miss) = match presentation_timing {
-        PresentationTiming::Predictive => (16.0, 32.0, 50.0, 1.0, 5.0, 15.0),
-        PresentationTiming::Estimated => (24.0, 45.0, 70.0, 3.0, 10.0, 25.0),
-        PresentationTiming::PacingOnly => (35.0, 65.0, 100.0, 10.0, 30.0, 80.0),
-    };
+/// Accumulates [`SyncSample`]/[`SyncReport`] pairs for offline analysis.
+///
+/// Recorded frames can be exported as CSV with
+/// [`write_csv`](Self::write_csv), one row per observation, so timing runs
+/// can be compared in a spreadsheet or plotting tool.
+#[derive(Clone, Debug, Default)]
+pub struct SyncRecorder {
+    rows: Vec<(SyncSample, SyncReport)>,
+}
 
-    if phase_error_abs_ms < a_phase && miss_rate_per_1000 < a_miss {
-        SyncGrade::A
-    } else if phase_error_abs_ms < b_phase && miss_rate_per_1000 < b_miss {
-        SyncGrade::B
-    } else if phase_error_abs_ms < c_phase && miss_rate_per_1000 < c_miss {
-        SyncGrade::C
-    } else {
-        SyncGrade::D
+impl SyncRecorder {
+    /// Column header written by [`write_csv`](Self::write_csv).
+    pub const CSV_HEADER: &'static str =
+        "frame,confidence,phase_error_ms,hard_miss,soft_miss,grade,score";
+
+    /// Creates an empty recorder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rows: Vec::new() }
+    }
+
+    /// Records one observation.
+    pub fn record(&mut self, sample: SyncSample, report: SyncReport) {
+        self.rows.push((sample, report));
+    }
+
+    /// Feeds `sample` to `tracker`, records the pair, and returns the report.
+    pub fn observe<const N: usize>(
+        &mut self,
+        tracker: &mut SyncTracker<N>,
+        sample: SyncSample,
+    ) -> SyncReport {
+        let report = tracker.observe(sample);
+        self.record(sample, report);
+        report
+    }
+
+    /// Returns the number of recorded observations.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns whether nothing has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the recorded observations, oldest first.
+    #[must_use]
+    pub fn rows(&self) -> &[(SyncSample, SyncReport)] {
+        &self.rows
+    }
+
+    /// Discards all recorded observations.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    /// Writes the recorded observations as CSV to `out`.
+    ///
+    /// The first line is [`CSV_HEADER`](Self::CSV_HEADER); each following
+    /// line is one observation, numbered from 0 in recording order. Miss
+    /// flags are written as `0`/`1` and confidence as the
+    /// [`PresentationTiming`] variant name.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported by `out`.
+    pub fn write_csv<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        writeln!(out, "{}", Self::CSV_HEADER)?;
+        for (frame, (sample, report)) in self.rows.iter().enumerate() {
+            writeln!(
+                out,
+                "{frame},{:?},{},{},{},{},{}",
+                sample.presentation_timing,
+                sample.phase_error_ms,
+                u8::from(sample.hard_miss),
+                u8::from(sample.soft_miss),
+                report.grade.as_str(),
+                report.score,
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -218,6 +695,248 @@ mod tests {
         }
     }
 
+    fn sample(miss: bool) -> SyncSample {
+        SyncSample {
+            presentation_timing: PresentationTiming::Predictive,
+            phase_error_ms: 1.0,
+            hard_miss: miss,
+            soft_miss: false,
+            frame_delta_ms: 16.7,
+        }
+    }
+
+    #[test]
+    fn early_miss_burst_slides_out_of_window() {
+        let mut t = SyncTracker::<32>::new(16.67);
+        let mut report = t.observe(sample(true));
+        for _ in 0..7 {
+            report = t.observe(sample(true));
+        }
+        assert_eq!(report.grade, SyncGrade::D);
+        assert!((report.window_miss_rate_per_1000 - 1000.0).abs() < 1e-6);
+
+        for _ in 0..31 {
+            report = t.observe(sample(false));
+        }
+        // One early miss is still in the window.
+        assert_eq!(report.grade, SyncGrade::D);
+
+        report = t.observe(sample(false));
+        assert_eq!(report.window_miss_rate_per_1000, 0.0);
+        assert_eq!(report.grade, SyncGrade::A);
+        assert_eq!(report.total_frames, 40);
+        assert_eq!(report.missed_frames, 8);
+        assert!((report.miss_rate_per_1000 - 200.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn reset_clears_counters_and_history() {
+        let mut t = SyncTracker::<4>::new(16.67);
+        for _ in 0..6 {
+            let _ = t.observe(SyncSample {
+                frame_delta_ms: 40.0,
+                ..sample(true)
+            });
+        }
+        t.reset();
+        assert_eq!(t.frame_deltas(), [16.67; 4]);
+
+        let report = t.observe(sample(false));
+        assert_eq!(report.total_frames, 1);
+        assert_eq!(report.missed_frames, 0);
+        assert_eq!(report.window_miss_rate_per_1000, 0.0);
+        assert_eq!(report.grade, SyncGrade::A);
+    }
+
+    #[test]
+    fn phase_error_p95_uses_recent_window() {
+        let mut t = SyncTracker::<100>::new(16.67);
+        assert_eq!(t.phase_error_p95(), 0.0);
+
+        // |phase error| of 1..=100 ms, alternating sign.
+        for i in 1..=100_u32 {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let _ = t.observe(SyncSample {
+                phase_error_ms: sign * f64::from(i),
+                ..sample(false)
+            });
+        }
+        assert!((t.phase_error_p95() - 95.0).abs() < 1e-9);
+
+        // Half the window replaced with small errors.
+        for _ in 0..50 {
+            let _ = t.observe(SyncSample {
+                phase_error_ms: 0.5,
+                ..sample(false)
+            });
+        }
+        assert!((t.phase_error_p95() - 95.0).abs() < 1e-9);
+        for _ in 0..50 {
+            let _ = t.observe(SyncSample {
+                phase_error_ms: 0.5,
+                ..sample(false)
+            });
+        }
+        assert!((t.phase_error_p95() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn phase_error_p95_partial_window() {
+        let mut t = SyncTracker::<64>::new(16.67);
+        for e in [2.0, 4.0, 6.0, 8.0, 10.0] {
+            let _ = t.observe(SyncSample {
+                phase_error_ms: e,
+                ..sample(false)
+            });
+        }
+        assert!((t.phase_error_p95() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn frame_delta_stddev_of_known_sequence() {
+        let mut t = SyncTracker::<8>::new(16.67);
+        assert!(t.frame_delta_stddev().abs() < 1e-9);
+
+        // Mean 5, population stddev 2.
+        for d in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            let _ = t.observe(SyncSample {
+                frame_delta_ms: d,
+                ..sample(false)
+            });
+        }
+        assert!((t.frame_delta_stddev() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn stricter_thresholds_downgrade() {
+        let strict = GradeThresholds {
+            predictive: GradeLimits {
+                phase_ms: [0.5, 2.0, 4.0],
+                miss_per_1000: [1.0, 5.0, 15.0],
+            },
+            ..GradeThresholds::DEFAULT
+        };
+        let mut default = SyncTracker::<4>::new(16.67);
+        let mut tracker = SyncTracker::<4>::with_thresholds(16.67, strict);
+        assert_eq!(default.observe(sample(false)).grade, SyncGrade::A);
+        assert_eq!(tracker.observe(sample(false)).grade, SyncGrade::B);
+
+        tracker.reset();
+        assert_eq!(tracker.thresholds(), &strict);
+    }
+
+    #[test]
+    fn score_decreases_with_phase_error() {
+        let t = GradeThresholds::DEFAULT;
+        let mut prev = t.score(PresentationTiming::Estimated, 0.0, 0.0);
+        assert_eq!(prev, 100.0);
+        let mut e = 0.5;
+        while e < 150.0 {
+            let score = t.score(PresentationTiming::Estimated, e, 0.0);
+            assert!(score <= prev, "score rose at {e} ms");
+            prev = score;
+            e += 0.5;
+        }
+        assert_eq!(prev, 0.0);
+    }
+
+    #[test]
+    fn score_bands_match_grades_at_limits() {
+        let t = GradeThresholds::DEFAULT;
+        let timing = PresentationTiming::Predictive;
+        let [a, b, c] = t.predictive.phase_ms;
+        for (limit, score, below, at) in [
+            (a, 75.0, SyncGrade::A, SyncGrade::B),
+            (b, 50.0, SyncGrade::B, SyncGrade::C),
+            (c, 25.0, SyncGrade::C, SyncGrade::D),
+        ] {
+            assert!((t.score(timing, limit, 0.0) - score).abs() < 1e-9);
+            assert_eq!(t.grade(timing, limit - 0.01, 0.0), below);
+            assert_eq!(t.grade(timing, limit, 0.0), at);
+        }
+
+        let [a_miss, ..] = t.predictive.miss_per_1000;
+        assert_eq!(t.grade(timing, 0.0, a_miss - 0.01), SyncGrade::A);
+        assert_eq!(t.grade(timing, 0.0, a_miss), SyncGrade::B);
+        // The worse metric wins.
+        assert_eq!(t.grade(timing, b, a_miss - 0.01), SyncGrade::C);
+    }
+
+    #[test]
+    fn dual_tracker_separates_audio_and_video() {
+        let mut t = DualSyncTracker::<16>::new(16.67);
+        let mut report = t.observe(sample(false), Some(1.0));
+        for i in 0..16 {
+            report = t.observe(
+                SyncSample {
+                    phase_error_ms: 10.0 + f64::from(i) * 4.0,
+                    ..sample(false)
+                },
+                Some(-1.5),
+            );
+        }
+        assert_eq!(report.audio.map(|a| a.grade), Some(SyncGrade::A));
+        assert_eq!(report.video.grade, SyncGrade::D);
+        assert_eq!(report.grade(), SyncGrade::D);
+        assert!(t.audio().phase_error_p95() < 2.0);
+        assert!(t.video().phase_error_p95() > 50.0);
+    }
+
+    #[test]
+    fn dual_tracker_skips_missing_audio() {
+        let mut t = DualSyncTracker::<4>::new(16.67);
+        let report = t.observe(sample(false), Some(f64::NAN));
+        assert!(report.audio.is_none());
+        assert_eq!(report.grade(), report.video.grade);
+        let report = t.observe(sample(false), Some(2.0));
+        assert_eq!(report.audio.map(|a| a.total_frames), Some(1));
+        assert_eq!(report.video.total_frames, 2);
+    }
+
+    #[test]
+    fn pathology_query_round_trips() {
+        for bits in 0_u8..16 {
+            let toggles = PathologyToggles {
+                decode_jitter: bits & 1 != 0,
+                gpu_stall: bits & 2 != 0,
+                timer_jitter: bits & 4 != 0,
+                vary_refresh: bits & 8 != 0,
+            };
+            let query = toggles.to_query();
+            assert_eq!(PathologyToggles::from_query(&query), toggles, "{query}");
+        }
+        let toggles = PathologyToggles {
+            decode_jitter: true,
+            gpu_stall: true,
+            ..PathologyToggles::default()
+        };
+        assert_eq!(toggles.to_query(), "decode_jitter=1&gpu_stall=1");
+        assert_eq!(PathologyToggles::default().to_query(), "");
+    }
+
+    #[test]
+    fn pathology_query_tolerates_unknown_and_missing_keys() {
+        assert_eq!(
+            PathologyToggles::from_query("?layers=100&gpu_stall&timer_jitter=off&bogus"),
+            PathologyToggles {
+                gpu_stall: true,
+                ..PathologyToggles::default()
+            }
+        );
+        assert_eq!(
+            PathologyToggles::from_query("vary_refresh=true&decode_jitter=on"),
+            PathologyToggles {
+                decode_jitter: true,
+                vary_refresh: true,
+                ..PathologyToggles::default()
+            }
+        );
+        assert_eq!(
+            PathologyToggles::from_query(""),
+            PathologyToggles::default()
+        );
+    }
+
     #[test]
     fn predictive_thresholds_are_stricter() {
         let mut t = SyncTracker::<4>::new(16.67);
@@ -239,4 +958,49 @@ mod tests {
         });
         assert_eq!(e.grade, SyncGrade::B);
     }
+
+    #[test]
+    fn recorder_writes_one_csv_row_per_observation() {
+        let mut t = SyncTracker::<8>::new(16.67);
+        let mut recorder = SyncRecorder::new();
+        for i in 0..5 {
+            let _ = recorder.observe(&mut t, sample(i == 2));
+        }
+        assert_eq!(recorder.len(), 5);
+
+        let mut csv = String::new();
+        recorder.write_csv(&mut csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[0],
+            "frame,confidence,phase_error_ms,hard_miss,soft_miss,grade,score"
+        );
+        for (i, line) in lines[1..].iter().enumerate() {
+            let fields: Vec<&str> = line.split(',').collect();
+            assert_eq!(fields.len(), 7);
+            assert_eq!(fields[0], alloc::format!("{i}"));
+            assert_eq!(fields[1], "Predictive");
+            assert_eq!(fields[3], if i == 2 { "1" } else { "0" });
+        }
+    }
+
+    #[test]
+    fn histogram_bins_ring_deltas() {
+        let mut t = SyncTracker::<8>::new(16.67);
+        for delta in [4.0, 8.0, 11.9, 12.0, 16.7, 16.7, 25.0, 50.0] {
+            let _ = t.observe(SyncSample {
+                frame_delta_ms: delta,
+                ..sample(false)
+            });
+        }
+        let edges = [8.0, 12.0, 16.0, 20.0, 33.0];
+        // 4.0 falls below the first edge; 50.0 lies past the last.
+        assert_eq!(t.histogram(&edges), [3, 1, 2, 1, 1]);
+        assert!(t.histogram(&[]).is_empty());
+
+        // Before any frame, every slot holds the seed delta.
+        let fresh = SyncTracker::<8>::new(16.67);
+        assert_eq!(fresh.histogram(&edges), [0, 0, 8, 0, 0]);
+    }
 }